@@ -0,0 +1,70 @@
+//! Compare building a 5000-task schedule with one `upsert_task_record` call
+//! per task against `Schedule::from_tasks`.
+//!
+//! Run with `cargo bench -p schedule-core --bench from_tasks`.
+
+use chrono::NaiveDate;
+use schedule_tool::{Schedule, ScheduleMetadata, Task};
+use std::time::{Duration, Instant};
+
+const TASK_COUNT: i32 = 5000;
+const RUNS: u32 = 3;
+
+fn tasks() -> Vec<Task> {
+    (1..=TASK_COUNT)
+        .map(|id| {
+            let mut task = Task::new(id, format!("T{id}"), i64::from(id % 5));
+            // Fifty parallel chains with occasional cross links.
+            task.predecessors = match id {
+                _ if id <= 50 => Vec::new(),
+                _ if id > 100 && id % 7 == 0 => vec![id - 50, id / 2],
+                _ => vec![id - 50],
+            };
+            task
+        })
+        .collect()
+}
+
+fn metadata() -> ScheduleMetadata {
+    ScheduleMetadata {
+        project_start_date: NaiveDate::from_ymd_opt(2025, 1, 6).unwrap(),
+        project_end_date: NaiveDate::from_ymd_opt(2030, 12, 31).unwrap(),
+        ..ScheduleMetadata::default()
+    }
+}
+
+fn upsert_loop(tasks: Vec<Task>) -> Schedule {
+    let mut schedule = Schedule::new_with_metadata(metadata());
+    for task in tasks {
+        schedule.upsert_task_record(task).unwrap();
+    }
+    schedule.refresh().unwrap();
+    schedule
+}
+
+fn bulk(tasks: Vec<Task>) -> Schedule {
+    let calendar = Schedule::new_with_metadata(metadata()).calendar().clone();
+    Schedule::from_tasks(metadata(), calendar, tasks).unwrap()
+}
+
+fn time(label: &str, build: fn(Vec<Task>) -> Schedule) -> Duration {
+    let mut best = Duration::MAX;
+    for _ in 0..RUNS {
+        let input = tasks();
+        let started = Instant::now();
+        let schedule = build(input);
+        best = best.min(started.elapsed());
+        assert_eq!(schedule.dataframe().height(), TASK_COUNT as usize);
+    }
+    println!("{label:<12} {TASK_COUNT} tasks: best of {RUNS} = {best:?}");
+    best
+}
+
+fn main() {
+    let looped = time("upsert loop", upsert_loop);
+    let bulk = time("from_tasks", bulk);
+    println!(
+        "speedup: {:.1}x",
+        looped.as_secs_f64() / bulk.as_secs_f64().max(f64::EPSILON)
+    );
+}
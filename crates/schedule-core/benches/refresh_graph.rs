@@ -0,0 +1,60 @@
+//! Time building the dependency graph of a 1000-task network against a
+//! full `Schedule::refresh`, which builds it once and shares it between
+//! the forward and backward passes.
+//!
+//! Run with `cargo bench -p schedule-core --bench refresh_graph`.
+
+use chrono::NaiveDate;
+use schedule_tool::graph::schedule_dag::ScheduleDag;
+use schedule_tool::{Schedule, ScheduleMetadata, Task, WorkCalendar};
+use std::time::{Duration, Instant};
+
+const TASK_COUNT: i32 = 1000;
+const RUNS: u32 = 20;
+
+fn schedule() -> Schedule {
+    let metadata = ScheduleMetadata {
+        project_start_date: NaiveDate::from_ymd_opt(2025, 1, 6).unwrap(),
+        project_end_date: NaiveDate::from_ymd_opt(2030, 12, 31).unwrap(),
+        ..ScheduleMetadata::default()
+    };
+    let tasks = (1..=TASK_COUNT)
+        .map(|id| {
+            let mut task = Task::new(id, format!("T{id}"), i64::from(id % 5));
+            // Twenty parallel chains with occasional cross links.
+            task.predecessors = match id {
+                _ if id <= 20 => Vec::new(),
+                _ if id > 40 && id % 7 == 0 => vec![id - 20, id / 2],
+                _ => vec![id - 20],
+            };
+            task
+        })
+        .collect();
+    let calendar = WorkCalendar::with_year_range(2025, 2030);
+    Schedule::from_tasks(metadata, calendar, tasks).unwrap()
+}
+
+fn time(label: &str, mut run: impl FnMut()) -> Duration {
+    let mut best = Duration::MAX;
+    for _ in 0..RUNS {
+        let started = Instant::now();
+        run();
+        best = best.min(started.elapsed());
+    }
+    println!("{label:<12} {TASK_COUNT} tasks: best of {RUNS} = {best:?}");
+    best
+}
+
+fn main() {
+    let mut schedule = schedule();
+    let graph = time("graph build", || {
+        ScheduleDag::build(schedule.dataframe()).unwrap();
+    });
+    let refresh = time("refresh", || {
+        schedule.refresh().unwrap();
+    });
+    println!(
+        "graph build share of refresh: {:.1}%",
+        100.0 * graph.as_secs_f64() / refresh.as_secs_f64().max(f64::EPSILON)
+    );
+}
@@ -0,0 +1,58 @@
+//! Time editing a single task in a 10 000-row schedule through the public
+//! setters.
+//!
+//! Run with `cargo bench -p schedule-core --bench single_edit`.
+
+use chrono::NaiveDate;
+use schedule_tool::{Schedule, ScheduleMetadata, Task, WorkCalendar};
+use std::time::Instant;
+
+const TASK_COUNT: i32 = 10_000;
+const EDITS: u32 = 200;
+const EDITED_TASK: i32 = TASK_COUNT / 2;
+
+fn schedule() -> Schedule {
+    let metadata = ScheduleMetadata {
+        project_start_date: NaiveDate::from_ymd_opt(2025, 1, 6).unwrap(),
+        project_end_date: NaiveDate::from_ymd_opt(2030, 12, 31).unwrap(),
+        ..ScheduleMetadata::default()
+    };
+    let tasks = (1..=TASK_COUNT)
+        .map(|id| {
+            let mut task = Task::new(id, format!("T{id}"), i64::from(id % 5));
+            // Fifty parallel chains keep the network inside the horizon.
+            if id > 50 {
+                task.predecessors = vec![id - 50];
+            }
+            task
+        })
+        .collect();
+    let calendar = WorkCalendar::with_year_range(2025, 2030);
+    Schedule::from_tasks(metadata, calendar, tasks).unwrap()
+}
+
+fn time(label: &str, mut edit: impl FnMut(&mut Schedule, u32)) {
+    let mut schedule = schedule();
+    let started = Instant::now();
+    for round in 0..EDITS {
+        edit(&mut schedule, round);
+    }
+    let per_edit = started.elapsed() / EDITS;
+    println!("{label:<22} {TASK_COUNT} tasks: {per_edit:?} per edit");
+}
+
+fn main() {
+    time("set_custom_field", |schedule, round| {
+        schedule
+            .set_custom_field(EDITED_TASK, "round", round.to_string())
+            .unwrap();
+    });
+
+    let template = schedule().find_task(EDITED_TASK).unwrap().unwrap();
+    time("upsert_task_record", |schedule, round| {
+        let mut task = template.clone();
+        task.name = format!("edit {round}");
+        task.baseline_finish = NaiveDate::from_ymd_opt(2025, 3, 3);
+        schedule.upsert_task_record(task).unwrap();
+    });
+}
@@ -0,0 +1,641 @@
+use crate::calendar::WorkCalendar;
+use crate::graph::schedule_dag::ScheduleDag;
+use crate::schedule::Schedule;
+use crate::task::Task;
+use chrono::{Duration, Local, NaiveDate};
+use petgraph::Direction;
+use petgraph::algo::toposort;
+use polars::prelude::{Column, DataFrame, NamedFrom, PolarsError, Series};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Total float (working days) at or below which a non-critical task counts as
+/// near-critical in [`Schedule::analytics_summary`].
+pub const DEFAULT_NEAR_CRITICAL_FLOAT_DAYS: i64 = 5;
+
+/// A day on which a resource is booked by more than one task at once.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceOverallocation {
+    pub resource_id: String,
+    pub date: NaiveDate,
+    /// Tasks using the resource that day, in ascending id order.
+    pub task_ids: Vec<i32>,
+    /// Summed units those tasks book that day.
+    pub units: f64,
+}
+
+/// Schedule-wide figures that are derived from tasks but not stored on them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnalyticsSummary {
+    pub task_count: usize,
+    /// `(predecessor, successor)` links where both tasks are critical.
+    pub critical_path_edges: Vec<(i32, i32)>,
+    pub near_critical_task_ids: Vec<i32>,
+    pub overallocations: Vec<ResourceOverallocation>,
+    /// Sum of [`Schedule::task_cost`] over every task.
+    pub total_cost: f64,
+}
+
+/// A finish-to-start link whose successor could start before its
+/// predecessor finishes, from [`Schedule::fast_track_candidates`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FastTrackCandidate {
+    pub predecessor_id: i32,
+    pub successor_id: i32,
+    /// The successor's total float. The project finish only moves when this
+    /// is zero or negative.
+    pub total_float: i64,
+    /// Working days the successor's start could move earlier by overlapping
+    /// this link, before the predecessor's duration, another predecessor or
+    /// a forced start holds it.
+    pub potential_days_saved: i64,
+}
+
+/// Cumulative planned and earned completion over the working days of a
+/// project, from [`Schedule::s_curve`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SCurve {
+    pub data_date: NaiveDate,
+    pub points: Vec<SCurvePoint>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SCurvePoint {
+    pub date: NaiveDate,
+    /// Duration-weighted share of baselined work planned done by `date`, as
+    /// a fraction like `percent_complete`. `None` if no task has a baseline.
+    pub planned_percent: Option<f64>,
+    /// Duration-weighted share of work earned by `date`. `None` after the
+    /// data date.
+    pub actual_percent: Option<f64>,
+}
+
+/// Task columns holding lists or JSON text, left out of
+/// [`AnalyticsFrames::tasks`] in favour of the tidy frames.
+const NESTED_TASK_COLUMNS: [&str; 6] = [
+    "predecessors",
+    "successors",
+    "task_attachments",
+    "resource_allocations",
+    "custom_fields",
+    "progress_log",
+];
+
+/// Flat, fully typed frames for joining schedule data with other datasets,
+/// from [`Schedule::analytics_frames`].
+#[derive(Debug, Clone)]
+pub struct AnalyticsFrames {
+    /// One row per task: the task table without its list and JSON columns.
+    pub tasks: DataFrame,
+    /// One row per task-resource pair: `task_id`, `resource_id`, `role`,
+    /// `allocation_units`, `max_units` and `cost_rate`.
+    pub resource_allocations: DataFrame,
+    /// One row per finish-to-start link: `predecessor_id`, `successor_id`.
+    pub dependencies: DataFrame,
+}
+
+impl TryFrom<&Schedule> for AnalyticsFrames {
+    type Error = PolarsError;
+
+    fn try_from(schedule: &Schedule) -> Result<Self, Self::Error> {
+        schedule.analytics_frames()
+    }
+}
+
+/// Share of `task` its baseline plans done by the end of `date`, spread
+/// evenly over the baseline's working days. `None` without usable baseline
+/// dates.
+fn planned_fraction(task: &Task, calendar: &WorkCalendar, date: NaiveDate) -> Option<f64> {
+    let (start, finish) = (task.baseline_start?, task.baseline_finish?);
+    let span = task.baseline_working_duration(calendar)?;
+    Some(if date >= finish {
+        1.0
+    } else if date <= start || span == 0 {
+        0.0
+    } else {
+        calendar.count_available_days(start + Duration::days(1), date) as f64 / span as f64
+    })
+}
+
+/// Share of `task` earned by the end of `date`. Recorded progress wins;
+/// otherwise progress runs evenly from the actual start to the actual
+/// finish (reaching 1) or to the data date (reaching `percent_complete`).
+fn earned_fraction(
+    task: &Task,
+    calendar: &WorkCalendar,
+    date: NaiveDate,
+    data_date: NaiveDate,
+) -> f64 {
+    if let Some(percent) = task.percent_complete_as_of(date) {
+        return percent;
+    }
+    let Some(start) = task.actual_start.filter(|start| *start <= date) else {
+        return 0.0;
+    };
+    let (end, target) = match task.actual_finish {
+        Some(finish) if finish <= date => return 1.0,
+        Some(finish) => (finish, 1.0),
+        None => (data_date, task.percent_complete.unwrap_or(0.0)),
+    };
+    let span = calendar.count_available_days(start + Duration::days(1), end);
+    if span == 0 {
+        return target;
+    }
+    let elapsed = calendar.count_available_days(start + Duration::days(1), date);
+    target * (elapsed.min(span) as f64 / span as f64)
+}
+
+/// Cost of one task: units summed over its working days times `cost_rate`,
+/// summed over its allocations. A flat allocation costs `allocation_units *
+/// cost_rate * duration_days`. Allocations without a cost rate cost nothing.
+fn cost_of(task: &Task) -> f64 {
+    task.resource_allocations
+        .iter()
+        .map(|allocation| {
+            allocation.total_units(task.duration_days) * allocation.cost_rate.unwrap_or(0.0)
+        })
+        .sum()
+}
+
+/// Working days between `pred`'s early finish and `succ`'s early start,
+/// beyond the hand-off the forward pass allows. `None` if either task is
+/// unscheduled.
+fn hand_off_gap(schedule: &Schedule, pred: &Task, succ: &Task) -> Result<Option<i64>, PolarsError> {
+    let (Some(early_finish), Some(succ_start)) = (pred.early_finish, succ.early_start) else {
+        return Ok(None);
+    };
+    let calendar = schedule.calendar();
+    let earliest_next = calendar.try_next_available(early_finish)?;
+    Ok(Some(Schedule::working_days_diff(
+        calendar,
+        earliest_next,
+        succ_start,
+    )))
+}
+
+impl Schedule {
+    /// The task table split into tidy frames: scalar task columns, one row
+    /// per resource allocation and one row per predecessor link. Links and
+    /// allocations keep task order, then list order.
+    pub fn analytics_frames(&self) -> Result<AnalyticsFrames, PolarsError> {
+        let df = self.dataframe();
+        let scalar_columns: Vec<_> = df
+            .get_column_names()
+            .into_iter()
+            .filter(|name| !NESTED_TASK_COLUMNS.contains(&name.as_str()))
+            .cloned()
+            .collect();
+        let tasks = df.select(scalar_columns)?;
+
+        let mut allocation_task_ids = Vec::new();
+        let mut resource_ids = Vec::new();
+        let mut roles = Vec::new();
+        let mut units = Vec::new();
+        let mut max_units = Vec::new();
+        let mut cost_rates = Vec::new();
+        let mut predecessor_ids = Vec::new();
+        let mut successor_ids = Vec::new();
+        for task in self.iter_tasks() {
+            let task = task?;
+            for allocation in task.resource_allocations {
+                allocation_task_ids.push(task.id);
+                resource_ids.push(allocation.resource_id);
+                roles.push(allocation.role);
+                units.push(allocation.allocation_units);
+                max_units.push(allocation.max_units);
+                cost_rates.push(allocation.cost_rate);
+            }
+            for predecessor in task.predecessors {
+                predecessor_ids.push(predecessor);
+                successor_ids.push(task.id);
+            }
+        }
+
+        let resource_allocations = DataFrame::new(vec![
+            Column::from(Series::new("task_id".into(), allocation_task_ids)),
+            Column::from(Series::new("resource_id".into(), resource_ids)),
+            Column::from(Series::new("role".into(), roles)),
+            Column::from(Series::new("allocation_units".into(), units)),
+            Column::from(Series::new("max_units".into(), max_units)),
+            Column::from(Series::new("cost_rate".into(), cost_rates)),
+        ])?;
+        let dependencies = DataFrame::new(vec![
+            Column::from(Series::new("predecessor_id".into(), predecessor_ids)),
+            Column::from(Series::new("successor_id".into(), successor_ids)),
+        ])?;
+
+        Ok(AnalyticsFrames {
+            tasks,
+            resource_allocations,
+            dependencies,
+        })
+    }
+
+    /// Cost of a single task, or `None` if the task does not exist.
+    pub fn task_cost(&self, task_id: i32) -> Result<Option<f64>, PolarsError> {
+        Ok(self.find_task(task_id)?.as_ref().map(cost_of))
+    }
+
+    pub fn total_cost(&self) -> Result<f64, PolarsError> {
+        let mut total = 0.0;
+        for task in self.iter_tasks() {
+            total += cost_of(&task?);
+        }
+        Ok(total)
+    }
+
+    /// Duration-weighted average `percent_complete` of the leaf tasks (tasks
+    /// no other task names as its parent). Tasks without progress count as
+    /// 0. `None` when the leaf durations sum to zero.
+    pub fn project_percent_complete(&self) -> Result<Option<f64>, PolarsError> {
+        let tasks = self.tasks()?;
+        let parents: HashSet<i32> = tasks.iter().filter_map(|task| task.parent_id).collect();
+        let (weighted, total) = tasks
+            .iter()
+            .filter(|task| !parents.contains(&task.id))
+            .fold((0.0, 0.0), |(weighted, total), task| {
+                let duration = task.duration_days as f64;
+                (
+                    weighted + duration * task.percent_complete.unwrap_or(0.0),
+                    total + duration,
+                )
+            });
+        Ok((total > 0.0).then(|| weighted / total))
+    }
+
+    /// Finish date if the remaining work of every task is scheduled from the
+    /// status date (see [`Schedule::set_status_date`]) in dependency order.
+    /// Remaining work is `duration_days * (1 - percent_complete)` rounded up;
+    /// finished tasks keep their actual or early finish. `None` for an empty
+    /// schedule.
+    pub fn forecast_finish(&self) -> Result<Option<NaiveDate>, PolarsError> {
+        let status_date = self
+            .status_date()
+            .unwrap_or_else(|| Local::now().date_naive());
+        let start_floor = status_date.max(self.metadata().project_start_date);
+        let calendar = self.calendar();
+        let tasks: HashMap<i32, Task> = self
+            .tasks()?
+            .into_iter()
+            .map(|task| (task.id, task))
+            .collect();
+        let dag = ScheduleDag::build(self.dataframe())?;
+        let order = toposort(&dag.graph, None)
+            .map_err(|_| PolarsError::ComputeError("Cycle detected in schedule DAG".into()))?;
+
+        let mut finishes: HashMap<i32, NaiveDate> = HashMap::new();
+        for node in order {
+            let task = &tasks[&dag.graph[node]];
+            let pct = task.percent_complete.unwrap_or(0.0);
+            let finish = match task.actual_finish.or(task.early_finish) {
+                Some(done) if pct >= 1.0 => done,
+                _ => {
+                    let mut start = start_floor;
+                    if let Some(latest_pred) = dag
+                        .graph
+                        .neighbors_directed(node, Direction::Incoming)
+                        .filter_map(|pred| finishes.get(&dag.graph[pred]).copied())
+                        .max()
+                        && latest_pred >= start
+                    {
+                        start = calendar.try_next_available(latest_pred)?;
+                    }
+                    let remaining = (task.duration_days as f64 * (1.0 - pct)).ceil() as i64;
+                    calendar.try_find_next_available(start, remaining)?
+                }
+            };
+            finishes.insert(task.id, finish);
+        }
+        Ok(finishes.into_values().max())
+    }
+
+    /// Gather the read-only analyses from the last refresh in one pass over
+    /// the helpers below. The schedule is not modified.
+    pub fn analytics_summary(&self) -> Result<AnalyticsSummary, PolarsError> {
+        Ok(AnalyticsSummary {
+            task_count: self.dataframe().height(),
+            critical_path_edges: self.critical_path_edges()?,
+            near_critical_task_ids: self.near_critical_tasks(DEFAULT_NEAR_CRITICAL_FLOAT_DAYS)?,
+            overallocations: self.resource_overallocations()?,
+            total_cost: self.total_cost()?,
+        })
+    }
+
+    /// Dependency links whose predecessor and successor both have zero or
+    /// negative total float, sorted by predecessor then successor.
+    pub fn critical_path_edges(&self) -> Result<Vec<(i32, i32)>, PolarsError> {
+        let tasks = self.tasks()?;
+        let critical: HashSet<i32> = tasks
+            .iter()
+            .filter(|task| task.total_float.is_some_and(|tf| tf <= 0))
+            .map(|task| task.id)
+            .collect();
+        let mut edges: Vec<(i32, i32)> = tasks
+            .iter()
+            .filter(|task| critical.contains(&task.id))
+            .flat_map(|task| {
+                task.predecessors
+                    .iter()
+                    .filter(|pred| critical.contains(pred))
+                    .map(move |pred| (*pred, task.id))
+            })
+            .collect();
+        edges.sort_unstable();
+        edges.dedup();
+        Ok(edges)
+    }
+
+    /// Working days a task can slip without delaying any successor's early
+    /// start. Tasks without successors fall back to their total float. `None`
+    /// if the task does not exist or has not been scheduled by a refresh.
+    pub fn free_float(&self, task_id: i32) -> Result<Option<i64>, PolarsError> {
+        let tasks = self.tasks()?;
+        let Some(task) = tasks.iter().find(|task| task.id == task_id) else {
+            return Ok(None);
+        };
+        let Some(early_finish) = task.early_finish else {
+            return Ok(None);
+        };
+        let calendar = self.calendar();
+        let earliest_next = calendar.try_next_available(early_finish)?;
+        let free = tasks
+            .iter()
+            .filter(|succ| succ.predecessors.contains(&task_id))
+            .filter_map(|succ| succ.early_start)
+            .map(|succ_start| Schedule::working_days_diff(calendar, earliest_next, succ_start))
+            .min();
+        Ok(free.or(task.total_float))
+    }
+
+    /// Working days between a predecessor's early finish and a successor's
+    /// early start, beyond the immediate hand-off the forward pass allows.
+    /// Positive where another predecessor or a forced start holds the
+    /// successor back. `None` if `succ_id` does not list `pred_id` or either
+    /// task has not been scheduled by a refresh.
+    pub fn link_lag(&self, pred_id: i32, succ_id: i32) -> Result<Option<i64>, PolarsError> {
+        let Some(succ) = self.find_task(succ_id)? else {
+            return Ok(None);
+        };
+        if !succ.predecessors.contains(&pred_id) {
+            return Ok(None);
+        }
+        let Some(pred) = self.find_task(pred_id)? else {
+            return Ok(None);
+        };
+        hand_off_gap(self, &pred, &succ)
+    }
+
+    /// Driving finish-to-start links on or near the critical path (successor
+    /// float up to [`DEFAULT_NEAR_CRITICAL_FLOAT_DAYS`]) where a negative lag
+    /// would pull the successor in. Links out of milestones or completed
+    /// tasks, and into started tasks, are skipped. Advisory only; sorted by
+    /// predecessor then successor.
+    pub fn fast_track_candidates(&self) -> Result<Vec<FastTrackCandidate>, PolarsError> {
+        let tasks = self.tasks()?;
+        let by_id: HashMap<i32, &Task> = tasks.iter().map(|task| (task.id, task)).collect();
+        let calendar = self.calendar();
+        let mut candidates = Vec::new();
+        for succ in &tasks {
+            let Some(total_float) = succ.total_float else {
+                continue;
+            };
+            if total_float > DEFAULT_NEAR_CRITICAL_FLOAT_DAYS || succ.actual_start.is_some() {
+                continue;
+            }
+            let Some(succ_start) = succ.early_start else {
+                continue;
+            };
+            let mut gaps = Vec::new();
+            for pred_id in &succ.predecessors {
+                if let Some(pred) = by_id.get(pred_id)
+                    && let Some(gap) = hand_off_gap(self, pred, succ)?
+                {
+                    gaps.push((pred, gap));
+                }
+            }
+            // A forced start holds the successor like another predecessor.
+            let forced_gap = match succ.forced_early_start {
+                Some(forced) => {
+                    let forced = if calendar.is_available(forced) {
+                        forced
+                    } else {
+                        calendar.try_next_available(forced)?
+                    };
+                    Some(Schedule::working_days_diff(calendar, forced, succ_start))
+                }
+                None => None,
+            };
+            for (index, (pred, gap)) in gaps.iter().enumerate() {
+                if *gap != 0 || pred.duration_days == 0 || pred.actual_finish.is_some() {
+                    continue;
+                }
+                let held_by = gaps
+                    .iter()
+                    .enumerate()
+                    .filter(|(other, _)| *other != index)
+                    .map(|(_, (_, other_gap))| *other_gap)
+                    .chain(forced_gap)
+                    .min();
+                let saved =
+                    held_by.map_or(pred.duration_days, |limit| limit.min(pred.duration_days));
+                if saved > 0 {
+                    candidates.push(FastTrackCandidate {
+                        predecessor_id: pred.id,
+                        successor_id: succ.id,
+                        total_float,
+                        potential_days_saved: saved,
+                    });
+                }
+            }
+        }
+        candidates.sort_by_key(|candidate| (candidate.predecessor_id, candidate.successor_id));
+        Ok(candidates)
+    }
+
+    /// Cumulative planned vs earned completion for each working day from the
+    /// project start to the latest early, baseline or actual finish. Leaf
+    /// tasks are weighted by duration as in
+    /// [`Schedule::project_percent_complete`]; the planned curve only counts
+    /// tasks with baseline dates. Earned values stop at `data_date`.
+    pub fn s_curve(&self, data_date: NaiveDate) -> Result<SCurve, PolarsError> {
+        let tasks = self.tasks()?;
+        let calendar = self.calendar();
+        let parents: HashSet<i32> = tasks.iter().filter_map(|task| task.parent_id).collect();
+        let leaves: Vec<&Task> = tasks
+            .iter()
+            .filter(|task| !parents.contains(&task.id))
+            .collect();
+        let start = self.metadata().project_start_date;
+        let finish = tasks
+            .iter()
+            .flat_map(|task| [task.early_finish, task.baseline_finish, task.actual_finish])
+            .flatten()
+            .max()
+            .unwrap_or(self.metadata().project_end_date);
+
+        let total: f64 = leaves.iter().map(|task| task.duration_days as f64).sum();
+        let planned_total: f64 = leaves
+            .iter()
+            .filter(|task| task.baseline_working_duration(calendar).is_some())
+            .map(|task| task.duration_days as f64)
+            .sum();
+        let points = calendar
+            .available_days_in_range(start, finish)
+            .into_iter()
+            .map(|date| {
+                let planned = leaves
+                    .iter()
+                    .filter_map(|task| {
+                        planned_fraction(task, calendar, date)
+                            .map(|fraction| fraction * task.duration_days as f64)
+                    })
+                    .sum::<f64>();
+                let earned = leaves
+                    .iter()
+                    .map(|task| {
+                        earned_fraction(task, calendar, date, data_date) * task.duration_days as f64
+                    })
+                    .sum::<f64>();
+                SCurvePoint {
+                    date,
+                    planned_percent: (planned_total > 0.0).then(|| planned / planned_total),
+                    actual_percent: (date <= data_date && total > 0.0).then(|| earned / total),
+                }
+            })
+            .collect();
+        Ok(SCurve { data_date, points })
+    }
+
+    /// Tasks with positive total float no greater than `max_float_days`.
+    pub fn near_critical_tasks(&self, max_float_days: i64) -> Result<Vec<i32>, PolarsError> {
+        let mut ids = Vec::new();
+        for task in self.iter_tasks() {
+            let task = task?;
+            if task
+                .total_float
+                .is_some_and(|tf| tf > 0 && tf <= max_float_days)
+            {
+                ids.push(task.id);
+            }
+        }
+        ids.sort_unstable();
+        Ok(ids)
+    }
+
+    /// Working days on which a resource is used by more than one task, treating
+    /// each `resource_id` as able to work one task at a time (the same model
+    /// [`Schedule::level_resources`] uses). Sorted by resource, then date.
+    pub fn resource_overallocations(&self) -> Result<Vec<ResourceOverallocation>, PolarsError> {
+        let calendar = self.calendar();
+        let mut usage: BTreeMap<(String, NaiveDate), (Vec<i32>, f64)> = BTreeMap::new();
+        for task in self.iter_tasks() {
+            let task = task?;
+            let (Some(start), Some(finish)) = (task.early_start, task.early_finish) else {
+                continue;
+            };
+            if task.duration_days == 0 {
+                continue;
+            }
+            for allocation in &task.resource_allocations {
+                let working_days = start
+                    .iter_days()
+                    .take_while(|day| *day <= finish)
+                    .filter(|day| calendar.is_available(*day));
+                for (offset, day) in (0i64..).zip(working_days) {
+                    let entry = usage
+                        .entry((allocation.resource_id.clone(), day))
+                        .or_default();
+                    if !entry.0.contains(&task.id) {
+                        entry.0.push(task.id);
+                    }
+                    entry.1 += allocation.units_on_day(offset, task.duration_days);
+                }
+            }
+        }
+        Ok(usage
+            .into_iter()
+            .filter(|(_, (task_ids, _))| task_ids.len() > 1)
+            .map(|((resource_id, date), (mut task_ids, units))| {
+                task_ids.sort_unstable();
+                ResourceOverallocation {
+                    resource_id,
+                    date,
+                    task_ids,
+                    units,
+                }
+            })
+            .collect())
+    }
+
+    /// Day-by-day usage of one resource: for each working day in the project
+    /// span, the summed units of every task active that day
+    /// (`early_start..=early_finish`), following each allocation's `profile`
+    /// where one is set. Days without usage report `0.0`.
+    pub fn resource_profile(
+        &self,
+        resource_id: &str,
+    ) -> Result<Vec<(NaiveDate, f64)>, PolarsError> {
+        let mut profiles = self.build_resource_profiles(Some(resource_id))?;
+        Ok(profiles
+            .remove(resource_id)
+            .unwrap_or_else(|| self.empty_profile()))
+    }
+
+    /// [`Schedule::resource_profile`] for every resource referenced by a task.
+    pub fn resource_profiles(&self) -> Result<HashMap<String, Vec<(NaiveDate, f64)>>, PolarsError> {
+        self.build_resource_profiles(None)
+    }
+
+    fn empty_profile(&self) -> Vec<(NaiveDate, f64)> {
+        self.calendar()
+            .available_days_in_range(self.project_start_date(), self.project_end_date())
+            .into_iter()
+            .map(|day| (day, 0.0))
+            .collect()
+    }
+
+    fn build_resource_profiles(
+        &self,
+        only: Option<&str>,
+    ) -> Result<HashMap<String, Vec<(NaiveDate, f64)>>, PolarsError> {
+        let days = self
+            .calendar()
+            .available_days_in_range(self.project_start_date(), self.project_end_date());
+        let day_index: HashMap<NaiveDate, usize> = days
+            .iter()
+            .enumerate()
+            .map(|(idx, day)| (*day, idx))
+            .collect();
+
+        let mut totals: HashMap<String, Vec<f64>> = HashMap::new();
+        for task in self.iter_tasks() {
+            let task = task?;
+            let (Some(start), Some(finish)) = (task.early_start, task.early_finish) else {
+                continue;
+            };
+            for allocation in &task.resource_allocations {
+                if only.is_some_and(|id| id != allocation.resource_id) {
+                    continue;
+                }
+                let usage = totals
+                    .entry(allocation.resource_id.clone())
+                    .or_insert_with(|| vec![0.0; days.len()]);
+                let task_days = start
+                    .iter_days()
+                    .take_while(|day| *day <= finish)
+                    .filter_map(|day| day_index.get(&day).copied());
+                for (offset, idx) in (0i64..).zip(task_days) {
+                    usage[idx] += allocation.units_on_day(offset, task.duration_days);
+                }
+            }
+        }
+
+        Ok(totals
+            .into_iter()
+            .map(|(resource_id, usage)| {
+                let profile = days.iter().copied().zip(usage).collect();
+                (resource_id, profile)
+            })
+            .collect())
+    }
+}
@@ -1,13 +1,50 @@
-use chrono::NaiveDate;
+use chrono::{Datelike, Duration, NaiveDate};
 use polars::prelude::{AnyValue, DataFrame};
 use schedule_tool::{
-    ProgressRationaleTemplate, Schedule, ScheduleMetadataError, WorkCalendarConfig,
-    load_schedule_from_csv, load_schedule_from_json, save_schedule_to_csv, save_schedule_to_json,
+    CalendarPreset, ProgressRationaleTemplate, ResourceAllocation, Schedule, ScheduleMetadataError,
+    SortOrder, Task, WorkCalendar, WorkCalendarConfig, load_schedule_from_csv,
+    load_schedule_from_json, save_schedule_to_csv, save_schedule_to_json, save_schedule_to_mspdi,
 };
-use serde_json;
+#[cfg(feature = "parquet")]
+use schedule_tool::{load_schedule_from_parquet, save_schedule_to_parquet};
+use serde::Serialize;
 use std::fs;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(not(feature = "parquet"))]
+const PARQUET_DISABLED: &str =
+    "Parquet support is not enabled in this build (rebuild with --features parquet).";
+
+/// Set by `--json` or `format json`. In JSON mode, data-bearing commands
+/// write one JSON document per line to stdout and everything else (prompt,
+/// status messages, errors, text tables) goes to stderr.
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+fn json_output() -> bool {
+    JSON_OUTPUT.load(Ordering::Relaxed)
+}
+
+/// `println!` in text mode, `eprintln!` in JSON mode.
+macro_rules! say {
+    ($($arg:tt)*) => {
+        if json_output() {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Write `value` to stdout as a single line of JSON.
+fn emit_json<T: Serialize + ?Sized>(value: &T) {
+    match serde_json::to_string(value) {
+        Ok(json) => println!("{}", json),
+        Err(e) => eprintln!("Error serializing output: {}", e),
+    }
+}
 
 fn parse_pred_list(s: &str) -> Vec<i32> {
     s.split(',')
@@ -15,37 +52,76 @@ fn parse_pred_list(s: &str) -> Vec<i32> {
         .collect()
 }
 
+/// ANSI escape used to highlight critical rows on a terminal.
+const CRITICAL_ROW_COLOR: &str = "\x1b[31m";
+const RESET_COLOR: &str = "\x1b[0m";
+
+/// Render one table cell. Dates print as `YYYY-MM-DD`, integer lists such as
+/// `predecessors` and `successors` as comma-separated ids, and `is_critical`
+/// as a `*` marker.
+fn format_cell(col_name: &str, av: &AnyValue) -> String {
+    match av {
+        AnyValue::Null => String::new(),
+        AnyValue::Int32(v) => v.to_string(),
+        AnyValue::Int64(v) => v.to_string(),
+        AnyValue::String(s) => s.to_string(),
+        AnyValue::Date(days) => NaiveDate::from_ymd_opt(1970, 1, 1)
+            .and_then(|epoch| epoch.checked_add_signed(Duration::days(i64::from(*days))))
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| av.to_string()),
+        AnyValue::Boolean(flag) if col_name == "is_critical" => {
+            if *flag {
+                "*".to_string()
+            } else {
+                String::new()
+            }
+        }
+        AnyValue::List(inner) => match inner.i32() {
+            Ok(ca) => ca
+                .into_iter()
+                .flatten()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+            Err(_) => av.to_string(),
+        },
+        _ => av.to_string(),
+    }
+}
+
 fn render_df_as_text_table(df: &DataFrame) -> String {
-    // Compute column widths
+    let color = io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    render_table(df, color)
+}
+
+/// Render `df` as an aligned text table. With `color`, rows of critical
+/// tasks are wrapped in ANSI color codes.
+fn render_table(df: &DataFrame, color: bool) -> String {
     let columns = df.get_columns();
     let col_names: Vec<String> = columns.iter().map(|c| c.name().to_string()).collect();
+    let cells: Vec<Vec<String>> = (0..df.height())
+        .map(|row_idx| {
+            columns
+                .iter()
+                .map(|col| match col.get(row_idx) {
+                    Ok(av) => format_cell(col.name(), &av),
+                    Err(_) => String::new(),
+                })
+                .collect()
+        })
+        .collect();
+    let critical_rows: Vec<bool> = match df.column("is_critical").and_then(|col| col.bool()) {
+        Ok(ca) => (0..df.height())
+            .map(|row_idx| ca.get(row_idx).unwrap_or(false))
+            .collect(),
+        Err(_) => vec![false; df.height()],
+    };
 
+    // Compute column widths
     let mut widths: Vec<usize> = col_names.iter().map(|n| n.len()).collect();
-    for (ci, col) in columns.iter().enumerate() {
-        for row_idx in 0..df.height() {
-            if let Ok(ref av) = col.get(row_idx) {
-                let s = match av {
-                    AnyValue::Null => String::new(),
-                    AnyValue::Int32(v) => v.to_string(),
-                    AnyValue::Int64(v) => v.to_string(),
-                    AnyValue::String(s) => s.to_string(),
-                    AnyValue::List(inner) if col.name() == "predecessors" => {
-                        if let Ok(ca) = inner.i32() {
-                            ca.into_iter()
-                                .flatten()
-                                .map(|v| v.to_string())
-                                .collect::<Vec<_>>()
-                                .join(",")
-                        } else {
-                            av.to_string()
-                        }
-                    }
-                    _ => av.to_string(),
-                };
-                if s.len() > widths[ci] {
-                    widths[ci] = s.len();
-                }
-            }
+    for row in &cells {
+        for (ci, cell) in row.iter().enumerate() {
+            widths[ci] = widths[ci].max(cell.len());
         }
     }
 
@@ -79,39 +155,25 @@ fn render_df_as_text_table(df: &DataFrame) -> String {
     out.push('\n');
 
     // Rows
-    for row_idx in 0..df.height() {
+    for (row, is_critical) in cells.iter().zip(critical_rows) {
+        let highlight = color && is_critical;
+        if highlight {
+            out.push_str(CRITICAL_ROW_COLOR);
+        }
         out.push('|');
-        for (ci, col) in columns.iter().enumerate() {
-            let mut s = String::new();
-            if let Ok(ref av) = col.get(row_idx) {
-                s = match av {
-                    AnyValue::Null => String::new(),
-                    AnyValue::Int32(v) => v.to_string(),
-                    AnyValue::Int64(v) => v.to_string(),
-                    AnyValue::String(st) => st.to_string(),
-                    AnyValue::List(inner) if col.name() == "predecessors" => {
-                        if let Ok(ca) = inner.i32() {
-                            ca.into_iter()
-                                .flatten()
-                                .map(|v| v.to_string())
-                                .collect::<Vec<_>>()
-                                .join(",")
-                        } else {
-                            av.to_string()
-                        }
-                    }
-                    _ => av.to_string(),
-                };
-            }
+        for (ci, cell) in row.iter().enumerate() {
             out.push(' ');
-            out.push_str(&s);
-            let pad = widths[ci].saturating_sub(s.len());
+            out.push_str(cell);
+            let pad = widths[ci].saturating_sub(cell.len());
             if pad > 0 {
                 out.push_str(&" ".repeat(pad));
             }
             out.push(' ');
             out.push('|');
         }
+        if highlight {
+            out.push_str(RESET_COLOR);
+        }
         out.push('\n');
     }
 
@@ -120,25 +182,156 @@ fn render_df_as_text_table(df: &DataFrame) -> String {
     out
 }
 
+fn print_task_float(schedule: &Schedule, id: i32) {
+    let Some(task) = find_task_or_report(schedule, id) else {
+        return;
+    };
+    let free_float = match schedule.free_float(id) {
+        Ok(value) => value,
+        Err(e) => {
+            say!("Error computing free float: {}", e);
+            return;
+        }
+    };
+    let fmt_opt = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+    say!("Task {} ({})", task.id, task.name);
+    say!(
+        "  total_float: {}",
+        fmt_opt(task.total_float.map(|v| v.to_string()))
+    );
+    say!(
+        "  free_float:  {}",
+        fmt_opt(free_float.map(|v| v.to_string()))
+    );
+    say!(
+        "  late_start:  {}",
+        fmt_opt(task.late_start.map(|d| d.to_string()))
+    );
+    say!(
+        "  late_finish: {}",
+        fmt_opt(task.late_finish.map(|d| d.to_string()))
+    );
+}
+
+fn find_task_or_report(schedule: &Schedule, id: i32) -> Option<Task> {
+    match schedule.find_task(id) {
+        Ok(Some(task)) => Some(task),
+        Ok(None) => {
+            say!("Task {id} not found.");
+            None
+        }
+        Err(e) => {
+            say!("Error reading task: {}", e);
+            None
+        }
+    }
+}
+
+/// Add `resource_id` to the task, or update its units and cost rate if the
+/// task already uses it. The task is re-upserted, so validation applies.
+fn assign_resource(
+    schedule: &mut Schedule,
+    id: i32,
+    resource_id: &str,
+    units: f64,
+    cost_rate: Option<f64>,
+    max_units: Option<f64>,
+) {
+    let Some(mut task) = find_task_or_report(schedule, id) else {
+        return;
+    };
+    match task
+        .resource_allocations
+        .iter_mut()
+        .find(|allocation| allocation.resource_id == resource_id)
+    {
+        Some(allocation) => {
+            allocation.allocation_units = units;
+            allocation.cost_rate = cost_rate;
+            allocation.max_units = max_units;
+        }
+        None => {
+            let mut allocation = ResourceAllocation::new(resource_id, units);
+            allocation.cost_rate = cost_rate;
+            allocation.max_units = max_units;
+            task.resource_allocations.push(allocation);
+        }
+    }
+    match schedule.upsert_task_record(task) {
+        Ok(_) => say!("Assigned resource '{}' to task {}.", resource_id, id),
+        Err(e) => say!("Error assigning resource: {}", e),
+    }
+}
+
+fn unassign_resource(schedule: &mut Schedule, id: i32, resource_id: &str) {
+    let Some(mut task) = find_task_or_report(schedule, id) else {
+        return;
+    };
+    let before = task.resource_allocations.len();
+    task.resource_allocations
+        .retain(|allocation| allocation.resource_id != resource_id);
+    if task.resource_allocations.len() == before {
+        say!("Task {} has no resource '{}'.", id, resource_id);
+        return;
+    }
+    match schedule.upsert_task_record(task) {
+        Ok(_) => say!("Removed resource '{}' from task {}.", resource_id, id),
+        Err(e) => say!("Error removing resource: {}", e),
+    }
+}
+
+fn print_task_resources(schedule: &Schedule, id: i32) {
+    let Some(task) = find_task_or_report(schedule, id) else {
+        return;
+    };
+    if task.resource_allocations.is_empty() {
+        say!("Task {} has no resources assigned.", id);
+        return;
+    }
+    say!("Resources for task {} ({}):", task.id, task.name);
+    for allocation in &task.resource_allocations {
+        match allocation.cost_rate {
+            Some(rate) => say!(
+                "  {}  units={}  cost_rate={}",
+                allocation.resource_id,
+                allocation.allocation_units,
+                rate
+            ),
+            None => say!(
+                "  {}  units={}",
+                allocation.resource_id,
+                allocation.allocation_units
+            ),
+        }
+    }
+}
+
 fn print_help() {
-    println!(
-        "Commands:\n  help                               Show this help\n  show                               Show current schedule\n  new                                Append empty task with next id\n  add <id> <name> <duration_days> [preds_csv]\n                                     Upsert a task (preds like 1,2,3)\n  delete <id>                        Delete a task and clean up dependencies\n  bstart  <id> <YYYY-MM-DD>          Set baseline_start\n  bfinish <id> <YYYY-MM-DD>          Set baseline_finish\n  astart  <id> <YYYY-MM-DD>          Set actual_start\n  afinish <id> <YYYY-MM-DD>          Set actual_finish\n  pct     <id> <float>               Set percent_complete\n  var     <id> <i64>                 Set schedule_variance_days\n  crit    <id> <true|false>          Set is_critical\n  parent  <id> <i32>                 Set parent_id\n  wbs     <id> <code>                Set wbs_code\n  notes   <id> <text...>             Set task_notes (rest of line)\n  succ    <id> <csv>                 Set successors (e.g. 2,3)\n  rationale templates                List available rationale templates\n  rationale template <id> <name>     Apply rationale template to task\n  meta show                          Show project metadata\n  meta name <text...>                Update project name\n  meta desc <text...>                Update project description\n  meta dates <start> <end>           Update project start/end dates (YYYY-MM-DD)\n  calendar show                      Display calendar configuration summary\n  calendar default                   Reset to default calendar for metadata span\n  calendar set <json_path>           Load calendar config from JSON file\n  calendar save <json_path>          Save current calendar config to JSON file\n  save <json|csv> <path>             Persist schedule to disk\n  load <json|csv> <path>             Load schedule from disk\n  compute                            Refresh schedule (forward + backward passes)\n  quit|exit                          Exit"
+    say!(
+        "Commands:\n  help                               Show this help\n  show [id|start|wbs|topo]           Show current schedule, optionally sorted\n  new                                Append empty task with next id\n  add <id> <name> <duration_days> [preds_csv]\n                                     Upsert a task (preds like 1,2,3)\n  delete <id>                        Delete a task and clean up dependencies\n  bstart  <id> <YYYY-MM-DD>          Set baseline_start\n  bfinish <id> <YYYY-MM-DD>          Set baseline_finish\n  astart  <id> <YYYY-MM-DD>          Set actual_start\n  afinish <id> <YYYY-MM-DD>          Set actual_finish\n  pct     <id> <float>               Set percent_complete\n  var     <id> <i64>                 Set schedule_variance_days\n  crit    <id> <true|false>          Set is_critical\n  parent  <id> <i32>                 Set parent_id\n  wbs     <id> <code>                Set wbs_code\n  notes   <id> <text...>             Set task_notes (rest of line)\n  succ    <id> <csv>                 Set successors (e.g. 2,3)\n  resource assign <id> <resource> <units> [cost_rate] [max_units]\n                                     Assign a resource (units above 1 need max_units)\n  resource unassign <id> <resource>  Remove a resource from a task\n  resource show <id>                 List a task's resource allocations\n  rationale templates                List available rationale templates\n  rationale template <id> <name>     Apply rationale template to task\n  meta show                          Show project metadata\n  meta name <text...>                Update project name\n  meta desc <text...>                Update project description\n  meta dates <start> <end>           Update project start/end dates (YYYY-MM-DD)\n  calendar show                      Display calendar configuration summary\n  calendar default                   Reset to default calendar for metadata span\n  calendar preset <name>             Use a preset (five_day_us|six_day|four_day|seven_day)\n  calendar set <json_path>           Load calendar config from JSON file\n  calendar save <json_path>          Save current calendar config to JSON file\n  workspace show                     Show the current workspace file\n  workspace save [path]              Save the session (defaults to the current workspace)\n  workspace open <path>              Replace the session with a saved workspace\n  save <json|csv|parquet|mspdi> <path>\n                                     Persist schedule to disk (mspdi is export-only)\n  load <json|csv|parquet> <path>     Load schedule from disk (parquet keeps current metadata)\n  format <json|text>                 Switch output mode (json: data on stdout, messages on stderr)\n  compute                            Refresh schedule (forward + backward passes)\n  critical                           Show the critical path from the last compute\n  float   <id>                       Show total/free float and late dates\n  quit|exit                          Exit"
     );
 }
 
 fn print_rationale_templates() {
-    println!("Available rationale templates:");
+    say!("Available rationale templates:");
     for (key, description) in ProgressRationaleTemplate::variants() {
-        println!("  {:<24} {}", key, description);
+        say!("  {:<24} {}", key, description);
     }
 }
 
 fn print_metadata(schedule: &Schedule) {
     let metadata = schedule.metadata();
-    println!("Project name       : {}", metadata.project_name);
-    println!("Project description: {}", metadata.project_description);
-    println!("Project start date : {}", metadata.project_start_date);
-    println!("Project end date   : {}", metadata.project_end_date);
+    if json_output() {
+        emit_json(metadata);
+        return;
+    }
+    say!("Project name       : {}", metadata.project_name);
+    say!("Project description: {}", metadata.project_description);
+    say!("Project start date : {}", metadata.project_start_date);
+    say!("Project end date   : {}", metadata.project_end_date);
+    if let Some(zone) = &metadata.timezone {
+        say!("Project timezone   : {}", zone);
+    }
 }
 
 fn print_calendar_info(schedule: &Schedule) {
@@ -155,9 +348,12 @@ fn print_calendar_info(schedule: &Schedule) {
         .map(|d| d.to_string())
         .collect::<Vec<_>>()
         .join(", ");
-    println!("Calendar custom    : {}", schedule.calendar_is_custom());
-    println!("Working days       : {}", working_days);
-    println!("Holidays           : {}", holidays);
+    say!("Calendar custom    : {}", schedule.calendar_is_custom());
+    if let Some(preset) = config.preset_origin() {
+        say!("Preset             : {}", preset.as_str());
+    }
+    say!("Working days       : {}", working_days);
+    say!("Holidays           : {}", holidays);
 }
 
 fn next_id(schedule: &Schedule) -> i32 {
@@ -174,20 +370,96 @@ fn next_id(schedule: &Schedule) -> i32 {
         .unwrap_or(1)
 }
 
-fn main() {
+const USAGE: &str = "Usage: cli [--json] [workspace.json]";
+
+/// Startup options parsed from the command line.
+#[derive(Debug, Default, PartialEq)]
+struct CliArgs {
+    /// Workspace file to open at startup. At most one path is accepted.
+    workspace: Option<PathBuf>,
+    /// Start in JSON output mode (`--json`).
+    json: bool,
+}
+
+/// Parse the command-line arguments, program name excluded.
+fn parse_args<I>(args: I) -> Result<CliArgs, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut parsed = CliArgs::default();
+    for arg in args {
+        if arg == "--json" {
+            parsed.json = true;
+        } else if arg.starts_with("--") || parsed.workspace.is_some() {
+            return Err(USAGE.to_string());
+        } else {
+            parsed.workspace = Some(PathBuf::from(arg));
+        }
+    }
+    Ok(parsed)
+}
+
+/// Load a workspace file and refresh it, printing any problem. Returns
+/// `None` if the file could not be read.
+fn open_workspace(path: &Path) -> Option<Schedule> {
+    match load_schedule_from_json(path) {
+        Ok(mut loaded) => {
+            if let Err(e) = loaded.refresh() {
+                say!("Loaded workspace but refresh failed: {}", e);
+            }
+            say!("Workspace opened from {}.", path.display());
+            Some(loaded)
+        }
+        Err(e) => {
+            say!("Error opening workspace {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+fn blank_schedule() -> Schedule {
     let mut schedule = Schedule::new();
     if schedule.dataframe().height() == 0 {
         let _ = schedule.upsert_task(1, "", 0, None);
     }
+    schedule
+}
 
-    println!("Schedule Tool (CLI) - type 'help' for commands\n");
-    println!("{}", render_df_as_text_table(schedule.dataframe()));
+fn main() {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(usage) => {
+            eprintln!("{}", usage);
+            std::process::exit(2);
+        }
+    };
+    JSON_OUTPUT.store(args.json, Ordering::Relaxed);
+    let mut workspace = args.workspace;
+
+    say!("Schedule Tool (CLI) - type 'help' for commands\n");
+    let mut schedule = match workspace.as_deref() {
+        Some(path) if path.exists() => open_workspace(path).unwrap_or_else(blank_schedule),
+        Some(path) => {
+            say!(
+                "Workspace {} does not exist yet; 'workspace save' will create it.",
+                path.display()
+            );
+            blank_schedule()
+        }
+        None => blank_schedule(),
+    };
+    say!("{}", render_df_as_text_table(schedule.dataframe()));
 
     let stdin = io::stdin();
     let mut line = String::new();
     loop {
-        print!("> ");
-        let _ = io::stdout().flush();
+        if json_output() {
+            eprint!("> ");
+            let _ = io::stderr().flush();
+        } else {
+            print!("> ");
+            let _ = io::stdout().flush();
+        }
         line.clear();
         if stdin.read_line(&mut line).is_err() {
             break;
@@ -206,13 +478,55 @@ fn main() {
             }
             "quit" | "exit" => break,
             "show" => {
-                println!("{}", render_df_as_text_table(schedule.dataframe()));
+                let order = match parts.next() {
+                    None => None,
+                    Some("id") => Some(SortOrder::Id),
+                    Some("start") => Some(SortOrder::EarlyStart),
+                    Some("wbs") => Some(SortOrder::Wbs),
+                    Some("topo") => Some(SortOrder::TopologicalDependency),
+                    Some(other) => {
+                        say!("Unknown order '{}'. Usage: show [id|start|wbs|topo]", other);
+                        continue;
+                    }
+                };
+                match (order, json_output()) {
+                    (None, false) => say!("{}", render_df_as_text_table(schedule.dataframe())),
+                    (order, json) => {
+                        let tasks = match order {
+                            Some(order) => schedule.sorted_tasks(order),
+                            None => schedule.tasks(),
+                        };
+                        match tasks {
+                            Ok(tasks) if json => emit_json(&tasks),
+                            Ok(tasks) => match Task::tasks_to_dataframe(&tasks) {
+                                Ok(df) => say!("{}", render_df_as_text_table(&df)),
+                                Err(e) => say!("Error reading tasks: {}", e),
+                            },
+                            Err(e) => say!("Error reading tasks: {}", e),
+                        }
+                    }
+                }
             }
+            "format" => match parts.next() {
+                Some("json") => {
+                    JSON_OUTPUT.store(true, Ordering::Relaxed);
+                    say!("Output format: json");
+                }
+                Some("text") => {
+                    JSON_OUTPUT.store(false, Ordering::Relaxed);
+                    say!("Output format: text");
+                }
+                Some(other) => say!("Unknown format '{}'. Usage: format <json|text>", other),
+                None => say!(
+                    "Output format: {}",
+                    if json_output() { "json" } else { "text" }
+                ),
+            },
             "new" => {
                 let id = next_id(&schedule);
                 let _ = schedule.upsert_task(id, "", 0, None);
-                println!("Added empty task id={}", id);
-                println!("{}", render_df_as_text_table(schedule.dataframe()));
+                say!("Added empty task id={}", id);
+                say!("{}", render_df_as_text_table(schedule.dataframe()));
             }
             "delete" => {
                 let id_s = parts.next();
@@ -220,15 +534,15 @@ fn main() {
                     Some(id_s) => match id_s.parse::<i32>() {
                         Ok(id) => match schedule.delete_task(id) {
                             Ok(true) => {
-                                println!("Deleted task {id}.");
-                                println!("{}", render_df_as_text_table(schedule.dataframe()));
+                                say!("Deleted task {id}.");
+                                say!("{}", render_df_as_text_table(schedule.dataframe()));
                             }
-                            Ok(false) => println!("Task {id} not found."),
-                            Err(e) => println!("Error deleting task: {}", e),
+                            Ok(false) => say!("Task {id} not found."),
+                            Err(e) => say!("Error deleting task: {}", e),
                         },
-                        Err(_) => println!("Invalid id"),
+                        Err(_) => say!("Invalid id"),
                     },
-                    None => println!("Usage: delete <id>"),
+                    None => say!("Usage: delete <id>"),
                 }
             }
             "add" => {
@@ -241,41 +555,66 @@ fn main() {
                         let id: i32 = match id_s.parse() {
                             Ok(v) => v,
                             Err(_) => {
-                                println!("Invalid id");
+                                say!("Invalid id");
                                 continue;
                             }
                         };
                         let duration_days: i64 = match dur_s.parse() {
                             Ok(v) => v,
                             Err(_) => {
-                                println!("Invalid duration_days");
+                                say!("Invalid duration_days");
                                 continue;
                             }
                         };
                         let preds = preds_s.map(parse_pred_list);
                         match schedule.upsert_task(id, name, duration_days, preds) {
                             Ok(_) => {
-                                println!("Task upserted.");
-                                println!("{}", render_df_as_text_table(schedule.dataframe()));
+                                say!("Task upserted.");
+                                say!("{}", render_df_as_text_table(schedule.dataframe()));
                             }
-                            Err(e) => println!("Error: {}", e),
+                            Err(e) => say!("Error: {}", e),
                         }
                     }
                     _ => {
-                        println!("Usage: add <id> <name> <duration_days> [preds_csv]");
+                        say!("Usage: add <id> <name> <duration_days> [preds_csv]");
                     }
                 }
             }
             "compute" => match schedule.refresh() {
+                Ok(summary) if json_output() => emit_json(&summary),
                 Ok(summary) => {
-                    println!(
+                    say!(
                         "Refreshed ({})\n{}",
                         summary.to_cli_summary(),
                         render_df_as_text_table(schedule.dataframe())
                     );
                 }
-                Err(e) => println!("Refresh error: {}", e),
+                Err(e) => say!("Refresh error: {}", e),
             },
+            "critical" => match schedule.critical_path() {
+                Ok(path) if path.is_empty() => {
+                    say!("No critical tasks. Run 'compute' to refresh float.")
+                }
+                Ok(path) => {
+                    let chain = path
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(" -> ");
+                    say!("Critical path: {}", chain);
+                }
+                Err(e) => say!("Error reading critical path: {}", e),
+            },
+            "float" => {
+                let id_s = parts.next();
+                match id_s {
+                    Some(id_s) => match id_s.parse::<i32>() {
+                        Ok(id) => print_task_float(&schedule, id),
+                        Err(_) => say!("Invalid id"),
+                    },
+                    None => say!("Usage: float <id>"),
+                }
+            }
             "bstart" | "bfinish" | "astart" | "afinish" => {
                 let id_s = parts.next();
                 let date_s = parts.next();
@@ -284,14 +623,14 @@ fn main() {
                         let id: i32 = match id_s.parse() {
                             Ok(v) => v,
                             Err(_) => {
-                                println!("Invalid id");
+                                say!("Invalid id");
                                 continue;
                             }
                         };
                         let date = match NaiveDate::parse_from_str(date_s, "%Y-%m-%d") {
                             Ok(d) => d,
                             Err(_) => {
-                                println!("Invalid date (YYYY-MM-DD)");
+                                say!("Invalid date (YYYY-MM-DD)");
                                 continue;
                             }
                         };
@@ -302,15 +641,15 @@ fn main() {
                             _ => schedule.set_actual_finish(id, date),
                         };
                         match res {
-                            Ok(_) => println!(
+                            Ok(_) => say!(
                                 "{} set.\n{}",
                                 cmd,
                                 render_df_as_text_table(schedule.dataframe())
                             ),
-                            Err(e) => println!("Error: {}", e),
+                            Err(e) => say!("Error: {}", e),
                         }
                     }
-                    _ => println!("Usage: {} <id> <YYYY-MM-DD>", cmd),
+                    _ => say!("Usage: {} <id> <YYYY-MM-DD>", cmd),
                 }
             }
             "pct" => {
@@ -321,26 +660,26 @@ fn main() {
                         let id: i32 = match id_s.parse() {
                             Ok(v) => v,
                             Err(_) => {
-                                println!("Invalid id");
+                                say!("Invalid id");
                                 continue;
                             }
                         };
                         let val: f64 = match val_s.parse() {
                             Ok(v) => v,
                             Err(_) => {
-                                println!("Invalid float");
+                                say!("Invalid float");
                                 continue;
                             }
                         };
                         match schedule.set_percent_complete(id, val) {
-                            Ok(_) => println!(
+                            Ok(_) => say!(
                                 "percent_complete set.\n{}",
                                 render_df_as_text_table(schedule.dataframe())
                             ),
-                            Err(e) => println!("Error: {}", e),
+                            Err(e) => say!("Error: {}", e),
                         }
                     }
-                    _ => println!("Usage: pct <id> <float>"),
+                    _ => say!("Usage: pct <id> <float>"),
                 }
             }
             "var" => {
@@ -351,26 +690,26 @@ fn main() {
                         let id: i32 = match id_s.parse() {
                             Ok(v) => v,
                             Err(_) => {
-                                println!("Invalid id");
+                                say!("Invalid id");
                                 continue;
                             }
                         };
                         let val: i64 = match val_s.parse() {
                             Ok(v) => v,
                             Err(_) => {
-                                println!("Invalid i64");
+                                say!("Invalid i64");
                                 continue;
                             }
                         };
                         match schedule.set_schedule_variance_days(id, val) {
-                            Ok(_) => println!(
+                            Ok(_) => say!(
                                 "schedule_variance_days set.\n{}",
                                 render_df_as_text_table(schedule.dataframe())
                             ),
-                            Err(e) => println!("Error: {}", e),
+                            Err(e) => say!("Error: {}", e),
                         }
                     }
-                    _ => println!("Usage: var <id> <i64>"),
+                    _ => say!("Usage: var <id> <i64>"),
                 }
             }
             "crit" => {
@@ -381,7 +720,7 @@ fn main() {
                         let id: i32 = match id_s.parse() {
                             Ok(v) => v,
                             Err(_) => {
-                                println!("Invalid id");
+                                say!("Invalid id");
                                 continue;
                             }
                         };
@@ -389,19 +728,19 @@ fn main() {
                             "true" => true,
                             "false" => false,
                             _ => {
-                                println!("Invalid bool (true|false)");
+                                say!("Invalid bool (true|false)");
                                 continue;
                             }
                         };
                         match schedule.set_is_critical(id, val) {
-                            Ok(_) => println!(
+                            Ok(_) => say!(
                                 "is_critical set.\n{}",
                                 render_df_as_text_table(schedule.dataframe())
                             ),
-                            Err(e) => println!("Error: {}", e),
+                            Err(e) => say!("Error: {}", e),
                         }
                     }
-                    _ => println!("Usage: crit <id> <true|false>"),
+                    _ => say!("Usage: crit <id> <true|false>"),
                 }
             }
             "parent" => {
@@ -412,26 +751,26 @@ fn main() {
                         let id: i32 = match id_s.parse() {
                             Ok(v) => v,
                             Err(_) => {
-                                println!("Invalid id");
+                                say!("Invalid id");
                                 continue;
                             }
                         };
                         let parent_id: i32 = match parent_s.parse() {
                             Ok(v) => v,
                             Err(_) => {
-                                println!("Invalid parent_id");
+                                say!("Invalid parent_id");
                                 continue;
                             }
                         };
                         match schedule.set_parent_id(id, parent_id) {
-                            Ok(_) => println!(
+                            Ok(_) => say!(
                                 "parent_id set.\n{}",
                                 render_df_as_text_table(schedule.dataframe())
                             ),
-                            Err(e) => println!("Error: {}", e),
+                            Err(e) => say!("Error: {}", e),
                         }
                     }
-                    _ => println!("Usage: parent <id> <i32>"),
+                    _ => say!("Usage: parent <id> <i32>"),
                 }
             }
             "wbs" => {
@@ -442,19 +781,19 @@ fn main() {
                         let id: i32 = match id_s.parse() {
                             Ok(v) => v,
                             Err(_) => {
-                                println!("Invalid id");
+                                say!("Invalid id");
                                 continue;
                             }
                         };
                         match schedule.set_wbs_code(id, code) {
-                            Ok(_) => println!(
+                            Ok(_) => say!(
                                 "wbs_code set.\n{}",
                                 render_df_as_text_table(schedule.dataframe())
                             ),
-                            Err(e) => println!("Error: {}", e),
+                            Err(e) => say!("Error: {}", e),
                         }
                     }
-                    _ => println!("Usage: wbs <id> <code>"),
+                    _ => say!("Usage: wbs <id> <code>"),
                 }
             }
             "notes" => {
@@ -465,20 +804,20 @@ fn main() {
                         let id: i32 = match id_s.parse() {
                             Ok(v) => v,
                             Err(_) => {
-                                println!("Invalid id");
+                                say!("Invalid id");
                                 continue;
                             }
                         };
                         let text = rest.join(" ");
                         match schedule.set_task_notes(id, &text) {
-                            Ok(_) => println!(
+                            Ok(_) => say!(
                                 "task_notes set.\n{}",
                                 render_df_as_text_table(schedule.dataframe())
                             ),
-                            Err(e) => println!("Error: {}", e),
+                            Err(e) => say!("Error: {}", e),
                         }
                     }
-                    _ => println!("Usage: notes <id> <text...>"),
+                    _ => say!("Usage: notes <id> <text...>"),
                 }
             }
             "succ" => {
@@ -489,20 +828,20 @@ fn main() {
                         let id: i32 = match id_s.parse() {
                             Ok(v) => v,
                             Err(_) => {
-                                println!("Invalid id");
+                                say!("Invalid id");
                                 continue;
                             }
                         };
                         let successors = parse_pred_list(csv);
                         match schedule.set_successors(id, successors) {
-                            Ok(_) => println!(
+                            Ok(_) => say!(
                                 "successors set.\n{}",
                                 render_df_as_text_table(schedule.dataframe())
                             ),
-                            Err(e) => println!("Error: {}", e),
+                            Err(e) => say!("Error: {}", e),
                         }
                     }
-                    _ => println!("Usage: succ <id> <csv>"),
+                    _ => say!("Usage: succ <id> <csv>"),
                 }
             }
             "rationale" => match parts.next() {
@@ -515,7 +854,7 @@ fn main() {
                             let id: i32 = match id_s.parse() {
                                 Ok(v) => v,
                                 Err(_) => {
-                                    println!("Invalid id");
+                                    say!("Invalid id");
                                     continue;
                                 }
                             };
@@ -524,35 +863,111 @@ fn main() {
                                     let key = template.key();
                                     match schedule.apply_rationale_template(id, template) {
                                         Ok(_) => {
-                                            println!(
+                                            say!(
                                                 "Applied rationale template '{}' to task {}.",
-                                                key, id
+                                                key,
+                                                id
                                             );
-                                            println!(
+                                            say!(
                                                 "{}",
                                                 render_df_as_text_table(schedule.dataframe())
                                             );
                                         }
-                                        Err(e) => println!("Error applying template: {}", e),
+                                        Err(e) => say!("Error applying template: {}", e),
                                     }
                                 }
                                 Err(_) => {
-                                    println!(
+                                    say!(
                                         "Unknown rationale template '{}'. Use 'rationale templates' to list options.",
                                         name
                                     );
                                 }
                             }
                         }
-                        _ => println!("Usage: rationale template <id> <name>"),
+                        _ => say!("Usage: rationale template <id> <name>"),
                     }
                 }
                 Some(other) => {
-                    println!("Unknown rationale command '{}'.", other);
-                    println!("Usage: rationale templates|template <id> <name>");
+                    say!("Unknown rationale command '{}'.", other);
+                    say!("Usage: rationale templates|template <id> <name>");
                 }
                 None => {
-                    println!("Usage: rationale templates|template <id> <name>");
+                    say!("Usage: rationale templates|template <id> <name>");
+                }
+            },
+            "resource" => match parts.next() {
+                Some("assign") => {
+                    let id_s = parts.next();
+                    let resource_id = parts.next();
+                    let units_s = parts.next();
+                    let rate_s = parts.next();
+                    let max_s = parts.next();
+                    match (id_s, resource_id, units_s) {
+                        (Some(id_s), Some(resource_id), Some(units_s)) => {
+                            let id: i32 = match id_s.parse() {
+                                Ok(v) => v,
+                                Err(_) => {
+                                    say!("Invalid id");
+                                    continue;
+                                }
+                            };
+                            let units: f64 = match units_s.parse() {
+                                Ok(v) => v,
+                                Err(_) => {
+                                    say!("Invalid units");
+                                    continue;
+                                }
+                            };
+                            let cost_rate = match rate_s.map(str::parse::<f64>) {
+                                Some(Ok(v)) => Some(v),
+                                Some(Err(_)) => {
+                                    say!("Invalid cost_rate");
+                                    continue;
+                                }
+                                None => None,
+                            };
+                            let max_units = match max_s.map(str::parse::<f64>) {
+                                Some(Ok(v)) => Some(v),
+                                Some(Err(_)) => {
+                                    say!("Invalid max_units");
+                                    continue;
+                                }
+                                None => None,
+                            };
+                            assign_resource(
+                                &mut schedule,
+                                id,
+                                resource_id,
+                                units,
+                                cost_rate,
+                                max_units,
+                            );
+                        }
+                        _ => say!(
+                            "Usage: resource assign <task_id> <resource_id> <units> [cost_rate] [max_units]"
+                        ),
+                    }
+                }
+                Some("unassign") => match (parts.next(), parts.next()) {
+                    (Some(id_s), Some(resource_id)) => match id_s.parse::<i32>() {
+                        Ok(id) => unassign_resource(&mut schedule, id, resource_id),
+                        Err(_) => say!("Invalid id"),
+                    },
+                    _ => say!("Usage: resource unassign <task_id> <resource_id>"),
+                },
+                Some("show") => match parts.next() {
+                    Some(id_s) => match id_s.parse::<i32>() {
+                        Ok(id) => print_task_resources(&schedule, id),
+                        Err(_) => say!("Invalid id"),
+                    },
+                    None => say!("Usage: resource show <task_id>"),
+                },
+                Some(other) => {
+                    say!("Unknown resource command '{}'.", other);
+                    say!("Usage: resource assign|unassign|show <task_id> ...");
+                }
+                None => {
+                    say!("Usage: resource assign|unassign|show <task_id> ...");
                 }
             },
             "meta" => match parts.next() {
@@ -560,23 +975,23 @@ fn main() {
                 Some("name") => {
                     let rest: Vec<&str> = parts.collect();
                     if rest.is_empty() {
-                        println!("Usage: meta name <text...>");
+                        say!("Usage: meta name <text...>");
                         continue;
                     }
                     let name = rest.join(" ");
                     schedule.set_project_name(name);
-                    println!("Project name updated.");
+                    say!("Project name updated.");
                     print_metadata(&schedule);
                 }
                 Some("desc") => {
                     let rest: Vec<&str> = parts.collect();
                     if rest.is_empty() {
-                        println!("Usage: meta desc <text...>");
+                        say!("Usage: meta desc <text...>");
                         continue;
                     }
                     let desc = rest.join(" ");
                     schedule.set_project_description(desc);
-                    println!("Project description updated.");
+                    say!("Project description updated.");
                     print_metadata(&schedule);
                 }
                 Some("dates") => {
@@ -587,30 +1002,30 @@ fn main() {
                             let start = match NaiveDate::parse_from_str(start_s, "%Y-%m-%d") {
                                 Ok(d) => d,
                                 Err(_) => {
-                                    println!("Invalid start date (YYYY-MM-DD)");
+                                    say!("Invalid start date (YYYY-MM-DD)");
                                     continue;
                                 }
                             };
                             let end = match NaiveDate::parse_from_str(end_s, "%Y-%m-%d") {
                                 Ok(d) => d,
                                 Err(_) => {
-                                    println!("Invalid end date (YYYY-MM-DD)");
+                                    say!("Invalid end date (YYYY-MM-DD)");
                                     continue;
                                 }
                             };
                             match schedule.set_project_dates(start, end) {
                                 Ok(_) => match schedule.refresh() {
                                     Ok(summary) => {
-                                        println!(
+                                        say!(
                                             "Metadata dates updated ({}).",
                                             summary.to_cli_summary()
                                         );
                                         print_metadata(&schedule);
                                     }
-                                    Err(e) => println!("Refresh error: {}", e),
+                                    Err(e) => say!("Refresh error: {}", e),
                                 },
                                 Err(ScheduleMetadataError::StartAfterEnd { .. }) => {
-                                    println!(
+                                    say!(
                                         "Project start date must be on or before project end date."
                                     );
                                 }
@@ -618,32 +1033,52 @@ fn main() {
                                     project_end,
                                     required_finish,
                                 }) => {
-                                    println!(
+                                    say!(
                                         "Project end date {} is before current schedule finish {}.",
-                                        project_end, required_finish
+                                        project_end,
+                                        required_finish
                                     );
                                 }
-                                Err(ScheduleMetadataError::Computation(message)) => {
-                                    println!("Metadata update error: {}", message);
-                                }
+                                Err(e) => say!("Metadata update error: {}", e),
                             }
                         }
-                        _ => println!("Usage: meta dates <YYYY-MM-DD> <YYYY-MM-DD>"),
+                        _ => say!("Usage: meta dates <YYYY-MM-DD> <YYYY-MM-DD>"),
                     }
                 }
                 Some(other) => {
-                    println!("Unknown meta command '{}'.", other);
-                    println!("Usage: meta show|name|desc|dates ...");
+                    say!("Unknown meta command '{}'.", other);
+                    say!("Usage: meta show|name|desc|dates ...");
                 }
             },
             "calendar" => match parts.next() {
                 Some("show") | None => print_calendar_info(&schedule),
                 Some("default") => match schedule.reset_calendar_to_default() {
                     Ok(_) => {
-                        println!("Calendar reset to default.");
+                        say!("Calendar reset to default.");
                         print_calendar_info(&schedule);
                     }
-                    Err(e) => println!("Error resetting calendar: {}", e),
+                    Err(e) => say!("Error resetting calendar: {}", e),
+                },
+                Some("preset") => match parts.next().map(CalendarPreset::from_str) {
+                    Some(Some(preset)) => {
+                        let metadata = schedule.metadata();
+                        let calendar = WorkCalendar::preset(
+                            preset,
+                            metadata.project_start_date.year(),
+                            metadata.project_end_date.year(),
+                        );
+                        match schedule.set_calendar(calendar) {
+                            Ok(_) => {
+                                say!("Calendar set to preset {}.", preset.as_str());
+                                print_calendar_info(&schedule);
+                            }
+                            Err(e) => say!("Error applying calendar: {}", e),
+                        }
+                    }
+                    _ => say!(
+                        "Usage: calendar preset <{}>",
+                        CalendarPreset::ALL.map(|preset| preset.as_str()).join("|")
+                    ),
                 },
                 Some("set") => {
                     let path = parts.next();
@@ -654,18 +1089,18 @@ fn main() {
                                     Ok(config) => {
                                         match schedule.set_calendar_from_config(&config) {
                                             Ok(_) => {
-                                                println!("Calendar updated from {}.", path);
+                                                say!("Calendar updated from {}.", path);
                                                 print_calendar_info(&schedule);
                                             }
-                                            Err(e) => println!("Error applying calendar: {}", e),
+                                            Err(e) => say!("Error applying calendar: {}", e),
                                         }
                                     }
-                                    Err(e) => println!("Invalid calendar JSON: {}", e),
+                                    Err(e) => say!("Invalid calendar JSON: {}", e),
                                 }
                             }
-                            Err(e) => println!("Error reading {}: {}", path, e),
+                            Err(e) => say!("Error reading {}: {}", path, e),
                         },
-                        None => println!("Usage: calendar set <json_path>"),
+                        None => say!("Usage: calendar set <json_path>"),
                     }
                 }
                 Some("save") => {
@@ -675,18 +1110,57 @@ fn main() {
                             let config = schedule.calendar_config();
                             match serde_json::to_string_pretty(&config) {
                                 Ok(json) => match fs::write(path, json) {
-                                    Ok(_) => println!("Calendar saved to {}.", path),
-                                    Err(e) => println!("Error writing {}: {}", path, e),
+                                    Ok(_) => say!("Calendar saved to {}.", path),
+                                    Err(e) => say!("Error writing {}: {}", path, e),
                                 },
-                                Err(e) => println!("Error serializing calendar: {}", e),
+                                Err(e) => say!("Error serializing calendar: {}", e),
                             }
                         }
-                        None => println!("Usage: calendar save <json_path>"),
+                        None => say!("Usage: calendar save <json_path>"),
+                    }
+                }
+                Some(other) => {
+                    say!("Unknown calendar command '{}'.", other);
+                    say!(
+                        "Usage: calendar show|default|preset <name>|set <json_path>|save <json_path>"
+                    );
+                }
+            },
+            "workspace" => match parts.next() {
+                Some("save") => {
+                    let target = parts
+                        .next()
+                        .map(PathBuf::from)
+                        .or_else(|| workspace.clone());
+                    match target {
+                        Some(path) => match save_schedule_to_json(&schedule, &path) {
+                            Ok(_) => {
+                                say!("Workspace saved to {}.", path.display());
+                                workspace = Some(path);
+                            }
+                            Err(e) => say!("Error saving workspace: {}", e),
+                        },
+                        None => say!("Usage: workspace save <path>"),
                     }
                 }
+                Some("open") => match parts.next() {
+                    Some(path) => {
+                        let path = PathBuf::from(path);
+                        if let Some(loaded) = open_workspace(&path) {
+                            schedule = loaded;
+                            workspace = Some(path);
+                            say!("{}", render_df_as_text_table(schedule.dataframe()));
+                        }
+                    }
+                    None => say!("Usage: workspace open <path>"),
+                },
+                Some("show") | None => match workspace.as_deref() {
+                    Some(path) => say!("Workspace: {}", path.display()),
+                    None => say!("No workspace file. Use 'workspace save <path>'."),
+                },
                 Some(other) => {
-                    println!("Unknown calendar command '{}'.", other);
-                    println!("Usage: calendar show|default|set <json_path>|save <json_path>");
+                    say!("Unknown workspace command '{}'.", other);
+                    say!("Usage: workspace show|save [path]|open <path>");
                 }
             },
             "save" => {
@@ -694,14 +1168,27 @@ fn main() {
                 let path = parts.next();
                 match (fmt, path) {
                     (Some("json"), Some(path)) => match save_schedule_to_json(&schedule, path) {
-                        Ok(_) => println!("Schedule saved to {}.", path),
-                        Err(e) => println!("Error saving schedule: {}", e),
+                        Ok(_) => say!("Schedule saved to {}.", path),
+                        Err(e) => say!("Error saving schedule: {}", e),
                     },
                     (Some("csv"), Some(path)) => match save_schedule_to_csv(&schedule, path) {
-                        Ok(_) => println!("Schedule saved to {}.", path),
-                        Err(e) => println!("Error saving schedule: {}", e),
+                        Ok(_) => say!("Schedule saved to {}.", path),
+                        Err(e) => say!("Error saving schedule: {}", e),
                     },
-                    _ => println!("Usage: save <json|csv> <path>"),
+                    #[cfg(feature = "parquet")]
+                    (Some("parquet"), Some(path)) => {
+                        match save_schedule_to_parquet(&schedule, path) {
+                            Ok(_) => say!("Schedule saved to {}.", path),
+                            Err(e) => say!("Error saving schedule: {}", e),
+                        }
+                    }
+                    #[cfg(not(feature = "parquet"))]
+                    (Some("parquet"), Some(_)) => say!("{}", PARQUET_DISABLED),
+                    (Some("mspdi"), Some(path)) => match save_schedule_to_mspdi(&schedule, path) {
+                        Ok(_) => say!("Schedule exported to {}.", path),
+                        Err(e) => say!("Error exporting schedule: {}", e),
+                    },
+                    _ => say!("Usage: save <json|csv|parquet|mspdi> <path>"),
                 }
             }
             "load" => {
@@ -712,30 +1199,106 @@ fn main() {
                         Ok(loaded) => {
                             schedule = loaded;
                             if let Err(e) = schedule.refresh() {
-                                println!("Loaded schedule but refresh failed: {}", e);
+                                say!("Loaded schedule but refresh failed: {}", e);
                             }
-                            println!("Schedule loaded from {}.", path);
-                            println!("{}", render_df_as_text_table(schedule.dataframe()));
+                            say!("Schedule loaded from {}.", path);
+                            say!("{}", render_df_as_text_table(schedule.dataframe()));
                         }
-                        Err(e) => println!("Error loading schedule: {}", e),
+                        Err(e) => say!("Error loading schedule: {}", e),
                     },
                     (Some("csv"), Some(path)) => match load_schedule_from_csv(path) {
                         Ok(mut loaded) => {
                             if let Err(e) = loaded.refresh() {
-                                println!("Loaded schedule but refresh failed: {}", e);
+                                say!("Loaded schedule but refresh failed: {}", e);
                             }
                             schedule = loaded;
-                            println!("Schedule loaded from {}.", path);
-                            println!("{}", render_df_as_text_table(schedule.dataframe()));
+                            say!("Schedule loaded from {}.", path);
+                            say!("{}", render_df_as_text_table(schedule.dataframe()));
                         }
-                        Err(e) => println!("Error loading schedule: {}", e),
+                        Err(e) => say!("Error loading schedule: {}", e),
                     },
-                    _ => println!("Usage: load <json|csv> <path>"),
+                    #[cfg(feature = "parquet")]
+                    (Some("parquet"), Some(path)) => {
+                        match load_schedule_from_parquet(path, &schedule) {
+                            Ok(mut loaded) => {
+                                if let Err(e) = loaded.refresh() {
+                                    say!("Loaded schedule but refresh failed: {}", e);
+                                }
+                                schedule = loaded;
+                                say!("Schedule loaded from {}.", path);
+                                say!("{}", render_df_as_text_table(schedule.dataframe()));
+                            }
+                            Err(e) => say!("Error loading schedule: {}", e),
+                        }
+                    }
+                    #[cfg(not(feature = "parquet"))]
+                    (Some("parquet"), Some(_)) => say!("{}", PARQUET_DISABLED),
+                    (Some("mspdi"), Some(_)) => {
+                        say!("mspdi is export-only; use 'save mspdi <path>'.")
+                    }
+                    _ => say!("Usage: load <json|csv|parquet> <path>"),
                 }
             }
             _ => {
-                println!("Unknown command. Type 'help'.");
+                say!("Unknown command. Type 'help'.");
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::{NamedFrom, Series};
+
+    #[test]
+    fn parse_args_reads_json_flag_and_workspace_path() {
+        let args = |list: &[&str]| parse_args(list.iter().map(|arg| arg.to_string()));
+        assert_eq!(args(&[]).unwrap(), CliArgs::default());
+        let parsed = args(&["session.json", "--json"]).unwrap();
+        assert!(parsed.json);
+        assert_eq!(parsed.workspace, Some(PathBuf::from("session.json")));
+        assert!(args(&["a.json", "b.json"]).is_err());
+        assert!(args(&["--verbose"]).is_err());
+    }
+
+    #[test]
+    fn format_cell_renders_dates_and_successor_lists() {
+        let mut schedule = Schedule::new();
+        schedule.upsert_task(1, "A", 2, None).unwrap();
+        schedule.upsert_task(2, "B", 1, Some(vec![1])).unwrap();
+        schedule.refresh().unwrap();
+        let df = schedule.dataframe();
+
+        let early_start = df.column("early_start").unwrap().get(0).unwrap();
+        let expected = schedule.find_task(1).unwrap().unwrap().early_start.unwrap();
+        assert_eq!(
+            format_cell("early_start", &early_start),
+            expected.format("%Y-%m-%d").to_string()
+        );
+
+        let successors = df.column("successors").unwrap().get(0).unwrap();
+        assert_eq!(format_cell("successors", &successors), "2");
+
+        let critical = Series::new("is_critical".into(), [true, false]);
+        assert_eq!(format_cell("is_critical", &critical.get(0).unwrap()), "*");
+        assert_eq!(format_cell("is_critical", &critical.get(1).unwrap()), "");
+    }
+
+    #[test]
+    fn render_table_colors_only_critical_rows() {
+        let df = DataFrame::new(vec![
+            Series::new("id".into(), [1i32, 2]).into(),
+            Series::new("is_critical".into(), [true, false]).into(),
+        ])
+        .unwrap();
+
+        let plain = render_table(&df, false);
+        assert!(!plain.contains(CRITICAL_ROW_COLOR));
+        assert!(plain.contains("| 1  | *           |"));
+
+        let colored = render_table(&df, true);
+        assert_eq!(colored.matches(CRITICAL_ROW_COLOR).count(), 1);
+        assert!(colored.contains(&format!("{CRITICAL_ROW_COLOR}| 1 ")));
+    }
+}
@@ -0,0 +1,173 @@
+use crate::schedule::Schedule;
+use crate::task::{Task, TaskKind};
+use polars::prelude::PolarsError;
+use std::collections::{HashMap, HashSet};
+
+impl Schedule {
+    /// Append a [`TaskKind::Buffer`] task after the last task of the
+    /// critical path, sized at `fraction` of the path's summed working-day
+    /// durations (rounded up). Returns the buffer's id, one above the
+    /// highest id in use. Needs a refreshed schedule with a critical path;
+    /// the project end date may have to move out before the next refresh.
+    pub fn insert_project_buffer(&mut self, fraction: f64) -> Result<i32, PolarsError> {
+        self.edit(|schedule| {
+            Self::check_buffer_fraction(fraction)?;
+            let tasks = schedule.tasks()?;
+            let by_id: HashMap<i32, &Task> = tasks.iter().map(|task| (task.id, task)).collect();
+            let critical_path = schedule.critical_path()?;
+            let Some(&finish_id) = critical_path.last() else {
+                return Err(PolarsError::ComputeError(
+                    "no critical path to buffer; refresh the schedule first".into(),
+                ));
+            };
+            let chain_days: i64 = critical_path
+                .iter()
+                .filter_map(|id| by_id.get(id))
+                .map(|task| task.duration_days)
+                .sum();
+
+            let buffer_id = Self::next_buffer_id(&tasks);
+            schedule.upsert_task_record(Self::buffer_task(
+                buffer_id,
+                "Project buffer",
+                fraction,
+                chain_days,
+                finish_id,
+            ))?;
+            Ok(buffer_id)
+        })
+    }
+
+    /// Put a [`TaskKind::Buffer`] task on every link where a non-critical
+    /// task feeds a critical one, sized at `fraction` of the longest
+    /// non-critical chain ending in the feeding task (rounded up). The
+    /// critical task then depends on the buffer instead. Returns the new
+    /// ids in ascending order; links already running through a buffer are
+    /// left alone. Reflects the criticality of the last refresh.
+    pub fn insert_feeding_buffers(&mut self, fraction: f64) -> Result<Vec<i32>, PolarsError> {
+        self.edit(|schedule| {
+            Self::check_buffer_fraction(fraction)?;
+            let tasks = schedule.tasks()?;
+            let by_id: HashMap<i32, &Task> = tasks.iter().map(|task| (task.id, task)).collect();
+            let critical: HashSet<i32> = tasks
+                .iter()
+                .filter(|task| task.is_critical == Some(true))
+                .map(|task| task.id)
+                .collect();
+
+            let mut feeds: Vec<(i32, i32)> = tasks
+                .iter()
+                .filter(|task| critical.contains(&task.id))
+                .flat_map(|task| task.predecessors.iter().map(move |pred| (*pred, task.id)))
+                .filter(|(pred, _)| {
+                    by_id.get(pred).is_some_and(|pred| {
+                        !critical.contains(&pred.id) && pred.task_kind != TaskKind::Buffer
+                    })
+                })
+                .collect();
+            feeds.sort_unstable();
+            feeds.dedup();
+
+            let mut chain_days: HashMap<i32, i64> = HashMap::new();
+            let first_id = Self::next_buffer_id(&tasks);
+            let mut inserted = Vec::with_capacity(feeds.len());
+            for (buffer_id, (feeder, joined)) in (first_id..).zip(feeds) {
+                let days = Self::feeding_chain_days(feeder, &by_id, &critical, &mut chain_days);
+                let name = format!("Feeding buffer {feeder}->{joined}");
+                schedule.upsert_task_record(Self::buffer_task(
+                    buffer_id, name, fraction, days, feeder,
+                ))?;
+                let mut joined_task = schedule.find_task(joined)?.ok_or_else(|| {
+                    PolarsError::ComputeError(format!("task {joined} not found").into())
+                })?;
+                for pred in &mut joined_task.predecessors {
+                    if *pred == feeder {
+                        *pred = buffer_id;
+                    }
+                }
+                schedule.upsert_task_record(joined_task)?;
+                inserted.push(buffer_id);
+            }
+            Ok(inserted)
+        })
+    }
+
+    fn check_buffer_fraction(fraction: f64) -> Result<(), PolarsError> {
+        if !fraction.is_finite() || fraction <= 0.0 {
+            return Err(PolarsError::ComputeError(
+                format!("buffer fraction must be positive (got {fraction})").into(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn next_buffer_id(tasks: &[Task]) -> i32 {
+        tasks.iter().map(|task| task.id).max().unwrap_or(0) + 1
+    }
+
+    fn buffer_task(
+        id: i32,
+        name: impl Into<String>,
+        fraction: f64,
+        chain_days: i64,
+        predecessor: i32,
+    ) -> Task {
+        let duration = (chain_days as f64 * fraction).ceil() as i64;
+        let mut task = Task::new(id, name, duration.max(0));
+        task.task_kind = TaskKind::Buffer;
+        task.predecessors = vec![predecessor];
+        task
+    }
+
+    /// Summed durations of the longest chain of non-critical tasks ending
+    /// in `task_id`. Walks the chain with an explicit stack so long chains
+    /// cannot overflow the call stack.
+    fn feeding_chain_days(
+        task_id: i32,
+        by_id: &HashMap<i32, &Task>,
+        critical: &HashSet<i32>,
+        memo: &mut HashMap<i32, i64>,
+    ) -> i64 {
+        let upstream = |id: i32| {
+            by_id
+                .get(&id)
+                .into_iter()
+                .flat_map(|task| task.predecessors.iter().copied())
+                .filter(|pred| !critical.contains(pred))
+        };
+        // Each entry is visited twice: once to queue its predecessors and
+        // once, after they are all known, to sum its own chain.
+        let mut stack = vec![(task_id, false)];
+        let mut on_stack = HashSet::new();
+        while let Some((id, expanded)) = stack.pop() {
+            if memo.contains_key(&id) {
+                continue;
+            }
+            let Some(task) = by_id.get(&id) else {
+                memo.insert(id, 0);
+                continue;
+            };
+            if !expanded {
+                // A cycle would never resolve; treat the repeat as empty.
+                if !on_stack.insert(id) {
+                    memo.insert(id, 0);
+                    continue;
+                }
+                stack.push((id, true));
+                stack.extend(
+                    upstream(id)
+                        .filter(|pred| !memo.contains_key(pred))
+                        .map(|pred| (pred, false)),
+                );
+                continue;
+            }
+            let longest = upstream(id)
+                .map(|pred| memo.get(&pred).copied().unwrap_or(0))
+                .max()
+                .unwrap_or(0);
+            on_stack.remove(&id);
+            memo.insert(id, longest + task.duration_days);
+        }
+        memo.get(&task_id).copied().unwrap_or(0)
+    }
+}
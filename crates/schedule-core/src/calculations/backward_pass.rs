@@ -1,8 +1,7 @@
+use super::{cpm, cpm_tasks};
 use crate::calendar::WorkCalendar;
 use crate::graph::schedule_dag::ScheduleDag;
-use chrono::NaiveDate;
-use petgraph::Direction;
-use petgraph::algo::toposort;
+use chrono::{Duration, NaiveDate};
 use polars::prelude::*;
 use std::collections::HashMap;
 
@@ -21,50 +20,37 @@ impl<'a> BackwardPass<'a> {
         project_end: NaiveDate,
     ) -> Result<HashMap<i32, (NaiveDate, NaiveDate)>, PolarsError> {
         let dag = ScheduleDag::build(self.df)?;
+        self.execute_with_dag(&dag, project_end)
+    }
 
-        // LS/LF maps keyed by task id
-        let mut late_starts: HashMap<i32, NaiveDate> = HashMap::new();
-        let mut late_finishes: HashMap<i32, NaiveDate> = HashMap::new();
-
-        // Reverse topological order
-        let mut order = toposort(&dag.graph, None)
-            .map_err(|_| PolarsError::ComputeError("Cycle detected in schedule DAG".into()))?;
-        order.reverse();
-
-        for node_ix in order {
-            let task_id = dag.graph[node_ix];
-
-            // Determine allowed late finish from successors' late starts
-            let mut lf = project_end;
-            let mut has_succ = false;
-            for succ_ix in dag.graph.neighbors_directed(node_ix, Direction::Outgoing) {
-                let succ_id = dag.graph[succ_ix];
-                if let Some(ls_succ) = late_starts.get(&succ_id).copied() {
-                    has_succ = true;
-                    let prev = self.calendar.prev_available(ls_succ);
-                    if prev < lf {
-                        lf = prev;
-                    }
-                }
-            }
-            if !has_succ {
-                lf = project_end;
-            }
-
-            let duration = *dag.durations.get(&task_id).unwrap_or(&0);
-            let ls = self.calendar.find_prev_available(lf, duration);
-
-            late_finishes.insert(task_id, lf);
-            late_starts.insert(task_id, ls);
+    /// Run the pass over a DAG the caller already built from this DataFrame.
+    pub fn execute_with_dag(
+        &self,
+        dag: &ScheduleDag,
+        project_end: NaiveDate,
+    ) -> Result<HashMap<i32, (NaiveDate, NaiveDate)>, PolarsError> {
+        let deadlines = self.deadlines()?;
+        let mut tasks = cpm_tasks(dag);
+        for task in &mut tasks {
+            task.deadline = deadlines.get(&task.id).copied();
         }
+        let dates = cpm::backward_pass(&tasks, self.calendar, project_end)?;
+        Ok(tasks.iter().map(|task| task.id).zip(dates).collect())
+    }
 
-        // Pack results
-        let mut results = HashMap::new();
-        for (task_id, ls) in late_starts.into_iter() {
-            if let Some(&lf) = late_finishes.get(&task_id) {
-                results.insert(task_id, (ls, lf));
+    fn deadlines(&self) -> Result<HashMap<i32, NaiveDate>, PolarsError> {
+        let mut deadlines = HashMap::new();
+        let Ok(column) = self.df.column("deadline") else {
+            return Ok(deadlines);
+        };
+        let ids = self.df.column("id")?.i32()?;
+        let dates = column.date()?;
+        let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+        for (idx, id) in ids.into_iter().enumerate() {
+            if let (Some(id), Some(days)) = (id, dates.get(idx)) {
+                deadlines.insert(id, epoch + Duration::days(days as i64));
             }
         }
-        Ok(results)
+        Ok(deadlines)
     }
 }
@@ -0,0 +1,317 @@
+//! Critical path method over plain structs.
+//!
+//! Nothing here depends on Polars, chrono or `std`; only `core` and `alloc`
+//! are used, so the module can be lifted into `no_std` builds. Dates are
+//! whatever the [`CpmCalendar`] works in. [`ForwardPass`] and
+//! [`BackwardPass`] are adapters that feed a task table through these
+//! functions.
+//!
+//! [`ForwardPass`]: super::forward_pass::ForwardPass
+//! [`BackwardPass`]: super::backward_pass::BackwardPass
+
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Working-time rules the passes schedule against.
+pub trait CpmCalendar {
+    type Date: Copy + Ord;
+    type Error;
+
+    fn is_available(&self, date: Self::Date) -> bool;
+
+    /// First available date strictly after `from`.
+    fn next_available(&self, from: Self::Date) -> Result<Self::Date, Self::Error>;
+
+    /// Last available date strictly before `from`.
+    fn prev_available(&self, from: Self::Date) -> Result<Self::Date, Self::Error>;
+
+    /// Signed distance from `early` to `late`, in the unit total float is
+    /// reported in.
+    fn float_between(&self, early: Self::Date, late: Self::Date) -> i64;
+}
+
+/// One task as the passes see it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CpmTask<D> {
+    /// Unique within the slice passed to the passes.
+    pub id: i32,
+    pub duration_days: i64,
+    /// Ids that must finish first. Ids not in the slice are ignored.
+    pub predecessors: Vec<i32>,
+    /// Floor on the early start; see `Task::forced_early_start`.
+    pub forced_early_start: Option<D>,
+    /// Cap on the late finish; see `Task::deadline`.
+    pub deadline: Option<D>,
+    /// Set once the task is under way; its early dates then come from the
+    /// work already done rather than from its predecessors.
+    pub progress: Option<CpmProgress<D>>,
+    /// Exact early start, e.g. a recorded actual start; predecessors and
+    /// the forced start no longer move it.
+    pub pinned_start: Option<D>,
+    /// Exact early finish, e.g. a recorded actual finish.
+    pub pinned_finish: Option<D>,
+}
+
+/// A started task's position in the forward pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CpmProgress<D> {
+    /// Early start, as recorded.
+    pub actual_start: D,
+    /// Where the remaining work is counted from: the actual start, or a
+    /// later data date.
+    pub resume_from: D,
+    /// Available days of work left.
+    pub remaining_duration_days: i64,
+}
+
+impl<D> CpmTask<D> {
+    pub fn new(id: i32, duration_days: i64, predecessors: Vec<i32>) -> Self {
+        Self {
+            id,
+            duration_days,
+            predecessors,
+            forced_early_start: None,
+            deadline: None,
+            progress: None,
+            pinned_start: None,
+            pinned_finish: None,
+        }
+    }
+}
+
+/// Dates and float for one task, in the order the tasks were given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CpmTaskResult<D> {
+    pub id: i32,
+    pub early_start: D,
+    pub early_finish: D,
+    pub late_start: D,
+    pub late_finish: D,
+    pub total_float: i64,
+    /// Zero or negative total float.
+    pub is_critical: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CpmResult<D> {
+    pub tasks: Vec<CpmTaskResult<D>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CpmError<E> {
+    /// The predecessor links form a cycle.
+    Cycle,
+    /// The calendar could not find an available date.
+    Calendar(E),
+}
+
+impl<E: fmt::Display> fmt::Display for CpmError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CpmError::Cycle => write!(f, "Cycle detected in schedule DAG"),
+            CpmError::Calendar(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+/// `(start, finish)` per task, in input order.
+type PassResult<C> = Result<
+    Vec<(<C as CpmCalendar>::Date, <C as CpmCalendar>::Date)>,
+    CpmError<<C as CpmCalendar>::Error>,
+>;
+
+/// Run both passes from `project_start` and back from `project_end`.
+pub fn compute<C: CpmCalendar>(
+    tasks: &[CpmTask<C::Date>],
+    calendar: &C,
+    project_start: C::Date,
+    project_end: C::Date,
+) -> Result<CpmResult<C::Date>, CpmError<C::Error>> {
+    let early = forward_pass(tasks, calendar, project_start)?;
+    let late = backward_pass(tasks, calendar, project_end)?;
+    let tasks = tasks
+        .iter()
+        .zip(early.into_iter().zip(late))
+        .map(
+            |(task, ((early_start, early_finish), (late_start, late_finish)))| {
+                let total_float = calendar.float_between(early_start, late_start);
+                CpmTaskResult {
+                    id: task.id,
+                    early_start,
+                    early_finish,
+                    late_start,
+                    late_finish,
+                    total_float,
+                    is_critical: total_float <= 0,
+                }
+            },
+        )
+        .collect();
+    Ok(CpmResult { tasks })
+}
+
+/// Early `(start, finish)` per task, in input order. A task starts on the
+/// first available day after its latest predecessor finishes, or on
+/// `project_start` without predecessors, and no earlier than its forced
+/// start. It finishes `duration_days` available days after it starts.
+/// A task in progress keeps its actual start and finishes its remaining
+/// days after it resumes. Pinned dates replace the computed ones.
+pub fn forward_pass<C: CpmCalendar>(
+    tasks: &[CpmTask<C::Date>],
+    calendar: &C,
+    project_start: C::Date,
+) -> PassResult<C> {
+    let network = Network::build(tasks)?;
+    let mut dates: Vec<Option<(C::Date, C::Date)>> = vec![None; tasks.len()];
+    for &idx in &network.order {
+        let task = &tasks[idx];
+        if let Some(progress) = &task.progress {
+            let mut finish =
+                roll_forward(calendar, progress.resume_from).map_err(CpmError::Calendar)?;
+            for _ in 0..progress.remaining_duration_days {
+                finish = calendar
+                    .next_available(finish)
+                    .map_err(CpmError::Calendar)?;
+            }
+            dates[idx] = Some((progress.actual_start, finish));
+            continue;
+        }
+        let mut start = project_start;
+        let mut has_pred = false;
+        for &pred in &network.predecessors[idx] {
+            if let Some((_, finish)) = dates[pred] {
+                has_pred = true;
+                start = start.max(finish);
+            }
+        }
+        if has_pred {
+            start = calendar.next_available(start).map_err(CpmError::Calendar)?;
+        }
+        // A forced start is a floor, moved off non-working days like a
+        // deadline is.
+        if let Some(forced) = task.forced_early_start {
+            start = start.max(roll_forward(calendar, forced).map_err(CpmError::Calendar)?);
+        }
+        if let Some(pinned) = task.pinned_start {
+            start = pinned;
+        }
+        let finish = match task.pinned_finish {
+            Some(pinned) => pinned,
+            None => {
+                let mut finish = start;
+                for _ in 0..task.duration_days {
+                    finish = calendar
+                        .next_available(finish)
+                        .map_err(CpmError::Calendar)?;
+                }
+                finish
+            }
+        };
+        dates[idx] = Some((start, finish));
+    }
+    Ok(dates.into_iter().flatten().collect())
+}
+
+/// Late `(start, finish)` per task, in input order. A task must finish on
+/// the last available day before its earliest successor's late start, or
+/// on `project_end` without successors, and no later than its deadline.
+pub fn backward_pass<C: CpmCalendar>(
+    tasks: &[CpmTask<C::Date>],
+    calendar: &C,
+    project_end: C::Date,
+) -> PassResult<C> {
+    let network = Network::build(tasks)?;
+    let mut dates: Vec<Option<(C::Date, C::Date)>> = vec![None; tasks.len()];
+    for &idx in network.order.iter().rev() {
+        let task = &tasks[idx];
+        let mut finish = project_end;
+        for &succ in &network.successors[idx] {
+            if let Some((succ_start, _)) = dates[succ] {
+                finish = finish.min(
+                    calendar
+                        .prev_available(succ_start)
+                        .map_err(CpmError::Calendar)?,
+                );
+            }
+        }
+        // A deadline caps the late finish; if it precedes the early finish
+        // the task ends up with negative total float.
+        if let Some(deadline) = task.deadline {
+            finish = finish.min(roll_back(calendar, deadline).map_err(CpmError::Calendar)?);
+        }
+        let mut start = finish;
+        for _ in 0..task.duration_days {
+            start = calendar.prev_available(start).map_err(CpmError::Calendar)?;
+        }
+        dates[idx] = Some((start, finish));
+    }
+    Ok(dates.into_iter().flatten().collect())
+}
+
+fn roll_forward<C: CpmCalendar>(calendar: &C, date: C::Date) -> Result<C::Date, C::Error> {
+    if calendar.is_available(date) {
+        Ok(date)
+    } else {
+        calendar.next_available(date)
+    }
+}
+
+fn roll_back<C: CpmCalendar>(calendar: &C, date: C::Date) -> Result<C::Date, C::Error> {
+    if calendar.is_available(date) {
+        Ok(date)
+    } else {
+        calendar.prev_available(date)
+    }
+}
+
+/// Links by input position, plus a topological order over them.
+struct Network {
+    predecessors: Vec<Vec<usize>>,
+    successors: Vec<Vec<usize>>,
+    order: Vec<usize>,
+}
+
+impl Network {
+    fn build<D, E>(tasks: &[CpmTask<D>]) -> Result<Self, CpmError<E>> {
+        let index: BTreeMap<i32, usize> = tasks
+            .iter()
+            .enumerate()
+            .map(|(idx, task)| (task.id, idx))
+            .collect();
+        let mut predecessors = vec![Vec::new(); tasks.len()];
+        let mut successors = vec![Vec::new(); tasks.len()];
+        for (idx, task) in tasks.iter().enumerate() {
+            for pred_id in &task.predecessors {
+                if let Some(&pred) = index.get(pred_id) {
+                    predecessors[idx].push(pred);
+                    successors[pred].push(idx);
+                }
+            }
+        }
+
+        // Kahn's algorithm; anything left unvisited sits on a cycle.
+        let mut waiting: Vec<usize> = predecessors.iter().map(Vec::len).collect();
+        let mut ready: VecDeque<usize> =
+            (0..tasks.len()).filter(|&idx| waiting[idx] == 0).collect();
+        let mut order = Vec::with_capacity(tasks.len());
+        while let Some(idx) = ready.pop_front() {
+            order.push(idx);
+            for &succ in &successors[idx] {
+                waiting[succ] -= 1;
+                if waiting[succ] == 0 {
+                    ready.push_back(succ);
+                }
+            }
+        }
+        if order.len() < tasks.len() {
+            return Err(CpmError::Cycle);
+        }
+        Ok(Self {
+            predecessors,
+            successors,
+            order,
+        })
+    }
+}
@@ -1,19 +1,40 @@
+use super::cpm::CpmProgress;
+use super::{cpm, cpm_tasks};
 use crate::calendar::WorkCalendar;
 use crate::graph::schedule_dag::ScheduleDag;
-use chrono::NaiveDate;
-use petgraph::Direction;
-use petgraph::algo::toposort;
+use chrono::{Duration, NaiveDate};
 use polars::prelude::*;
 use std::collections::HashMap;
 
 pub struct ForwardPass<'a> {
     df: &'a DataFrame,
     calendar: &'a WorkCalendar,
+    data_date: Option<NaiveDate>,
+    pin_actuals: bool,
 }
 
 impl<'a> ForwardPass<'a> {
     pub fn new(df: &'a DataFrame, calendar: &'a WorkCalendar) -> Self {
-        Self { df, calendar }
+        Self {
+            df,
+            calendar,
+            data_date: None,
+            pin_actuals: false,
+        }
+    }
+
+    /// Count the remaining work of tasks in progress from `data_date` when
+    /// it falls after their actual start.
+    pub fn with_data_date(mut self, data_date: Option<NaiveDate>) -> Self {
+        self.data_date = data_date;
+        self
+    }
+
+    /// Start tasks on their recorded actual start, and finish completed
+    /// ones on their actual finish, whatever their predecessors say.
+    pub fn with_pinned_actuals(mut self, pin_actuals: bool) -> Self {
+        self.pin_actuals = pin_actuals;
+        self
     }
 
     pub fn execute(
@@ -21,50 +42,102 @@ impl<'a> ForwardPass<'a> {
         project_start: NaiveDate,
     ) -> Result<HashMap<i32, (NaiveDate, NaiveDate)>, PolarsError> {
         let dag = ScheduleDag::build(self.df)?;
+        self.execute_with_dag(&dag, project_start)
+    }
 
-        // ES/EF maps keyed by task id
-        let mut early_starts: HashMap<i32, NaiveDate> = HashMap::new();
-        let mut early_finishes: HashMap<i32, NaiveDate> = HashMap::new();
-
-        // Topological order over nodes
-        let order = toposort(&dag.graph, None)
-            .map_err(|_| PolarsError::ComputeError("Cycle detected in schedule DAG".into()))?;
-
-        for node_ix in order {
-            let task_id = dag.graph[node_ix];
-
-            // Determine early start from predecessors' early finishes
-            let mut es = project_start;
-            let mut has_pred = false;
-            for pred_ix in dag.graph.neighbors_directed(node_ix, Direction::Incoming) {
-                let pred_id = dag.graph[pred_ix];
-                if let Some(ef) = early_finishes.get(&pred_id).copied() {
-                    has_pred = true;
-                    if ef > es {
-                        es = ef;
-                    }
-                }
-            }
-            if has_pred {
-                es = self.calendar.next_available(es);
+    /// Run the pass over a DAG the caller already built from this DataFrame.
+    pub fn execute_with_dag(
+        &self,
+        dag: &ScheduleDag,
+        project_start: NaiveDate,
+    ) -> Result<HashMap<i32, (NaiveDate, NaiveDate)>, PolarsError> {
+        let forced_starts = self.forced_early_starts()?;
+        let mut progress = self.in_progress()?;
+        let mut pinned = if self.pin_actuals {
+            self.actual_dates()?
+        } else {
+            HashMap::new()
+        };
+        let mut tasks = cpm_tasks(dag);
+        for task in &mut tasks {
+            task.forced_early_start = forced_starts.get(&task.id).copied();
+            task.progress = progress.remove(&task.id);
+            if let Some((start, finish)) = pinned.remove(&task.id) {
+                task.pinned_start = Some(start);
+                task.pinned_finish = finish;
             }
+        }
+        let dates = cpm::forward_pass(&tasks, self.calendar, project_start)?;
+        Ok(tasks.iter().map(|task| task.id).zip(dates).collect())
+    }
 
-            let duration = *dag.durations.get(&task_id).unwrap_or(&0);
-            let ef = self.calendar.find_next_available(es, duration);
-
-            early_starts.insert(task_id, es);
-            early_finishes.insert(task_id, ef);
+    /// Started, unfinished tasks with a percent complete strictly between
+    /// 0 and 1. Their remaining duration is `(1 - percent) * duration_days`,
+    /// rounded up to whole working days.
+    fn in_progress(&self) -> Result<HashMap<i32, CpmProgress<NaiveDate>>, PolarsError> {
+        let mut progress = HashMap::new();
+        let ids = self.df.column("id")?.i32()?;
+        let durations = self.df.column("duration_days")?.i64()?;
+        let actual_starts = self.df.column("actual_start")?.date()?;
+        let actual_finishes = self.df.column("actual_finish")?.date()?;
+        let percents = self.df.column("percent_complete")?.f64()?;
+        let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+        for (idx, id) in ids.into_iter().enumerate() {
+            let (Some(id), Some(days), Some(percent)) =
+                (id, actual_starts.get(idx), percents.get(idx))
+            else {
+                continue;
+            };
+            if actual_finishes.get(idx).is_some() || percent <= 0.0 || percent >= 1.0 {
+                continue;
+            }
+            let actual_start = epoch + Duration::days(days as i64);
+            let duration = durations.get(idx).unwrap_or(0);
+            let remaining = ((1.0 - percent) * duration as f64).ceil() as i64;
+            let resume_from = self
+                .data_date
+                .map_or(actual_start, |data_date| data_date.max(actual_start));
+            progress.insert(
+                id,
+                CpmProgress {
+                    actual_start,
+                    resume_from,
+                    remaining_duration_days: remaining,
+                },
+            );
         }
+        Ok(progress)
+    }
 
-        // Pack results
-        let mut results = HashMap::new();
-        for (task_id, es) in early_starts.into_iter() {
-            if let Some(&ef) = early_finishes.get(&task_id) {
-                results.insert(task_id, (es, ef));
+    /// Recorded actual start, and actual finish if any, of started tasks.
+    fn actual_dates(&self) -> Result<HashMap<i32, (NaiveDate, Option<NaiveDate>)>, PolarsError> {
+        let mut actuals = HashMap::new();
+        let ids = self.df.column("id")?.i32()?;
+        let starts = self.df.column("actual_start")?.date()?;
+        let finishes = self.df.column("actual_finish")?.date()?;
+        let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+        let date = |days: i32| epoch + Duration::days(days as i64);
+        for (idx, id) in ids.into_iter().enumerate() {
+            if let (Some(id), Some(start)) = (id, starts.get(idx)) {
+                actuals.insert(id, (date(start), finishes.get(idx).map(date)));
             }
         }
-        Ok(results)
+        Ok(actuals)
     }
 
-    // Note: branch-based processing replaced by petgraph traversal.
+    fn forced_early_starts(&self) -> Result<HashMap<i32, NaiveDate>, PolarsError> {
+        let mut forced = HashMap::new();
+        let Ok(column) = self.df.column("forced_early_start") else {
+            return Ok(forced);
+        };
+        let ids = self.df.column("id")?.i32()?;
+        let dates = column.date()?;
+        let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+        for (idx, id) in ids.into_iter().enumerate() {
+            if let (Some(id), Some(days)) = (id, dates.get(idx)) {
+                forced.insert(id, epoch + Duration::days(days as i64));
+            }
+        }
+        Ok(forced)
+    }
 }
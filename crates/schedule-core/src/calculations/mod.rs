@@ -1,2 +1,152 @@
 pub mod backward_pass;
+pub mod cpm;
 pub mod forward_pass;
+
+use crate::calendar::{CalendarError, WorkCalendar};
+use crate::graph::schedule_dag::ScheduleDag;
+use crate::task::Task;
+use backward_pass::BackwardPass;
+use chrono::NaiveDate;
+use cpm::{CpmCalendar, CpmError, CpmTask};
+use forward_pass::ForwardPass;
+use petgraph::Direction;
+use polars::prelude::*;
+
+impl CpmCalendar for WorkCalendar {
+    type Date = NaiveDate;
+    type Error = CalendarError;
+
+    fn is_available(&self, date: NaiveDate) -> bool {
+        WorkCalendar::is_available(self, date)
+    }
+
+    fn next_available(&self, from: NaiveDate) -> Result<NaiveDate, CalendarError> {
+        self.try_next_available(from)
+    }
+
+    fn prev_available(&self, from: NaiveDate) -> Result<NaiveDate, CalendarError> {
+        self.try_prev_available(from)
+    }
+
+    /// Calendar days, as `total_float` is stored on the task table.
+    fn float_between(&self, early: NaiveDate, late: NaiveDate) -> i64 {
+        (late - early).num_days()
+    }
+}
+
+impl From<CpmError<CalendarError>> for PolarsError {
+    fn from(value: CpmError<CalendarError>) -> Self {
+        PolarsError::ComputeError(value.to_string().into())
+    }
+}
+
+/// The DAG's tasks and links as [`CpmTask`]s, without forced starts or
+/// deadlines.
+pub(crate) fn cpm_tasks(dag: &ScheduleDag) -> Vec<CpmTask<NaiveDate>> {
+    dag.graph
+        .node_indices()
+        .map(|node| {
+            let id = dag.graph[node];
+            let predecessors = dag
+                .graph
+                .neighbors_directed(node, Direction::Incoming)
+                .map(|pred| dag.graph[pred])
+                .collect();
+            CpmTask::new(id, *dag.durations.get(&id).unwrap_or(&0), predecessors)
+        })
+        .collect()
+}
+
+/// Forward-pass settings [`crate::Schedule::refresh`] takes from the
+/// schedule rather than the task table. A schedule's own are returned by
+/// [`crate::Schedule::critical_path_options`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CriticalPathOptions {
+    /// Date the remaining work of tasks in progress is counted from.
+    pub data_date: Option<NaiveDate>,
+    /// Hold started and finished tasks on their actual dates.
+    pub pin_actual_dates: bool,
+}
+
+impl CriticalPathOptions {
+    pub fn with_data_date(mut self, data_date: Option<NaiveDate>) -> Self {
+        self.data_date = data_date;
+        self
+    }
+
+    pub fn with_pinned_actuals(mut self, pin_actual_dates: bool) -> Self {
+        self.pin_actual_dates = pin_actual_dates;
+        self
+    }
+}
+
+/// Critical chain of a task table without touching it, with default
+/// [`CriticalPathOptions`]. See [`critical_path_with_options`].
+pub fn critical_path(
+    df: &DataFrame,
+    calendar: &WorkCalendar,
+    project_start: NaiveDate,
+    project_end: NaiveDate,
+) -> Result<Vec<i32>, PolarsError> {
+    critical_path_with_options(
+        df,
+        calendar,
+        project_start,
+        project_end,
+        CriticalPathOptions::default(),
+    )
+}
+
+/// Critical chain of a task table without touching it: runs both passes on
+/// a read-only basis and returns tasks with zero or negative total float in
+/// the same order as [`crate::Schedule::critical_path`]. Date columns
+/// already in `df` are ignored, but `percent_complete` is read as stored.
+/// Given a refreshed schedule's table, calendar and options the result
+/// matches its critical path; ALAP scheduling only moves floating tasks, so
+/// it needs no option.
+pub fn critical_path_with_options(
+    df: &DataFrame,
+    calendar: &WorkCalendar,
+    project_start: NaiveDate,
+    project_end: NaiveDate,
+    options: CriticalPathOptions,
+) -> Result<Vec<i32>, PolarsError> {
+    if df.height() == 0 {
+        return Ok(Vec::new());
+    }
+    let dag = ScheduleDag::build(df)?;
+    let early = ForwardPass::new(df, calendar)
+        .with_data_date(options.data_date)
+        .with_pinned_actuals(options.pin_actual_dates)
+        .execute_with_dag(&dag, project_start)?;
+    let late = BackwardPass::new(df, calendar).execute_with_dag(&dag, project_end)?;
+
+    let id_ca = df.column("id")?.i32()?;
+    let priority_ca = df.column("priority")?.i32()?;
+    let mut critical = Vec::new();
+    for idx in 0..df.height() {
+        let Some(id) = id_ca.get(idx) else {
+            continue;
+        };
+        let Some(&(es, _)) = early.get(&id) else {
+            continue;
+        };
+        let ls = late.get(&id).map_or(es, |&(ls, _)| ls);
+        if (ls - es).num_days() <= 0 {
+            let priority = priority_ca.get(idx).unwrap_or(Task::DEFAULT_PRIORITY);
+            critical.push((es, priority, id));
+        }
+    }
+    Ok(order_critical_tasks(critical))
+}
+
+/// Order critical `(early_start, priority, id)` entries into a chain: by
+/// early start, then higher priority first, then by id.
+pub(crate) fn order_critical_tasks(mut critical: Vec<(NaiveDate, i32, i32)>) -> Vec<i32> {
+    critical.sort_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then_with(|| b.1.cmp(&a.1))
+            .then_with(|| a.2.cmp(&b.2))
+    });
+    critical.into_iter().map(|(_, _, id)| id).collect()
+}
@@ -1,17 +1,163 @@
 use chrono::{Datelike, Duration, NaiveDate, Weekday};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+
+/// Days searched for an available date before giving up, about ten years.
+pub const MAX_AVAILABILITY_SEARCH_DAYS: i64 = 3653;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalendarError {
+    /// Every weekday is non-working, so no date could ever be scheduled.
+    NoWorkingDays,
+    /// No available date within [`MAX_AVAILABILITY_SEARCH_DAYS`] of `from`.
+    NoAvailableDay { from: NaiveDate },
+    /// An iCalendar feed could not be read or has a malformed `DTSTART`.
+    #[cfg(feature = "ics")]
+    InvalidIcs { line: usize, message: String },
+}
+
+impl fmt::Display for CalendarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalendarError::NoWorkingDays => {
+                write!(f, "calendar requires at least one working day")
+            }
+            CalendarError::NoAvailableDay { from } => write!(
+                f,
+                "no available day within {MAX_AVAILABILITY_SEARCH_DAYS} days of {from}"
+            ),
+            #[cfg(feature = "ics")]
+            CalendarError::InvalidIcs { line, message } => {
+                write!(f, "invalid iCalendar data at line {line}: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CalendarError {}
+
+impl From<CalendarError> for polars::prelude::PolarsError {
+    fn from(value: CalendarError) -> Self {
+        polars::prelude::PolarsError::ComputeError(value.to_string().into())
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WorkCalendar {
     holidays: HashSet<NaiveDate>,
     non_working_days: HashSet<Weekday>,
+    /// Rules checked date by date, so they apply to every year.
+    #[serde(default)]
+    recurring_rules: Vec<RecurringHoliday>,
+    /// Preset the calendar was built from; kept through later edits.
+    #[serde(default)]
+    preset: Option<CalendarPreset>,
+    /// Descriptive labels for some of `holidays`. Availability ignores them.
+    #[serde(default)]
+    named_holidays: HashMap<NaiveDate, String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WorkCalendarConfig {
     working_days: Vec<Weekday>,
     holidays: Vec<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    recurring_rules: Vec<RecurringHoliday>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    preset: Option<CalendarPreset>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    named_holidays: BTreeMap<NaiveDate, String>,
+}
+
+/// Common calendars that [`WorkCalendar::preset`] builds in one call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CalendarPreset {
+    /// Monday to Friday with US federal holidays; the default calendar.
+    FiveDayUs,
+    /// Monday to Saturday with US federal holidays.
+    SixDay,
+    /// Monday to Thursday with US federal holidays.
+    FourDay,
+    /// Every day of the week with no holidays, for round-the-clock work.
+    SevenDay,
+}
+
+impl CalendarPreset {
+    /// Every preset, in declaration order.
+    pub const ALL: [CalendarPreset; 4] = [
+        CalendarPreset::FiveDayUs,
+        CalendarPreset::SixDay,
+        CalendarPreset::FourDay,
+        CalendarPreset::SevenDay,
+    ];
+
+    /// Wire token for the preset, matching the serde representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CalendarPreset::FiveDayUs => "five_day_us",
+            CalendarPreset::SixDay => "six_day",
+            CalendarPreset::FourDay => "four_day",
+            CalendarPreset::SevenDay => "seven_day",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|preset| preset.as_str() == value)
+    }
+
+    fn working_days(&self) -> &'static [Weekday] {
+        use Weekday::*;
+        match self {
+            CalendarPreset::FiveDayUs => &[Mon, Tue, Wed, Thu, Fri],
+            CalendarPreset::SixDay => &[Mon, Tue, Wed, Thu, Fri, Sat],
+            CalendarPreset::FourDay => &[Mon, Tue, Wed, Thu],
+            CalendarPreset::SevenDay => &WorkCalendar::ALL_WEEKDAYS,
+        }
+    }
+}
+
+/// A holiday that falls on the same rule every year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RecurringHoliday {
+    /// The same month and day each year, e.g. December 24. Years without the
+    /// date (February 29) have no holiday.
+    Fixed { month: u32, day: u32 },
+    /// The `n`th `weekday` of a month, e.g. the 4th Thursday of November.
+    /// Years without an `n`th occurrence have no holiday.
+    NthWeekday {
+        month: u32,
+        weekday: Weekday,
+        n: u32,
+    },
+}
+
+impl RecurringHoliday {
+    /// The holiday's date in `year`, if the rule produces one.
+    pub fn date_in(&self, year: i32) -> Option<NaiveDate> {
+        match *self {
+            RecurringHoliday::Fixed { month, day } => NaiveDate::from_ymd_opt(year, month, day),
+            RecurringHoliday::NthWeekday { month, weekday, n } => {
+                if n == 0 {
+                    return None;
+                }
+                let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+                let offset = (7 + weekday.num_days_from_monday()
+                    - first.weekday().num_days_from_monday())
+                    % 7;
+                NaiveDate::from_ymd_opt(year, month, 1 + offset + 7 * (n - 1))
+            }
+        }
+    }
+
+    fn matches(&self, date: NaiveDate) -> bool {
+        self.date_in(date.year()) == Some(date)
+    }
 }
 
 impl Default for WorkCalendar {
@@ -41,12 +187,35 @@ impl WorkCalendar {
         let mut calendar = Self {
             holidays: HashSet::new(),
             non_working_days: HashSet::from([Weekday::Sat, Weekday::Sun]),
+            recurring_rules: Vec::new(),
+            preset: None,
+            named_holidays: HashMap::new(),
         };
 
         calendar.add_us_holidays_range(start, end);
         calendar
     }
 
+    /// Build a preset calendar, with holidays (if the preset has any) for
+    /// `start_year..=end_year`. The calendar remembers the preset, which
+    /// carries into its config; further edits keep that origin.
+    pub fn preset(preset: CalendarPreset, start_year: i32, end_year: i32) -> Self {
+        let mut calendar = Self::with_year_range(start_year, end_year);
+        if preset == CalendarPreset::SevenDay {
+            calendar.clear_holidays();
+        }
+        calendar
+            .set_working_days(preset.working_days().to_vec())
+            .expect("every preset has working days");
+        calendar.preset = Some(preset);
+        calendar
+    }
+
+    /// Preset the calendar was built from, if any.
+    pub fn preset_origin(&self) -> Option<CalendarPreset> {
+        self.preset
+    }
+
     pub fn custom<I, J>(working_days: I, holidays: J) -> Self
     where
         I: IntoIterator<Item = Weekday>,
@@ -56,11 +225,20 @@ impl WorkCalendar {
         Self::from_config(&config)
     }
 
+    /// # Panics
+    ///
+    /// If the config has no working days; see [`WorkCalendar::try_from_config`].
     pub fn from_config(config: &WorkCalendarConfig) -> Self {
+        Self::try_from_config(config).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Build a calendar from a config that may not have gone through
+    /// [`WorkCalendarConfig::new`], e.g. one read from a file.
+    pub fn try_from_config(config: &WorkCalendarConfig) -> Result<Self, CalendarError> {
         let mut non_working_days = HashSet::new();
         let working_set: HashSet<Weekday> = config.working_days.iter().copied().collect();
         if working_set.is_empty() {
-            panic!("WorkCalendar requires at least one working day");
+            return Err(CalendarError::NoWorkingDays);
         }
         for day in Self::ALL_WEEKDAYS {
             if !working_set.contains(&day) {
@@ -68,11 +246,24 @@ impl WorkCalendar {
             }
         }
 
-        let holidays = config.holidays.iter().copied().collect();
-        Self {
+        // A label implies the holiday, even if the plain list omits it.
+        let holidays = config
+            .holidays
+            .iter()
+            .chain(config.named_holidays.keys())
+            .copied()
+            .collect();
+        Ok(Self {
             holidays,
             non_working_days,
-        }
+            recurring_rules: config.recurring_rules.clone(),
+            preset: config.preset,
+            named_holidays: config
+                .named_holidays
+                .iter()
+                .map(|(date, label)| (*date, label.clone()))
+                .collect(),
+        })
     }
 
     pub fn to_config(&self) -> WorkCalendarConfig {
@@ -123,7 +314,7 @@ impl WorkCalendar {
     }
 
     /// Add US federal holidays for a range of years (inclusive)
-    fn add_us_holidays_range(&mut self, start_year: i32, end_year: i32) {
+    pub(crate) fn add_us_holidays_range(&mut self, start_year: i32, end_year: i32) {
         for year in start_year..=end_year {
             self.add_us_holidays(year);
         }
@@ -141,7 +332,7 @@ impl WorkCalendar {
                     return date;
                 }
             }
-            date = date + Duration::days(1);
+            date += Duration::days(1);
         }
         panic!("Could not find {}th {} in {}/{}", n, weekday, month, year);
     }
@@ -153,10 +344,10 @@ impl WorkCalendar {
         } else {
             NaiveDate::from_ymd_opt(year, month + 1, 1).unwrap()
         };
-        date = date - Duration::days(1); // Last day of the month
+        date -= Duration::days(1); // Last day of the month
 
         while date.weekday() != weekday {
-            date = date - Duration::days(1);
+            date -= Duration::days(1);
         }
         date
     }
@@ -171,6 +362,91 @@ impl WorkCalendar {
         self.holidays.extend(dates);
     }
 
+    /// Add a holiday with a label such as "Company Shutdown", replacing any
+    /// earlier label for the date. Labels are for reports only.
+    pub fn add_named_holiday(&mut self, date: NaiveDate, label: impl Into<String>) {
+        self.holidays.insert(date);
+        self.named_holidays.insert(date, label.into());
+    }
+
+    /// Label of a holiday added with [`WorkCalendar::add_named_holiday`].
+    pub fn holiday_label(&self, date: NaiveDate) -> Option<&str> {
+        self.named_holidays.get(&date).map(String::as_str)
+    }
+
+    /// Add the start date of every `VEVENT` in an iCalendar feed as a holiday
+    /// and return how many were new. Timed events count for the date part of
+    /// their `DTSTART`. Nothing is added if the feed is malformed.
+    #[cfg(feature = "ics")]
+    pub fn add_holidays_from_ics<R: std::io::Read>(
+        &mut self,
+        mut reader: R,
+    ) -> Result<usize, CalendarError> {
+        let mut text = String::new();
+        reader
+            .read_to_string(&mut text)
+            .map_err(|err| CalendarError::InvalidIcs {
+                line: 0,
+                message: err.to_string(),
+            })?;
+
+        // Unfold continuation lines (RFC 5545 3.1), keeping each logical
+        // line's starting line number for errors.
+        let mut lines: Vec<(usize, String)> = Vec::new();
+        for (number, raw) in text.lines().enumerate() {
+            match (raw.strip_prefix([' ', '\t']), lines.last_mut()) {
+                (Some(rest), Some((_, previous))) => previous.push_str(rest),
+                _ => lines.push((number + 1, raw.to_string())),
+            }
+        }
+
+        let mut dates = Vec::new();
+        let mut in_event = false;
+        for (number, line) in &lines {
+            let Some((name, value)) = line.split_once(':') else {
+                continue;
+            };
+            let property = name.split(';').next().unwrap_or_default();
+            match property.to_ascii_uppercase().as_str() {
+                "BEGIN" if value.eq_ignore_ascii_case("VEVENT") => in_event = true,
+                "END" if value.eq_ignore_ascii_case("VEVENT") => in_event = false,
+                "DTSTART" if in_event => {
+                    let date = value
+                        .get(..8)
+                        .and_then(|digits| NaiveDate::parse_from_str(digits, "%Y%m%d").ok())
+                        .ok_or_else(|| CalendarError::InvalidIcs {
+                            line: *number,
+                            message: format!("unreadable DTSTART '{value}'"),
+                        })?;
+                    dates.push(date);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(dates
+            .into_iter()
+            .filter(|date| self.holidays.insert(*date))
+            .count())
+    }
+
+    /// Remove a holiday. Returns `true` if the date was a holiday.
+    pub fn remove_holiday(&mut self, date: NaiveDate) -> bool {
+        self.named_holidays.remove(&date);
+        self.holidays.remove(&date)
+    }
+
+    /// Remove every holiday, leaving only the weekly working pattern
+    pub fn clear_holidays(&mut self) {
+        self.holidays.clear();
+        self.named_holidays.clear();
+    }
+
+    /// Iterate over the current holidays, in no particular order
+    pub fn holidays(&self) -> impl Iterator<Item = &NaiveDate> {
+        self.holidays.iter()
+    }
+
     /// Add the same custom holiday for multiple years
     /// Example: Add Dec 24 (Christmas Eve) for 2025-2030
     pub fn add_recurring_holiday(&mut self, month: u32, day: u32, start_year: i32, end_year: i32) {
@@ -181,6 +457,20 @@ impl WorkCalendar {
         }
     }
 
+    /// Add a rule-based holiday that applies to every year, including years
+    /// the calendar is later extended to. Adding the same rule twice has no
+    /// effect.
+    pub fn add_recurring_rule(&mut self, rule: RecurringHoliday) {
+        if !self.recurring_rules.contains(&rule) {
+            self.recurring_rules.push(rule);
+        }
+    }
+
+    /// Rule-based holidays, in the order they were added
+    pub fn recurring_rules(&self) -> &[RecurringHoliday] {
+        &self.recurring_rules
+    }
+
     /// Add recurring holidays that fall on a specific weekday
     /// Example: Add "Black Friday" (day after Thanksgiving) for multiple years
     pub fn add_recurring_weekday_holiday(
@@ -197,64 +487,186 @@ impl WorkCalendar {
         }
     }
 
-    /// Set custom working days (e.g., Mon-Sat for 6-day weeks)
-    pub fn set_working_days(&mut self, days: Vec<Weekday>) {
-        self.non_working_days.clear();
-        for day in Self::ALL_WEEKDAYS {
-            if !days.contains(&day) {
-                self.non_working_days.insert(day);
-            }
+    /// Set custom working days (e.g., Mon-Sat for 6-day weeks). Duplicates
+    /// are ignored; an empty list is rejected and leaves the calendar as is.
+    pub fn set_working_days(&mut self, days: Vec<Weekday>) -> Result<(), CalendarError> {
+        let working: HashSet<Weekday> = days.into_iter().collect();
+        if working.is_empty() {
+            return Err(CalendarError::NoWorkingDays);
         }
+        self.non_working_days = Self::ALL_WEEKDAYS
+            .into_iter()
+            .filter(|day| !working.contains(day))
+            .collect();
+        Ok(())
     }
 
     /// Check if a date is available for scheduling
     pub fn is_available(&self, date: NaiveDate) -> bool {
-        !self.holidays.contains(&date) && !self.non_working_days.contains(&date.weekday())
+        !self.holidays.contains(&date)
+            && !self.non_working_days.contains(&date.weekday())
+            && !self.recurring_rules.iter().any(|rule| rule.matches(date))
+    }
+
+    /// A calendar where a day is available only if it is available in both.
+    /// Non-working weekdays, holidays and recurring rules are all combined.
+    /// The result may have no working days at all (e.g. Mon–Tue with
+    /// Thu–Fri); searches on it then fail with [`CalendarError`].
+    pub fn intersect(&self, other: &WorkCalendar) -> WorkCalendar {
+        let mut combined = Self {
+            holidays: self.holidays.union(&other.holidays).copied().collect(),
+            non_working_days: self
+                .non_working_days
+                .union(&other.non_working_days)
+                .copied()
+                .collect(),
+            recurring_rules: self.recurring_rules.clone(),
+            preset: None,
+            named_holidays: other
+                .named_holidays
+                .iter()
+                .chain(&self.named_holidays)
+                .map(|(date, label)| (*date, label.clone()))
+                .collect(),
+        };
+        for rule in &other.recurring_rules {
+            combined.add_recurring_rule(*rule);
+        }
+        combined
+    }
+
+    /// A calendar where a day is available if it is available in either.
+    /// Only weekdays off in both stay non-working, and only rules present in
+    /// both carry over. A dated holiday is kept when the other calendar is
+    /// also unavailable that day. Dates that are only blocked by two
+    /// different recurring rules are treated as available.
+    pub fn union(&self, other: &WorkCalendar) -> WorkCalendar {
+        let holidays: HashSet<NaiveDate> = self
+            .holidays
+            .iter()
+            .filter(|date| !other.is_available(**date))
+            .chain(
+                other
+                    .holidays
+                    .iter()
+                    .filter(|date| !self.is_available(**date)),
+            )
+            .copied()
+            .collect();
+        let named_holidays = other
+            .named_holidays
+            .iter()
+            .chain(&self.named_holidays)
+            .filter(|(date, _)| holidays.contains(*date))
+            .map(|(date, label)| (*date, label.clone()))
+            .collect();
+        Self {
+            holidays,
+            non_working_days: self
+                .non_working_days
+                .intersection(&other.non_working_days)
+                .copied()
+                .collect(),
+            recurring_rules: self
+                .recurring_rules
+                .iter()
+                .filter(|rule| other.recurring_rules.contains(rule))
+                .copied()
+                .collect(),
+            preset: None,
+            named_holidays,
+        }
     }
 
     /// Find the next available date after a given date
+    ///
+    /// # Panics
+    ///
+    /// Where [`WorkCalendar::try_next_available`] errors.
     pub fn next_available(&self, from: NaiveDate) -> NaiveDate {
-        let mut current = from + Duration::days(1);
-        while !self.is_available(current) {
-            current = current + Duration::days(1);
-        }
-        current
+        self.try_next_available(from)
+            .unwrap_or_else(|err| panic!("{err}"))
     }
 
     /// Find a date N available days ahead
+    ///
+    /// # Panics
+    ///
+    /// Where [`WorkCalendar::try_find_next_available`] errors.
     pub fn find_next_available(&self, from: NaiveDate, days_ahead: i64) -> NaiveDate {
-        let mut current = from;
-        let mut count = 0;
-
-        while count < days_ahead {
-            current = current + Duration::days(1);
-            if self.is_available(current) {
-                count += 1;
-            }
-        }
-        current
+        self.try_find_next_available(from, days_ahead)
+            .unwrap_or_else(|err| panic!("{err}"))
     }
 
     /// Find the previous available date before a given date
+    ///
+    /// # Panics
+    ///
+    /// Where [`WorkCalendar::try_prev_available`] errors.
     pub fn prev_available(&self, from: NaiveDate) -> NaiveDate {
-        let mut current = from - Duration::days(1);
-        while !self.is_available(current) {
-            current = current - Duration::days(1);
-        }
-        current
+        self.try_prev_available(from)
+            .unwrap_or_else(|err| panic!("{err}"))
     }
 
     /// Find a date N available days back (inverse of find_next_available)
+    ///
+    /// # Panics
+    ///
+    /// Where [`WorkCalendar::try_find_prev_available`] errors.
     pub fn find_prev_available(&self, from: NaiveDate, days_back: i64) -> NaiveDate {
+        self.try_find_prev_available(from, days_back)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// [`WorkCalendar::next_available`], erroring instead of searching more
+    /// than [`MAX_AVAILABILITY_SEARCH_DAYS`] for one available day.
+    pub fn try_next_available(&self, from: NaiveDate) -> Result<NaiveDate, CalendarError> {
+        self.step_to_available(from, 1)
+    }
+
+    /// [`WorkCalendar::find_next_available`], with the same search limit per
+    /// available day as [`WorkCalendar::try_next_available`].
+    pub fn try_find_next_available(
+        &self,
+        from: NaiveDate,
+        days_ahead: i64,
+    ) -> Result<NaiveDate, CalendarError> {
         let mut current = from;
-        let mut count = 0;
-        while count < days_back {
-            current = current - Duration::days(1);
+        for _ in 0..days_ahead {
+            current = self.step_to_available(current, 1)?;
+        }
+        Ok(current)
+    }
+
+    /// [`WorkCalendar::prev_available`] with the search limit.
+    pub fn try_prev_available(&self, from: NaiveDate) -> Result<NaiveDate, CalendarError> {
+        self.step_to_available(from, -1)
+    }
+
+    /// [`WorkCalendar::find_prev_available`] with the search limit.
+    pub fn try_find_prev_available(
+        &self,
+        from: NaiveDate,
+        days_back: i64,
+    ) -> Result<NaiveDate, CalendarError> {
+        let mut current = from;
+        for _ in 0..days_back {
+            current = self.step_to_available(current, -1)?;
+        }
+        Ok(current)
+    }
+
+    /// First available date strictly after (`step` = 1) or before
+    /// (`step` = -1) `from`.
+    fn step_to_available(&self, from: NaiveDate, step: i64) -> Result<NaiveDate, CalendarError> {
+        let mut current = from;
+        for _ in 0..MAX_AVAILABILITY_SEARCH_DAYS {
+            current += Duration::days(step);
             if self.is_available(current) {
-                count += 1;
+                return Ok(current);
             }
         }
-        current
+        Err(CalendarError::NoAvailableDay { from })
     }
 
     /// Get all available days in a date range
@@ -266,11 +678,20 @@ impl WorkCalendar {
             if self.is_available(current) {
                 days.push(current);
             }
-            current = current + Duration::days(1);
+            current += Duration::days(1);
         }
         days
     }
 
+    /// First and last year that contain a listed holiday, or `None` for a
+    /// calendar without any. Dates outside these years only get weekends and
+    /// recurring rules, which apply to every year.
+    pub fn holiday_years(&self) -> Option<(i32, i32)> {
+        let first = self.holidays.iter().map(Datelike::year).min()?;
+        let last = self.holidays.iter().map(Datelike::year).max()?;
+        Some((first, last))
+    }
+
     /// Count available days in a date range
     pub fn count_available_days(&self, start: NaiveDate, end: NaiveDate) -> i64 {
         let mut count = 0;
@@ -280,10 +701,104 @@ impl WorkCalendar {
             if self.is_available(current) {
                 count += 1;
             }
-            current = current + Duration::days(1);
+            current += Duration::days(1);
         }
         count
     }
+
+    /// Working days in a calendar month, or `None` for an invalid month.
+    pub fn working_days_in_month(&self, year: i32, month: u32) -> Option<i64> {
+        let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+        let next_first = if month == 12 {
+            NaiveDate::from_ymd_opt(year + 1, 1, 1)?
+        } else {
+            NaiveDate::from_ymd_opt(year, month + 1, 1)?
+        };
+        Some(self.working_days_between(first, next_first - Duration::days(1)))
+    }
+
+    /// Working days in a calendar year, or `None` if the year is out of range.
+    pub fn working_days_in_year(&self, year: i32) -> Option<i64> {
+        let first = NaiveDate::from_ymd_opt(year, 1, 1)?;
+        let last = NaiveDate::from_ymd_opt(year, 12, 31)?;
+        Some(self.working_days_between(first, last))
+    }
+
+    /// The date `n` working days after `start`, not counting `start` itself.
+    ///
+    /// Returns the same date as [`WorkCalendar::find_next_available`] but
+    /// skips whole weeks at a time, so large `n` stay cheap. It is the inverse
+    /// of counting: for `n >= 1`,
+    /// `count_available_days(start + 1 day, nth_working_day_from(start, n)) == n`.
+    /// `n <= 0` returns `start`.
+    ///
+    /// # Panics
+    ///
+    /// Where [`WorkCalendar::try_find_next_available`] errors.
+    pub fn nth_working_day_from(&self, start: NaiveDate, n: i64) -> NaiveDate {
+        let per_week = self.working_days_per_week();
+        if per_week == 0 {
+            // No weekly pattern to skip by; fall through to the bounded search.
+            return self.find_next_available(start, n);
+        }
+        let mut current = start;
+        let mut remaining = n;
+        while remaining > per_week {
+            // Land strictly before the target so the final steps stay day by day.
+            let weeks = (remaining - 1) / per_week;
+            let target = current + Duration::days(7 * weeks);
+            let skipped = self.holidays_on_working_days(current + Duration::days(1), target);
+            remaining -= weeks * per_week - skipped;
+            current = target;
+        }
+        self.find_next_available(current, remaining)
+    }
+
+    /// Count of working days in `start..=end`, computed from whole weeks plus
+    /// a remainder and adjusted for holidays, without visiting every day.
+    fn working_days_between(&self, start: NaiveDate, end: NaiveDate) -> i64 {
+        if end < start {
+            return 0;
+        }
+        let span = (end - start).num_days() + 1;
+        let weeks = span / 7;
+        let mut count = weeks * self.working_days_per_week();
+        let mut current = start + Duration::days(weeks * 7);
+        while current <= end {
+            if !self.non_working_days.contains(&current.weekday()) {
+                count += 1;
+            }
+            current += Duration::days(1);
+        }
+        count - self.holidays_on_working_days(start, end)
+    }
+
+    fn working_days_per_week(&self) -> i64 {
+        7 - self.non_working_days.len() as i64
+    }
+
+    /// Holidays in `start..=end` that would otherwise be working days. Rule
+    /// dates are counted once, even when they coincide with a listed holiday
+    /// or another rule.
+    fn holidays_on_working_days(&self, start: NaiveDate, end: NaiveDate) -> i64 {
+        let on_working_day = |day: &NaiveDate| {
+            *day >= start && *day <= end && !self.non_working_days.contains(&day.weekday())
+        };
+        let listed = self
+            .holidays
+            .iter()
+            .filter(|day| on_working_day(day))
+            .count();
+        let ruled: HashSet<NaiveDate> = (start.year()..=end.year())
+            .flat_map(|year| {
+                self.recurring_rules
+                    .iter()
+                    .filter_map(move |rule| rule.date_in(year))
+            })
+            .filter(|day| on_working_day(day) && !self.holidays.contains(day))
+            .collect();
+        (listed + ruled.len()) as i64
+    }
 }
 
 impl WorkCalendarConfig {
@@ -306,7 +821,23 @@ impl WorkCalendarConfig {
         Self {
             working_days: working,
             holidays,
+            recurring_rules: Vec::new(),
+            preset: None,
+            named_holidays: BTreeMap::new(),
+        }
+    }
+
+    /// Add rule-based holidays; duplicates are dropped.
+    pub fn with_recurring_rules<I>(mut self, rules: I) -> Self
+    where
+        I: IntoIterator<Item = RecurringHoliday>,
+    {
+        for rule in rules {
+            if !self.recurring_rules.contains(&rule) {
+                self.recurring_rules.push(rule);
+            }
         }
+        self
     }
 
     pub fn working_days(&self) -> &[Weekday] {
@@ -316,6 +847,25 @@ impl WorkCalendarConfig {
     pub fn holidays(&self) -> &[NaiveDate] {
         &self.holidays
     }
+
+    pub fn recurring_rules(&self) -> &[RecurringHoliday] {
+        &self.recurring_rules
+    }
+
+    /// Holiday labels, by date.
+    pub fn named_holidays(&self) -> &BTreeMap<NaiveDate, String> {
+        &self.named_holidays
+    }
+
+    /// Record which preset this config derives from.
+    pub fn with_preset_origin(mut self, preset: Option<CalendarPreset>) -> Self {
+        self.preset = preset;
+        self
+    }
+
+    pub fn preset_origin(&self) -> Option<CalendarPreset> {
+        self.preset
+    }
 }
 
 impl Default for WorkCalendarConfig {
@@ -340,6 +890,13 @@ impl From<&WorkCalendar> for WorkCalendarConfig {
         Self {
             working_days: working,
             holidays,
+            recurring_rules: calendar.recurring_rules.clone(),
+            preset: calendar.preset,
+            named_holidays: calendar
+                .named_holidays
+                .iter()
+                .map(|(date, label)| (*date, label.clone()))
+                .collect(),
         }
     }
 }
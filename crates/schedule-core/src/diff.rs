@@ -0,0 +1,141 @@
+use crate::schedule::{Schedule, TaskNotFound};
+use crate::task::Task;
+use chrono::NaiveDate;
+use polars::prelude::PolarsError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Computed dates and float of one task in two versions of a schedule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskDateChange {
+    pub task_id: i32,
+    pub early_start_before: Option<NaiveDate>,
+    pub early_start_after: Option<NaiveDate>,
+    pub early_finish_before: Option<NaiveDate>,
+    pub early_finish_after: Option<NaiveDate>,
+    pub late_finish_before: Option<NaiveDate>,
+    pub late_finish_after: Option<NaiveDate>,
+    pub total_float_before: Option<i64>,
+    pub total_float_after: Option<i64>,
+    pub is_critical_before: Option<bool>,
+    pub is_critical_after: Option<bool>,
+    /// Working days the early finish moved; positive means later. `None` when
+    /// either side has no early finish.
+    pub finish_shift_days: Option<i64>,
+}
+
+/// What differs between two versions of a schedule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleDiff {
+    /// Tasks only in the newer schedule, in ascending id order.
+    pub added_task_ids: Vec<i32>,
+    /// Tasks only in the older schedule, in ascending id order.
+    pub removed_task_ids: Vec<i32>,
+    /// Tasks in both whose computed dates, float or criticality differ, in
+    /// ascending id order.
+    pub changed: Vec<TaskDateChange>,
+    pub project_finish_before: Option<NaiveDate>,
+    pub project_finish_after: Option<NaiveDate>,
+}
+
+impl ScheduleDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_task_ids.is_empty()
+            && self.removed_task_ids.is_empty()
+            && self.changed.is_empty()
+            && self.project_finish_before == self.project_finish_after
+    }
+}
+
+fn date_change(schedule: &Schedule, before: &Task, after: &Task) -> Option<TaskDateChange> {
+    let unchanged = before.early_start == after.early_start
+        && before.early_finish == after.early_finish
+        && before.late_start == after.late_start
+        && before.late_finish == after.late_finish
+        && before.total_float == after.total_float
+        && before.is_critical == after.is_critical;
+    if unchanged {
+        return None;
+    }
+    let finish_shift_days = before
+        .early_finish
+        .zip(after.early_finish)
+        .map(|(from, to)| Schedule::working_days_diff(schedule.calendar(), from, to));
+    Some(TaskDateChange {
+        task_id: after.id,
+        early_start_before: before.early_start,
+        early_start_after: after.early_start,
+        early_finish_before: before.early_finish,
+        early_finish_after: after.early_finish,
+        late_finish_before: before.late_finish,
+        late_finish_after: after.late_finish,
+        total_float_before: before.total_float,
+        total_float_after: after.total_float,
+        is_critical_before: before.is_critical,
+        is_critical_after: after.is_critical,
+        finish_shift_days,
+    })
+}
+
+impl Schedule {
+    /// Compare this schedule (the older version) with `other`. Only computed
+    /// dates, float and criticality count as changes; shifts are measured on
+    /// `other`'s calendar.
+    pub fn diff(&self, other: &Schedule) -> Result<ScheduleDiff, PolarsError> {
+        let before: BTreeMap<i32, Task> = self
+            .tasks()?
+            .into_iter()
+            .map(|task| (task.id, task))
+            .collect();
+        let after: BTreeMap<i32, Task> = other
+            .tasks()?
+            .into_iter()
+            .map(|task| (task.id, task))
+            .collect();
+
+        let added_task_ids = after
+            .keys()
+            .filter(|id| !before.contains_key(id))
+            .copied()
+            .collect();
+        let removed_task_ids = before
+            .keys()
+            .filter(|id| !after.contains_key(id))
+            .copied()
+            .collect();
+        let changed = after
+            .iter()
+            .filter_map(|(id, task)| {
+                before
+                    .get(id)
+                    .and_then(|previous| date_change(other, previous, task))
+            })
+            .collect();
+
+        Ok(ScheduleDiff {
+            added_task_ids,
+            removed_task_ids,
+            changed,
+            project_finish_before: self.latest_early_finish()?,
+            project_finish_after: other.latest_early_finish()?,
+        })
+    }
+
+    /// Dry run of [`Schedule::update_task_duration`]: applies the change to a
+    /// copy, refreshes the copy and returns what would move. `self` is left
+    /// untouched. Errors if the task does not exist or the copy fails to
+    /// refresh.
+    pub fn preview_duration_change(
+        &self,
+        task_id: i32,
+        new_duration_days: i64,
+    ) -> Result<ScheduleDiff, PolarsError> {
+        if self.find_task(task_id)?.is_none() {
+            return Err(TaskNotFound::task(task_id).into());
+        }
+        let mut fork = self.fork();
+        fork.update_task_duration(task_id, new_duration_days)?;
+        fork.refresh()?;
+        self.diff(&fork)
+    }
+}
@@ -0,0 +1,204 @@
+use crate::graph::schedule_dag::ScheduleDag;
+use crate::schedule::Schedule;
+use petgraph::Direction;
+use petgraph::algo::toposort;
+use petgraph::graph::NodeIndex;
+use polars::prelude::PolarsError;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// A run of tasks linked one-to-one: each task after the first has exactly
+/// one predecessor, which has no other successor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Branch {
+    /// Task ids in execution order.
+    pub tasks: Vec<i32>,
+}
+
+/// A task where two or more dependency paths merge.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JoinPoint {
+    pub task_id: i32,
+    /// Direct predecessors, in ascending id order.
+    pub predecessors: Vec<i32>,
+}
+
+/// One step of a [`MetaDependencyTree`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ExecutionNode {
+    /// A task that nothing else runs alongside at this step.
+    Task { task_id: i32 },
+    /// Branches that depend only on earlier steps and can run side by side,
+    /// ordered by their first task id.
+    ParallelBranches { branches: Vec<Branch> },
+}
+
+/// The dependency network collapsed into sequential steps of single tasks and
+/// parallel branches. A branch sits at the step after the latest branch it
+/// depends on, so every task appears after all of its predecessors.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetaDependencyTree {
+    pub nodes: Vec<ExecutionNode>,
+    /// Tasks with more than one predecessor, in ascending id order.
+    pub join_points: Vec<JoinPoint>,
+}
+
+impl MetaDependencyTree {
+    pub fn from_dag(dag: &ScheduleDag) -> Result<Self, PolarsError> {
+        let graph = &dag.graph;
+        let order = toposort(graph, None)
+            .map_err(|_| PolarsError::ComputeError("Cycle detected in schedule DAG".into()))?;
+
+        let sole_predecessor = |node: NodeIndex| -> Option<NodeIndex> {
+            let mut preds = graph.neighbors_directed(node, Direction::Incoming);
+            let pred = preds.next()?;
+            (preds.next().is_none()
+                && graph.neighbors_directed(pred, Direction::Outgoing).count() == 1)
+                .then_some(pred)
+        };
+
+        // Walk each chain from its head; heads come out in topological order.
+        let mut branch_of: HashMap<NodeIndex, usize> = HashMap::new();
+        let mut branches: Vec<Vec<NodeIndex>> = Vec::new();
+        for &node in &order {
+            if sole_predecessor(node).is_some() {
+                continue;
+            }
+            let mut chain = vec![node];
+            let mut current = node;
+            while let Some(next) = graph
+                .neighbors_directed(current, Direction::Outgoing)
+                .next()
+                .filter(|&next| sole_predecessor(next) == Some(current))
+            {
+                chain.push(next);
+                current = next;
+            }
+            for &member in &chain {
+                branch_of.insert(member, branches.len());
+            }
+            branches.push(chain);
+        }
+
+        let mut step_of = vec![0_usize; branches.len()];
+        for (index, chain) in branches.iter().enumerate() {
+            step_of[index] = graph
+                .neighbors_directed(chain[0], Direction::Incoming)
+                .map(|pred| step_of[branch_of[&pred]] + 1)
+                .max()
+                .unwrap_or(0);
+        }
+
+        let mut steps: BTreeMap<usize, Vec<Branch>> = BTreeMap::new();
+        for (index, chain) in branches.iter().enumerate() {
+            steps.entry(step_of[index]).or_default().push(Branch {
+                tasks: chain.iter().map(|&node| graph[node]).collect(),
+            });
+        }
+
+        let mut nodes = Vec::new();
+        for (_, mut step) in steps {
+            if step.len() == 1 {
+                nodes.extend(
+                    step.remove(0)
+                        .tasks
+                        .into_iter()
+                        .map(|task_id| ExecutionNode::Task { task_id }),
+                );
+            } else {
+                step.sort_by_key(|branch| branch.tasks[0]);
+                nodes.push(ExecutionNode::ParallelBranches { branches: step });
+            }
+        }
+
+        let mut join_points: Vec<JoinPoint> = graph
+            .node_indices()
+            .filter_map(|node| {
+                let mut predecessors: Vec<i32> = graph
+                    .neighbors_directed(node, Direction::Incoming)
+                    .map(|pred| graph[pred])
+                    .collect();
+                (predecessors.len() > 1).then(|| {
+                    predecessors.sort_unstable();
+                    predecessors.dedup();
+                    JoinPoint {
+                        task_id: graph[node],
+                        predecessors,
+                    }
+                })
+            })
+            .collect();
+        join_points.sort_by_key(|join| join.task_id);
+
+        Ok(Self { nodes, join_points })
+    }
+
+    /// Task ids in the order the steps list them.
+    pub fn execution_order(&self) -> Vec<i32> {
+        self.nodes
+            .iter()
+            .flat_map(|node| match node {
+                ExecutionNode::Task { task_id } => vec![*task_id],
+                ExecutionNode::ParallelBranches { branches } => branches
+                    .iter()
+                    .flat_map(|branch| branch.tasks.iter().copied())
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+impl Schedule {
+    /// Groups the dependency network into sequential steps and parallel
+    /// branches. Fails if the network has a cycle.
+    pub fn dependency_tree(&self) -> Result<MetaDependencyTree, PolarsError> {
+        MetaDependencyTree::from_dag(&ScheduleDag::build(self.dataframe())?)
+    }
+
+    /// The chain of linked tasks, from a task without predecessors to one
+    /// without successors, whose durations add up to the most working days.
+    /// Unlike [`Schedule::critical_path`] it ignores float, so forced starts
+    /// and actuals do not change it; in an unconstrained network the two
+    /// agree. Ties go to the lower task id. Fails if the network has a cycle.
+    pub fn longest_path(&self) -> Result<Vec<i32>, PolarsError> {
+        let dag = ScheduleDag::build(self.dataframe())?;
+        let graph = &dag.graph;
+        let order = toposort(graph, None)
+            .map_err(|_| PolarsError::ComputeError("Cycle detected in schedule DAG".into()))?;
+
+        let mut length: HashMap<NodeIndex, i64> = HashMap::with_capacity(order.len());
+        let mut via: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+        for &node in &order {
+            let best = graph
+                .neighbors_directed(node, Direction::Incoming)
+                .max_by_key(|pred| (length[pred], std::cmp::Reverse(graph[*pred])));
+            let upstream = best.map_or(0, |pred| length[&pred]);
+            if let Some(pred) = best {
+                via.insert(node, pred);
+            }
+            length.insert(node, upstream + dag.durations[&graph[node]].max(0));
+        }
+
+        let Some(mut node) = order
+            .iter()
+            .copied()
+            .filter(|&node| {
+                graph
+                    .neighbors_directed(node, Direction::Outgoing)
+                    .next()
+                    .is_none()
+            })
+            .max_by_key(|node| (length[node], std::cmp::Reverse(graph[*node])))
+        else {
+            return Ok(Vec::new());
+        };
+        let mut path = vec![graph[node]];
+        while let Some(&pred) = via.get(&node) {
+            path.push(graph[pred]);
+            node = pred;
+        }
+        path.reverse();
+        Ok(path)
+    }
+}
@@ -1 +1,3 @@
+pub mod dependency_tree;
+pub mod queries;
 pub mod schedule_dag;
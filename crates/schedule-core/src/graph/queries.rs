@@ -0,0 +1,92 @@
+use crate::graph::schedule_dag::ScheduleDag;
+use crate::schedule::{Schedule, TaskNotFound};
+use petgraph::Direction;
+use petgraph::graph::NodeIndex;
+use polars::prelude::PolarsError;
+use std::collections::{HashSet, VecDeque};
+
+impl Schedule {
+    /// Direct predecessors of `task_id` that exist in the schedule, in
+    /// ascending id order.
+    pub fn predecessors_of(&self, task_id: i32) -> Result<Vec<i32>, PolarsError> {
+        self.linked_tasks(task_id, Direction::Incoming, false)
+    }
+
+    /// Tasks that list `task_id` as a predecessor, in ascending id order.
+    pub fn successors_of(&self, task_id: i32) -> Result<Vec<i32>, PolarsError> {
+        self.linked_tasks(task_id, Direction::Outgoing, false)
+    }
+
+    /// Every task `task_id` depends on, directly or transitively, in
+    /// ascending id order.
+    pub fn all_upstream(&self, task_id: i32) -> Result<Vec<i32>, PolarsError> {
+        self.linked_tasks(task_id, Direction::Incoming, true)
+    }
+
+    /// Every task that depends on `task_id`, directly or transitively, in
+    /// ascending id order. These are the tasks a delay to `task_id` can push.
+    pub fn all_downstream(&self, task_id: i32) -> Result<Vec<i32>, PolarsError> {
+        self.linked_tasks(task_id, Direction::Outgoing, true)
+    }
+
+    /// Tasks that neither depend on `task_id` nor lead to it, directly or
+    /// transitively, in ascending id order. Dependency logic alone lets them
+    /// run at the same time as `task_id`.
+    pub fn concurrent_tasks(&self, task_id: i32) -> Result<Vec<i32>, PolarsError> {
+        let dag = ScheduleDag::build(self.dataframe())?;
+        let start = Self::dag_node(&dag, task_id)?;
+        let mut linked = Self::reachable(&dag, start, Direction::Outgoing, true);
+        linked.extend(Self::reachable(&dag, start, Direction::Incoming, true));
+
+        let mut concurrent: Vec<i32> = dag
+            .graph
+            .node_indices()
+            .filter(|node| *node != start && !linked.contains(node))
+            .map(|node| dag.graph[node])
+            .collect();
+        concurrent.sort_unstable();
+        Ok(concurrent)
+    }
+
+    fn linked_tasks(
+        &self,
+        task_id: i32,
+        direction: Direction,
+        transitive: bool,
+    ) -> Result<Vec<i32>, PolarsError> {
+        let dag = ScheduleDag::build(self.dataframe())?;
+        let start = Self::dag_node(&dag, task_id)?;
+        let mut ids: Vec<i32> = Self::reachable(&dag, start, direction, transitive)
+            .into_iter()
+            .map(|node| dag.graph[node])
+            .collect();
+        ids.sort_unstable();
+        Ok(ids)
+    }
+
+    fn dag_node(dag: &ScheduleDag, task_id: i32) -> Result<NodeIndex, PolarsError> {
+        dag.id_to_index
+            .get(&task_id)
+            .copied()
+            .ok_or_else(|| TaskNotFound::task(task_id).into())
+    }
+
+    /// Breadth-first walk from `start`, excluding `start` itself.
+    fn reachable(
+        dag: &ScheduleDag,
+        start: NodeIndex,
+        direction: Direction,
+        transitive: bool,
+    ) -> HashSet<NodeIndex> {
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            for next in dag.graph.neighbors_directed(node, direction) {
+                if next != start && seen.insert(next) && transitive {
+                    queue.push_back(next);
+                }
+            }
+        }
+        seen
+    }
+}
@@ -31,16 +31,15 @@ impl ScheduleDag {
         // Add edges: pred -> task
         let ids_ca = df.column("id")?.i32()?;
         for (idx, id_opt) in ids_ca.into_iter().enumerate() {
-            if let Some(task_id) = id_opt {
-                if let Some(series) = preds_lc.get_as_series(idx) {
-                    for pred_opt in series.i32()?.into_iter() {
-                        if let Some(pred_id) = pred_opt {
-                            if let (Some(&u), Some(&v)) =
-                                (id_to_index.get(&pred_id), id_to_index.get(&task_id))
-                            {
-                                graph.add_edge(u, v, ());
-                            }
-                        }
+            if let Some(task_id) = id_opt
+                && let Some(series) = preds_lc.get_as_series(idx)
+            {
+                for pred_opt in series.i32()?.into_iter() {
+                    if let Some(pred_id) = pred_opt
+                        && let (Some(&u), Some(&v)) =
+                            (id_to_index.get(&pred_id), id_to_index.get(&task_id))
+                    {
+                        graph.add_edge(u, v, ());
                     }
                 }
             }
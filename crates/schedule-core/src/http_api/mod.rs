@@ -1,9 +1,9 @@
-use std::{net::SocketAddr, str::FromStr, sync::Arc};
+use std::{future::Future, net::SocketAddr, str::FromStr, sync::Arc};
 
 use axum::{
     Json, Router,
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
     response::{IntoResponse, Response},
     routing::{get, post},
 };
@@ -11,7 +11,11 @@ use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
-use crate::{ProgressRationaleTemplate, RefreshSummary, Schedule, ScheduleMetadata, Task};
+use crate::{
+    AnalyticsSummary, ProgressRationaleTemplate, RefreshSummary, Schedule, ScheduleMetadata, Task,
+    TaskNotFound,
+    calendar::{WorkCalendar, WorkCalendarConfig},
+};
 
 #[derive(Clone)]
 pub struct AppState {
@@ -48,6 +52,46 @@ enum ApiError {
     Internal(String),
 }
 
+#[derive(Debug, Default, Deserialize)]
+struct ListTasksQuery {
+    #[serde(default)]
+    offset: usize,
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AddPredecessorPayload {
+    predecessor_id: i32,
+    /// Only a zero lag is supported until links carry lag.
+    #[serde(default)]
+    lag_days: i64,
+    /// Only finish-to-start ("FS") links are supported.
+    #[serde(default)]
+    kind: Option<String>,
+}
+
+/// A full schedule to compute without touching the held one. Without a
+/// calendar, the default calendar for the metadata's years is used.
+#[derive(Debug, Deserialize)]
+struct ComputePayload {
+    metadata: ScheduleMetadata,
+    #[serde(default)]
+    calendar: Option<WorkCalendarConfig>,
+    tasks: Vec<Task>,
+}
+
+#[derive(Debug, Serialize)]
+struct ComputeResponse {
+    tasks: Vec<Task>,
+    summary: RefreshSummary,
+}
+
+#[derive(Debug, Serialize)]
+struct SnapshotResponse {
+    metadata: ScheduleMetadata,
+    tasks: Vec<Task>,
+}
+
 #[derive(Debug, Deserialize)]
 struct ApplyTemplatePayload {
     template: String,
@@ -63,9 +107,14 @@ impl ApiError {
     }
 }
 
+/// A [`TaskNotFound`] from the core is a `404`; any other schedule error is
+/// taken as a bad request.
 impl From<polars::prelude::PolarsError> for ApiError {
     fn from(value: polars::prelude::PolarsError) -> Self {
-        ApiError::Invalid(value.to_string())
+        match TaskNotFound::find(&value) {
+            Some(_) => ApiError::NotFound(value.to_string()),
+            None => ApiError::Invalid(value.to_string()),
+        }
     }
 }
 
@@ -107,31 +156,96 @@ impl IntoResponse for ApiError {
 pub fn router(state: AppState) -> Router {
     Router::new()
         .route("/health", get(health))
+        .route("/ready", get(ready))
         .route("/metadata", get(get_metadata).put(update_metadata))
         .route("/tasks", get(list_tasks).post(create_task))
         .route(
             "/tasks/:id",
-            get(get_task).put(update_task).delete(delete_task),
+            get(get_task)
+                .put(update_task)
+                .patch(patch_task)
+                .delete(delete_task),
+        )
+        .route(
+            "/tasks/:id/predecessors",
+            get(list_predecessors).post(add_predecessor),
+        )
+        .route(
+            "/tasks/:id/predecessors/:pred_id",
+            axum::routing::delete(remove_predecessor),
         )
         .route(
             "/tasks/:id/rationale_template",
             post(apply_rationale_template),
         )
         .route("/refresh", post(refresh_schedule))
+        .route("/compute", post(compute_schedule))
+        .route("/summary", get(get_summary))
+        .route("/analytics", get(get_analytics))
+        .route("/snapshot", get(export_snapshot))
         .with_state(state)
 }
 
 pub async fn serve(addr: SocketAddr, schedule: Schedule) -> std::io::Result<()> {
-    let state = AppState::new(schedule);
-    let app = router(state);
+    serve_with_shutdown(
+        addr,
+        Arc::new(RwLock::new(schedule)),
+        std::future::pending(),
+    )
+    .await
+}
+
+/// Serve a schedule the caller also holds, stopping gracefully once
+/// `shutdown` completes. In-flight requests finish before this returns, so the
+/// caller can persist the shared schedule afterwards.
+pub async fn serve_with_shutdown<F>(
+    addr: SocketAddr,
+    schedule: Arc<RwLock<Schedule>>,
+    shutdown: F,
+) -> std::io::Result<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let app = router(AppState::with_shared(schedule));
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown)
+        .await
 }
 
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 async fn health() -> impl IntoResponse {
     Json(json!({ "status": "ok" }))
 }
 
+/// Readiness: `200` with the task count and last-refresh figures while the
+/// schedule would refresh, `503` with the reason once it would not (for
+/// example after a horizon change the tasks no longer fit).
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+async fn ready(State(state): State<AppState>) -> Response {
+    let schedule = state.schedule();
+    let guard = schedule.read();
+    let summary = guard
+        .validate_computable()
+        .and_then(|()| guard.current_summary());
+    match summary {
+        Ok(summary) => Json(json!({
+            "status": "ready",
+            "task_count": summary.task_count,
+            "latest_finish": summary.latest_finish,
+            "negative_float_count": summary.negative_float_count,
+            "missed_deadline_count": summary.missed_deadlines.len(),
+        }))
+        .into_response(),
+        Err(err) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({ "status": "unavailable", "error": err.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 async fn get_metadata(State(state): State<AppState>) -> Json<ScheduleMetadata> {
     let schedule = state.schedule();
     let metadata = {
@@ -141,6 +255,7 @@ async fn get_metadata(State(state): State<AppState>) -> Json<ScheduleMetadata> {
     Json(metadata)
 }
 
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 async fn update_metadata(
     State(state): State<AppState>,
     Json(metadata): Json<ScheduleMetadata>,
@@ -148,10 +263,12 @@ async fn update_metadata(
     let schedule = state.schedule();
     {
         let mut guard = schedule.write();
-        guard
-            .set_metadata(metadata.clone())
-            .map_err(|err| ApiError::invalid(err.to_string()))?;
-        guard.refresh().map_err(ApiError::from)?;
+        guard.edit(|schedule| {
+            schedule
+                .set_metadata(metadata.clone())
+                .map_err(|err| ApiError::invalid(err.to_string()))?;
+            schedule.refresh().map_err(ApiError::from)
+        })?;
     }
     let current = {
         let guard = schedule.read();
@@ -160,15 +277,28 @@ async fn update_metadata(
     Ok(Json(current))
 }
 
-async fn list_tasks(State(state): State<AppState>) -> Result<Json<Vec<Task>>, ApiError> {
+/// List tasks in schedule order. `offset` and `limit` select a page; the
+/// unpaged total is always returned in `X-Total-Count`.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+async fn list_tasks(
+    State(state): State<AppState>,
+    Query(query): Query<ListTasksQuery>,
+) -> Result<impl IntoResponse, ApiError> {
     let schedule = state.schedule();
-    let tasks = {
+    let (total, tasks) = {
         let guard = schedule.read();
-        guard.tasks()?
+        (
+            guard.dataframe().height(),
+            guard.tasks_page(query.offset, query.limit)?,
+        )
     };
-    Ok(Json(tasks))
+    Ok(([("x-total-count", total.to_string())], Json(tasks)))
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(task_id = task_id))
+)]
 async fn get_task(
     State(state): State<AppState>,
     Path(task_id): Path<i32>,
@@ -184,6 +314,7 @@ async fn get_task(
     }
 }
 
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 async fn create_task(
     State(state): State<AppState>,
     Json(task): Json<Task>,
@@ -198,9 +329,11 @@ async fn create_task(
             )));
         }
         guard
-            .upsert_task_record(task.clone())
+            .edit(|schedule| {
+                schedule.upsert_task_record(task.clone())?;
+                schedule.refresh()
+            })
             .map_err(ApiError::from)?;
-        guard.refresh().map_err(ApiError::from)?;
     }
     let created = {
         let guard = schedule.read();
@@ -211,9 +344,29 @@ async fn create_task(
     Ok((StatusCode::CREATED, Json(created)))
 }
 
+/// Revision the client based its edit on: the `If-Match` header when present
+/// (quotes and a weak `W/` prefix are ignored), else the payload's `revision`.
+fn expected_revision(headers: &HeaderMap, task: &Task) -> Result<u64, ApiError> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(task.revision);
+    };
+    let raw = value
+        .to_str()
+        .map_err(|_| ApiError::invalid("If-Match header is not valid text"))?;
+    let trimmed = raw.trim().trim_start_matches("W/").trim_matches('"');
+    trimmed
+        .parse()
+        .map_err(|_| ApiError::invalid(format!("If-Match header '{raw}' is not a revision")))
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(task_id = task_id))
+)]
 async fn update_task(
     State(state): State<AppState>,
     Path(task_id): Path<i32>,
+    headers: HeaderMap,
     Json(task): Json<Task>,
 ) -> Result<Json<Task>, ApiError> {
     if task.id != task_id {
@@ -221,16 +374,99 @@ async fn update_task(
             "task id in payload does not match path parameter",
         ));
     }
+    let expected = expected_revision(&headers, &task)?;
+    let schedule = state.schedule();
+    {
+        let mut guard = schedule.write();
+        let Some(current) = guard.task_revision(task_id)? else {
+            return Err(ApiError::not_found(format!("task {task_id} not found")));
+        };
+        if current != expected {
+            return Err(ApiError::Conflict(format!(
+                "task {task_id} is at revision {current}, update was based on revision {expected}"
+            )));
+        }
+        guard
+            .edit(|schedule| {
+                schedule.upsert_task_record(task.clone())?;
+                schedule.refresh()
+            })
+            .map_err(ApiError::from)?;
+    }
+    let updated = {
+        let guard = schedule.read();
+        guard
+            .find_task(task_id)?
+            .ok_or_else(|| ApiError::internal("task not found after update"))?
+    };
+    Ok(Json(updated))
+}
+
+/// Apply the fields present in `patch` on top of the stored task. Absent
+/// fields keep their value and explicit nulls clear optional ones. The
+/// revision to check comes from If-Match or a `revision` field; without
+/// either the patch is applied unconditionally.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(task_id = task_id))
+)]
+async fn patch_task(
+    State(state): State<AppState>,
+    Path(task_id): Path<i32>,
+    headers: HeaderMap,
+    Json(patch): Json<serde_json::Value>,
+) -> Result<Json<Task>, ApiError> {
+    let serde_json::Value::Object(fields) = patch else {
+        return Err(ApiError::invalid("patch body must be a JSON object"));
+    };
+    if fields
+        .get("id")
+        .is_some_and(|id| id.as_i64() != Some(i64::from(task_id)))
+    {
+        return Err(ApiError::invalid(
+            "task id in payload does not match path parameter",
+        ));
+    }
     let schedule = state.schedule();
     {
         let mut guard = schedule.write();
-        if guard.find_task(task_id)?.is_none() {
+        let Some(current) = guard.find_task(task_id)? else {
             return Err(ApiError::not_found(format!("task {task_id} not found")));
+        };
+        let expected = if headers.contains_key(header::IF_MATCH) {
+            Some(expected_revision(&headers, &current)?)
+        } else {
+            fields
+                .get("revision")
+                .map(|revision| {
+                    revision
+                        .as_u64()
+                        .ok_or_else(|| ApiError::invalid("revision must be a non-negative integer"))
+                })
+                .transpose()?
+        };
+        if let Some(expected) = expected.filter(|expected| *expected != current.revision) {
+            return Err(ApiError::Conflict(format!(
+                "task {task_id} is at revision {}, patch was based on revision {expected}",
+                current.revision
+            )));
         }
+
+        let mut merged =
+            serde_json::to_value(&current).map_err(|err| ApiError::internal(err.to_string()))?;
+        if let serde_json::Value::Object(target) = &mut merged {
+            for (key, value) in fields {
+                target.insert(key, value);
+            }
+        }
+        let task: Task = serde_json::from_value(merged)
+            .map_err(|err| ApiError::invalid(format!("invalid task patch: {err}")))?;
         guard
-            .upsert_task_record(task.clone())
+            .edit(|schedule| {
+                schedule.replace_task_record(task)?;
+                schedule.refresh()
+            })
             .map_err(ApiError::from)?;
-        guard.refresh().map_err(ApiError::from)?;
     }
     let updated = {
         let guard = schedule.read();
@@ -241,6 +477,10 @@ async fn update_task(
     Ok(Json(updated))
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(task_id = task_id))
+)]
 async fn delete_task(
     State(state): State<AppState>,
     Path(task_id): Path<i32>,
@@ -256,6 +496,91 @@ async fn delete_task(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(task_id = task_id))
+)]
+async fn list_predecessors(
+    State(state): State<AppState>,
+    Path(task_id): Path<i32>,
+) -> Result<Json<Vec<i32>>, ApiError> {
+    let schedule = state.schedule();
+    let task = {
+        let guard = schedule.read();
+        guard.find_task(task_id)?
+    };
+    match task {
+        Some(task) => Ok(Json(task.predecessors)),
+        None => Err(ApiError::not_found(format!("task {task_id} not found"))),
+    }
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(task_id = task_id))
+)]
+async fn add_predecessor(
+    State(state): State<AppState>,
+    Path(task_id): Path<i32>,
+    Json(payload): Json<AddPredecessorPayload>,
+) -> Result<Json<Task>, ApiError> {
+    if payload.lag_days != 0 {
+        return Err(ApiError::invalid("dependency lag is not supported"));
+    }
+    if let Some(kind) = payload.kind.as_deref()
+        && !kind.eq_ignore_ascii_case("fs")
+    {
+        return Err(ApiError::invalid(format!(
+            "dependency kind '{kind}' is not supported; only FS links are"
+        )));
+    }
+    let pred_id = payload.predecessor_id;
+    let schedule = state.schedule();
+    let mut guard = schedule.write();
+    guard.edit(|schedule| {
+        // Past a missing task, the only link the core refuses is a cycle.
+        schedule.add_predecessor(task_id, pred_id).map_err(|err| {
+            match TaskNotFound::find(&err) {
+                Some(_) => ApiError::from(err),
+                None => ApiError::Conflict(err.to_string()),
+            }
+        })?;
+        schedule.refresh().map_err(ApiError::from)
+    })?;
+    let updated = guard
+        .find_task(task_id)?
+        .ok_or_else(|| ApiError::internal("task not found after linking"))?;
+    Ok(Json(updated))
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(task_id = task_id, pred_id = pred_id))
+)]
+async fn remove_predecessor(
+    State(state): State<AppState>,
+    Path((task_id, pred_id)): Path<(i32, i32)>,
+) -> Result<Json<Task>, ApiError> {
+    let schedule = state.schedule();
+    let mut guard = schedule.write();
+    let removed = guard.edit(|schedule| {
+        if !schedule.remove_predecessor(task_id, pred_id)? {
+            return Ok(false);
+        }
+        schedule.refresh().map(|_| true)
+    })?;
+    if !removed {
+        return Err(ApiError::not_found(format!(
+            "task {task_id} has no predecessor {pred_id}"
+        )));
+    }
+    let updated = guard
+        .find_task(task_id)?
+        .ok_or_else(|| ApiError::internal("task not found after unlinking"))?;
+    Ok(Json(updated))
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 async fn refresh_schedule(State(state): State<AppState>) -> Result<Json<RefreshSummary>, ApiError> {
     let schedule = state.schedule();
     let summary = {
@@ -265,6 +590,70 @@ async fn refresh_schedule(State(state): State<AppState>) -> Result<Json<RefreshS
     Ok(Json(summary))
 }
 
+/// Run CPM on the posted schedule and return the computed tasks. The
+/// server's held schedule is neither read nor changed.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+async fn compute_schedule(
+    Json(payload): Json<ComputePayload>,
+) -> Result<Json<ComputeResponse>, ApiError> {
+    let calendar = match payload.calendar {
+        Some(config) => WorkCalendar::try_from_config(&config)
+            .map_err(|err| ApiError::invalid(err.to_string()))?,
+        None => Schedule::calendar_for_metadata(&payload.metadata),
+    };
+    let schedule = Schedule::from_tasks(payload.metadata, calendar, payload.tasks)?;
+    let summary = schedule.current_summary()?;
+    let tasks = schedule.tasks()?;
+    Ok(Json(ComputeResponse { tasks, summary }))
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+async fn get_summary(State(state): State<AppState>) -> Result<Json<RefreshSummary>, ApiError> {
+    let schedule = state.schedule();
+    let summary = {
+        let guard = schedule.read();
+        guard.current_summary()?
+    };
+    Ok(Json(summary))
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+async fn get_analytics(State(state): State<AppState>) -> Result<Json<AnalyticsSummary>, ApiError> {
+    let schedule = state.schedule();
+    let summary = {
+        let guard = schedule.read();
+        guard.analytics_summary()?
+    };
+    Ok(Json(summary))
+}
+
+/// Point-in-time export of the metadata and every task. The read lock is
+/// held only long enough to fork the schedule; building and serializing the
+/// body runs on the blocking pool against the fork, so writers are not held
+/// up by a large export.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+async fn export_snapshot(State(state): State<AppState>) -> Result<Response, ApiError> {
+    let schedule = state.schedule();
+    let snapshot = {
+        let guard = schedule.read();
+        guard.fork()
+    };
+    let body = tokio::task::spawn_blocking(move || -> Result<Vec<u8>, ApiError> {
+        let response = SnapshotResponse {
+            metadata: snapshot.metadata().clone(),
+            tasks: snapshot.tasks()?,
+        };
+        serde_json::to_vec(&response).map_err(|err| ApiError::internal(err.to_string()))
+    })
+    .await
+    .map_err(|err| ApiError::internal(err.to_string()))??;
+    Ok(([(header::CONTENT_TYPE, "application/json")], body).into_response())
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(task_id = task_id))
+)]
 async fn apply_rationale_template(
     State(state): State<AppState>,
     Path(task_id): Path<i32>,
@@ -274,20 +663,11 @@ async fn apply_rationale_template(
         ApiError::invalid(format!("unknown rationale template '{}'", payload.template))
     })?;
     let schedule = state.schedule();
-    {
+    let updated = {
         let mut guard = schedule.write();
         guard
             .apply_rationale_template(task_id, template)
-            .map_err(ApiError::from)?;
-    }
-    let updated = {
-        let guard = schedule.read();
-        guard
-            .find_task(task_id)
             .map_err(ApiError::from)?
-            .ok_or_else(|| {
-                ApiError::internal("task not found after rationale template application")
-            })?
     };
     Ok(Json(updated))
 }
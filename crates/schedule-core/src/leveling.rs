@@ -0,0 +1,186 @@
+use crate::graph::schedule_dag::ScheduleDag;
+use crate::schedule::Schedule;
+use chrono::NaiveDate;
+use petgraph::Direction;
+use polars::prelude::PolarsError;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Dates a task would take once resource conflicts are resolved.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeveledTask {
+    pub task_id: i32,
+    pub start: NaiveDate,
+    pub finish: NaiveDate,
+    /// Working days the start moved past the unleveled early start.
+    pub delay_days: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelingResult {
+    /// Leveled dates for every task, in the order they were placed.
+    pub tasks: Vec<LeveledTask>,
+}
+
+impl LevelingResult {
+    pub fn get(&self, task_id: i32) -> Option<&LeveledTask> {
+        self.tasks.iter().find(|task| task.task_id == task_id)
+    }
+
+    pub fn delayed(&self) -> impl Iterator<Item = &LeveledTask> {
+        self.tasks.iter().filter(|task| task.delay_days > 0)
+    }
+}
+
+/// The fields of a task leveling needs, captured in a single scan.
+struct ScannedTask {
+    early_start: NaiveDate,
+    duration_days: i64,
+    total_float: i64,
+    priority: i32,
+    resources: Vec<String>,
+}
+
+impl ScannedTask {
+    /// Ordering used to pick among ready tasks: earliest start, then least
+    /// float, then highest priority, then lowest id.
+    fn placement_key(&self, task_id: i32) -> (NaiveDate, i64, Reverse<i32>, i32) {
+        (
+            self.early_start,
+            self.total_float,
+            Reverse(self.priority),
+            task_id,
+        )
+    }
+}
+
+impl Schedule {
+    /// Resolve resource over-allocation by delaying tasks, treating each
+    /// `resource_id` as able to work one task at a time.
+    ///
+    /// Tasks are placed in dependency order; when several are ready the one
+    /// with the earliest start, least float and highest priority goes first,
+    /// so lower-priority tasks are the ones pushed back. Reads the current
+    /// early dates and float, so call [`Schedule::refresh`] first. The
+    /// schedule itself is left unchanged.
+    pub fn level_resources(&self) -> Result<LevelingResult, PolarsError> {
+        let calendar = self.calendar();
+        let project_start = self.project_start_date();
+        let mut scanned: HashMap<i32, ScannedTask> =
+            HashMap::with_capacity(self.dataframe().height());
+        for task in self.iter_tasks() {
+            let task = task?;
+            let mut resources: Vec<String> = task
+                .resource_allocations
+                .iter()
+                .map(|allocation| allocation.resource_id.clone())
+                .collect();
+            resources.sort();
+            resources.dedup();
+            scanned.insert(
+                task.id,
+                ScannedTask {
+                    early_start: task.early_start.unwrap_or(project_start),
+                    duration_days: task.duration_days,
+                    total_float: task.total_float.unwrap_or(0),
+                    priority: task.effective_priority(),
+                    resources,
+                },
+            );
+        }
+
+        let dag = ScheduleDag::build(self.dataframe())?;
+        let mut pending: HashMap<i32, usize> = HashMap::with_capacity(scanned.len());
+        let mut ready = BinaryHeap::new();
+        for node_ix in dag.graph.node_indices() {
+            let task_id = dag.graph[node_ix];
+            let preds = dag
+                .graph
+                .neighbors_directed(node_ix, Direction::Incoming)
+                .count();
+            if preds == 0 {
+                ready.push(Reverse(scanned[&task_id].placement_key(task_id)));
+            } else {
+                pending.insert(task_id, preds);
+            }
+        }
+
+        let mut bookings: HashMap<&str, Vec<(NaiveDate, NaiveDate)>> = HashMap::new();
+        let mut finishes: HashMap<i32, NaiveDate> = HashMap::with_capacity(scanned.len());
+        let mut placed = Vec::with_capacity(scanned.len());
+        while let Some(Reverse((_, _, _, task_id))) = ready.pop() {
+            let task = &scanned[&task_id];
+            let node_ix = dag.id_to_index[&task_id];
+
+            let mut start = project_start;
+            let mut has_pred = false;
+            for pred_ix in dag.graph.neighbors_directed(node_ix, Direction::Incoming) {
+                if let Some(&finish) = finishes.get(&dag.graph[pred_ix]) {
+                    has_pred = true;
+                    start = start.max(finish);
+                }
+            }
+            if has_pred {
+                start = calendar.try_next_available(start)?;
+            }
+            start = start.max(task.early_start);
+
+            let mut finish = calendar.try_find_next_available(start, task.duration_days)?;
+            // Milestones consume no resource time.
+            if task.duration_days > 0 {
+                while let Some(blocked_until) = task
+                    .resources
+                    .iter()
+                    .filter_map(|resource| bookings.get(resource.as_str()))
+                    .flatten()
+                    .filter(|(booked_start, booked_finish)| {
+                        *booked_start <= finish && start <= *booked_finish
+                    })
+                    .map(|(_, booked_finish)| *booked_finish)
+                    .max()
+                {
+                    start = calendar.try_next_available(blocked_until)?;
+                    finish = calendar.try_find_next_available(start, task.duration_days)?;
+                }
+                for resource in &task.resources {
+                    bookings
+                        .entry(resource.as_str())
+                        .or_default()
+                        .push((start, finish));
+                }
+            }
+
+            let delay_days = if start > task.early_start {
+                calendar.count_available_days(task.early_start, start) - 1
+            } else {
+                0
+            };
+            finishes.insert(task_id, finish);
+            placed.push(LeveledTask {
+                task_id,
+                start,
+                finish,
+                delay_days,
+            });
+
+            for succ_ix in dag.graph.neighbors_directed(node_ix, Direction::Outgoing) {
+                let succ_id = dag.graph[succ_ix];
+                if let Some(remaining) = pending.get_mut(&succ_id) {
+                    *remaining -= 1;
+                    if *remaining == 0 {
+                        pending.remove(&succ_id);
+                        ready.push(Reverse(scanned[&succ_id].placement_key(succ_id)));
+                    }
+                }
+            }
+        }
+
+        if placed.len() < scanned.len() {
+            return Err(PolarsError::ComputeError(
+                "Cycle detected in schedule DAG".into(),
+            ));
+        }
+        Ok(LevelingResult { tasks: placed })
+    }
+}
@@ -1,23 +1,61 @@
+extern crate alloc;
+
+pub mod analytics;
+pub mod buffers;
 pub mod calculations;
 pub mod calendar;
+pub mod diff;
 pub mod graph;
 #[cfg(feature = "http_api")]
 pub mod http_api;
+pub mod leveling;
 pub mod metadata;
 pub mod persistence;
+pub mod quality;
 pub mod resource;
 pub mod schedule;
 pub mod task;
 pub(crate) mod task_validation;
+pub mod validation;
 
-pub use calendar::{WorkCalendar, WorkCalendarConfig};
+pub use analytics::{
+    AnalyticsFrames, AnalyticsSummary, FastTrackCandidate, ResourceOverallocation, SCurve,
+    SCurvePoint,
+};
+pub use calendar::{
+    CalendarError, CalendarPreset, RecurringHoliday, WorkCalendar, WorkCalendarConfig,
+};
+pub use diff::{ScheduleDiff, TaskDateChange};
+pub use leveling::{LeveledTask, LevelingResult};
 pub use metadata::ScheduleMetadata;
+#[cfg(feature = "http_api")]
+pub use persistence::async_store::{AsyncScheduleStore, BlockingStore};
+#[cfg(feature = "parquet")]
+pub use persistence::parquet::{load_schedule_from_parquet, save_schedule_to_parquet};
 #[cfg(feature = "sqlite")]
-pub use persistence::sqlite::SqliteScheduleStore;
+pub use persistence::sqlite::{DEFAULT_SCHEDULE_NAME, SqliteScheduleStore};
 pub use persistence::{
-    PersistenceError, ScheduleStore, load_schedule_from_csv, load_schedule_from_json,
-    save_schedule_to_csv, save_schedule_to_json, validate_schedule, validate_tasks,
+    ColumnMapping, CsvOptions, JsonOptions, LinkKind, LinkSpec, PersistenceError,
+    SCHEDULE_SNAPSHOT_VERSION, ScheduleStore, load_resource_allocations_from_csv,
+    load_schedule_from_csv, load_schedule_from_csv_mapped, load_schedule_from_csv_mapped_with,
+    load_schedule_from_csv_with, load_schedule_from_json, load_schedule_from_json_with,
+    save_resource_allocations_to_csv, save_schedule_to_csv, save_schedule_to_csv_with,
+    save_schedule_to_json, save_schedule_to_json_with, save_schedule_to_mspdi, validate_schedule,
+    validate_tasks, validate_tasks_with,
+};
+pub use quality::{
+    BookendReport, QualityCheck, QualityCheckKind, QualityReport, QualityThresholds,
 };
 pub use resource::ResourceAllocation;
-pub use schedule::{RefreshSummary, Schedule, ScheduleMetadataError};
-pub use task::{ProgressMeasurement, ProgressRationaleTemplate, RationaleItem, Task};
+pub use schedule::{
+    CalendarCoverageGap, MissedDeadline, NEGATIVE_FLOAT_REPORT_LIMIT, NegativeFloat,
+    RefreshSummary, Schedule, ScheduleMetadataError, SchedulingMode, SortOrder, TaskNotFound,
+    VarianceBasis,
+};
+pub use task::{
+    ProgressMeasurement, ProgressRationaleTemplate, RationaleItem, Task, TaskBuilder, TaskKind,
+};
+pub use task_validation::{TaskValidationError, ValidationOptions};
+pub use validation::{
+    BaselineDurationMismatch, ValidationIssue, ValidationReport, ValidationSeverity,
+};
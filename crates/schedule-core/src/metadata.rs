@@ -1,12 +1,25 @@
-use chrono::NaiveDate;
+use chrono::{NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ScheduleMetadata {
     pub project_name: String,
     pub project_description: String,
     pub project_start_date: NaiveDate,
     pub project_end_date: NaiveDate,
+    /// IANA zone the project dates are meant in, e.g. "America/New_York".
+    /// Informational only: date arithmetic stays on `NaiveDate`. With the
+    /// `timezone` feature, `Schedule::set_metadata` rejects unknown names.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// See `Schedule::revision`. Maintained by the schedule: values passed
+    /// to `Schedule::set_metadata` are ignored. 0 in files written before
+    /// it existed.
+    #[serde(default)]
+    pub revision: u64,
+    /// See `Schedule::modified_at`; maintained like `revision`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified_at: Option<NaiveDateTime>,
 }
 
 impl Default for ScheduleMetadata {
@@ -16,6 +29,9 @@ impl Default for ScheduleMetadata {
             project_description: "No description".to_string(),
             project_start_date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
             project_end_date: NaiveDate::from_ymd_opt(2025, 12, 31).unwrap(),
+            timezone: None,
+            revision: 0,
+            modified_at: None,
         }
     }
 }
@@ -0,0 +1,66 @@
+use super::{PersistenceError, PersistenceResult, ScheduleStore};
+use crate::Schedule;
+use std::future::Future;
+use std::io;
+use std::sync::Arc;
+
+/// [`ScheduleStore`] for async callers such as the HTTP handlers. Wrap a
+/// synchronous store in [`BlockingStore`] to get one.
+pub trait AsyncScheduleStore: Send + Sync {
+    fn save_schedule(
+        &self,
+        schedule: &Schedule,
+    ) -> impl Future<Output = PersistenceResult<()>> + Send;
+    fn load_schedule(&self) -> impl Future<Output = PersistenceResult<Option<Schedule>>> + Send;
+}
+
+/// Runs a synchronous [`ScheduleStore`] on tokio's blocking pool, so disk
+/// and database work does not stall the async runtime. Saving forks the
+/// schedule, which is cheap, because the blocking task must own it.
+pub struct BlockingStore<S> {
+    inner: Arc<S>,
+}
+
+impl<S> BlockingStore<S> {
+    pub fn new(store: S) -> Self {
+        Self {
+            inner: Arc::new(store),
+        }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+}
+
+impl<S> Clone for BlockingStore<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<S> AsyncScheduleStore for BlockingStore<S>
+where
+    S: ScheduleStore + Send + Sync + 'static,
+{
+    async fn save_schedule(&self, schedule: &Schedule) -> PersistenceResult<()> {
+        let store = Arc::clone(&self.inner);
+        let schedule = schedule.fork();
+        tokio::task::spawn_blocking(move || store.save_schedule(&schedule))
+            .await
+            .map_err(join_error)?
+    }
+
+    async fn load_schedule(&self) -> PersistenceResult<Option<Schedule>> {
+        let store = Arc::clone(&self.inner);
+        tokio::task::spawn_blocking(move || store.load_schedule())
+            .await
+            .map_err(join_error)?
+    }
+}
+
+fn join_error(err: tokio::task::JoinError) -> PersistenceError {
+    PersistenceError::Io(io::Error::other(err))
+}
@@ -1,17 +1,27 @@
+use super::links::{self, LinkSpec};
 use super::{PersistenceError, PersistenceResult};
 use crate::{
     Schedule, ScheduleMetadata, Task,
     calendar::{WorkCalendar, WorkCalendarConfig},
     resource::ResourceAllocation,
-    task::{ProgressMeasurement, RationaleItem},
+    task::{ProgressMeasurement, RationaleItem, TaskKind},
+    task_validation::ValidationOptions,
 };
+use chrono::format::{Item, StrftimeItems};
 use chrono::{Datelike, NaiveDate};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
 use std::path::Path;
 
+/// Format version written by [`save_schedule_to_json`]. Files without a
+/// `version` field predate versioning and are read as version 0.
+pub const SCHEDULE_SNAPSHOT_VERSION: u32 = 1;
+
 #[derive(Serialize, Deserialize)]
 struct ScheduleSnapshot {
+    #[serde(default)]
+    version: u32,
     metadata: ScheduleMetadata,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     calendar: Option<WorkCalendarConfig>,
@@ -29,6 +39,7 @@ impl ScheduleSnapshot {
         }
         super::validate_tasks(&tasks)?;
         Ok(Self {
+            version: SCHEDULE_SNAPSHOT_VERSION,
             metadata: schedule.metadata().clone(),
             calendar: Some(schedule.calendar_config()),
             calendar_is_custom: schedule.calendar_is_custom(),
@@ -36,30 +47,55 @@ impl ScheduleSnapshot {
         })
     }
 
-    fn into_schedule(self) -> PersistenceResult<Schedule> {
-        super::validate_tasks(&self.tasks)?;
-        let calendar = self
-            .calendar
-            .map(|config| WorkCalendar::from_config(&config))
-            .unwrap_or_else(|| {
-                WorkCalendar::with_year_range(
-                    self.metadata.project_start_date.year(),
-                    self.metadata.project_end_date.year(),
-                )
-            });
-
-        let mut schedule = Schedule::from_parts(self.metadata, calendar, self.calendar_is_custom);
-        for task in self.tasks {
-            schedule.upsert_task_record(task)?;
-        }
-        Ok(schedule)
+    fn into_schedule(self, validation: &ValidationOptions) -> PersistenceResult<Schedule> {
+        super::validate_tasks_with(&self.tasks, validation)?;
+        let calendar = match self.calendar {
+            Some(config) => WorkCalendar::try_from_config(&config)?,
+            None => WorkCalendar::with_year_range(
+                self.metadata.project_start_date.year(),
+                self.metadata.project_end_date.year(),
+            ),
+        };
+
+        Ok(Schedule::from_tasks_unrefreshed(
+            self.metadata,
+            calendar,
+            self.calendar_is_custom,
+            self.tasks,
+            validation,
+        )?)
     }
 }
 
+/// Options for JSON export.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonOptions {
+    /// Refresh a copy of the schedule before writing, so late dates, float
+    /// and criticality are filled in even if the schedule was never
+    /// refreshed. The refresh can fail, e.g. when tasks run past the
+    /// project end date; nothing is written then.
+    pub refresh: bool,
+}
+
 pub fn save_schedule_to_json<P: AsRef<Path>>(
     schedule: &Schedule,
     path: P,
 ) -> PersistenceResult<()> {
+    save_schedule_to_json_with(schedule, path, &JsonOptions::default())
+}
+
+pub fn save_schedule_to_json_with<P: AsRef<Path>>(
+    schedule: &Schedule,
+    path: P,
+    options: &JsonOptions,
+) -> PersistenceResult<()> {
+    let refreshed;
+    let schedule = if options.refresh {
+        refreshed = refreshed_copy(schedule)?;
+        &refreshed
+    } else {
+        schedule
+    };
     let snapshot = ScheduleSnapshot::from_schedule(schedule)?;
     let file = File::create(path)?;
     serde_json::to_writer_pretty(file, &snapshot)?;
@@ -67,9 +103,121 @@ pub fn save_schedule_to_json<P: AsRef<Path>>(
 }
 
 pub fn load_schedule_from_json<P: AsRef<Path>>(path: P) -> PersistenceResult<Schedule> {
+    load_schedule_from_json_with(path, &ValidationOptions::default())
+}
+
+/// Load a JSON snapshot, validating its tasks with `validation` instead of
+/// the strict defaults.
+pub fn load_schedule_from_json_with<P: AsRef<Path>>(
+    path: P,
+    validation: &ValidationOptions,
+) -> PersistenceResult<Schedule> {
     let file = File::open(path)?;
-    let snapshot: ScheduleSnapshot = serde_json::from_reader(file)?;
-    snapshot.into_schedule()
+    let raw: serde_json::Value = serde_json::from_reader(file)?;
+    let snapshot: ScheduleSnapshot = serde_json::from_value(migrate_snapshot(raw)?)?;
+    snapshot.into_schedule(validation)
+}
+
+/// Upgrade a raw snapshot document to [`SCHEDULE_SNAPSHOT_VERSION`], one
+/// version at a time. Versions newer than this build understands are rejected.
+fn migrate_snapshot(mut raw: serde_json::Value) -> PersistenceResult<serde_json::Value> {
+    let found = match raw.get("version") {
+        None => 0,
+        Some(value) => value
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| {
+                PersistenceError::InvalidData(format!("invalid snapshot version {value}"))
+            })?,
+    };
+    if found > SCHEDULE_SNAPSHOT_VERSION {
+        return Err(PersistenceError::InvalidData(format!(
+            "unsupported snapshot version {found} (expected {SCHEDULE_SNAPSHOT_VERSION} or earlier)"
+        )));
+    }
+
+    let object = raw
+        .as_object_mut()
+        .ok_or_else(|| PersistenceError::InvalidData("snapshot must be a JSON object".into()))?;
+    for from in found..SCHEDULE_SNAPSHOT_VERSION {
+        match from {
+            // Version 0 files share the version 1 layout; they only lack the field.
+            0 => {}
+            _ => unreachable!("no migration registered from snapshot version {from}"),
+        }
+    }
+    object.insert("version".into(), SCHEDULE_SNAPSHOT_VERSION.into());
+    Ok(raw)
+}
+
+/// Formatting options for CSV import and export.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvOptions {
+    /// `chrono` strftime pattern used for every date column.
+    pub date_format: String,
+    /// Field delimiter byte, e.g. `b','` or `b';'`.
+    pub delimiter: u8,
+    /// Export only: refresh a copy of the schedule before writing, as
+    /// [`JsonOptions::refresh`] does.
+    pub refresh: bool,
+    /// Import only: round each `percent_complete` to the nearest value its
+    /// `progress_measurement` allows (0.6 under 25/75 becomes 0.75) instead
+    /// of rejecting the file.
+    pub snap_progress_to_measurement: bool,
+    /// Import only: tolerances used to validate the imported tasks.
+    pub validation: ValidationOptions,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            date_format: "%Y-%m-%d".to_string(),
+            delimiter: b',',
+            refresh: false,
+            snap_progress_to_measurement: false,
+            validation: ValidationOptions::default(),
+        }
+    }
+}
+
+impl CsvOptions {
+    pub fn new(date_format: impl Into<String>, delimiter: u8) -> Self {
+        Self {
+            date_format: date_format.into(),
+            delimiter,
+            refresh: false,
+            snap_progress_to_measurement: false,
+            validation: ValidationOptions::default(),
+        }
+    }
+
+    /// Refresh a copy of the schedule before exporting.
+    pub fn with_refresh(mut self, refresh: bool) -> Self {
+        self.refresh = refresh;
+        self
+    }
+
+    /// Snap imported progress to the task's measurement steps.
+    pub fn with_snap_progress_to_measurement(mut self, snap: bool) -> Self {
+        self.snap_progress_to_measurement = snap;
+        self
+    }
+
+    /// Validate imported tasks with `validation` instead of the strict defaults.
+    pub fn with_validation(mut self, validation: ValidationOptions) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    fn validate(&self) -> PersistenceResult<()> {
+        if StrftimeItems::new(&self.date_format).any(|item| matches!(item, Item::Error)) {
+            return Err(PersistenceError::InvalidData(format!(
+                "invalid date format '{}'",
+                self.date_format
+            )));
+        }
+        Ok(())
+    }
 }
 
 #[derive(Default, Serialize, Deserialize)]
@@ -100,6 +248,16 @@ struct TaskCsvRecord {
     #[serde(default)]
     resource_allocations: String,
     #[serde(default)]
+    deadline: String,
+    #[serde(default)]
+    forced_early_start: String,
+    #[serde(default)]
+    priority: String,
+    #[serde(default)]
+    task_kind: String,
+    #[serde(default)]
+    custom_fields: String,
+    #[serde(default)]
     metadata_json: String,
     #[serde(default)]
     calendar_json: String,
@@ -107,77 +265,92 @@ struct TaskCsvRecord {
     calendar_is_custom: String,
 }
 
-impl From<&Task> for TaskCsvRecord {
-    fn from(task: &Task) -> Self {
-        let mut record = TaskCsvRecord::default();
-        record.id = task.id;
-        record.name = task.name.clone();
-        record.duration_days = task.duration_days;
-        record.predecessors = join_i32(&task.predecessors);
-        record.early_start = format_date(task.early_start);
-        record.early_finish = format_date(task.early_finish);
-        record.late_start = format_date(task.late_start);
-        record.late_finish = format_date(task.late_finish);
-        record.baseline_start = format_date(task.baseline_start);
-        record.baseline_finish = format_date(task.baseline_finish);
-        record.actual_start = format_date(task.actual_start);
-        record.actual_finish = format_date(task.actual_finish);
-        record.percent_complete = format_option_f64(task.percent_complete);
-        record.progress_measurement = task.progress_measurement.as_str().to_string();
-        record.pre_defined_rationale = serde_json::to_string(&task.pre_defined_rationale)
-            .unwrap_or_else(|_| "[]".to_string());
-        record.schedule_variance_days = format_option_i64(task.schedule_variance_days);
-        record.total_float = format_option_i64(task.total_float);
-        record.is_critical = format_option_bool(task.is_critical);
-        record.successors = join_i32(&task.successors);
-        record.parent_id = format_option_i32(task.parent_id);
-        record.wbs_code = task.wbs_code.clone().unwrap_or_default();
-        record.task_notes = task.task_notes.clone().unwrap_or_default();
-        record.task_attachments = join_strings(&task.task_attachments);
-        record.resource_allocations = serde_json::to_string(&task.resource_allocations)
-            .unwrap_or_else(|_| "[]".to_string());
-        record
+impl TaskCsvRecord {
+    fn from_task(task: &Task, options: &CsvOptions) -> Self {
+        TaskCsvRecord {
+            id: task.id,
+            name: task.name.clone(),
+            duration_days: task.duration_days,
+            predecessors: join_links(&task.predecessors),
+            early_start: format_date(task.early_start, &options.date_format),
+            early_finish: format_date(task.early_finish, &options.date_format),
+            late_start: format_date(task.late_start, &options.date_format),
+            late_finish: format_date(task.late_finish, &options.date_format),
+            baseline_start: format_date(task.baseline_start, &options.date_format),
+            baseline_finish: format_date(task.baseline_finish, &options.date_format),
+            actual_start: format_date(task.actual_start, &options.date_format),
+            actual_finish: format_date(task.actual_finish, &options.date_format),
+            percent_complete: format_option_f64(task.percent_complete),
+            progress_measurement: task.progress_measurement.as_str().to_string(),
+            pre_defined_rationale: serde_json::to_string(&task.pre_defined_rationale)
+                .unwrap_or_else(|_| "[]".to_string()),
+            schedule_variance_days: format_option_i64(task.schedule_variance_days),
+            total_float: format_option_i64(task.total_float),
+            is_critical: format_option_bool(task.is_critical),
+            successors: join_links(&task.successors),
+            parent_id: format_option_i32(task.parent_id),
+            wbs_code: task.wbs_code.clone().unwrap_or_default(),
+            task_notes: task.task_notes.clone().unwrap_or_default(),
+            task_attachments: join_strings(&task.task_attachments),
+            resource_allocations: serde_json::to_string(&task.resource_allocations)
+                .unwrap_or_else(|_| "[]".to_string()),
+            deadline: format_date(task.deadline, &options.date_format),
+            forced_early_start: format_date(task.forced_early_start, &options.date_format),
+            priority: format_option_i32(task.priority),
+            task_kind: task.task_kind.as_str().to_string(),
+            custom_fields: serde_json::to_string(&task.custom_fields)
+                .unwrap_or_else(|_| "{}".to_string()),
+            ..TaskCsvRecord::default()
+        }
     }
-}
 
-impl TaskCsvRecord {
     fn metadata_row(schedule: &Schedule) -> PersistenceResult<Self> {
         let metadata_json = serde_json::to_string(schedule.metadata())?;
         let calendar_json = serde_json::to_string(&schedule.calendar_config())?;
-        let mut record = TaskCsvRecord::default();
-        record.name = "__metadata__".to_string();
-        record.metadata_json = metadata_json;
-        record.calendar_json = calendar_json;
-        record.calendar_is_custom = schedule.calendar_is_custom().to_string();
-        Ok(record)
+        Ok(TaskCsvRecord {
+            name: "__metadata__".to_string(),
+            metadata_json,
+            calendar_json,
+            calendar_is_custom: schedule.calendar_is_custom().to_string(),
+            ..TaskCsvRecord::default()
+        })
     }
 
     fn is_metadata_row(&self) -> bool {
         !self.metadata_json.trim().is_empty()
     }
 
-    fn into_task(self) -> PersistenceResult<Task> {
+    fn into_task(self, options: &CsvOptions) -> PersistenceResult<Task> {
         if self.is_metadata_row() {
             return Err(PersistenceError::InvalidData(
                 "metadata row cannot be converted to task".into(),
             ));
         }
         let mut task = Task::new(self.id, self.name, self.duration_days);
-        task.predecessors = split_i32(&self.predecessors)?;
-        task.successors = split_i32(&self.successors)?;
-        task.early_start = parse_date(&self.early_start)?;
-        task.early_finish = parse_date(&self.early_finish)?;
-        task.late_start = parse_date(&self.late_start)?;
-        task.late_finish = parse_date(&self.late_finish)?;
-        task.baseline_start = parse_date(&self.baseline_start)?;
-        task.baseline_finish = parse_date(&self.baseline_finish)?;
-        task.actual_start = parse_date(&self.actual_start)?;
-        task.actual_finish = parse_date(&self.actual_finish)?;
+        task.predecessors = split_links(self.id, "predecessors", &self.predecessors)?;
+        task.successors = split_links(self.id, "successors", &self.successors)?;
+        task.early_start = parse_date(&self.early_start, &options.date_format)?;
+        task.early_finish = parse_date(&self.early_finish, &options.date_format)?;
+        task.late_start = parse_date(&self.late_start, &options.date_format)?;
+        task.late_finish = parse_date(&self.late_finish, &options.date_format)?;
+        task.baseline_start = parse_date(&self.baseline_start, &options.date_format)?;
+        task.baseline_finish = parse_date(&self.baseline_finish, &options.date_format)?;
+        task.actual_start = parse_date(&self.actual_start, &options.date_format)?;
+        task.actual_finish = parse_date(&self.actual_finish, &options.date_format)?;
+        task.deadline = parse_date(&self.deadline, &options.date_format)?;
+        task.forced_early_start = parse_date(&self.forced_early_start, &options.date_format)?;
         task.percent_complete = parse_f64(&self.percent_complete)?;
         task.schedule_variance_days = parse_i64(&self.schedule_variance_days)?;
         task.total_float = parse_i64(&self.total_float)?;
         task.is_critical = parse_bool(&self.is_critical)?;
         task.parent_id = parse_i32(&self.parent_id)?;
+        task.priority = parse_i32(&self.priority)?;
+        task.task_kind = match self.task_kind.trim() {
+            "" => TaskKind::default(),
+            raw => TaskKind::from_str(raw).ok_or_else(|| {
+                PersistenceError::InvalidData(format!("invalid task_kind '{}'", self.task_kind))
+            })?,
+        };
         task.wbs_code = parse_string_option(self.wbs_code);
         task.task_notes = parse_string_option(self.task_notes);
         task.task_attachments = split_strings(&self.task_attachments);
@@ -188,6 +361,11 @@ impl TaskCsvRecord {
                     self.progress_measurement
                 ))
             })?;
+        if options.snap_progress_to_measurement {
+            task.percent_complete = task
+                .percent_complete
+                .map(|percent| task.progress_measurement.snap_percent(percent));
+        }
         task.pre_defined_rationale = if self.pre_defined_rationale.trim().is_empty() {
             Vec::new()
         } else {
@@ -201,32 +379,71 @@ impl TaskCsvRecord {
             Vec::new()
         } else {
             serde_json::from_str::<Vec<ResourceAllocation>>(&self.resource_allocations).map_err(
-                |err| {
-                    PersistenceError::InvalidData(format!("invalid resource_allocations: {err}"))
-                },
+                |err| PersistenceError::InvalidData(format!("invalid resource_allocations: {err}")),
             )?
         };
+        task.custom_fields = if self.custom_fields.trim().is_empty() {
+            HashMap::new()
+        } else {
+            serde_json::from_str::<HashMap<String, String>>(&self.custom_fields).map_err(|err| {
+                PersistenceError::InvalidData(format!("invalid custom_fields: {err}"))
+            })?
+        };
         Ok(task)
     }
 }
 
 pub fn save_schedule_to_csv<P: AsRef<Path>>(schedule: &Schedule, path: P) -> PersistenceResult<()> {
+    save_schedule_to_csv_with(schedule, path, &CsvOptions::default())
+}
+
+pub fn save_schedule_to_csv_with<P: AsRef<Path>>(
+    schedule: &Schedule,
+    path: P,
+    options: &CsvOptions,
+) -> PersistenceResult<()> {
+    options.validate()?;
+    let refreshed;
+    let schedule = if options.refresh {
+        refreshed = refreshed_copy(schedule)?;
+        &refreshed
+    } else {
+        schedule
+    };
     super::validate_schedule(schedule)?;
     let file = File::create(path)?;
-    let mut writer = csv::Writer::from_writer(file);
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(options.delimiter)
+        .from_writer(file);
     writer.serialize(TaskCsvRecord::metadata_row(schedule)?)?;
     let df = schedule.dataframe();
     for row_idx in 0..df.height() {
         let task = Task::from_dataframe_row(df, row_idx)?;
-        writer.serialize(TaskCsvRecord::from(&task))?;
+        writer.serialize(TaskCsvRecord::from_task(&task, options))?;
     }
     writer.flush()?;
     Ok(())
 }
 
+fn refreshed_copy(schedule: &Schedule) -> PersistenceResult<Schedule> {
+    let mut copy = schedule.fork();
+    copy.refresh()?;
+    Ok(copy)
+}
+
 pub fn load_schedule_from_csv<P: AsRef<Path>>(path: P) -> PersistenceResult<Schedule> {
+    load_schedule_from_csv_with(path, &CsvOptions::default())
+}
+
+pub fn load_schedule_from_csv_with<P: AsRef<Path>>(
+    path: P,
+    options: &CsvOptions,
+) -> PersistenceResult<Schedule> {
+    options.validate()?;
     let file = File::open(path)?;
-    let mut reader = csv::Reader::from_reader(file);
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .from_reader(file);
     let mut tasks = Vec::new();
     let mut metadata: Option<ScheduleMetadata> = None;
     let mut calendar_config: Option<WorkCalendarConfig> = None;
@@ -259,7 +476,7 @@ pub fn load_schedule_from_csv<P: AsRef<Path>>(path: P) -> PersistenceResult<Sche
             }
             continue;
         }
-        tasks.push(record.into_task()?);
+        tasks.push(record.into_task(options)?);
     }
 
     if tasks.is_empty() {
@@ -268,11 +485,11 @@ pub fn load_schedule_from_csv<P: AsRef<Path>>(path: P) -> PersistenceResult<Sche
         ));
     }
 
-    super::validate_tasks(&tasks)?;
+    super::validate_tasks_with(&tasks, &options.validation)?;
 
-    let mut schedule = if let Some(metadata) = metadata {
+    let schedule = if let Some(metadata) = metadata {
         let (calendar, has_custom_config) = if let Some(config) = calendar_config {
-            (WorkCalendar::from_config(&config), true)
+            (WorkCalendar::try_from_config(&config)?, true)
         } else {
             (
                 WorkCalendar::with_year_range(
@@ -282,26 +499,330 @@ pub fn load_schedule_from_csv<P: AsRef<Path>>(path: P) -> PersistenceResult<Sche
                 false,
             )
         };
-        Schedule::from_parts(metadata, calendar, calendar_is_custom && has_custom_config)
+        Schedule::from_tasks_unrefreshed(
+            metadata,
+            calendar,
+            calendar_is_custom && has_custom_config,
+            tasks,
+            &options.validation,
+        )?
     } else {
-        Schedule::new()
+        let metadata = ScheduleMetadata::default();
+        let calendar = Schedule::calendar_for_metadata(&metadata);
+        Schedule::from_tasks_unrefreshed(metadata, calendar, false, tasks, &options.validation)?
     };
-    for task in tasks {
-        schedule.upsert_task_record(task)?;
-    }
     Ok(schedule)
 }
 
-fn format_date(date: Option<NaiveDate>) -> String {
-    date.map(|d| d.format("%Y-%m-%d").to_string())
+/// One row of the normalized resource-allocation CSV.
+#[derive(Default, Serialize, Deserialize)]
+struct AllocationCsvRecord {
+    task_id: i32,
+    resource_id: String,
+    allocation_units: f64,
+    #[serde(default)]
+    cost_rate: String,
+    #[serde(default)]
+    max_units: String,
+    #[serde(default)]
+    role: String,
+    #[serde(default)]
+    notes: String,
+}
+
+impl AllocationCsvRecord {
+    fn from_allocation(task_id: i32, allocation: &ResourceAllocation) -> Self {
+        Self {
+            task_id,
+            resource_id: allocation.resource_id.clone(),
+            allocation_units: allocation.allocation_units,
+            cost_rate: format_option_f64(allocation.cost_rate),
+            max_units: format_option_f64(allocation.max_units),
+            role: allocation.role.clone().unwrap_or_default(),
+            notes: allocation.notes.clone().unwrap_or_default(),
+        }
+    }
+
+    fn into_allocation(self) -> PersistenceResult<ResourceAllocation> {
+        let mut allocation = ResourceAllocation::new(self.resource_id, self.allocation_units);
+        allocation.cost_rate = parse_f64(&self.cost_rate)?;
+        allocation.max_units = parse_f64(&self.max_units)?;
+        allocation.role = (!self.role.is_empty()).then_some(self.role);
+        allocation.notes = (!self.notes.is_empty()).then_some(self.notes);
+        Ok(allocation)
+    }
+}
+
+/// Write every resource allocation as its own row of `task_id`,
+/// `resource_id`, `allocation_units`, `cost_rate`, `max_units`, `role` and
+/// `notes`: the spreadsheet-friendly companion to the JSON-packed
+/// `resource_allocations` column of [`save_schedule_to_csv`]. Time-phased
+/// profiles are not written.
+pub fn save_resource_allocations_to_csv<P: AsRef<Path>>(
+    schedule: &Schedule,
+    path: P,
+) -> PersistenceResult<()> {
+    let mut writer = csv::Writer::from_path(path)?;
+    for task in schedule.tasks()? {
+        for allocation in &task.resource_allocations {
+            writer.serialize(AllocationCsvRecord::from_allocation(task.id, allocation))?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Merge a file written by [`save_resource_allocations_to_csv`] into
+/// `schedule`. Every task named in the file gets exactly the file's
+/// allocations, keeping the profile of a resource it already had; tasks
+/// not named keep theirs. Unknown task ids or invalid allocations reject
+/// the whole file before anything changes. Returns the number of tasks
+/// updated.
+pub fn load_resource_allocations_from_csv<P: AsRef<Path>>(
+    schedule: &mut Schedule,
+    path: P,
+) -> PersistenceResult<usize> {
+    let mut reader = csv::Reader::from_path(path)?;
+    let mut by_task: BTreeMap<i32, Vec<ResourceAllocation>> = BTreeMap::new();
+    for record in reader.deserialize::<AllocationCsvRecord>() {
+        let record = record?;
+        by_task
+            .entry(record.task_id)
+            .or_default()
+            .push(record.into_allocation()?);
+    }
+
+    let mut updated = Vec::with_capacity(by_task.len());
+    for (task_id, mut allocations) in by_task {
+        let mut task = schedule.find_task(task_id)?.ok_or_else(|| {
+            PersistenceError::InvalidData(format!("allocation for unknown task {task_id}"))
+        })?;
+        for allocation in &mut allocations {
+            if let Some(existing) = task
+                .resource_allocations
+                .iter()
+                .find(|existing| existing.resource_id == allocation.resource_id)
+            {
+                allocation.profile = existing.profile.clone();
+            }
+        }
+        task.resource_allocations = allocations;
+        updated.push(task);
+    }
+    let count = updated.len();
+    schedule.import_tasks(updated)?;
+    Ok(count)
+}
+
+/// Maps canonical task fields (the column names written by `save_schedule_to_csv`)
+/// to the header names used by a foreign CSV export.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnMapping {
+    columns: HashMap<String, String>,
+}
+
+impl ColumnMapping {
+    const REQUIRED_FIELDS: [&'static str; 3] = ["id", "name", "duration_days"];
+    const TASK_FIELDS: [&'static str; 29] = [
+        "id",
+        "name",
+        "duration_days",
+        "predecessors",
+        "early_start",
+        "early_finish",
+        "late_start",
+        "late_finish",
+        "baseline_start",
+        "baseline_finish",
+        "actual_start",
+        "actual_finish",
+        "percent_complete",
+        "progress_measurement",
+        "pre_defined_rationale",
+        "schedule_variance_days",
+        "total_float",
+        "is_critical",
+        "successors",
+        "parent_id",
+        "wbs_code",
+        "task_notes",
+        "task_attachments",
+        "resource_allocations",
+        "deadline",
+        "forced_early_start",
+        "priority",
+        "task_kind",
+        "custom_fields",
+    ];
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read the canonical `field` from the column headed `header`.
+    pub fn map(mut self, field: impl Into<String>, header: impl Into<String>) -> Self {
+        self.columns.insert(field.into(), header.into());
+        self
+    }
+
+    pub fn header_for(&self, field: &str) -> Option<&str> {
+        self.columns.get(field).map(String::as_str)
+    }
+
+    fn resolve(&self, headers: &csv::StringRecord) -> PersistenceResult<HashMap<String, usize>> {
+        for field in self.columns.keys() {
+            if !Self::TASK_FIELDS.contains(&field.as_str()) {
+                return Err(PersistenceError::InvalidData(format!(
+                    "column mapping references unknown field '{field}'"
+                )));
+            }
+        }
+        for field in Self::REQUIRED_FIELDS {
+            if !self.columns.contains_key(field) {
+                return Err(PersistenceError::InvalidData(format!(
+                    "column mapping is missing required field '{field}'"
+                )));
+            }
+        }
+
+        let mut index = HashMap::with_capacity(self.columns.len());
+        for field in Self::TASK_FIELDS {
+            let Some(header) = self.columns.get(field) else {
+                continue;
+            };
+            let position = headers
+                .iter()
+                .position(|candidate| candidate.trim() == header.trim())
+                .ok_or_else(|| {
+                    PersistenceError::InvalidData(format!(
+                        "CSV header '{header}' mapped to field '{field}' was not found"
+                    ))
+                })?;
+            index.insert(field.to_string(), position);
+        }
+        Ok(index)
+    }
+}
+
+impl TaskCsvRecord {
+    fn from_mapped_row(
+        row: &csv::StringRecord,
+        index: &HashMap<String, usize>,
+    ) -> PersistenceResult<Self> {
+        let field = |name: &str| -> String {
+            index
+                .get(name)
+                .and_then(|&pos| row.get(pos))
+                .unwrap_or_default()
+                .trim()
+                .to_string()
+        };
+        let id = parse_i32(&field("id"))?
+            .ok_or_else(|| PersistenceError::InvalidData("CSV row is missing a task id".into()))?;
+        let duration_days = parse_i64(&field("duration_days"))?.ok_or_else(|| {
+            PersistenceError::InvalidData(format!("task {id} is missing duration_days"))
+        })?;
+        let progress_measurement = match field("progress_measurement") {
+            value if value.is_empty() => ProgressMeasurement::default().as_str().to_string(),
+            value => value,
+        };
+        Ok(TaskCsvRecord {
+            id,
+            name: field("name"),
+            duration_days,
+            predecessors: field("predecessors"),
+            early_start: field("early_start"),
+            early_finish: field("early_finish"),
+            late_start: field("late_start"),
+            late_finish: field("late_finish"),
+            baseline_start: field("baseline_start"),
+            baseline_finish: field("baseline_finish"),
+            actual_start: field("actual_start"),
+            actual_finish: field("actual_finish"),
+            percent_complete: field("percent_complete"),
+            progress_measurement,
+            pre_defined_rationale: field("pre_defined_rationale"),
+            schedule_variance_days: field("schedule_variance_days"),
+            total_float: field("total_float"),
+            is_critical: field("is_critical"),
+            successors: field("successors"),
+            parent_id: field("parent_id"),
+            wbs_code: field("wbs_code"),
+            task_notes: field("task_notes"),
+            task_attachments: field("task_attachments"),
+            resource_allocations: field("resource_allocations"),
+            deadline: field("deadline"),
+            forced_early_start: field("forced_early_start"),
+            priority: field("priority"),
+            task_kind: field("task_kind"),
+            custom_fields: field("custom_fields"),
+            ..TaskCsvRecord::default()
+        })
+    }
+}
+
+/// Load tasks from a CSV whose headers differ from the canonical field names.
+/// Unmapped optional fields are left empty; the schedule uses default metadata.
+pub fn load_schedule_from_csv_mapped<P: AsRef<Path>>(
+    path: P,
+    mapping: &ColumnMapping,
+) -> PersistenceResult<Schedule> {
+    load_schedule_from_csv_mapped_with(path, mapping, &CsvOptions::default())
+}
+
+/// Like [`load_schedule_from_csv_mapped`], reading with `options`: its
+/// delimiter, date format, progress snapping and validation apply as in
+/// [`load_schedule_from_csv_with`].
+pub fn load_schedule_from_csv_mapped_with<P: AsRef<Path>>(
+    path: P,
+    mapping: &ColumnMapping,
+    options: &CsvOptions,
+) -> PersistenceResult<Schedule> {
+    options.validate()?;
+    let file = File::open(path)?;
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .from_reader(file);
+    let index = mapping.resolve(reader.headers()?)?;
+
+    let mut tasks = Vec::new();
+    for row in reader.records() {
+        let row = row?;
+        if row.iter().all(|value| value.trim().is_empty()) {
+            continue;
+        }
+        tasks.push(TaskCsvRecord::from_mapped_row(&row, &index)?.into_task(options)?);
+    }
+
+    if tasks.is_empty() {
+        return Err(PersistenceError::InvalidData(
+            "CSV file contained no tasks".into(),
+        ));
+    }
+
+    super::validate_tasks_with(&tasks, &options.validation)?;
+
+    let metadata = ScheduleMetadata::default();
+    let calendar = Schedule::calendar_for_metadata(&metadata);
+    Ok(Schedule::from_tasks_unrefreshed(
+        metadata,
+        calendar,
+        false,
+        tasks,
+        &options.validation,
+    )?)
+}
+
+fn format_date(date: Option<NaiveDate>, format: &str) -> String {
+    date.map(|d| d.format(format).to_string())
         .unwrap_or_default()
 }
 
-fn parse_date(input: &str) -> PersistenceResult<Option<NaiveDate>> {
+fn parse_date(input: &str, format: &str) -> PersistenceResult<Option<NaiveDate>> {
     if input.trim().is_empty() {
         return Ok(None);
     }
-    NaiveDate::parse_from_str(input.trim(), "%Y-%m-%d")
+    NaiveDate::parse_from_str(input.trim(), format)
         .map(Some)
         .map_err(|e| PersistenceError::InvalidData(format!("invalid date '{input}': {e}")))
 }
@@ -368,24 +889,24 @@ fn parse_bool(input: &str) -> PersistenceResult<Option<bool>> {
     }
 }
 
-fn join_i32(values: &[i32]) -> String {
-    values
-        .iter()
-        .map(|v| v.to_string())
-        .collect::<Vec<_>>()
-        .join(",")
+fn join_links(ids: &[i32]) -> String {
+    let links: Vec<LinkSpec> = ids.iter().copied().map(LinkSpec::finish_to_start).collect();
+    links::format_links(&links)
 }
 
-fn split_i32(input: &str) -> PersistenceResult<Vec<i32>> {
-    if input.trim().is_empty() {
-        return Ok(Vec::new());
-    }
-    input
-        .split(',')
-        .map(|part| {
-            part.trim().parse::<i32>().map_err(|e| {
-                PersistenceError::InvalidData(format!("invalid integer '{part}': {e}"))
-            })
+/// Task links only carry ids, so anything but a plain finish-to-start link
+/// is rejected rather than silently flattened.
+fn split_links(task_id: i32, column: &str, input: &str) -> PersistenceResult<Vec<i32>> {
+    links::parse_links(input)?
+        .into_iter()
+        .map(|link| {
+            if link.is_plain_finish_to_start() {
+                Ok(link.id)
+            } else {
+                Err(PersistenceError::InvalidData(format!(
+                    "task {task_id} {column} link '{link}' is not supported; only FS links without lag are"
+                )))
+            }
         })
         .collect()
 }
@@ -0,0 +1,125 @@
+//! Compact text encoding for dependency links, used by the CSV
+//! `predecessors` and `successors` columns.
+//!
+//! A link is `<id>[<kind>][<lag>]`, e.g. `3FS+2` or `5SS`: the other task's
+//! id, an optional kind (`FS`, `SS`, `FF`, `SF`, case-insensitive, default
+//! `FS`) and an optional signed lag in working days (default 0). Links are
+//! separated by `;`; commas are accepted too, so files holding the older
+//! bare-id lists (`3,5`) read as finish-to-start links without lag.
+
+use super::{PersistenceError, PersistenceResult};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LinkKind {
+    #[default]
+    FinishToStart,
+    StartToStart,
+    FinishToFinish,
+    StartToFinish,
+}
+
+impl LinkKind {
+    pub fn code(self) -> &'static str {
+        match self {
+            LinkKind::FinishToStart => "FS",
+            LinkKind::StartToStart => "SS",
+            LinkKind::FinishToFinish => "FF",
+            LinkKind::StartToFinish => "SF",
+        }
+    }
+
+    fn from_code(code: &str) -> Option<Self> {
+        match code.to_ascii_uppercase().as_str() {
+            "FS" => Some(LinkKind::FinishToStart),
+            "SS" => Some(LinkKind::StartToStart),
+            "FF" => Some(LinkKind::FinishToFinish),
+            "SF" => Some(LinkKind::StartToFinish),
+            _ => None,
+        }
+    }
+}
+
+/// One encoded link: the task on the other end, the link kind and the lag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LinkSpec {
+    pub id: i32,
+    pub kind: LinkKind,
+    pub lag_days: i64,
+}
+
+impl LinkSpec {
+    pub fn new(id: i32, kind: LinkKind, lag_days: i64) -> Self {
+        Self { id, kind, lag_days }
+    }
+
+    /// The only link a task's id lists can express today.
+    pub fn finish_to_start(id: i32) -> Self {
+        Self::new(id, LinkKind::FinishToStart, 0)
+    }
+
+    pub fn is_plain_finish_to_start(&self) -> bool {
+        self.kind == LinkKind::FinishToStart && self.lag_days == 0
+    }
+}
+
+impl fmt::Display for LinkSpec {
+    /// Plain finish-to-start links are written as the bare id.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id)?;
+        if self.is_plain_finish_to_start() {
+            return Ok(());
+        }
+        write!(f, "{}", self.kind.code())?;
+        if self.lag_days != 0 {
+            write!(f, "{:+}", self.lag_days)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for LinkSpec {
+    type Err = PersistenceError;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let invalid = || PersistenceError::InvalidData(format!("invalid link '{token}'"));
+        let trimmed = token.trim();
+        let id_end = trimmed
+            .char_indices()
+            .find(|(idx, ch)| !(ch.is_ascii_digit() || (*idx == 0 && *ch == '-')))
+            .map_or(trimmed.len(), |(idx, _)| idx);
+        let id = trimmed[..id_end].parse::<i32>().map_err(|_| invalid())?;
+
+        let rest = &trimmed[id_end..];
+        let lag_start = rest.find(['+', '-']).unwrap_or(rest.len());
+        let (code, lag) = rest.split_at(lag_start);
+        let kind = if code.is_empty() {
+            LinkKind::FinishToStart
+        } else {
+            LinkKind::from_code(code).ok_or_else(invalid)?
+        };
+        let lag_days = if lag.is_empty() {
+            0
+        } else {
+            lag.parse::<i64>().map_err(|_| invalid())?
+        };
+        Ok(Self { id, kind, lag_days })
+    }
+}
+
+/// Parse a `;`- or `,`-separated link list. Empty input is an empty list.
+pub fn parse_links(input: &str) -> PersistenceResult<Vec<LinkSpec>> {
+    if input.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    input.split([';', ',']).map(str::parse).collect()
+}
+
+pub fn format_links(links: &[LinkSpec]) -> String {
+    links
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(";")
+}
@@ -1,4 +1,5 @@
-use crate::task_validation;
+use crate::calendar::CalendarError;
+use crate::task_validation::{self, TaskValidationError, TaskValidationKind, ValidationOptions};
 use crate::{Schedule, Task};
 use polars::prelude::PolarsError;
 use serde_json::Error as SerdeJsonError;
@@ -13,6 +14,25 @@ pub enum PersistenceError {
     #[cfg(feature = "sqlite")]
     Sqlite(rusqlite::Error),
     Csv(csv::Error),
+    /// Two tasks in one collection share an id.
+    DuplicateTaskId(i32),
+    NegativeDuration {
+        id: i32,
+        duration: i64,
+    },
+    /// `percent_complete` outside 0..=1 or not finite.
+    InvalidProgress {
+        id: i32,
+        pct: f64,
+    },
+    /// Pre-defined rationale weights that do not sum to 1.0.
+    RationaleWeightSum {
+        id: i32,
+        total: f64,
+    },
+    /// A stored calendar config that cannot be used, e.g. one without
+    /// working days.
+    Calendar(CalendarError),
     InvalidData(String),
     NotFound,
 }
@@ -25,6 +45,35 @@ impl fmt::Display for PersistenceError {
             PersistenceError::Io(err) => write!(f, "io error: {err}"),
             PersistenceError::Sqlite(err) => write!(f, "sqlite error: {err}"),
             PersistenceError::Csv(err) => write!(f, "csv error: {err}"),
+            PersistenceError::DuplicateTaskId(id) => {
+                write!(
+                    f,
+                    "invalid data: {}",
+                    TaskValidationKind::DuplicateTaskId(*id)
+                )
+            }
+            PersistenceError::NegativeDuration { id, duration } => write!(
+                f,
+                "invalid data: {}",
+                TaskValidationKind::NegativeDuration {
+                    id: *id,
+                    duration: *duration,
+                }
+            ),
+            PersistenceError::InvalidProgress { id, pct } => write!(
+                f,
+                "invalid data: {}",
+                TaskValidationKind::InvalidProgress { id: *id, pct: *pct }
+            ),
+            PersistenceError::RationaleWeightSum { id, total } => write!(
+                f,
+                "invalid data: {}",
+                TaskValidationKind::RationaleWeightSum {
+                    id: *id,
+                    total: *total,
+                }
+            ),
+            PersistenceError::Calendar(err) => write!(f, "invalid calendar: {err}"),
             PersistenceError::InvalidData(msg) => write!(f, "invalid data: {msg}"),
             PersistenceError::NotFound => write!(f, "no schedule stored"),
         }
@@ -58,6 +107,28 @@ impl From<rusqlite::Error> for PersistenceError {
     }
 }
 
+impl From<TaskValidationError> for PersistenceError {
+    fn from(value: TaskValidationError) -> Self {
+        match value.into_kind() {
+            TaskValidationKind::DuplicateTaskId(id) => Self::DuplicateTaskId(id),
+            TaskValidationKind::NegativeDuration { id, duration } => {
+                Self::NegativeDuration { id, duration }
+            }
+            TaskValidationKind::InvalidProgress { id, pct } => Self::InvalidProgress { id, pct },
+            TaskValidationKind::RationaleWeightSum { id, total } => {
+                Self::RationaleWeightSum { id, total }
+            }
+            TaskValidationKind::Other(message) => Self::InvalidData(message),
+        }
+    }
+}
+
+impl From<CalendarError> for PersistenceError {
+    fn from(value: CalendarError) -> Self {
+        Self::Calendar(value)
+    }
+}
+
 impl From<csv::Error> for PersistenceError {
     fn from(value: csv::Error) -> Self {
         Self::Csv(value)
@@ -69,11 +140,30 @@ pub type PersistenceResult<T> = Result<T, PersistenceError>;
 pub trait ScheduleStore {
     fn save_schedule(&self, schedule: &Schedule) -> PersistenceResult<()>;
     fn load_schedule(&self) -> PersistenceResult<Option<Schedule>>;
+
+    /// Insert or replace `tasks` in the stored schedule, creating an empty
+    /// schedule first if none is stored. Either every task is saved or none is.
+    ///
+    /// The default loads the whole schedule, applies the tasks in memory and
+    /// saves it back; stores with native transactions should override it.
+    fn save_tasks(&self, tasks: &[Task]) -> PersistenceResult<()> {
+        validate_tasks(tasks)?;
+        let mut schedule = self.load_schedule()?.unwrap_or_default();
+        for task in tasks {
+            schedule.upsert_task_record(task.clone())?;
+        }
+        self.save_schedule(&schedule)
+    }
 }
 
 pub fn validate_tasks(tasks: &[Task]) -> PersistenceResult<()> {
-    task_validation::validate_task_collection(tasks)
-        .map_err(|err| PersistenceError::InvalidData(err.to_string()))
+    validate_tasks_with(tasks, &ValidationOptions::default())
+}
+
+pub fn validate_tasks_with(tasks: &[Task], options: &ValidationOptions) -> PersistenceResult<()> {
+    Ok(task_validation::validate_task_collection_with(
+        tasks, options,
+    )?)
 }
 
 pub fn validate_schedule(schedule: &Schedule) -> PersistenceResult<()> {
@@ -85,10 +175,22 @@ pub fn validate_schedule(schedule: &Schedule) -> PersistenceResult<()> {
     validate_tasks(&tasks)
 }
 
+#[cfg(feature = "http_api")]
+pub mod async_store;
 pub mod file;
+pub mod links;
+pub mod mspdi;
+#[cfg(feature = "parquet")]
+pub mod parquet;
 #[cfg(feature = "sqlite")]
 pub mod sqlite;
 
 pub use file::{
-    load_schedule_from_csv, load_schedule_from_json, save_schedule_to_csv, save_schedule_to_json,
+    ColumnMapping, CsvOptions, JsonOptions, SCHEDULE_SNAPSHOT_VERSION,
+    load_resource_allocations_from_csv, load_schedule_from_csv, load_schedule_from_csv_mapped,
+    load_schedule_from_csv_mapped_with, load_schedule_from_csv_with, load_schedule_from_json,
+    load_schedule_from_json_with, save_resource_allocations_to_csv, save_schedule_to_csv,
+    save_schedule_to_csv_with, save_schedule_to_json, save_schedule_to_json_with,
 };
+pub use links::{LinkKind, LinkSpec, format_links, parse_links};
+pub use mspdi::save_schedule_to_mspdi;
@@ -0,0 +1,125 @@
+use super::PersistenceResult;
+use crate::{Schedule, Task};
+use chrono::NaiveDate;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Working hours per day assumed when converting day durations to MSPDI's
+/// hour-based `PT…H…M…S` values.
+const HOURS_PER_DAY: i64 = 8;
+
+/// Export the schedule as Microsoft Project XML (MSPDI). This is export-only:
+/// tasks are written with their early dates, progress and finish-to-start
+/// predecessor links; resources and custom fields are not included.
+pub fn save_schedule_to_mspdi<P: AsRef<Path>>(
+    schedule: &Schedule,
+    path: P,
+) -> PersistenceResult<()> {
+    super::validate_schedule(schedule)?;
+    let mut tasks = schedule.tasks()?;
+    tasks.sort_by_key(|task| task.id);
+    fs::write(path, render_mspdi(schedule, &tasks))?;
+    Ok(())
+}
+
+fn render_mspdi(schedule: &Schedule, tasks: &[Task]) -> String {
+    let metadata = schedule.metadata();
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n");
+    xml.push_str("<Project xmlns=\"http://schemas.microsoft.com/project\">\n");
+    let _ = writeln!(xml, "  <Name>{}</Name>", escape(&metadata.project_name));
+    let _ = writeln!(xml, "  <Title>{}</Title>", escape(&metadata.project_name));
+    let _ = writeln!(
+        xml,
+        "  <StartDate>{}</StartDate>",
+        start_of_day(metadata.project_start_date)
+    );
+    let _ = writeln!(
+        xml,
+        "  <FinishDate>{}</FinishDate>",
+        end_of_day(metadata.project_end_date)
+    );
+    xml.push_str("  <ScheduleFromStart>1</ScheduleFromStart>\n");
+    let _ = writeln!(
+        xml,
+        "  <MinutesPerDay>{}</MinutesPerDay>",
+        HOURS_PER_DAY * 60
+    );
+    xml.push_str("  <Tasks>\n");
+    for (position, task) in tasks.iter().enumerate() {
+        render_task(&mut xml, position + 1, task);
+    }
+    xml.push_str("  </Tasks>\n");
+    xml.push_str("</Project>\n");
+    xml
+}
+
+fn render_task(xml: &mut String, position: usize, task: &Task) {
+    let percent = task
+        .percent_complete
+        .map(|pct| (pct * 100.0).round().clamp(0.0, 100.0) as i64)
+        .unwrap_or(0);
+    xml.push_str("    <Task>\n");
+    let _ = writeln!(xml, "      <UID>{}</UID>", task.id);
+    let _ = writeln!(xml, "      <ID>{}</ID>", position);
+    let _ = writeln!(xml, "      <Name>{}</Name>", escape(&task.name));
+    if let Some(wbs) = task.wbs_code.as_deref() {
+        let _ = writeln!(xml, "      <WBS>{}</WBS>", escape(wbs));
+    }
+    let _ = writeln!(
+        xml,
+        "      <Duration>PT{}H0M0S</Duration>",
+        task.duration_days * HOURS_PER_DAY
+    );
+    let _ = writeln!(
+        xml,
+        "      <Milestone>{}</Milestone>",
+        u8::from(task.duration_days == 0)
+    );
+    if let Some(start) = task.early_start {
+        let _ = writeln!(xml, "      <Start>{}</Start>", start_of_day(start));
+    }
+    if let Some(finish) = task.early_finish {
+        let _ = writeln!(xml, "      <Finish>{}</Finish>", end_of_day(finish));
+    }
+    let _ = writeln!(xml, "      <PercentComplete>{}</PercentComplete>", percent);
+    if let Some(is_critical) = task.is_critical {
+        let _ = writeln!(xml, "      <Critical>{}</Critical>", u8::from(is_critical));
+    }
+    if let Some(notes) = task.task_notes.as_deref() {
+        let _ = writeln!(xml, "      <Notes>{}</Notes>", escape(notes));
+    }
+    for pred in &task.predecessors {
+        // Type 1 is finish-to-start, the only link kind the schedule models.
+        let _ = writeln!(
+            xml,
+            "      <PredecessorLink>\n        <PredecessorUID>{}</PredecessorUID>\n        <Type>1</Type>\n      </PredecessorLink>",
+            pred
+        );
+    }
+    xml.push_str("    </Task>\n");
+}
+
+fn start_of_day(date: NaiveDate) -> String {
+    format!("{}T08:00:00", date.format("%Y-%m-%d"))
+}
+
+fn end_of_day(date: NaiveDate) -> String {
+    format!("{}T17:00:00", date.format("%Y-%m-%d"))
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,45 @@
+use super::{PersistenceError, PersistenceResult};
+use crate::{Schedule, Task, ValidationOptions};
+use polars::prelude::{ParquetReader, ParquetWriter, SerReader};
+use std::fs::File;
+use std::path::Path;
+
+/// Write the task table to a Parquet file, one row per task in the same
+/// column layout as [`Schedule::dataframe`]. Project metadata and the
+/// calendar are not part of the file.
+pub fn save_schedule_to_parquet<P: AsRef<Path>>(
+    schedule: &Schedule,
+    path: P,
+) -> PersistenceResult<()> {
+    super::validate_schedule(schedule)?;
+    let mut df = schedule.dataframe().clone();
+    let file = File::create(path)?;
+    ParquetWriter::new(file).finish(&mut df)?;
+    Ok(())
+}
+
+/// Read a task table written by [`save_schedule_to_parquet`]. Parquet files
+/// carry only tasks, so the project metadata and calendar are copied from
+/// `base`; its existing tasks are not kept.
+pub fn load_schedule_from_parquet<P: AsRef<Path>>(
+    path: P,
+    base: &Schedule,
+) -> PersistenceResult<Schedule> {
+    let file = File::open(path)?;
+    let df = ParquetReader::new(file).finish()?;
+    let mut tasks = Vec::with_capacity(df.height());
+    for row_idx in 0..df.height() {
+        tasks.push(Task::from_dataframe_row(&df, row_idx).map_err(|err| {
+            PersistenceError::InvalidData(format!("parquet row {row_idx}: {err}"))
+        })?);
+    }
+    super::validate_tasks(&tasks)?;
+
+    Ok(Schedule::from_tasks_unrefreshed(
+        base.metadata().clone(),
+        base.calendar().clone(),
+        base.calendar_is_custom(),
+        tasks,
+        &ValidationOptions::default(),
+    )?)
+}
@@ -1,8 +1,14 @@
-use super::{PersistenceResult, ScheduleStore};
-use crate::{Schedule, ScheduleMetadata, Task};
+use super::{PersistenceError, PersistenceResult, ScheduleStore};
+use crate::{Schedule, ScheduleMetadata, Task, task_validation};
 use rusqlite::{Connection, OptionalExtension, params};
+use std::collections::HashSet;
 use std::sync::Mutex;
 
+/// Name used by the [`ScheduleStore`] methods, which take no schedule name.
+pub const DEFAULT_SCHEDULE_NAME: &str = "default";
+
+/// SQLite-backed store holding any number of schedules, each under its own
+/// name. Tasks are keyed on `(schedule_name, task_id)`.
 pub struct SqliteScheduleStore {
     connection: Mutex<Connection>,
 }
@@ -19,62 +25,68 @@ impl SqliteScheduleStore {
     fn initialize_schema(connection: &Connection) -> PersistenceResult<()> {
         let ddl = r#"
             PRAGMA foreign_keys = ON;
-            CREATE TABLE IF NOT EXISTS schedule_metadata (
-                id INTEGER PRIMARY KEY CHECK (id = 1),
+            CREATE TABLE IF NOT EXISTS schedules (
+                name TEXT PRIMARY KEY,
                 metadata_json TEXT NOT NULL
             );
-            CREATE TABLE IF NOT EXISTS tasks (
-                id INTEGER PRIMARY KEY,
-                task_json TEXT NOT NULL
+            CREATE TABLE IF NOT EXISTS schedule_tasks (
+                schedule_name TEXT NOT NULL REFERENCES schedules(name) ON DELETE CASCADE,
+                task_id INTEGER NOT NULL,
+                task_json TEXT NOT NULL,
+                PRIMARY KEY (schedule_name, task_id)
             );
         "#;
         connection.execute_batch(ddl)?;
-        Ok(())
+        Self::migrate_single_schedule_tables(connection)
     }
 
-    fn save_metadata(
-        &self,
-        tx: &rusqlite::Transaction,
-        metadata: &ScheduleMetadata,
-    ) -> PersistenceResult<()> {
-        let json = serde_json::to_string(metadata)?;
-        tx.execute("DELETE FROM schedule_metadata", [])?;
-        tx.execute(
-            "INSERT INTO schedule_metadata (id, metadata_json) VALUES (1, ?1)",
-            params![json],
+    /// Stores created before named schedules kept one schedule in
+    /// `schedule_metadata`/`tasks`. Move it under [`DEFAULT_SCHEDULE_NAME`]
+    /// and drop the old tables.
+    fn migrate_single_schedule_tables(connection: &Connection) -> PersistenceResult<()> {
+        let legacy: i64 = connection.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'schedule_metadata'",
+            [],
+            |row| row.get(0),
         )?;
-        Ok(())
-    }
-
-    fn save_tasks(&self, tx: &rusqlite::Transaction, schedule: &Schedule) -> PersistenceResult<()> {
-        tx.execute("DELETE FROM tasks", [])?;
-        let df = schedule.dataframe();
-        let mut stmt = tx.prepare("INSERT INTO tasks (id, task_json) VALUES (?1, ?2)")?;
-        for row_idx in 0..df.height() {
-            let task = Task::from_dataframe_row(df, row_idx)?;
-            let json = serde_json::to_string(&task)?;
-            stmt.execute(params![task.id, json])?;
+        if legacy == 0 {
+            return Ok(());
         }
+        connection.execute_batch(&format!(
+            r#"
+            BEGIN;
+            INSERT OR IGNORE INTO schedules (name, metadata_json)
+                SELECT '{DEFAULT_SCHEDULE_NAME}', metadata_json FROM schedule_metadata WHERE id = 1;
+            INSERT OR IGNORE INTO schedule_tasks (schedule_name, task_id, task_json)
+                SELECT '{DEFAULT_SCHEDULE_NAME}', id, task_json FROM tasks
+                WHERE EXISTS (SELECT 1 FROM schedules WHERE name = '{DEFAULT_SCHEDULE_NAME}');
+            DROP TABLE tasks;
+            DROP TABLE schedule_metadata;
+            COMMIT;
+            "#
+        ))?;
         Ok(())
     }
-}
 
-impl ScheduleStore for SqliteScheduleStore {
-    fn save_schedule(&self, schedule: &Schedule) -> PersistenceResult<()> {
+    /// Save `schedule` under `name`, replacing any schedule already stored
+    /// with that name. Other named schedules are untouched.
+    pub fn save_named(&self, name: &str, schedule: &Schedule) -> PersistenceResult<()> {
         super::validate_schedule(schedule)?;
         let mut conn = self.connection.lock().expect("sqlite mutex poisoned");
         let tx = conn.transaction()?;
-        self.save_metadata(&tx, schedule.metadata())?;
-        self.save_tasks(&tx, schedule)?;
+        self.save_metadata(&tx, name, schedule.metadata())?;
+        self.write_tasks(&tx, name, schedule)?;
         tx.commit()?;
         Ok(())
     }
 
-    fn load_schedule(&self) -> PersistenceResult<Option<Schedule>> {
+    /// Load the schedule stored under `name`, or `None` if there is none.
+    pub fn load_named(&self, name: &str) -> PersistenceResult<Option<Schedule>> {
         let conn = self.connection.lock().expect("sqlite mutex poisoned");
 
-        let mut stmt = conn.prepare("SELECT metadata_json FROM schedule_metadata WHERE id = 1")?;
-        let metadata_json_opt: Option<String> = stmt.query_row([], |row| row.get(0)).optional()?;
+        let mut stmt = conn.prepare("SELECT metadata_json FROM schedules WHERE name = ?1")?;
+        let metadata_json_opt: Option<String> =
+            stmt.query_row(params![name], |row| row.get(0)).optional()?;
 
         let Some(metadata_json) = metadata_json_opt else {
             return Ok(None);
@@ -82,8 +94,10 @@ impl ScheduleStore for SqliteScheduleStore {
 
         let metadata: ScheduleMetadata = serde_json::from_str(&metadata_json)?;
 
-        let mut stmt = conn.prepare("SELECT task_json FROM tasks ORDER BY id ASC")?;
-        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        let mut stmt = conn.prepare(
+            "SELECT task_json FROM schedule_tasks WHERE schedule_name = ?1 ORDER BY task_id ASC",
+        )?;
+        let rows = stmt.query_map(params![name], |row| row.get::<_, String>(0))?;
 
         let mut tasks = Vec::new();
         for json in rows {
@@ -94,11 +108,109 @@ impl ScheduleStore for SqliteScheduleStore {
 
         super::validate_tasks(&tasks)?;
 
-        let mut schedule = Schedule::new_with_metadata(metadata);
-        for task in tasks {
-            schedule.upsert_task_record(task)?;
-        }
+        let calendar = Schedule::calendar_for_metadata(&metadata);
+        let schedule = Schedule::from_tasks_unrefreshed(
+            metadata,
+            calendar,
+            false,
+            tasks,
+            &task_validation::ValidationOptions::default(),
+        )?;
 
         Ok(Some(schedule))
     }
+
+    /// Insert or replace `tasks` in the schedule stored under `name` inside a
+    /// single transaction. Each task is validated as it is written; the first
+    /// invalid or repeated task rolls back the whole batch. A schedule that
+    /// does not exist yet is created with default metadata.
+    pub fn save_tasks_named(&self, name: &str, tasks: &[Task]) -> PersistenceResult<()> {
+        let mut conn = self.connection.lock().expect("sqlite mutex poisoned");
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT OR IGNORE INTO schedules (name, metadata_json) VALUES (?1, ?2)",
+            params![name, serde_json::to_string(&ScheduleMetadata::default())?],
+        )?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO schedule_tasks (schedule_name, task_id, task_json) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(schedule_name, task_id) DO UPDATE SET task_json = excluded.task_json",
+            )?;
+            let mut seen = HashSet::with_capacity(tasks.len());
+            for task in tasks {
+                if !seen.insert(task.id) {
+                    return Err(PersistenceError::DuplicateTaskId(task.id));
+                }
+                task_validation::validate_task(task)?;
+                stmt.execute(params![name, task.id, serde_json::to_string(task)?])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Names of every stored schedule, sorted.
+    pub fn list_schedules(&self) -> PersistenceResult<Vec<String>> {
+        let conn = self.connection.lock().expect("sqlite mutex poisoned");
+        let mut stmt = conn.prepare("SELECT name FROM schedules ORDER BY name ASC")?;
+        let names = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(names)
+    }
+
+    fn save_metadata(
+        &self,
+        tx: &rusqlite::Transaction,
+        name: &str,
+        metadata: &ScheduleMetadata,
+    ) -> PersistenceResult<()> {
+        let json = serde_json::to_string(metadata)?;
+        tx.execute(
+            "INSERT INTO schedules (name, metadata_json) VALUES (?1, ?2)
+             ON CONFLICT(name) DO UPDATE SET metadata_json = excluded.metadata_json",
+            params![name, json],
+        )?;
+        Ok(())
+    }
+
+    fn write_tasks(
+        &self,
+        tx: &rusqlite::Transaction,
+        name: &str,
+        schedule: &Schedule,
+    ) -> PersistenceResult<()> {
+        tx.execute(
+            "DELETE FROM schedule_tasks WHERE schedule_name = ?1",
+            params![name],
+        )?;
+        let df = schedule.dataframe();
+        let mut stmt = tx.prepare(
+            "INSERT INTO schedule_tasks (schedule_name, task_id, task_json) VALUES (?1, ?2, ?3)",
+        )?;
+        for row_idx in 0..df.height() {
+            let task = Task::from_dataframe_row(df, row_idx)?;
+            let json = serde_json::to_string(&task)?;
+            stmt.execute(params![name, task.id, json])?;
+        }
+        Ok(())
+    }
+}
+
+impl ScheduleStore for SqliteScheduleStore {
+    /// Saves under [`DEFAULT_SCHEDULE_NAME`].
+    fn save_schedule(&self, schedule: &Schedule) -> PersistenceResult<()> {
+        self.save_named(DEFAULT_SCHEDULE_NAME, schedule)
+    }
+
+    /// Loads the schedule stored under [`DEFAULT_SCHEDULE_NAME`].
+    fn load_schedule(&self) -> PersistenceResult<Option<Schedule>> {
+        self.load_named(DEFAULT_SCHEDULE_NAME)
+    }
+
+    /// Writes the batch in one SQLite transaction; see
+    /// [`SqliteScheduleStore::save_tasks_named`].
+    fn save_tasks(&self, tasks: &[Task]) -> PersistenceResult<()> {
+        self.save_tasks_named(DEFAULT_SCHEDULE_NAME, tasks)
+    }
 }
@@ -0,0 +1,352 @@
+use crate::schedule::Schedule;
+use polars::prelude::PolarsError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// The subset of the DCMA 14-point assessment evaluated by [`Schedule::quality_checks`].
+///
+/// The leads and lags points are not checked: links between tasks are
+/// plain finish-to-start links without lag (see
+/// [`LinkSpec`](crate::LinkSpec)), so a schedule cannot hold a lead or a
+/// lag to flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QualityCheckKind {
+    /// Tasks missing a predecessor or a successor.
+    Logic,
+    /// Tasks with a hard date constraint. A deadline caps the late finish
+    /// like a finish-no-later-than constraint; a forced early start only
+    /// sets a floor, which DCMA counts as soft, so it is not flagged.
+    HardConstraints,
+    /// Tasks whose total float exceeds the high-float threshold.
+    HighFloat,
+    /// Tasks with negative total float.
+    NegativeFloat,
+    /// Tasks whose duration exceeds the high-duration threshold.
+    HighDuration,
+}
+
+impl QualityCheckKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QualityCheckKind::Logic => "logic",
+            QualityCheckKind::HardConstraints => "hard_constraints",
+            QualityCheckKind::HighFloat => "high_float",
+            QualityCheckKind::NegativeFloat => "negative_float",
+            QualityCheckKind::HighDuration => "high_duration",
+        }
+    }
+}
+
+/// Limits used when grading a schedule. Defaults follow the DCMA guidance.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QualityThresholds {
+    /// Total float (working days) above which a task counts as high float.
+    pub high_float_days: i64,
+    /// Duration (working days) above which a task counts as high duration.
+    pub high_duration_days: i64,
+    /// Maximum share of incomplete tasks allowed to have logic gaps.
+    pub max_logic_ratio: f64,
+    /// Maximum share of incomplete tasks allowed to have hard constraints.
+    pub max_hard_constraint_ratio: f64,
+    /// Maximum share of incomplete tasks allowed to have high float.
+    pub max_high_float_ratio: f64,
+    /// Maximum share of incomplete tasks allowed to have negative float.
+    pub max_negative_float_ratio: f64,
+    /// Maximum share of incomplete tasks allowed to have high duration.
+    pub max_high_duration_ratio: f64,
+}
+
+impl Default for QualityThresholds {
+    fn default() -> Self {
+        Self {
+            high_float_days: 44,
+            high_duration_days: 44,
+            max_logic_ratio: 0.05,
+            max_hard_constraint_ratio: 0.05,
+            max_high_float_ratio: 0.05,
+            max_negative_float_ratio: 0.0,
+            max_high_duration_ratio: 0.05,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QualityCheck {
+    pub kind: QualityCheckKind,
+    /// Ids of the tasks failing this check, in ascending order.
+    pub task_ids: Vec<i32>,
+    /// Offending tasks as a share of the evaluated tasks.
+    pub ratio: f64,
+    /// Maximum ratio tolerated before the check fails.
+    pub threshold: f64,
+    pub passed: bool,
+}
+
+impl QualityCheck {
+    fn new(
+        kind: QualityCheckKind,
+        mut task_ids: Vec<i32>,
+        evaluated: usize,
+        threshold: f64,
+    ) -> Self {
+        task_ids.sort_unstable();
+        let ratio = if evaluated == 0 {
+            0.0
+        } else {
+            task_ids.len() as f64 / evaluated as f64
+        };
+        Self {
+            kind,
+            task_ids,
+            ratio,
+            threshold,
+            passed: ratio <= threshold,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QualityReport {
+    /// Number of incomplete tasks the checks were evaluated against.
+    pub evaluated_task_count: usize,
+    pub checks: Vec<QualityCheck>,
+}
+
+impl QualityReport {
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|check| check.passed)
+    }
+
+    pub fn check(&self, kind: QualityCheckKind) -> Option<&QualityCheck> {
+        self.checks.iter().find(|check| check.kind == kind)
+    }
+
+    pub fn failed_checks(&self) -> impl Iterator<Item = &QualityCheck> {
+        self.checks.iter().filter(|check| !check.passed)
+    }
+}
+
+/// Tasks that break the single-start, single-finish structure, from
+/// [`Schedule::enforce_bookends`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BookendReport {
+    /// Tasks with no predecessors, in ascending order.
+    pub open_starts: Vec<i32>,
+    /// Tasks nothing depends on, in ascending order.
+    pub open_finishes: Vec<i32>,
+    /// Whether the open ends were linked to the milestones.
+    pub linked: bool,
+}
+
+impl BookendReport {
+    pub fn is_clean(&self) -> bool {
+        self.open_starts.is_empty() && self.open_finishes.is_empty()
+    }
+}
+
+/// The fields of a task the quality checks need, captured in a single scan.
+struct ScannedTask {
+    id: i32,
+    predecessors: Vec<i32>,
+    duration_days: i64,
+    total_float: Option<i64>,
+    has_hard_constraint: bool,
+    is_complete: bool,
+}
+
+impl Schedule {
+    /// Tasks with neither predecessors nor successors, in schedule order:
+    /// islands outside the network that the logic check also flags. Uses the
+    /// `successors` column from the last refresh. A schedule of a single task
+    /// has nothing to link to and reports none.
+    pub fn dangling_tasks(&self) -> Result<Vec<i32>, PolarsError> {
+        if self.dataframe().height() <= 1 {
+            return Ok(Vec::new());
+        }
+        let mut dangling = Vec::new();
+        for task in self.iter_tasks() {
+            let task = task?;
+            if task.predecessors.is_empty() && task.successors.is_empty() {
+                dangling.push(task.id);
+            }
+        }
+        Ok(dangling)
+    }
+
+    /// Check that every task hangs off the start milestone and feeds the
+    /// finish milestone: tasks other than the two milestones must have a
+    /// predecessor and a successor. Both milestones must exist with zero
+    /// duration. With `auto_link`, open starts get the start milestone as a
+    /// predecessor and open finishes become predecessors of the finish
+    /// milestone; call [`Schedule::refresh`] afterwards to recompute dates.
+    /// The report lists the open ends found before any linking.
+    pub fn enforce_bookends(
+        &mut self,
+        start_milestone_id: i32,
+        finish_milestone_id: i32,
+        auto_link: bool,
+    ) -> Result<BookendReport, PolarsError> {
+        self.edit(|schedule| {
+            if start_milestone_id == finish_milestone_id {
+                return Err(PolarsError::ComputeError(
+                    "start and finish milestones must be different tasks".into(),
+                ));
+            }
+            for (role, id) in [
+                ("start", start_milestone_id),
+                ("finish", finish_milestone_id),
+            ] {
+                let milestone = schedule.find_task(id)?.ok_or_else(|| {
+                    PolarsError::ComputeError(format!("{role} milestone {id} not found").into())
+                })?;
+                if milestone.duration_days != 0 {
+                    return Err(PolarsError::ComputeError(
+                    format!(
+                        "{role} milestone {id} has duration {} (milestones must be zero-duration)",
+                        milestone.duration_days
+                    )
+                    .into(),
+                ));
+                }
+            }
+
+            let mut tasks = Vec::with_capacity(schedule.dataframe().height());
+            for task in schedule.iter_tasks() {
+                let task = task?;
+                tasks.push((task.id, task.predecessors));
+            }
+            let has_successor: HashSet<i32> = tasks
+                .iter()
+                .flat_map(|(_, predecessors)| predecessors.iter().copied())
+                .collect();
+
+            let mut open_starts = Vec::new();
+            let mut open_finishes = Vec::new();
+            for (id, predecessors) in &tasks {
+                if *id == start_milestone_id || *id == finish_milestone_id {
+                    continue;
+                }
+                if predecessors.is_empty() {
+                    open_starts.push(*id);
+                }
+                if !has_successor.contains(id) {
+                    open_finishes.push(*id);
+                }
+            }
+            open_starts.sort_unstable();
+            open_finishes.sort_unstable();
+
+            if auto_link {
+                for id in &open_starts {
+                    schedule.add_predecessor(*id, start_milestone_id)?;
+                }
+                for id in &open_finishes {
+                    schedule.add_predecessor(finish_milestone_id, *id)?;
+                }
+            }
+
+            Ok(BookendReport {
+                open_starts,
+                open_finishes,
+                linked: auto_link,
+            })
+        })
+    }
+
+    /// Run the schedule quality checks with the default DCMA thresholds.
+    pub fn quality_checks(&self) -> Result<QualityReport, PolarsError> {
+        self.quality_checks_with(&QualityThresholds::default())
+    }
+
+    /// Run the schedule quality checks against the current DataFrame without
+    /// recomputing it. Completed tasks (those with an actual finish) are skipped.
+    pub fn quality_checks_with(
+        &self,
+        thresholds: &QualityThresholds,
+    ) -> Result<QualityReport, PolarsError> {
+        let mut tasks: Vec<ScannedTask> = Vec::with_capacity(self.dataframe().height());
+        for task in self.iter_tasks() {
+            let task = task?;
+            tasks.push(ScannedTask {
+                id: task.id,
+                predecessors: task.predecessors,
+                duration_days: task.duration_days,
+                total_float: task.total_float,
+                has_hard_constraint: task.deadline.is_some(),
+                is_complete: task.actual_finish.is_some(),
+            });
+        }
+        let known_ids: HashSet<i32> = tasks.iter().map(|task| task.id).collect();
+        let mut has_successor: HashSet<i32> = HashSet::new();
+        for task in &tasks {
+            for pred in &task.predecessors {
+                if known_ids.contains(pred) {
+                    has_successor.insert(*pred);
+                }
+            }
+        }
+
+        let incomplete: Vec<&ScannedTask> = tasks.iter().filter(|task| !task.is_complete).collect();
+        let evaluated = incomplete.len();
+
+        let mut logic = Vec::new();
+        let mut hard_constraints = Vec::new();
+        let mut high_float = Vec::new();
+        let mut negative_float = Vec::new();
+        let mut high_duration = Vec::new();
+        for task in &incomplete {
+            let has_predecessor = task.predecessors.iter().any(|id| known_ids.contains(id));
+            if !has_predecessor || !has_successor.contains(&task.id) {
+                logic.push(task.id);
+            }
+            if task.has_hard_constraint {
+                hard_constraints.push(task.id);
+            }
+            match task.total_float {
+                Some(tf) if tf > thresholds.high_float_days => high_float.push(task.id),
+                Some(tf) if tf < 0 => negative_float.push(task.id),
+                _ => {}
+            }
+            if task.duration_days > thresholds.high_duration_days {
+                high_duration.push(task.id);
+            }
+        }
+
+        Ok(QualityReport {
+            evaluated_task_count: evaluated,
+            checks: vec![
+                QualityCheck::new(
+                    QualityCheckKind::Logic,
+                    logic,
+                    evaluated,
+                    thresholds.max_logic_ratio,
+                ),
+                QualityCheck::new(
+                    QualityCheckKind::HardConstraints,
+                    hard_constraints,
+                    evaluated,
+                    thresholds.max_hard_constraint_ratio,
+                ),
+                QualityCheck::new(
+                    QualityCheckKind::HighFloat,
+                    high_float,
+                    evaluated,
+                    thresholds.max_high_float_ratio,
+                ),
+                QualityCheck::new(
+                    QualityCheckKind::NegativeFloat,
+                    negative_float,
+                    evaluated,
+                    thresholds.max_negative_float_ratio,
+                ),
+                QualityCheck::new(
+                    QualityCheckKind::HighDuration,
+                    high_duration,
+                    evaluated,
+                    thresholds.max_high_duration_ratio,
+                ),
+            ],
+        })
+    }
+}
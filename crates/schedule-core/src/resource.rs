@@ -8,24 +8,72 @@ pub struct ResourceAllocation {
     /// Optional role or description for the resource while working on the task.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub role: Option<String>,
-    /// Planned units for the allocation (e.g., hours or FTE-days). Must be non-negative.
+    /// Planned units for the allocation (e.g., hours or FTE-days). Must be
+    /// non-negative and no more than [`ResourceAllocation::unit_limit`].
     pub allocation_units: f64,
+    /// Upper bound for `allocation_units`. Unset means
+    /// [`ResourceAllocation::DEFAULT_MAX_UNITS`]; set it higher to allow a
+    /// deliberate over-allocation or units measured in hours.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_units: Option<f64>,
     /// Optional cost rate per unit (e.g., hourly rate). Non-negative when provided.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cost_rate: Option<f64>,
     /// Optional free-form notes about the allocation.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub notes: Option<String>,
+    /// Time-phased units as `(fraction of duration, units)` breakpoints in
+    /// ascending fraction order, each fraction in `0..1`. A breakpoint's
+    /// units apply from that point of the task until the next one; working
+    /// days before the first breakpoint use `allocation_units`. Empty means
+    /// flat `allocation_units` for the whole task.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profile: Vec<(f64, f64)>,
 }
 
 impl ResourceAllocation {
+    /// One full unit of the resource.
+    pub const DEFAULT_MAX_UNITS: f64 = 1.0;
+
     pub fn new(resource_id: impl Into<String>, allocation_units: f64) -> Self {
         Self {
             resource_id: resource_id.into(),
             role: None,
             allocation_units,
+            max_units: None,
             cost_rate: None,
             notes: None,
+            profile: Vec::new(),
+        }
+    }
+
+    pub fn unit_limit(&self) -> f64 {
+        self.max_units.unwrap_or(Self::DEFAULT_MAX_UNITS)
+    }
+
+    /// Units booked on the `day`th working day (zero-based) of a task
+    /// lasting `duration_days` working days.
+    pub fn units_on_day(&self, day: i64, duration_days: i64) -> f64 {
+        if duration_days <= 0 {
+            return self.allocation_units;
+        }
+        let fraction = day as f64 / duration_days as f64;
+        self.profile
+            .iter()
+            .take_while(|(start, _)| *start <= fraction)
+            .last()
+            .map_or(self.allocation_units, |(_, units)| *units)
+    }
+
+    /// Units summed over every working day of a task lasting
+    /// `duration_days` working days. Flat allocations give
+    /// `allocation_units * duration_days`.
+    pub fn total_units(&self, duration_days: i64) -> f64 {
+        if self.profile.is_empty() {
+            return self.allocation_units * duration_days.max(0) as f64;
         }
+        (0..duration_days)
+            .map(|day| self.units_on_day(day, duration_days))
+            .sum()
     }
 }
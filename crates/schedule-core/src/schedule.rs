@@ -1,17 +1,122 @@
+use crate::calculations::CriticalPathOptions;
 use crate::calculations::backward_pass::BackwardPass as CalcBackwardPass;
 use crate::calculations::forward_pass::ForwardPass as CalcForwardPass;
 use crate::calendar::{WorkCalendar, WorkCalendarConfig};
+use crate::graph::schedule_dag::ScheduleDag;
 use crate::metadata::ScheduleMetadata;
-use crate::task::{ProgressRationaleTemplate, Task};
-use crate::task_validation::{self, TaskValidationError};
-use chrono::{Datelike, Duration, NaiveDate};
+use crate::task::{ProgressEntry, ProgressMeasurement, ProgressRationaleTemplate, Task, TaskKind};
+use crate::task_validation::{self, TaskValidationError, ValidationOptions};
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime};
 use polars::prelude::PlSmallStr;
 use polars::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Runs one refresh step inside a debug-level span carrying the task count,
+/// logging how long it took. Without the `tracing` feature this is just the
+/// step itself.
+macro_rules! traced_step {
+    ($name:literal, $tasks:expr, $step:expr) => {{
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!($name, tasks = $tasks).entered();
+        #[cfg(feature = "tracing")]
+        let started = std::time::Instant::now();
+        #[cfg(not(feature = "tracing"))]
+        let _ = $tasks;
+        let result = $step;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            tasks = $tasks,
+            elapsed_us = started.elapsed().as_micros() as u64,
+            concat!($name, " finished")
+        );
+        result
+    }};
+}
+
+/// A task whose forecast finish lands after its deadline.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MissedDeadline {
+    pub task_id: i32,
+    pub deadline: NaiveDate,
+    pub early_finish: NaiveDate,
+    /// Working days between the deadline and the forecast finish.
+    pub days_late: i64,
+}
+
+/// Which dates a task's `schedule_variance_days` was measured against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VarianceBasis {
+    /// Baseline compared with actual dates.
+    Actual,
+    /// Baseline compared with the current early dates, used before any
+    /// actuals exist when [`Schedule::set_forecast_variance`] is enabled.
+    Forecast,
+    /// Baseline finish compared with the forecast finish of a started,
+    /// unfinished task with partial progress: its early finish, which the
+    /// forward pass counts from the remaining work at the status date.
+    InProgress,
+}
+
+/// Where [`Schedule::refresh`] places tasks that have float.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchedulingMode {
+    /// As soon as possible: early dates come from the forward pass.
+    #[default]
+    Asap,
+    /// As late as possible: tasks with positive float and no actual start
+    /// take their late dates as `early_start`/`early_finish`. Float and the
+    /// critical path are still measured from the forward pass.
+    Alap,
+}
+
+/// Display order for [`Schedule::sorted_tasks`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    /// Earliest early start first; unscheduled tasks last.
+    EarlyStart,
+    /// WBS segments compared numerically ("1.2" before "1.10"); tasks
+    /// without a code last.
+    Wbs,
+    Id,
+    /// The [`MetaDependencyTree`](crate::graph::dependency_tree::MetaDependencyTree)
+    /// execution order, so predecessors come before their successors.
+    TopologicalDependency,
+}
+
+/// Computed dates that fall outside the years a custom calendar has holidays
+/// for. Those dates are scheduled as if every weekday were working.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CalendarCoverageGap {
+    pub first_holiday_year: i32,
+    pub last_holiday_year: i32,
+    /// Earliest computed early or late date.
+    pub schedule_start: NaiveDate,
+    /// Latest computed early or late date.
+    pub schedule_finish: NaiveDate,
+}
+
+/// `schedule_variance_days` per row plus each task's [`VarianceBasis`].
+type VarianceColumn = (Vec<Option<i64>>, BTreeMap<i32, VarianceBasis>);
+
+/// Chunk count above which a column edited in place is rechunked.
+const MAX_COLUMN_CHUNKS: usize = 64;
+
+/// Maximum number of tasks listed in [`RefreshSummary::worst_negative_float`].
+pub const NEGATIVE_FLOAT_REPORT_LIMIT: usize = 10;
+
+/// A task whose late dates fall before its early dates.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NegativeFloat {
+    pub task_id: i32,
+    pub total_float: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RefreshSummary {
     pub task_count: usize,
     pub critical_count: usize,
@@ -20,6 +125,48 @@ pub struct RefreshSummary {
     pub positive_variance_count: usize,
     pub negative_variance_count: usize,
     pub on_track_variance_count: usize,
+    #[serde(default)]
+    pub missed_deadlines: Vec<MissedDeadline>,
+    /// Basis of each task's variance, keyed by task id. Tasks without a
+    /// variance are absent.
+    #[serde(default)]
+    pub variance_basis: BTreeMap<i32, VarianceBasis>,
+    /// Tasks with negative total float, i.e. an infeasible schedule.
+    #[serde(default)]
+    pub negative_float_count: usize,
+    /// The most negative-float tasks, worst first, at most
+    /// [`NEGATIVE_FLOAT_REPORT_LIMIT`] entries.
+    #[serde(default)]
+    pub worst_negative_float: Vec<NegativeFloat>,
+    /// Working days between the project start and end dates, inclusive.
+    #[serde(default)]
+    pub project_working_days: i64,
+    /// Set when a custom calendar does not cover the computed dates. Default
+    /// calendars are extended instead.
+    #[serde(default)]
+    pub calendar_coverage_gap: Option<CalendarCoverageGap>,
+    /// See [`Schedule::project_percent_complete`].
+    #[serde(default)]
+    pub project_percent_complete: Option<f64>,
+    /// See [`Schedule::forecast_finish`].
+    #[serde(default)]
+    pub forecast_finish: Option<NaiveDate>,
+    /// Debug signal: tasks the forward-pass engine left out, whose early
+    /// dates were computed by the fallback in the last forward pass. Should
+    /// be zero; anything else points at a graph-building bug.
+    #[serde(default)]
+    pub forward_pass_fallback_count: usize,
+    /// Tasks the last backward pass could not place in the network, in
+    /// ascending id order: work tasks linked to nothing, whose late dates
+    /// only measure the gap to the project end, and tasks the engine left
+    /// undated, whose late dates were copied from the early ones to keep the
+    /// columns non-null. Either way their float means "not computed".
+    /// Zero-duration milestones and support tasks are not reported.
+    #[serde(default)]
+    pub unscheduled_tasks: Vec<i32>,
+    /// Tasks linked to nothing, see [`Schedule::dangling_tasks`].
+    #[serde(default)]
+    pub open_ends: usize,
 }
 
 impl RefreshSummary {
@@ -39,6 +186,30 @@ impl RefreshSummary {
         if self.on_track_variance_count > 0 {
             parts.push(format!("variance0={}", self.on_track_variance_count));
         }
+        if !self.missed_deadlines.is_empty() {
+            parts.push(format!("missed_deadlines={}", self.missed_deadlines.len()));
+        }
+        if self.negative_float_count > 0 {
+            parts.push(format!("negative_float={}", self.negative_float_count));
+        }
+        if self.open_ends > 0 {
+            parts.push(format!("open_ends={}", self.open_ends));
+        }
+        if !self.unscheduled_tasks.is_empty() {
+            parts.push(format!("unscheduled={}", self.unscheduled_tasks.len()));
+        }
+        if let Some(pct) = self.project_percent_complete {
+            parts.push(format!("complete={:.1}%", pct * 100.0));
+        }
+        if let Some(date) = self.forecast_finish {
+            parts.push(format!("forecast={}", date));
+        }
+        if let Some(gap) = &self.calendar_coverage_gap {
+            parts.push(format!(
+                "calendar_holidays={}..{}",
+                gap.first_holiday_year, gap.last_holiday_year
+            ));
+        }
         if !self.critical_path.is_empty() {
             let chain = self
                 .critical_path
@@ -52,6 +223,85 @@ impl RefreshSummary {
     }
 }
 
+/// Multi-line report for people; [`RefreshSummary::to_cli_summary`] is the
+/// one-line form.
+impl fmt::Display for RefreshSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Tasks: {} ({} critical)",
+            self.task_count, self.critical_count
+        )?;
+        if let Some(date) = self.latest_finish {
+            writeln!(f, "Finish: {date}")?;
+        }
+        if let Some(date) = self.forecast_finish {
+            writeln!(f, "Forecast finish: {date}")?;
+        }
+        if let Some(pct) = self.project_percent_complete {
+            writeln!(f, "Complete: {:.1}%", pct * 100.0)?;
+        }
+        if self.critical_path.is_empty() {
+            writeln!(f, "Critical path: none")?;
+        } else {
+            let chain = self
+                .critical_path
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            writeln!(f, "Critical path: {chain}")?;
+        }
+        writeln!(
+            f,
+            "Variance: {} positive, {} negative, {} on track",
+            self.positive_variance_count,
+            self.negative_variance_count,
+            self.on_track_variance_count
+        )?;
+        for missed in &self.missed_deadlines {
+            writeln!(
+                f,
+                "Missed deadline: task {} finishes {} ({} days after {})",
+                missed.task_id, missed.early_finish, missed.days_late, missed.deadline
+            )?;
+        }
+        if self.negative_float_count > 0 {
+            writeln!(f, "Negative float: {} tasks", self.negative_float_count)?;
+        }
+        if self.open_ends > 0 {
+            writeln!(f, "Open ends: {} unlinked tasks", self.open_ends)?;
+        }
+        if let Some(gap) = &self.calendar_coverage_gap {
+            writeln!(
+                f,
+                "Calendar holidays cover {}..{} only; schedule runs {}..{}",
+                gap.first_holiday_year,
+                gap.last_holiday_year,
+                gap.schedule_start,
+                gap.schedule_finish
+            )?;
+        }
+        if self.forward_pass_fallback_count > 0 {
+            writeln!(
+                f,
+                "Forward-pass fallbacks: {}",
+                self.forward_pass_fallback_count
+            )?;
+        }
+        if !self.unscheduled_tasks.is_empty() {
+            let ids = self
+                .unscheduled_tasks
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(f, "Unscheduled (late dates copied from early): {ids}")?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum ScheduleMetadataError {
     StartAfterEnd {
@@ -63,6 +313,9 @@ pub enum ScheduleMetadataError {
         required_finish: NaiveDate,
     },
     Computation(String),
+    /// `timezone` is not a known IANA zone name.
+    #[cfg(feature = "timezone")]
+    UnknownTimezone(String),
 }
 
 impl fmt::Display for ScheduleMetadataError {
@@ -80,17 +333,98 @@ impl fmt::Display for ScheduleMetadataError {
                 "project end date {project_end} is before the current schedule finish {required_finish}"
             ),
             ScheduleMetadataError::Computation(message) => write!(f, "{message}"),
+            #[cfg(feature = "timezone")]
+            ScheduleMetadataError::UnknownTimezone(zone) => {
+                write!(f, "unknown IANA timezone '{zone}'")
+            }
         }
     }
 }
 
 impl std::error::Error for ScheduleMetadataError {}
 
+/// A task an operation named is not in the schedule. Schedule methods
+/// return it inside [`PolarsError`] as an I/O error of kind
+/// [`std::io::ErrorKind::NotFound`]; [`TaskNotFound::find`] gets it back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskNotFound {
+    pub task_id: i32,
+    /// The id was given as a predecessor of another task.
+    pub as_predecessor: bool,
+}
+
+impl TaskNotFound {
+    pub fn task(task_id: i32) -> Self {
+        Self {
+            task_id,
+            as_predecessor: false,
+        }
+    }
+
+    pub fn predecessor(task_id: i32) -> Self {
+        Self {
+            task_id,
+            as_predecessor: true,
+        }
+    }
+
+    /// The missing task `err` reports, if it is one.
+    pub fn find(err: &PolarsError) -> Option<Self> {
+        match err {
+            PolarsError::IO { error, .. } => error.get_ref()?.downcast_ref::<Self>().copied(),
+            PolarsError::Context { error, .. } => Self::find(error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TaskNotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let role = if self.as_predecessor {
+            "predecessor"
+        } else {
+            "task"
+        };
+        write!(f, "{role} {} not found", self.task_id)
+    }
+}
+
+impl std::error::Error for TaskNotFound {}
+
+impl From<TaskNotFound> for PolarsError {
+    fn from(value: TaskNotFound) -> Self {
+        std::io::Error::new(std::io::ErrorKind::NotFound, value).into()
+    }
+}
+
 pub struct Schedule {
     df: DataFrame,
+    /// Row position of each task id in `df`. Column replacements keep row
+    /// order, so only inserts and whole-frame rebuilds need to touch it.
+    row_index: HashMap<i32, usize>,
     metadata: ScheduleMetadata,
     calendar: WorkCalendar,
     calendar_is_custom: bool,
+    derive_rationale_percent: bool,
+    forecast_variance: bool,
+    status_date: Option<NaiveDate>,
+    scheduling_mode: SchedulingMode,
+    finish_includes_support_tasks: bool,
+    pin_actual_dates: bool,
+    /// Tasks the last forward pass computed outside the engine.
+    forward_pass_fallback_count: usize,
+    /// Tasks the last backward pass left undated.
+    unscheduled_tasks: Vec<i32>,
+    /// Nesting depth of [`Schedule::edit`] calls in progress.
+    edit_depth: u32,
+    /// Whether the edit in progress changed anything yet.
+    edited: bool,
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Schedule {
@@ -104,10 +438,161 @@ impl Schedule {
 
         Self {
             df: schedule,
+            row_index: HashMap::new(),
             metadata,
             calendar,
             calendar_is_custom,
+            derive_rationale_percent: false,
+            forecast_variance: false,
+            status_date: None,
+            scheduling_mode: SchedulingMode::Asap,
+            finish_includes_support_tasks: false,
+            pin_actual_dates: false,
+            forward_pass_fallback_count: 0,
+            unscheduled_tasks: Vec::new(),
+            edit_depth: 0,
+            edited: false,
+        }
+    }
+
+    /// Build a schedule from `tasks` in one step: the collection is validated
+    /// once, the task table is constructed column by column and the schedule
+    /// is refreshed once, with tasks stored in dependency order (see
+    /// [`Schedule::import_tasks`]). This is much faster than calling
+    /// [`Schedule::upsert_task_record`] per task for large imports.
+    ///
+    /// The calendar counts as custom when it differs from the one the
+    /// metadata's year range would produce.
+    pub fn from_tasks(
+        metadata: ScheduleMetadata,
+        calendar: WorkCalendar,
+        tasks: Vec<Task>,
+    ) -> Result<Self, PolarsError> {
+        let calendar_is_custom = calendar != Self::calendar_for_metadata(&metadata);
+        let mut schedule = Self::from_tasks_unrefreshed(
+            metadata,
+            calendar,
+            calendar_is_custom,
+            tasks,
+            &ValidationOptions::default(),
+        )?;
+        if schedule.df.height() > 0 {
+            schedule.refresh()?;
+        }
+        Ok(schedule)
+    }
+
+    /// [`Schedule::from_tasks`] without the final refresh, for the
+    /// persistence loaders: stored CPM results load exactly as they were
+    /// saved, the loader says whether the stored calendar is custom and
+    /// `options` may relax validation. Loading is not a change, so the
+    /// metadata's revision counters are kept.
+    pub(crate) fn from_tasks_unrefreshed(
+        metadata: ScheduleMetadata,
+        calendar: WorkCalendar,
+        calendar_is_custom: bool,
+        tasks: Vec<Task>,
+        options: &ValidationOptions,
+    ) -> Result<Self, PolarsError> {
+        task_validation::validate_task_collection_with(&tasks, options)
+            .map_err(Self::validation_error)?;
+        let (revision, modified_at) = (metadata.revision, metadata.modified_at);
+        let mut schedule = Self::from_parts(metadata, calendar, calendar_is_custom);
+        if !tasks.is_empty() {
+            let ordered = Self::dependency_order(tasks);
+            schedule.replace_dataframe(Task::tasks_to_dataframe(&ordered)?)?;
         }
+        schedule.metadata.revision = revision;
+        schedule.metadata.modified_at = modified_at;
+        Ok(schedule)
+    }
+
+    /// Insert or replace `tasks` in one step. New tasks are appended in
+    /// dependency order, predecessors first, whatever order they arrive in;
+    /// a task whose id is already stored is replaced in place, as by
+    /// [`Schedule::replace_task_record`]. The collection is validated once
+    /// and the schedule refreshed once at the end, instead of after every
+    /// duration write as with [`Schedule::upsert_task_record`]. Returns the
+    /// ids in the order they were applied.
+    pub fn import_tasks(&mut self, tasks: Vec<Task>) -> Result<Vec<i32>, PolarsError> {
+        self.edit(|schedule| {
+            task_validation::validate_task_collection(&tasks).map_err(Self::validation_error)?;
+            if tasks.is_empty() {
+                return Ok(Vec::new());
+            }
+            let ordered = Self::dependency_order(tasks);
+            let applied = ordered.iter().map(|task| task.id).collect();
+
+            let mut records = schedule.tasks()?;
+            let rows: HashMap<i32, usize> = records
+                .iter()
+                .enumerate()
+                .map(|(row, task)| (task.id, row))
+                .collect();
+            for mut task in ordered {
+                match rows.get(&task.id) {
+                    Some(&row) => {
+                        task.revision = records[row].revision + 1;
+                        records[row] = task;
+                    }
+                    None => records.push(task),
+                }
+            }
+            schedule.replace_dataframe(Task::tasks_to_dataframe(&records)?)?;
+            schedule.refresh()?;
+            Ok(applied)
+        })
+    }
+
+    /// Order `tasks` so each comes after its predecessors within the set,
+    /// lowest id first among ready tasks. Predecessors outside the set do
+    /// not hold a task back. Tasks on a cycle keep their input order at the
+    /// end; the refresh that follows reports the cycle.
+    fn dependency_order(tasks: Vec<Task>) -> Vec<Task> {
+        let ids: HashSet<i32> = tasks.iter().map(|task| task.id).collect();
+        let mut waiting_on: HashMap<i32, usize> = HashMap::with_capacity(tasks.len());
+        let mut dependents: HashMap<i32, Vec<i32>> = HashMap::new();
+        for task in &tasks {
+            let preds: HashSet<i32> = task
+                .predecessors
+                .iter()
+                .copied()
+                .filter(|pred| ids.contains(pred) && *pred != task.id)
+                .collect();
+            waiting_on.insert(task.id, preds.len());
+            for pred in preds {
+                dependents.entry(pred).or_default().push(task.id);
+            }
+        }
+
+        let mut ready: BTreeSet<i32> = waiting_on
+            .iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(id, _)| *id)
+            .collect();
+        let mut order = Vec::with_capacity(tasks.len());
+        while let Some(id) = ready.pop_first() {
+            order.push(id);
+            for dependent in dependents.get(&id).into_iter().flatten() {
+                let count = waiting_on
+                    .get_mut(dependent)
+                    .expect("dependent is in the set");
+                *count -= 1;
+                if *count == 0 {
+                    ready.insert(*dependent);
+                }
+            }
+        }
+
+        let position: HashMap<i32, usize> = order
+            .iter()
+            .enumerate()
+            .map(|(pos, id)| (*id, pos))
+            .collect();
+        let mut tasks = tasks;
+        // Stable sort: tasks missing from `order` (cycles) keep input order.
+        tasks.sort_by_key(|task| position.get(&task.id).copied().unwrap_or(usize::MAX));
+        tasks
     }
 
     fn validate_metadata_dates(metadata: &ScheduleMetadata) -> Result<(), ScheduleMetadataError> {
@@ -120,6 +605,18 @@ impl Schedule {
         Ok(())
     }
 
+    #[cfg(feature = "timezone")]
+    fn validate_metadata_timezone(
+        metadata: &ScheduleMetadata,
+    ) -> Result<(), ScheduleMetadataError> {
+        match &metadata.timezone {
+            Some(zone) if zone.parse::<chrono_tz::Tz>().is_err() => {
+                Err(ScheduleMetadataError::UnknownTimezone(zone.clone()))
+            }
+            _ => Ok(()),
+        }
+    }
+
     fn validate_schedule_finish_against_metadata(
         &self,
         metadata: &ScheduleMetadata,
@@ -130,25 +627,32 @@ impl Schedule {
         let latest_finish = self
             .latest_early_finish()
             .map_err(|err| ScheduleMetadataError::Computation(err.to_string()))?;
-        if let Some(required_finish) = latest_finish {
-            if required_finish > metadata.project_end_date {
-                return Err(ScheduleMetadataError::EndPrecedesScheduleFinish {
-                    project_end: metadata.project_end_date,
-                    required_finish,
-                });
-            }
+        if let Some(required_finish) = latest_finish
+            && required_finish > metadata.project_end_date
+        {
+            return Err(ScheduleMetadataError::EndPrecedesScheduleFinish {
+                project_end: metadata.project_end_date,
+                required_finish,
+            });
         }
         Ok(())
     }
 
     fn validate_metadata(&self, metadata: &ScheduleMetadata) -> Result<(), ScheduleMetadataError> {
         Self::validate_metadata_dates(metadata)?;
+        #[cfg(feature = "timezone")]
+        Self::validate_metadata_timezone(metadata)?;
         self.validate_schedule_finish_against_metadata(metadata)?;
         Ok(())
     }
 
     fn apply_metadata(&mut self, metadata: ScheduleMetadata) {
-        self.metadata = metadata;
+        self.metadata = ScheduleMetadata {
+            revision: self.metadata.revision,
+            modified_at: self.metadata.modified_at,
+            ..metadata
+        };
+        self.touch();
         if !self.calendar_is_custom {
             self.calendar = Self::calendar_for_metadata(&self.metadata);
         }
@@ -178,9 +682,11 @@ impl Schedule {
         let start =
             NaiveDate::from_ymd_opt(start_year, 1, 1).expect("invalid start year for schedule");
         let end = NaiveDate::from_ymd_opt(end_year, 12, 31).expect("invalid end year for schedule");
-        let mut metadata = ScheduleMetadata::default();
-        metadata.project_start_date = start;
-        metadata.project_end_date = end;
+        let metadata = ScheduleMetadata {
+            project_start_date: start,
+            project_end_date: end,
+            ..ScheduleMetadata::default()
+        };
         Self::new_with_metadata(metadata)
     }
 
@@ -191,6 +697,18 @@ impl Schedule {
         Self::from_parts(metadata, calendar, true)
     }
 
+    /// Like [`Schedule::new_with_metadata`], but `configure` first adjusts
+    /// the default calendar for the metadata's years (holidays, working
+    /// days). The result is treated as a custom calendar.
+    pub fn new_with_metadata_and(
+        metadata: ScheduleMetadata,
+        configure: impl FnOnce(&mut WorkCalendar),
+    ) -> Self {
+        let mut calendar = Self::calendar_for_metadata(&metadata);
+        configure(&mut calendar);
+        Self::from_parts(metadata, calendar, true)
+    }
+
     pub fn set_metadata(
         &mut self,
         metadata: ScheduleMetadata,
@@ -200,6 +718,69 @@ impl Schedule {
         Ok(())
     }
 
+    /// Independent copy for what-if edits. The DataFrame clone shares column
+    /// buffers copy-on-write, so forking is cheap even for large schedules.
+    pub fn fork(&self) -> Schedule {
+        Self {
+            df: self.df.clone(),
+            row_index: self.row_index.clone(),
+            metadata: self.metadata.clone(),
+            calendar: self.calendar.clone(),
+            calendar_is_custom: self.calendar_is_custom,
+            derive_rationale_percent: self.derive_rationale_percent,
+            forecast_variance: self.forecast_variance,
+            status_date: self.status_date,
+            scheduling_mode: self.scheduling_mode,
+            finish_includes_support_tasks: self.finish_includes_support_tasks,
+            pin_actual_dates: self.pin_actual_dates,
+            forward_pass_fallback_count: self.forward_pass_fallback_count,
+            unscheduled_tasks: self.unscheduled_tasks.clone(),
+            edit_depth: 0,
+            edited: false,
+        }
+    }
+
+    /// Whether both schedules describe the same project: equal metadata,
+    /// calendar config and tasks, regardless of row order or predecessor
+    /// order. Fields a refresh derives (early and late dates, float,
+    /// criticality, variance, successors) and revision counters are ignored.
+    pub fn semantically_equals(&self, other: &Schedule) -> bool {
+        let uncounted = |metadata: &ScheduleMetadata| ScheduleMetadata {
+            revision: 0,
+            modified_at: None,
+            ..metadata.clone()
+        };
+        if uncounted(&self.metadata) != uncounted(&other.metadata)
+            || self.calendar_config() != other.calendar_config()
+            || self.df.height() != other.df.height()
+        {
+            return false;
+        }
+        match (self.canonical_tasks(), other.canonical_tasks()) {
+            (Ok(ours), Ok(theirs)) => ours == theirs,
+            _ => false,
+        }
+    }
+
+    /// Tasks by id with derived fields cleared, for [`Schedule::semantically_equals`].
+    fn canonical_tasks(&self) -> Result<Vec<Task>, PolarsError> {
+        let mut tasks = self.tasks()?;
+        for task in &mut tasks {
+            task.predecessors.sort_unstable();
+            task.early_start = None;
+            task.early_finish = None;
+            task.late_start = None;
+            task.late_finish = None;
+            task.total_float = None;
+            task.is_critical = None;
+            task.schedule_variance_days = None;
+            task.successors.clear();
+            task.revision = 0;
+        }
+        tasks.sort_by_key(|task| task.id);
+        Ok(tasks)
+    }
+
     pub fn dataframe(&self) -> &DataFrame {
         &self.df
     }
@@ -237,13 +818,56 @@ impl Schedule {
     }
 
     pub fn set_project_name(&mut self, name: impl Into<String>) {
+        self.touch();
         self.metadata.project_name = name.into();
     }
 
     pub fn set_project_description(&mut self, description: impl Into<String>) {
+        self.touch();
         self.metadata.project_description = description.into();
     }
 
+    /// Counter bumped once by every call that changes tasks, metadata,
+    /// calendar or settings, and by each refresh, however many columns the
+    /// call rewrites. Equal revisions of the same schedule mean nothing
+    /// changed in between, so it can key caches and ETags.
+    /// Starts at 0. It is kept in [`ScheduleMetadata::revision`], so every
+    /// store that saves the metadata resumes from the saved value.
+    pub fn revision(&self) -> u64 {
+        self.metadata.revision
+    }
+
+    /// Local time of the last [`Schedule::touch`], or `None` if the
+    /// schedule has not changed since it was created.
+    pub fn modified_at(&self) -> Option<NaiveDateTime> {
+        self.metadata.modified_at
+    }
+
+    /// Record a change: bump [`Schedule::revision`] and stamp
+    /// [`Schedule::modified_at`]. Every mutating method calls this; inside
+    /// [`Schedule::edit`] the bump waits for the outermost edit to end.
+    pub(crate) fn touch(&mut self) {
+        if self.edit_depth > 0 {
+            self.edited = true;
+            return;
+        }
+        self.metadata.revision += 1;
+        self.metadata.modified_at = Some(Local::now().naive_local());
+    }
+
+    /// Run `op` as one change: however many writes and nested public calls
+    /// it makes, [`Schedule::revision`] moves by at most one, when the
+    /// outermost edit ends. A failing `op` still counts the writes it made.
+    pub(crate) fn edit<R>(&mut self, op: impl FnOnce(&mut Self) -> R) -> R {
+        self.edit_depth += 1;
+        let result = op(self);
+        self.edit_depth -= 1;
+        if self.edit_depth == 0 && std::mem::take(&mut self.edited) {
+            self.touch();
+        }
+        result
+    }
+
     pub fn set_project_start_date(&mut self, date: NaiveDate) -> Result<(), ScheduleMetadataError> {
         self.update_metadata_with(|metadata| {
             metadata.project_start_date = date;
@@ -268,73 +892,493 @@ impl Schedule {
     }
 
     pub fn tasks(&self) -> Result<Vec<Task>, PolarsError> {
-        let df = self.dataframe();
-        let mut tasks = Vec::with_capacity(df.height());
-        for idx in 0..df.height() {
-            tasks.push(Task::from_dataframe_row(df, idx)?);
+        let mut tasks = Vec::with_capacity(self.df.height());
+        for task in self.iter_tasks() {
+            tasks.push(task?);
         }
         Ok(tasks)
     }
 
-    pub fn find_task(&self, task_id: i32) -> Result<Option<Task>, PolarsError> {
-        if self.df.height() == 0 {
-            return Ok(None);
+    /// Tasks in rows `offset..offset + limit`, slicing the DataFrame before
+    /// materializing. `limit: None` returns every row from `offset` on.
+    pub fn tasks_page(
+        &self,
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Result<Vec<Task>, PolarsError> {
+        let height = self.df.height();
+        let offset = offset.min(height);
+        let length = limit.unwrap_or(height).min(height - offset);
+        let page = self.df.slice(offset as i64, length);
+        (0..page.height())
+            .map(|idx| Task::from_dataframe_row(&page, idx))
+            .collect()
+    }
+
+    /// Lazily yield one task per row, in DataFrame order, without collecting.
+    pub fn iter_tasks(&self) -> impl Iterator<Item = Result<Task, PolarsError>> + '_ {
+        let df = self.dataframe();
+        (0..df.height()).map(move |idx| Task::from_dataframe_row(df, idx))
+    }
+
+    /// Tasks scheduled to be in progress on `date`: `early_start <= date <=
+    /// early_finish`, in schedule order. Tasks without early dates are left
+    /// out.
+    pub fn tasks_active_on(&self, date: NaiveDate) -> Result<Vec<Task>, PolarsError> {
+        self.tasks_active_between(date, |task| (task.early_start, task.early_finish))
+    }
+
+    /// Like [`Schedule::tasks_active_on`], but recorded actual dates take
+    /// precedence over the early dates. A started task without an actual
+    /// finish runs until its early finish.
+    pub fn tasks_active_on_with_actuals(&self, date: NaiveDate) -> Result<Vec<Task>, PolarsError> {
+        self.tasks_active_between(date, |task| {
+            (
+                task.actual_start.or(task.early_start),
+                task.actual_finish.or(task.early_finish),
+            )
+        })
+    }
+
+    fn tasks_active_between(
+        &self,
+        date: NaiveDate,
+        span: impl Fn(&Task) -> (Option<NaiveDate>, Option<NaiveDate>),
+    ) -> Result<Vec<Task>, PolarsError> {
+        let mut active = Vec::new();
+        for task in self.iter_tasks() {
+            let task = task?;
+            if let (Some(start), Some(finish)) = span(&task)
+                && start <= date
+                && date <= finish
+            {
+                active.push(task);
+            }
         }
-        let ids = self.df.column("id")?.i32()?;
-        for (idx, id_opt) in ids.into_iter().enumerate() {
-            if id_opt == Some(task_id) {
-                let task = Task::from_dataframe_row(self.dataframe(), idx)?;
-                return Ok(Some(task));
+        Ok(active)
+    }
+
+    /// Every task in `order`, ties broken by id. The DataFrame keeps its
+    /// insertion order. The topological order fails if dependencies form a
+    /// cycle.
+    pub fn sorted_tasks(&self, order: SortOrder) -> Result<Vec<Task>, PolarsError> {
+        let mut tasks = self.tasks()?;
+        match order {
+            SortOrder::EarlyStart => {
+                tasks.sort_by_key(|task| (task.early_start.is_none(), task.early_start, task.id))
+            }
+            SortOrder::Wbs => tasks.sort_by(|a, b| match (&a.wbs_code, &b.wbs_code) {
+                (Some(a_code), Some(b_code)) => {
+                    Self::compare_wbs(a_code, b_code).then(a.id.cmp(&b.id))
+                }
+                (a_code, b_code) => b_code
+                    .is_some()
+                    .cmp(&a_code.is_some())
+                    .then(a.id.cmp(&b.id)),
+            }),
+            SortOrder::Id => tasks.sort_by_key(|task| task.id),
+            SortOrder::TopologicalDependency => {
+                let position: HashMap<i32, usize> = self
+                    .dependency_tree()?
+                    .execution_order()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(position, id)| (id, position))
+                    .collect();
+                tasks.sort_by_key(|task| {
+                    (
+                        position.get(&task.id).copied().unwrap_or(usize::MAX),
+                        task.id,
+                    )
+                });
             }
         }
-        Ok(None)
+        Ok(tasks)
     }
 
-    pub fn delete_task(&mut self, task_id: i32) -> Result<bool, PolarsError> {
-        if self.df.height() == 0 {
-            return Ok(false);
+    fn compare_wbs(a: &str, b: &str) -> std::cmp::Ordering {
+        let mut a_parts = a.split('.');
+        let mut b_parts = b.split('.');
+        loop {
+            match (a_parts.next(), b_parts.next()) {
+                (Some(a_part), Some(b_part)) => {
+                    let ordering = match (a_part.parse::<u64>(), b_part.parse::<u64>()) {
+                        (Ok(a_num), Ok(b_num)) => a_num.cmp(&b_num),
+                        _ => a_part.cmp(b_part),
+                    };
+                    if ordering.is_ne() {
+                        return ordering;
+                    }
+                }
+                (a_part, b_part) => return a_part.is_some().cmp(&b_part.is_some()),
+            }
         }
-        let snapshot = self.df.clone();
-        let mut tasks: Vec<Task> = Vec::with_capacity(snapshot.height());
-        let mut found = false;
-        for idx in 0..snapshot.height() {
-            let mut task = Task::from_dataframe_row(&snapshot, idx)?;
-            if task.id == task_id {
-                found = true;
-                continue;
+    }
+
+    pub fn find_task(&self, task_id: i32) -> Result<Option<Task>, PolarsError> {
+        self.row_of(task_id)
+            .map(|row| Task::from_dataframe_row(self.dataframe(), row))
+            .transpose()
+    }
+
+    /// Physically reorder the task rows to follow `order`, which must list
+    /// every task id exactly once. Only row order changes, so exports,
+    /// [`Schedule::tasks`] and `show` follow it; dates and links are untouched.
+    pub fn reorder_rows(&mut self, order: &[i32]) -> Result<(), PolarsError> {
+        self.edit(|schedule| {
+            let mut seen = HashSet::with_capacity(order.len());
+            let mut positions: Vec<IdxSize> = Vec::with_capacity(order.len());
+            for &task_id in order {
+                let row = schedule
+                    .row_of(task_id)
+                    .ok_or_else(|| TaskNotFound::task(task_id))?;
+                if !seen.insert(task_id) {
+                    return Err(PolarsError::ComputeError(
+                        format!("task {task_id} listed more than once").into(),
+                    ));
+                }
+                positions.push(row as IdxSize);
+            }
+            if positions.len() != schedule.df.height() {
+                let mut missing: Vec<i32> = schedule
+                    .row_index
+                    .keys()
+                    .filter(|id| !seen.contains(id))
+                    .copied()
+                    .collect();
+                missing.sort_unstable();
+                return Err(PolarsError::ComputeError(
+                    format!("order is missing tasks {missing:?}").into(),
+                ));
+            }
+            let positions = IdxCa::from_vec(PlSmallStr::from_static("row"), positions);
+            let reordered = schedule.df.take(&positions)?;
+            schedule.replace_dataframe(reordered)
+        })
+    }
+
+    pub fn delete_task(&mut self, task_id: i32) -> Result<bool, PolarsError> {
+        self.edit(|schedule| Ok(schedule.delete_tasks(&[task_id])? > 0))
+    }
+
+    /// Remove every task in `ids` and strip references to them from the
+    /// remaining tasks, recomputing the schedule once at the end. Returns the
+    /// number of tasks actually removed; unknown ids are ignored.
+    pub fn delete_tasks(&mut self, ids: &[i32]) -> Result<usize, PolarsError> {
+        self.edit(|schedule| {
+            if schedule.df.height() == 0 || ids.is_empty() {
+                return Ok(0);
+            }
+            let doomed: HashSet<i32> = ids.iter().copied().collect();
+            let mut tasks: Vec<Task> = Vec::with_capacity(schedule.df.height());
+            let mut removed = 0usize;
+            for task in schedule.iter_tasks() {
+                let mut task = task?;
+                if doomed.contains(&task.id) {
+                    removed += 1;
+                    continue;
+                }
+                let links = task.predecessors.len();
+                task.predecessors.retain(|pred| !doomed.contains(pred));
+                task.successors.retain(|succ| !doomed.contains(succ));
+                if task.predecessors.len() != links {
+                    task.revision += 1;
+                }
+                tasks.push(task);
+            }
+            if removed == 0 {
+                return Ok(0);
+            }
+
+            schedule.rebuild_from_tasks(&tasks)?; // Recompute schedule after structural change
+            Ok(removed)
+        })
+    }
+
+    /// Renumber task `old` to `new`, rewriting every predecessor, successor
+    /// and parent reference to it, then refresh. Fails if `old` does not
+    /// exist or `new` is already taken.
+    pub fn rename_task_id(&mut self, old: i32, new: i32) -> Result<(), PolarsError> {
+        self.edit(|schedule| {
+            if schedule.row_of(old).is_none() {
+                return Err(TaskNotFound::task(old).into());
+            }
+            if old == new {
+                return Ok(());
+            }
+            if schedule.row_of(new).is_some() {
+                return Err(PolarsError::ComputeError(
+                    format!("task {new} already exists").into(),
+                ));
+            }
+
+            let rename = |id: &mut i32| {
+                if *id == old {
+                    *id = new;
+                }
+            };
+            schedule.rewrite_tasks(|task| {
+                rename(&mut task.id);
+                task.predecessors.iter_mut().for_each(rename);
+                task.successors.iter_mut().for_each(rename);
+                if let Some(parent_id) = task.parent_id.as_mut() {
+                    rename(parent_id);
+                }
+                Ok(())
+            })
+        })
+    }
+
+    /// Renumber tasks to 1..=n in ascending order of their current ids and
+    /// rewrite every reference, then refresh. Returns the old-to-new id map.
+    /// References to ids that no task has are dropped, since they could
+    /// otherwise collide with a new id.
+    pub fn compact_ids(&mut self) -> Result<HashMap<i32, i32>, PolarsError> {
+        self.edit(|schedule| {
+            let mut ids: Vec<i32> = schedule.row_index.keys().copied().collect();
+            ids.sort_unstable();
+            let mapping: HashMap<i32, i32> = ids.into_iter().zip(1..).collect();
+            if mapping.iter().all(|(old, new)| old == new) {
+                return Ok(mapping);
+            }
+
+            schedule.rewrite_tasks(|task| {
+                task.id = mapping[&task.id];
+                let remap = |ids: &mut Vec<i32>| {
+                    *ids = ids
+                        .iter()
+                        .filter_map(|id| mapping.get(id).copied())
+                        .collect();
+                };
+                remap(&mut task.predecessors);
+                remap(&mut task.successors);
+                task.parent_id = task.parent_id.and_then(|id| mapping.get(&id).copied());
+                Ok(())
+            })?;
+            Ok(mapping)
+        })
+    }
+
+    /// Move the project to start on `new_start` and refresh. The end date
+    /// stays put, so this fails, leaving the schedule unchanged, when the
+    /// work no longer fits before it.
+    pub fn shift_project(&mut self, new_start: NaiveDate) -> Result<RefreshSummary, PolarsError> {
+        let mut shifted = self.fork();
+        let summary = shifted.edit(|shifted| {
+            shifted
+                .set_project_start_date(new_start)
+                .map_err(|err| PolarsError::ComputeError(err.to_string().into()))?;
+            shifted.refresh()
+        })?;
+        *self = shifted;
+        Ok(summary)
+    }
+
+    /// Move every baseline and actual date by `days` working days (back
+    /// when negative) on the schedule's calendar, then refresh.
+    pub fn shift_all_working_days(&mut self, days: i64) -> Result<(), PolarsError> {
+        self.edit(|schedule| {
+            if days == 0 {
+                return Ok(());
+            }
+            let calendar = schedule.calendar.clone();
+            let shift = |date: &mut Option<NaiveDate>| -> Result<(), PolarsError> {
+                if let Some(current) = *date {
+                    *date = Some(if days > 0 {
+                        calendar.try_find_next_available(current, days)?
+                    } else {
+                        calendar.try_find_prev_available(current, -days)?
+                    });
+                }
+                Ok(())
+            };
+            schedule.rewrite_tasks(|task| {
+                shift(&mut task.baseline_start)?;
+                shift(&mut task.baseline_finish)?;
+                shift(&mut task.actual_start)?;
+                shift(&mut task.actual_finish)
+            })
+        })
+    }
+
+    /// Rebuild the task table from every task after `edit`, keeping row
+    /// order, then refresh.
+    fn rewrite_tasks(
+        &mut self,
+        mut edit: impl FnMut(&mut Task) -> Result<(), PolarsError>,
+    ) -> Result<(), PolarsError> {
+        let mut tasks: Vec<Task> = Vec::with_capacity(self.df.height());
+        for task in self.iter_tasks() {
+            let original = task?;
+            let mut task = original.clone();
+            edit(&mut task)?;
+            if task != original {
+                task.revision += 1;
             }
-            task.predecessors.retain(|&pred| pred != task_id);
-            task.successors.retain(|&succ| succ != task_id);
             tasks.push(task);
         }
-        if !found {
-            return Ok(false);
-        }
+        self.rebuild_from_tasks(&tasks)
+    }
 
-        self.df = DataFrame::empty_with_schema(&Self::default_schema());
-        for task in tasks {
-            self.upsert_task_record(task)?;
-        }
-        self.refresh()?; // Recompute schedule after structural change
-        Ok(true)
+    /// Replace every row with `tasks` in one columnar build, then refresh
+    /// once. The collection is validated first, so a failure leaves the
+    /// schedule untouched.
+    fn rebuild_from_tasks(&mut self, tasks: &[Task]) -> Result<(), PolarsError> {
+        task_validation::validate_task_collection(tasks).map_err(Self::validation_error)?;
+        let df = if tasks.is_empty() {
+            DataFrame::empty_with_schema(&Self::default_schema())
+        } else {
+            Task::tasks_to_dataframe(tasks)?
+        };
+        self.replace_dataframe(df)?;
+        self.refresh()?;
+        Ok(())
+    }
+
+    /// Overwrite `wbs_code` with dotted codes ("1", "1.1", "1.2", "2")
+    /// derived from `parent_id`. Siblings are numbered in task order. Tasks
+    /// without a parent, or whose parent does not exist, are top-level.
+    /// Fails without changing anything if `parent_id` links form a cycle.
+    pub fn generate_wbs_codes(&mut self) -> Result<(), PolarsError> {
+        self.edit(|schedule| {
+            let ids = schedule.df.column("id")?.i32()?;
+            let parents = schedule.df.column("parent_id")?.i32()?;
+
+            let mut children: HashMap<Option<i32>, Vec<usize>> = HashMap::new();
+            for idx in 0..schedule.df.height() {
+                let parent = parents
+                    .get(idx)
+                    .filter(|parent| schedule.row_index.contains_key(parent));
+                children.entry(parent).or_default().push(idx);
+            }
+
+            let mut codes: Vec<Option<String>> = vec![None; schedule.df.height()];
+            let mut stack: Vec<(usize, String)> = Vec::new();
+            let push_children =
+                |stack: &mut Vec<(usize, String)>, parent: Option<i32>, prefix: &str| {
+                    if let Some(rows) = children.get(&parent) {
+                        // Reversed so the first sibling is popped first.
+                        for (position, &row) in rows.iter().enumerate().rev() {
+                            stack.push((row, format!("{prefix}{}", position + 1)));
+                        }
+                    }
+                };
+            push_children(&mut stack, None, "");
+            while let Some((row, code)) = stack.pop() {
+                push_children(&mut stack, ids.get(row), &format!("{code}."));
+                codes[row] = Some(code);
+            }
+
+            // Rows never reached from a top-level task hang off a parent cycle.
+            if let Some(row) = codes.iter().position(Option::is_none) {
+                let task_id = ids.get(row).unwrap_or_default();
+                return Err(PolarsError::ComputeError(
+                    format!("parent_id cycle detected at task {task_id}").into(),
+                ));
+            }
+            let series = Series::new(PlSmallStr::from_static("wbs_code"), codes);
+            schedule.touch();
+            schedule.df.replace("wbs_code", series)?;
+            Ok(())
+        })
+    }
+
+    /// When enabled, `refresh()` overwrites `percent_complete` on
+    /// pre-defined-rationale tasks with [`Task::computed_percent_complete`].
+    pub fn set_derive_rationale_percent(&mut self, enabled: bool) {
+        self.touch();
+        self.derive_rationale_percent = enabled;
+    }
+
+    pub fn derive_rationale_percent(&self) -> bool {
+        self.derive_rationale_percent
+    }
+
+    /// When enabled, `refresh()` fills `schedule_variance_days` for tasks
+    /// that have a baseline but no actuals by comparing the baseline with the
+    /// current early dates ([`VarianceBasis::Forecast`]).
+    pub fn set_forecast_variance(&mut self, enabled: bool) {
+        self.touch();
+        self.forecast_variance = enabled;
+    }
+
+    pub fn forecast_variance(&self) -> bool {
+        self.forecast_variance
+    }
+
+    /// When enabled, level-of-effort and hammock tasks count towards the
+    /// project finish and the horizon check; by default only
+    /// [`TaskKind::Work`] and [`TaskKind::Buffer`] tasks do.
+    pub fn set_finish_includes_support_tasks(&mut self, enabled: bool) {
+        self.touch();
+        self.finish_includes_support_tasks = enabled;
+    }
+
+    pub fn finish_includes_support_tasks(&self) -> bool {
+        self.finish_includes_support_tasks
+    }
+
+    /// When enabled, the forward pass starts every task that has an actual
+    /// start on that date, and finishes it on its actual finish if it has
+    /// one, instead of deriving the dates from predecessors and the
+    /// calendar. Successors are scheduled from these dates.
+    pub fn set_pin_actual_dates(&mut self, enabled: bool) {
+        self.touch();
+        self.pin_actual_dates = enabled;
+    }
+
+    pub fn pin_actual_dates(&self) -> bool {
+        self.pin_actual_dates
+    }
+
+    pub fn set_scheduling_mode(&mut self, mode: SchedulingMode) {
+        self.touch();
+        self.scheduling_mode = mode;
+    }
+
+    pub fn scheduling_mode(&self) -> SchedulingMode {
+        self.scheduling_mode
+    }
+
+    /// Date remaining work is projected from in [`Schedule::forecast_finish`].
+    /// `None` means the local date at the time of the call. The forward pass
+    /// also counts the remaining work of tasks in progress from this date,
+    /// but only when it is set.
+    pub fn set_status_date(&mut self, date: Option<NaiveDate>) {
+        self.touch();
+        self.status_date = date;
+    }
+
+    pub fn status_date(&self) -> Option<NaiveDate> {
+        self.status_date
+    }
+
+    /// The forward-pass settings refresh uses, for
+    /// [`crate::calculations::critical_path_with_options`].
+    pub fn critical_path_options(&self) -> CriticalPathOptions {
+        CriticalPathOptions::default()
+            .with_data_date(self.status_date)
+            .with_pinned_actuals(self.pin_actual_dates)
     }
 
     pub fn set_calendar_from_config(
         &mut self,
         config: &WorkCalendarConfig,
     ) -> Result<(), PolarsError> {
-        let calendar = WorkCalendar::from_config(config);
-        self.set_calendar(calendar)
+        self.edit(|schedule| {
+            let calendar = WorkCalendar::try_from_config(config)?;
+            schedule.set_calendar(calendar)
+        })
     }
 
-    fn calendar_for_metadata(metadata: &ScheduleMetadata) -> WorkCalendar {
+    pub(crate) fn calendar_for_metadata(metadata: &ScheduleMetadata) -> WorkCalendar {
         let start_year = metadata.project_start_date.year();
         let end_year = metadata.project_end_date.year();
         WorkCalendar::with_year_range(start_year, end_year)
     }
 
     fn default_schema() -> Schema {
-        let schema = Schema::from_iter(vec![
+        Schema::from_iter(vec![
             Field::new("id".into(), DataType::Int32),
             Field::new("name".into(), DataType::String),
             Field::new("duration_days".into(), DataType::Int64),
@@ -368,8 +1412,71 @@ impl Schedule {
                 DataType::List(Box::new(DataType::String)),
             ),
             Field::new("resource_allocations".into(), DataType::String),
-        ]);
-        schema
+            Field::new("deadline".into(), DataType::Date),
+            Field::new("forced_early_start".into(), DataType::Date),
+            Field::new("priority".into(), DataType::Int32),
+            Field::new("task_kind".into(), DataType::String),
+            Field::new("custom_fields".into(), DataType::String),
+            Field::new("progress_log".into(), DataType::String),
+            Field::new("revision".into(), DataType::UInt64),
+        ])
+    }
+
+    /// Row position of `task_id`, or `None` if the task does not exist.
+    fn row_of(&self, task_id: i32) -> Option<usize> {
+        self.row_index.get(&task_id).copied()
+    }
+
+    /// Swap in a new task table and rebuild the row index from its ids.
+    fn replace_dataframe(&mut self, df: DataFrame) -> Result<(), PolarsError> {
+        self.touch();
+        let row_index = df
+            .column("id")?
+            .i32()?
+            .into_iter()
+            .enumerate()
+            .filter_map(|(row, id)| id.map(|id| (id, row)))
+            .collect();
+        self.df = df;
+        self.row_index = row_index;
+        Ok(())
+    }
+
+    fn append_row(&mut self, task_id: i32, row: &DataFrame) -> Result<(), PolarsError> {
+        self.touch();
+        let position = self.df.height();
+        self.df = self.df.vstack(row)?;
+        self.row_index.insert(task_id, position);
+        Ok(())
+    }
+
+    /// Overwrite one cell of `column_name` with the single value in `value`.
+    ///
+    /// The column is re-formed from zero-copy slices around the task's row
+    /// plus the new value, so an edit costs O(1) in the number of rows rather
+    /// than rebuilding the column. Each edit adds chunks; the column is
+    /// rechunked once it holds more than [`MAX_COLUMN_CHUNKS`]. Unknown task
+    /// ids are ignored, as before.
+    fn set_cell(
+        &mut self,
+        column_name: &str,
+        task_id: i32,
+        value: Series,
+    ) -> Result<(), PolarsError> {
+        let Some(row) = self.row_of(task_id) else {
+            return Ok(());
+        };
+        self.touch();
+        let column = self.df.column(column_name)?.as_materialized_series();
+        let value = value.cast(column.dtype())?;
+        let mut updated = column.slice(0, row);
+        updated.append(&value)?;
+        updated.append(&column.slice(row as i64 + 1, column.len() - row - 1))?;
+        if updated.n_chunks() > MAX_COLUMN_CHUNKS {
+            updated = updated.rechunk();
+        }
+        self.df.replace(column_name, updated)?;
+        Ok(())
     }
 
     fn update_string_column(
@@ -378,27 +1485,7 @@ impl Schedule {
         task_id: i32,
         new_value: &str,
     ) -> Result<(), PolarsError> {
-        let id_col = self.df.column("id")?;
-        let target_col = self.df.column(column_name)?;
-
-        // Create new series with conditional values
-        let new_series = target_col
-            .str()?
-            .into_iter()
-            .zip(id_col.i32()?.into_iter())
-            .map(|(val, id)| {
-                if id == Some(task_id) {
-                    Some(new_value)
-                } else {
-                    val
-                }
-            })
-            .collect::<StringChunked>()
-            .into_series()
-            .with_name(column_name.into());
-
-        self.df.replace(column_name, new_series)?;
-        Ok(())
+        self.set_cell(column_name, task_id, Series::new("".into(), [new_value]))
     }
 
     fn update_i32_column(
@@ -407,27 +1494,7 @@ impl Schedule {
         task_id: i32,
         new_value: i32,
     ) -> Result<(), PolarsError> {
-        let id_col = self.df.column("id")?;
-        let target_col = self.df.column(column_name)?;
-
-        // Create new series with conditional values
-        let new_series = target_col
-            .i32()?
-            .into_iter()
-            .zip(id_col.i32()?.into_iter())
-            .map(|(val, id)| {
-                if id == Some(task_id) {
-                    Some(new_value)
-                } else {
-                    val
-                }
-            })
-            .collect::<Int32Chunked>()
-            .into_series()
-            .with_name(column_name.into());
-
-        self.df.replace(column_name, new_series)?;
-        Ok(())
+        self.set_cell(column_name, task_id, Series::new("".into(), [new_value]))
     }
 
     fn update_i64_column(
@@ -436,26 +1503,16 @@ impl Schedule {
         task_id: i32,
         new_value: i64,
     ) -> Result<(), PolarsError> {
-        let id_col = self.df.column("id")?;
-        let target_col = self.df.column(column_name)?;
-
-        let new_series = target_col
-            .i64()?
-            .into_iter()
-            .zip(id_col.i32()?.into_iter())
-            .map(|(val, id)| {
-                if id == Some(task_id) {
-                    Some(new_value)
-                } else {
-                    val
-                }
-            })
-            .collect::<Int64Chunked>()
-            .into_series()
-            .with_name(column_name.into());
+        self.set_cell(column_name, task_id, Series::new("".into(), [new_value]))
+    }
 
-        self.df.replace(column_name, new_series)?;
-        Ok(())
+    fn update_u64_column(
+        &mut self,
+        column_name: &str,
+        task_id: i32,
+        new_value: u64,
+    ) -> Result<(), PolarsError> {
+        self.set_cell(column_name, task_id, Series::new("".into(), [new_value]))
     }
 
     fn update_list_i32_column(
@@ -464,27 +1521,8 @@ impl Schedule {
         task_id: i32,
         new_values: Vec<i32>,
     ) -> Result<(), PolarsError> {
-        let id_col = self.df.column("id")?;
-        let target_col = self.df.column(column_name)?;
-
-        let new_series = target_col
-            .list()?
-            .into_iter()
-            .zip(id_col.i32()?.into_iter())
-            .map(|(val, id)| {
-                if id == Some(task_id) {
-                    // Replace with new list value
-                    Some(Series::new(PlSmallStr::from_static(""), new_values.clone()))
-                } else {
-                    val
-                }
-            })
-            .collect::<ListChunked>()
-            .into_series()
-            .with_name(column_name.into());
-
-        self.df.replace(column_name, new_series)?;
-        Ok(())
+        let list = Series::new(PlSmallStr::from_static(""), new_values);
+        self.set_cell(column_name, task_id, Series::new("".into(), [list]))
     }
 
     fn update_list_str_column(
@@ -493,27 +1531,8 @@ impl Schedule {
         task_id: i32,
         new_values: Vec<String>,
     ) -> Result<(), PolarsError> {
-        let id_col = self.df.column("id")?;
-        let target_col = self.df.column(column_name)?;
-
-        let replacement = Series::new(PlSmallStr::from_static(""), new_values);
-        let new_series = target_col
-            .list()?
-            .into_iter()
-            .zip(id_col.i32()?.into_iter())
-            .map(|(val, id)| {
-                if id == Some(task_id) {
-                    Some(replacement.clone())
-                } else {
-                    val
-                }
-            })
-            .collect::<ListChunked>()
-            .into_series()
-            .with_name(column_name.into());
-
-        self.df.replace(column_name, new_series)?;
-        Ok(())
+        let list = Series::new(PlSmallStr::from_static(""), new_values);
+        self.set_cell(column_name, task_id, Series::new("".into(), [list]))
     }
 
     fn update_float_column(
@@ -522,27 +1541,7 @@ impl Schedule {
         task_id: i32,
         new_value: f64,
     ) -> Result<(), PolarsError> {
-        let id_col = self.df.column("id")?;
-        let target_col = self.df.column(column_name)?;
-
-        // Create new series with conditional values
-        let new_series = target_col
-            .f64()?
-            .into_iter()
-            .zip(id_col.i32()?.into_iter())
-            .map(|(val, id)| {
-                if id == Some(task_id) {
-                    Some(new_value)
-                } else {
-                    val
-                }
-            })
-            .collect::<Float64Chunked>()
-            .into_series()
-            .with_name(column_name.into());
-
-        self.df.replace(column_name, new_series)?;
-        Ok(())
+        self.set_cell(column_name, task_id, Series::new("".into(), [new_value]))
     }
 
     fn update_bool_column(
@@ -551,27 +1550,7 @@ impl Schedule {
         task_id: i32,
         new_value: bool,
     ) -> Result<(), PolarsError> {
-        let id_col = self.df.column("id")?;
-        let target_col = self.df.column(column_name)?;
-
-        // Create new series with conditional values
-        let new_series = target_col
-            .bool()?
-            .into_iter()
-            .zip(id_col.i32()?.into_iter())
-            .map(|(val, id)| {
-                if id == Some(task_id) {
-                    Some(new_value)
-                } else {
-                    val
-                }
-            })
-            .collect::<BooleanChunked>()
-            .into_series()
-            .with_name(column_name.into());
-
-        self.df.replace(column_name, new_series)?;
-        Ok(())
+        self.set_cell(column_name, task_id, Series::new("".into(), [new_value]))
     }
 
     fn update_date_column(
@@ -580,18 +1559,8 @@ impl Schedule {
         task_id: i32,
         new_date: NaiveDate,
     ) -> Result<(), PolarsError> {
-        self.df = self
-            .df
-            .clone()
-            .lazy()
-            .with_column(
-                when(col("id").eq(lit(task_id)))
-                    .then(lit(new_date).cast(DataType::Date))
-                    .otherwise(col(column_name).cast(DataType::Date))
-                    .alias(column_name),
-            )
-            .collect()?;
-        Ok(())
+        let days = Series::new("".into(), [Self::date_to_i32(new_date)]);
+        self.set_cell(column_name, task_id, days)
     }
 
     fn update_duration_column(
@@ -601,8 +1570,9 @@ impl Schedule {
     ) -> Result<(), PolarsError> {
         self.update_i64_column("duration_days", task_id, new_duration_days)?;
         // Duration changes ripple through schedule calculations; recompute dates.
-        self.forward_pass()?;
-        self.backward_pass()?;
+        let dag = ScheduleDag::build(&self.df)?;
+        self.forward_pass_with_dag(&dag)?;
+        self.backward_pass_with_dag(&dag)?;
         Ok(())
     }
 
@@ -621,7 +1591,11 @@ impl Schedule {
         chunk.get(idx).map(Self::i32_to_date)
     }
 
-    fn working_days_diff(cal: &WorkCalendar, baseline: NaiveDate, actual: NaiveDate) -> i64 {
+    pub(crate) fn working_days_diff(
+        cal: &WorkCalendar,
+        baseline: NaiveDate,
+        actual: NaiveDate,
+    ) -> i64 {
         if baseline == actual {
             0
         } else if actual > baseline {
@@ -632,13 +1606,21 @@ impl Schedule {
         }
     }
 
-    fn latest_early_finish(&self) -> Result<Option<NaiveDate>, PolarsError> {
+    pub(crate) fn latest_early_finish(&self) -> Result<Option<NaiveDate>, PolarsError> {
         if self.df.height() == 0 {
             return Ok(None);
         }
         let early_finish = self.df.column("early_finish")?.date()?;
+        let task_kind = self.df.column("task_kind")?.str()?;
         let mut latest: Option<NaiveDate> = None;
         for idx in 0..early_finish.len() {
+            let is_support = task_kind
+                .get(idx)
+                .and_then(TaskKind::from_str)
+                .is_some_and(|kind| kind.is_support());
+            if is_support && !self.finish_includes_support_tasks {
+                continue;
+            }
             if let Some(days) = early_finish.get(idx) {
                 let candidate = Self::i32_to_date(days);
                 latest = Some(match latest {
@@ -651,34 +1633,102 @@ impl Schedule {
     }
 
     fn set_schedule_variance(&mut self) -> Result<(), PolarsError> {
+        let (values, _) = self.schedule_variance()?;
+        let series = Series::new(PlSmallStr::from_static("schedule_variance_days"), values);
+        self.df.replace("schedule_variance_days", series)?;
+        Ok(())
+    }
+
+    /// Variance per row and the basis it was measured against, keyed by task
+    /// id, derived from the baseline, actual and early date columns.
+    fn schedule_variance(&self) -> Result<VarianceColumn, PolarsError> {
         let height = self.df.height();
+        let ids = self.df.column("id")?.i32()?;
         let baseline_finish = self.df.column("baseline_finish")?.date()?;
         let actual_finish = self.df.column("actual_finish")?.date()?;
         let baseline_start = self.df.column("baseline_start")?.date()?;
         let actual_start = self.df.column("actual_start")?.date()?;
+        let early_finish = self.df.column("early_finish")?.date()?;
+        let early_start = self.df.column("early_start")?.date()?;
+        let percent = self.df.column("percent_complete")?.f64()?;
+
+        // Finish dates take precedence over start dates for either basis.
+        let compare = |baseline: &DateChunked, current: &DateChunked, idx: usize| match (
+            Self::date_from_chunk(baseline, idx),
+            Self::date_from_chunk(current, idx),
+        ) {
+            (Some(b), Some(c)) => Some(Self::working_days_diff(&self.calendar, b, c)),
+            _ => None,
+        };
 
         let mut values: Vec<Option<i64>> = Vec::with_capacity(height);
+        let mut bases = BTreeMap::new();
         for idx in 0..height {
-            let variance = match (
-                Self::date_from_chunk(&baseline_finish, idx),
-                Self::date_from_chunk(&actual_finish, idx),
-            ) {
-                (Some(bf), Some(af)) => Some(Self::working_days_diff(&self.calendar, bf, af)),
-                _ => match (
-                    Self::date_from_chunk(&baseline_start, idx),
-                    Self::date_from_chunk(&actual_start, idx),
-                ) {
-                    (Some(bs), Some(as_)) => Some(Self::working_days_diff(&self.calendar, bs, as_)),
-                    _ => None,
-                },
+            let in_progress = actual_start.get(idx).is_some()
+                && actual_finish.get(idx).is_none()
+                && percent.get(idx).is_some_and(|pct| pct > 0.0 && pct < 1.0);
+            let live = in_progress
+                .then(|| compare(baseline_finish, early_finish, idx))
+                .flatten();
+            let actual = compare(baseline_finish, actual_finish, idx)
+                .or_else(|| compare(baseline_start, actual_start, idx));
+            let has_actuals = Self::date_from_chunk(actual_finish, idx).is_some()
+                || Self::date_from_chunk(actual_start, idx).is_some();
+            let (variance, basis) = match (live, actual) {
+                (Some(days), _) => (Some(days), Some(VarianceBasis::InProgress)),
+                (None, Some(days)) => (Some(days), Some(VarianceBasis::Actual)),
+                (None, None) if self.forecast_variance && !has_actuals => {
+                    let forecast = compare(baseline_finish, early_finish, idx)
+                        .or_else(|| compare(baseline_start, early_start, idx));
+                    (forecast, forecast.map(|_| VarianceBasis::Forecast))
+                }
+                (None, None) => (None, None),
             };
+            if let (Some(id), Some(basis)) = (ids.get(idx), basis) {
+                bases.insert(id, basis);
+            }
             values.push(variance);
         }
-        let series = Series::new(PlSmallStr::from_static("schedule_variance_days"), values);
-        self.df.replace("schedule_variance_days", series)?;
+        Ok((values, bases))
+    }
+
+    fn set_rationale_percent_complete(&mut self) -> Result<(), PolarsError> {
+        let mut values: Vec<Option<f64>> = Vec::with_capacity(self.df.height());
+        for task in self.iter_tasks() {
+            let task = task?;
+            values.push(match task.progress_measurement {
+                ProgressMeasurement::PreDefinedRationale => task.computed_percent_complete(),
+                _ => task.percent_complete,
+            });
+        }
+        let series = Series::new(PlSmallStr::from_static("percent_complete"), values);
+        self.df.replace("percent_complete", series)?;
         Ok(())
     }
 
+    /// Tasks with both actual dates are complete, so their `percent_complete`
+    /// becomes 1.0, which every progress measurement allows. Started tasks
+    /// keep theirs, clamped to 0..=1.
+    fn set_actuals_percent_complete(&mut self) -> Result<(), PolarsError> {
+        let actual_start = self.df.column("actual_start")?.date()?;
+        let actual_finish = self.df.column("actual_finish")?.date()?;
+        let percent = self.df.column("percent_complete")?.f64()?;
+        let values: Vec<Option<f64>> = (0..self.df.height())
+            .map(
+                |idx| match (actual_start.get(idx), actual_finish.get(idx)) {
+                    (Some(_), Some(_)) => Some(1.0),
+                    (Some(_), None) => percent.get(idx).map(|pct| pct.clamp(0.0, 1.0)),
+                    _ => percent.get(idx),
+                },
+            )
+            .collect();
+        let series = Series::new(PlSmallStr::from_static("percent_complete"), values);
+        self.df.replace("percent_complete", series)?;
+        Ok(())
+    }
+
+    /// Rebuilds every row's successors from the predecessor lists, clearing
+    /// any stored value that no longer matches.
     fn set_successors_column(&mut self) -> Result<(), PolarsError> {
         let id_col = self.df.column("id")?.i32()?;
         let predecessors = self.df.column("predecessors")?.list()?;
@@ -690,12 +1740,12 @@ impl Schedule {
         }
 
         for (idx, maybe_id) in ids.iter().enumerate() {
-            if let Some(task_id) = maybe_id {
-                if let Some(series) = predecessors.get_as_series(idx) {
-                    let pred_col = series.i32()?;
-                    for pred in pred_col.into_iter().flatten() {
-                        successors_map.entry(pred).or_default().push(*task_id);
-                    }
+            if let Some(task_id) = maybe_id
+                && let Some(series) = predecessors.get_as_series(idx)
+            {
+                let pred_col = series.i32()?;
+                for pred in pred_col.into_iter().flatten() {
+                    successors_map.entry(pred).or_default().push(*task_id);
                 }
             }
         }
@@ -715,38 +1765,143 @@ impl Schedule {
             })
             .collect();
 
-        let list_chunked: ListChunked = successor_rows.into_iter().collect();
-        self.df.replace("successors", list_chunked.into_series())?;
+        let list_chunked: ListChunked = successor_rows.into_iter().collect();
+        self.df.replace("successors", list_chunked.into_series())?;
+        Ok(())
+    }
+
+    fn validate_project_horizon(&self) -> Result<(), PolarsError> {
+        if self.metadata.project_start_date > self.metadata.project_end_date {
+            return Err(PolarsError::ComputeError(
+                "project_end_date must be on or after project_start_date".into(),
+            ));
+        }
+
+        if let Some(latest_finish) = self.latest_early_finish()?
+            && latest_finish > self.metadata.project_end_date
+        {
+            return Err(PolarsError::ComputeError(
+                format!(
+                    "project_end_date {} precedes schedule finish {}",
+                    self.metadata.project_end_date, latest_finish
+                )
+                .into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check, without recomputing, that the schedule as it stands would
+    /// refresh: the horizon is ordered and covers the stored early dates,
+    /// and predecessor links form no cycle.
+    pub fn validate_computable(&self) -> Result<(), PolarsError> {
+        self.validate_project_horizon()?;
+        let dag = ScheduleDag::build(&self.df)?;
+        if petgraph::algo::is_cyclic_directed(&dag.graph) {
+            return Err(PolarsError::ComputeError(
+                "Cycle detected in schedule DAG".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn forward_pass(&mut self) -> Result<(), PolarsError> {
+        self.edit(|schedule| {
+            if schedule.df.height() == 0 {
+                return Ok(());
+            }
+            let dag = ScheduleDag::build(&schedule.df)?;
+            schedule.forward_pass_with_dag(&dag)
+        })
+    }
+
+    /// Run the forward pass. A default calendar whose holidays stop short of
+    /// the computed dates gains the missing years' holidays and the pass is
+    /// repeated, since the new holidays can move dates again.
+    fn forward_pass_with_dag(&mut self, dag: &ScheduleDag) -> Result<(), PolarsError> {
+        self.touch();
+        self.compute_early_dates(dag)?;
+        while !self.calendar_is_custom && self.extend_default_calendar()? {
+            self.compute_early_dates(dag)?;
+        }
         Ok(())
     }
 
-    fn validate_project_horizon(&self) -> Result<(), PolarsError> {
-        if self.metadata.project_start_date > self.metadata.project_end_date {
-            return Err(PolarsError::ComputeError(
-                "project_end_date must be on or after project_start_date".into(),
-            ));
+    /// Add US holidays for years the early dates reach but the default
+    /// calendar lacks. Returns whether anything was added.
+    fn extend_default_calendar(&mut self) -> Result<bool, PolarsError> {
+        let (Some((first, last)), Some((start, finish))) = (
+            self.calendar.holiday_years(),
+            self.date_span(&["early_start", "early_finish"])?,
+        ) else {
+            return Ok(false);
+        };
+        if start.year() >= first && finish.year() <= last {
+            return Ok(false);
+        }
+        if start.year() < first {
+            self.calendar.add_us_holidays_range(start.year(), first - 1);
         }
+        if finish.year() > last {
+            self.calendar.add_us_holidays_range(last + 1, finish.year());
+        }
+        Ok(true)
+    }
 
-        if let Some(latest_finish) = self.latest_early_finish()? {
-            if latest_finish > self.metadata.project_end_date {
-                return Err(PolarsError::ComputeError(
-                    format!(
-                        "project_end_date {} precedes schedule finish {}",
-                        self.metadata.project_end_date, latest_finish
-                    )
-                    .into(),
-                ));
+    /// Earliest and latest non-null date across `columns`.
+    fn date_span(&self, columns: &[&str]) -> Result<Option<(NaiveDate, NaiveDate)>, PolarsError> {
+        let mut span: Option<(i32, i32)> = None;
+        for name in columns {
+            let dates = self.df.column(name)?.date()?;
+            if let (Some(min), Some(max)) = (dates.min(), dates.max()) {
+                span = Some(match span {
+                    Some((lo, hi)) => (lo.min(min), hi.max(max)),
+                    None => (min, max),
+                });
             }
         }
-        Ok(())
+        Ok(span.map(|(lo, hi)| (Self::i32_to_date(lo), Self::i32_to_date(hi))))
     }
 
-    pub fn forward_pass(&mut self) -> Result<(), PolarsError> {
+    /// Working days between the project start and end dates, inclusive.
+    pub fn project_working_days(&self) -> i64 {
+        self.calendar.count_available_days(
+            self.metadata.project_start_date,
+            self.metadata.project_end_date,
+        )
+    }
+
+    /// Report computed dates that fall outside the years the calendar has
+    /// holidays for. Calendars without any holidays are never reported.
+    pub fn calendar_coverage_gap(&self) -> Result<Option<CalendarCoverageGap>, PolarsError> {
+        let Some((first, last)) = self.calendar.holiday_years() else {
+            return Ok(None);
+        };
+        let Some((start, finish)) =
+            self.date_span(&["early_start", "early_finish", "late_start", "late_finish"])?
+        else {
+            return Ok(None);
+        };
+        if start.year() >= first && finish.year() <= last {
+            return Ok(None);
+        }
+        Ok(Some(CalendarCoverageGap {
+            first_holiday_year: first,
+            last_holiday_year: last,
+            schedule_start: start,
+            schedule_finish: finish,
+        }))
+    }
+
+    fn compute_early_dates(&mut self, dag: &ScheduleDag) -> Result<(), PolarsError> {
+        self.forward_pass_fallback_count = 0;
         if self.df.height() == 0 {
             return Ok(());
         }
-        let engine = CalcForwardPass::new(&self.df, &self.calendar);
-        let results = engine.execute(self.metadata.project_start_date)?;
+        let engine = CalcForwardPass::new(&self.df, &self.calendar)
+            .with_data_date(self.status_date)
+            .with_pinned_actuals(self.pin_actual_dates);
+        let results = engine.execute_with_dag(dag, self.metadata.project_start_date)?;
 
         // Persist results into early_start / early_finish
         let id_ca = self.df.column("id")?.i32()?;
@@ -761,6 +1916,7 @@ impl Schedule {
                     finish_vals[idx] = Some(Self::date_to_i32(*ef));
                 } else {
                     // Fallback compute for tasks not covered by the engine (e.g., join points)
+                    self.forward_pass_fallback_count += 1;
                     let preds_lc = self.df.column("predecessors")?.list()?;
                     let duration = self
                         .df
@@ -782,9 +1938,11 @@ impl Schedule {
                             .filter_map(|p| results.get(p).map(|(_, ef)| *ef))
                             .max()
                             .unwrap_or(project_start);
-                        self.calendar.next_available(max_pred_finish)
+                        self.calendar.try_next_available(max_pred_finish)?
                     };
-                    let early_finish = self.calendar.find_next_available(early_start, duration);
+                    let early_finish = self
+                        .calendar
+                        .try_find_next_available(early_start, duration)?;
                     start_vals[idx] = Some(Self::date_to_i32(early_start));
                     finish_vals[idx] = Some(Self::date_to_i32(early_finish));
                 }
@@ -802,39 +1960,76 @@ impl Schedule {
     }
 
     pub fn backward_pass(&mut self) -> Result<(), PolarsError> {
+        self.edit(|schedule| {
+            if schedule.df.height() == 0 {
+                return Ok(());
+            }
+            let dag = ScheduleDag::build(&schedule.df)?;
+            schedule.backward_pass_with_dag(&dag)
+        })
+    }
+
+    /// A task with duration and no links in a network of several tasks.
+    /// Zero-duration bookend milestones are never disconnected.
+    fn is_disconnected(dag: &ScheduleDag, task_id: i32) -> bool {
+        let Some(&node) = dag.id_to_index.get(&task_id) else {
+            return false;
+        };
+        dag.graph.node_count() > 1
+            && dag.durations.get(&task_id).is_some_and(|days| *days > 0)
+            && dag.graph.neighbors_undirected(node).next().is_none()
+    }
+
+    fn backward_pass_with_dag(&mut self, dag: &ScheduleDag) -> Result<(), PolarsError> {
+        self.touch();
+        self.unscheduled_tasks.clear();
         if self.df.height() == 0 {
             return Ok(());
         }
         // Compute late dates using petgraph engine
         let engine = CalcBackwardPass::new(&self.df, &self.calendar);
-        let results = engine.execute(self.metadata.project_end_date)?;
+        let results = engine.execute_with_dag(dag, self.metadata.project_end_date)?;
 
         // Persist late_start / late_finish
         let id_ca = self.df.column("id")?.i32()?;
+        let kinds = self.df.column("task_kind")?.str()?;
         let height = self.df.height();
         let mut ls_vals: Vec<Option<i32>> = vec![None; height];
         let mut lf_vals: Vec<Option<i32>> = vec![None; height];
         for (idx, id_opt) in id_ca.into_iter().enumerate() {
-            if let Some(task_id) = id_opt {
-                if let Some((ls, lf)) = results.get(&task_id) {
+            let Some(task_id) = id_opt else {
+                continue;
+            };
+            match results.get(&task_id) {
+                Some((ls, lf)) => {
                     ls_vals[idx] = Some(Self::date_to_i32(*ls));
                     lf_vals[idx] = Some(Self::date_to_i32(*lf));
+                    let is_support = kinds
+                        .get(idx)
+                        .and_then(TaskKind::from_str)
+                        .is_some_and(|kind| kind.is_support());
+                    if !is_support && Self::is_disconnected(dag, task_id) {
+                        self.unscheduled_tasks.push(task_id);
+                    }
                 }
+                None => self.unscheduled_tasks.push(task_id),
             }
         }
-        // Fallback fill: if any late values remain None, use early counterparts to avoid nulls
+        self.unscheduled_tasks.sort_unstable();
+        // Fallback fill: late values the engine did not produce take their
+        // early counterparts to avoid nulls; `unscheduled_tasks` records them.
         let es_dates = self.df.column("early_start")?.date()?;
         let ef_dates = self.df.column("early_finish")?.date()?;
         for i in 0..height {
-            if ls_vals[i].is_none() {
-                if let Some(es_i) = es_dates.get(i) {
-                    ls_vals[i] = Some(es_i);
-                }
+            if ls_vals[i].is_none()
+                && let Some(es_i) = es_dates.get(i)
+            {
+                ls_vals[i] = Some(es_i);
             }
-            if lf_vals[i].is_none() {
-                if let Some(ef_i) = ef_dates.get(i) {
-                    lf_vals[i] = Some(ef_i);
-                }
+            if lf_vals[i].is_none()
+                && let Some(ef_i) = ef_dates.get(i)
+            {
+                lf_vals[i] = Some(ef_i);
             }
         }
 
@@ -849,10 +2044,10 @@ impl Schedule {
         let es_col = self.df.column("early_start")?.date()?;
         let mut es_map: HashMap<i32, i32> = HashMap::new();
         for (i, id_opt) in self.df.column("id")?.i32()?.into_iter().enumerate() {
-            if let Some(id) = id_opt {
-                if let Some(es_days) = es_col.get(i) {
-                    es_map.insert(id, es_days);
-                }
+            if let Some(id) = id_opt
+                && let Some(es_days) = es_col.get(i)
+            {
+                es_map.insert(id, es_days);
             }
         }
         let id_ca2 = self.df.column("id")?.i32()?;
@@ -865,7 +2060,9 @@ impl Schedule {
                 let ls_days = ls_col.get(i).unwrap_or(0) as i64;
                 let tf = ls_days - es_days;
                 tf_vals.push(tf);
-                crit_vals.push(tf == 0);
+                // Negative float means the schedule cannot meet a constraint;
+                // those tasks are critical too.
+                crit_vals.push(tf <= 0);
             } else {
                 tf_vals.push(0);
                 crit_vals.push(false);
@@ -879,54 +2076,156 @@ impl Schedule {
         Ok(())
     }
 
-    pub fn refresh(&mut self) -> Result<RefreshSummary, PolarsError> {
-        if self.metadata.project_start_date > self.metadata.project_end_date {
-            return Err(PolarsError::ComputeError(
-                "project_end_date must be on or after project_start_date".into(),
-            ));
+    /// Move tasks with positive float and no actual start to their late
+    /// dates, for [`SchedulingMode::Alap`].
+    fn apply_late_dates_to_floating_tasks(&mut self) -> Result<(), PolarsError> {
+        let total_float = self.df.column("total_float")?.i64()?;
+        let actual_start = self.df.column("actual_start")?.date()?;
+        let early_start = self.df.column("early_start")?.date()?;
+        let early_finish = self.df.column("early_finish")?.date()?;
+        let late_start = self.df.column("late_start")?.date()?;
+        let late_finish = self.df.column("late_finish")?.date()?;
+
+        let mut start_vals: Vec<Option<i32>> = Vec::with_capacity(self.df.height());
+        let mut finish_vals: Vec<Option<i32>> = Vec::with_capacity(self.df.height());
+        for idx in 0..self.df.height() {
+            let floats = total_float.get(idx).is_some_and(|tf| tf > 0);
+            match (late_start.get(idx), late_finish.get(idx)) {
+                (Some(ls), Some(lf)) if floats && actual_start.get(idx).is_none() => {
+                    start_vals.push(Some(ls));
+                    finish_vals.push(Some(lf));
+                }
+                _ => {
+                    start_vals.push(early_start.get(idx));
+                    finish_vals.push(early_finish.get(idx));
+                }
+            }
         }
 
-        self.forward_pass()?;
-        self.validate_project_horizon()?;
-        self.backward_pass()?;
-        self.set_schedule_variance()?;
-        self.set_successors_column()?;
+        let start_series = Series::new(PlSmallStr::from_static("early_start"), start_vals)
+            .cast(&DataType::Date)?;
+        let finish_series = Series::new(PlSmallStr::from_static("early_finish"), finish_vals)
+            .cast(&DataType::Date)?;
+        self.df.replace("early_start", start_series)?;
+        self.df.replace("early_finish", finish_series)?;
+        Ok(())
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            name = "refresh",
+            skip_all,
+            fields(tasks = self.df.height())
+        )
+    )]
+    pub fn refresh(&mut self) -> Result<RefreshSummary, PolarsError> {
+        self.edit(|schedule| {
+            if schedule.metadata.project_start_date > schedule.metadata.project_end_date {
+                return Err(PolarsError::ComputeError(
+                    "project_end_date must be on or after project_start_date".into(),
+                ));
+            }
+
+            schedule.touch();
+            // Percent complete feeds the remaining-duration forecast, so derive
+            // it before the forward pass rather than one refresh late.
+            if schedule.derive_rationale_percent {
+                schedule.set_rationale_percent_complete()?;
+            }
+            schedule.set_actuals_percent_complete()?;
+            let task_count = schedule.df.height();
+            // Both passes walk the same dependency graph; build it once per refresh.
+            let dag = ScheduleDag::build(&schedule.df)?;
+            traced_step!(
+                "forward_pass",
+                task_count,
+                schedule.forward_pass_with_dag(&dag)
+            )?;
+            schedule.validate_project_horizon()?;
+            traced_step!(
+                "backward_pass",
+                task_count,
+                schedule.backward_pass_with_dag(&dag)
+            )?;
+            if schedule.scheduling_mode == SchedulingMode::Alap {
+                schedule.apply_late_dates_to_floating_tasks()?;
+            }
+            traced_step!(
+                "set_schedule_variance",
+                task_count,
+                schedule.set_schedule_variance()
+            )?;
+            traced_step!(
+                "set_successors_column",
+                task_count,
+                schedule.set_successors_column()
+            )?;
 
+            schedule.current_summary()
+        })
+    }
+
+    /// The summary [`Schedule::refresh`] would return, built from the columns
+    /// as they stand without re-running the passes. Figures reflect the last
+    /// refresh plus any edits made since; the variance basis is re-derived
+    /// from the baseline, actual and early dates.
+    pub fn current_summary(&self) -> Result<RefreshSummary, PolarsError> {
+        let (_, variance_basis) = self.schedule_variance()?;
         let task_count = self.df.height();
         let id_ca = self.df.column("id")?.i32()?;
-        let tf_ca = self.df.column("total_float")?.i64()?;
         let variance_ca = self.df.column("schedule_variance_days")?.i64()?;
         let critical_ca = self.df.column("is_critical")?.bool()?;
-        let early_start_ca = self.df.column("early_start")?.date()?;
+        let tf_ca = self.df.column("total_float")?.i64()?;
+        let early_finish_ca = self.df.column("early_finish")?.date()?;
+        let deadline_ca = self.df.column("deadline")?.date()?;
 
         let mut critical_count = 0usize;
+        let mut negative_float = Vec::new();
         let mut positive_variance_count = 0usize;
         let mut negative_variance_count = 0usize;
         let mut on_track_variance_count = 0usize;
-        let mut critical_path: Vec<(NaiveDate, i32)> = Vec::new();
+        let mut missed_deadlines = Vec::new();
 
         for idx in 0..task_count {
             if let Some(true) = critical_ca.get(idx) {
                 critical_count += 1;
             }
+            if let (Some(task_id), Some(total_float)) = (id_ca.get(idx), tf_ca.get(idx))
+                && total_float < 0
+            {
+                negative_float.push(NegativeFloat {
+                    task_id,
+                    total_float,
+                });
+            }
             match variance_ca.get(idx) {
                 Some(v) if v > 0 => positive_variance_count += 1,
                 Some(v) if v < 0 => negative_variance_count += 1,
                 Some(_) => on_track_variance_count += 1,
                 None => {}
             }
-            if let (Some(id), Some(tf)) = (id_ca.get(idx), tf_ca.get(idx)) {
-                if tf == 0 {
-                    let start = Self::date_from_chunk(&early_start_ca, idx)
-                        .unwrap_or(self.metadata.project_start_date);
-                    critical_path.push((start, id));
-                }
+            if let (Some(task_id), Some(deadline), Some(early_finish)) = (
+                id_ca.get(idx),
+                Self::date_from_chunk(deadline_ca, idx),
+                Self::date_from_chunk(early_finish_ca, idx),
+            ) && early_finish > deadline
+            {
+                missed_deadlines.push(MissedDeadline {
+                    task_id,
+                    deadline,
+                    early_finish,
+                    days_late: Self::working_days_diff(&self.calendar, deadline, early_finish),
+                });
             }
         }
+        missed_deadlines.sort_by_key(|missed| missed.task_id);
+        negative_float.sort_by_key(|entry| (entry.total_float, entry.task_id));
+        let negative_float_count = negative_float.len();
+        negative_float.truncate(NEGATIVE_FLOAT_REPORT_LIMIT);
 
-        critical_path.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
-        let critical_path_ids = critical_path.into_iter().map(|(_, id)| id).collect();
-
+        let critical_path_ids = self.critical_path()?;
         let latest_finish = self.latest_early_finish()?;
 
         Ok(RefreshSummary {
@@ -937,9 +2236,43 @@ impl Schedule {
             positive_variance_count,
             negative_variance_count,
             on_track_variance_count,
+            missed_deadlines,
+            variance_basis,
+            negative_float_count,
+            worst_negative_float: negative_float,
+            project_working_days: self.project_working_days(),
+            calendar_coverage_gap: self.calendar_coverage_gap()?,
+            project_percent_complete: self.project_percent_complete()?,
+            forecast_finish: self.forecast_finish()?,
+            forward_pass_fallback_count: self.forward_pass_fallback_count,
+            unscheduled_tasks: self.unscheduled_tasks.clone(),
+            open_ends: self.dangling_tasks()?.len(),
         })
     }
 
+    /// Tasks with zero or negative float in chain order: by early start, then
+    /// higher priority first for same-day tasks, then by id. Reflects the last
+    /// refresh.
+    pub fn critical_path(&self) -> Result<Vec<i32>, PolarsError> {
+        let id_ca = self.df.column("id")?.i32()?;
+        let tf_ca = self.df.column("total_float")?.i64()?;
+        let early_start_ca = self.df.column("early_start")?.date()?;
+        let priority_ca = self.df.column("priority")?.i32()?;
+
+        let mut critical_path: Vec<(NaiveDate, i32, i32)> = Vec::new();
+        for idx in 0..self.df.height() {
+            if let (Some(id), Some(tf)) = (id_ca.get(idx), tf_ca.get(idx))
+                && tf <= 0
+            {
+                let start = Self::date_from_chunk(early_start_ca, idx)
+                    .unwrap_or(self.metadata.project_start_date);
+                let priority = priority_ca.get(idx).unwrap_or(Task::DEFAULT_PRIORITY);
+                critical_path.push((start, priority, id));
+            }
+        }
+        Ok(crate::calculations::order_critical_tasks(critical_path))
+    }
+
     fn validation_error(err: TaskValidationError) -> PolarsError {
         PolarsError::ComputeError(err.to_string().into())
     }
@@ -951,51 +2284,50 @@ impl Schedule {
         duration_days: i64,
         predecessors: Option<Vec<i32>>,
     ) -> Result<(), PolarsError> {
-        if duration_days < 0 {
-            return Err(PolarsError::ComputeError(
-                format!("task {} has negative duration {}", id, duration_days).into(),
-            ));
-        }
-        let id_exists = if self.df.height() == 0 {
-            false
-        } else {
-            self.df
-                .column("id")?
-                .i32()?
-                .into_iter()
-                .any(|v| v == Some(id))
-        };
+        self.edit(|schedule| {
+            if duration_days < 0 {
+                return Err(PolarsError::ComputeError(
+                    format!("task {} has negative duration {}", id, duration_days).into(),
+                ));
+            }
+            let id_exists = schedule.row_of(id).is_some();
 
-        if id_exists {
-            self.update_string_column("name", id, name)?;
-            if let Some(preds) = predecessors {
-                self.update_list_i32_column("predecessors", id, preds)?;
+            if id_exists {
+                schedule.update_string_column("name", id, name)?;
+                if let Some(preds) = predecessors {
+                    schedule.update_list_i32_column("predecessors", id, preds)?;
+                }
+                schedule.update_duration_column(id, duration_days)?;
+                return Ok(());
             }
-            self.update_duration_column(id, duration_days)?;
-            return Ok(());
-        }
 
-        let mut task = Task::new(id, name, duration_days);
-        if let Some(preds) = predecessors {
-            task.predecessors = preds;
-        }
-        task_validation::validate_task(&task).map_err(Self::validation_error)?;
-        let new_row = task.to_dataframe_row()?;
-        self.df = self.df.vstack(&new_row)?;
-        Ok(())
+            let mut task = Task::new(id, name, duration_days);
+            if let Some(preds) = predecessors {
+                task.predecessors = preds;
+            }
+            task_validation::validate_task(&task).map_err(Self::validation_error)?;
+            schedule.append_row(task.id, &task.to_dataframe_row()?)?;
+            Ok(())
+        })
     }
 
+    /// Apply a rationale template to a task and return the task as stored.
     pub fn apply_rationale_template(
         &mut self,
         task_id: i32,
         template: ProgressRationaleTemplate,
-    ) -> Result<(), PolarsError> {
-        let mut task = self
-            .find_task(task_id)?
-            .ok_or_else(|| PolarsError::ComputeError(format!("task {task_id} not found").into()))?;
-        task.apply_rationale_template(template)
-            .map_err(Self::validation_error)?;
-        self.upsert_task_record(task)
+    ) -> Result<Task, PolarsError> {
+        self.edit(|schedule| {
+            let mut task = schedule
+                .find_task(task_id)?
+                .ok_or_else(|| TaskNotFound::task(task_id))?;
+            task.apply_rationale_template(template)
+                .map_err(Self::validation_error)?;
+            schedule.upsert_task_record(task)?;
+            schedule.find_task(task_id)?.ok_or_else(|| {
+                PolarsError::ComputeError(format!("task {task_id} not found after update").into())
+            })
+        })
     }
 
     pub fn update_task_duration(
@@ -1003,120 +2335,304 @@ impl Schedule {
         task_id: i32,
         new_duration_days: i64,
     ) -> Result<(), PolarsError> {
-        self.update_duration_column(task_id, new_duration_days)
+        self.edit(|schedule| schedule.update_duration_column(task_id, new_duration_days))
     }
 
+    /// Insert `task`, or overwrite the stored task with the same id. Updates
+    /// bump the stored revision; inserts keep the revision the task carries.
     pub fn upsert_task_record(&mut self, task: Task) -> Result<(), PolarsError> {
-        task_validation::validate_task(&task).map_err(Self::validation_error)?;
-        let id_exists = if self.df.height() == 0 {
-            false
-        } else {
-            self.df
-                .column("id")?
-                .i32()?
-                .into_iter()
-                .any(|v| v == Some(task.id))
-        };
+        self.edit(|schedule| {
+            task_validation::validate_task(&task).map_err(Self::validation_error)?;
+            let current_revision = schedule.task_revision(task.id)?;
+
+            if let Some(revision) = current_revision {
+                schedule.update_u64_column("revision", task.id, revision + 1)?;
+                schedule.update_string_column("name", task.id, &task.name)?;
+                schedule.update_list_i32_column(
+                    "predecessors",
+                    task.id,
+                    task.predecessors.clone(),
+                )?;
+                schedule.update_duration_column(task.id, task.duration_days)?;
 
-        if id_exists {
-            self.update_string_column("name", task.id, &task.name)?;
-            self.update_list_i32_column("predecessors", task.id, task.predecessors.clone())?;
-            self.update_duration_column(task.id, task.duration_days)?;
+                if let Some(date) = task.early_start {
+                    schedule.update_date_column("early_start", task.id, date)?;
+                }
 
-            if let Some(date) = task.early_start {
-                self.update_date_column("early_start", task.id, date)?;
-            }
+                if let Some(date) = task.early_finish {
+                    schedule.update_date_column("early_finish", task.id, date)?;
+                }
 
-            if let Some(date) = task.early_finish {
-                self.update_date_column("early_finish", task.id, date)?;
-            }
+                if let Some(date) = task.late_start {
+                    schedule.update_date_column("late_start", task.id, date)?;
+                }
 
-            if let Some(date) = task.late_start {
-                self.update_date_column("late_start", task.id, date)?;
-            }
+                if let Some(date) = task.late_finish {
+                    schedule.update_date_column("late_finish", task.id, date)?;
+                }
 
-            if let Some(date) = task.late_finish {
-                self.update_date_column("late_finish", task.id, date)?;
-            }
+                if let Some(date) = task.baseline_start {
+                    schedule.update_date_column("baseline_start", task.id, date)?;
+                }
 
-            if let Some(date) = task.baseline_start {
-                self.update_date_column("baseline_start", task.id, date)?;
-            }
+                if let Some(date) = task.baseline_finish {
+                    schedule.update_date_column("baseline_finish", task.id, date)?;
+                }
 
-            if let Some(date) = task.baseline_finish {
-                self.update_date_column("baseline_finish", task.id, date)?;
-            }
+                if let Some(date) = task.actual_start {
+                    schedule.update_date_column("actual_start", task.id, date)?;
+                }
 
-            if let Some(date) = task.actual_start {
-                self.update_date_column("actual_start", task.id, date)?;
-            }
+                if let Some(date) = task.actual_finish {
+                    schedule.update_date_column("actual_finish", task.id, date)?;
+                }
 
-            if let Some(date) = task.actual_finish {
-                self.update_date_column("actual_finish", task.id, date)?;
-            }
+                if let Some(date) = task.deadline {
+                    schedule.update_date_column("deadline", task.id, date)?;
+                }
 
-            if let Some(percent) = task.percent_complete {
-                self.update_float_column("percent_complete", task.id, percent)?;
-            }
+                if let Some(date) = task.forced_early_start {
+                    schedule.update_date_column("forced_early_start", task.id, date)?;
+                }
 
-            if let Some(variance) = task.schedule_variance_days {
-                self.update_i64_column("schedule_variance_days", task.id, variance)?;
-            }
+                if let Some(priority) = task.priority {
+                    schedule.update_i32_column("priority", task.id, priority)?;
+                }
 
-            if let Some(total_float) = task.total_float {
-                self.update_i64_column("total_float", task.id, total_float)?;
-            }
+                schedule.update_string_column("task_kind", task.id, task.task_kind.as_str())?;
 
-            if let Some(is_critical) = task.is_critical {
-                self.update_bool_column("is_critical", task.id, is_critical)?;
-            }
+                if let Some(percent) = task.percent_complete {
+                    schedule.update_float_column("percent_complete", task.id, percent)?;
+                }
 
-            if !task.successors.is_empty() {
-                self.update_list_i32_column("successors", task.id, task.successors.clone())?;
-            }
+                if let Some(variance) = task.schedule_variance_days {
+                    schedule.update_i64_column("schedule_variance_days", task.id, variance)?;
+                }
 
-            if let Some(parent) = task.parent_id {
-                self.update_i32_column("parent_id", task.id, parent)?;
-            }
+                if let Some(total_float) = task.total_float {
+                    schedule.update_i64_column("total_float", task.id, total_float)?;
+                }
 
-            if let Some(ref wbs) = task.wbs_code {
-                self.update_string_column("wbs_code", task.id, wbs)?;
-            }
+                if let Some(is_critical) = task.is_critical {
+                    schedule.update_bool_column("is_critical", task.id, is_critical)?;
+                }
 
-            if let Some(ref notes) = task.task_notes {
-                self.update_string_column("task_notes", task.id, notes)?;
-            }
+                if let Some(parent) = task.parent_id {
+                    schedule.update_i32_column("parent_id", task.id, parent)?;
+                }
+
+                if let Some(ref wbs) = task.wbs_code {
+                    schedule.update_string_column("wbs_code", task.id, wbs)?;
+                }
+
+                if let Some(ref notes) = task.task_notes {
+                    schedule.update_string_column("task_notes", task.id, notes)?;
+                }
+
+                if !task.task_attachments.is_empty() {
+                    schedule.update_list_str_column(
+                        "task_attachments",
+                        task.id,
+                        task.task_attachments.clone(),
+                    )?;
+                }
 
-            if !task.task_attachments.is_empty() {
-                self.update_list_str_column(
-                    "task_attachments",
+                schedule.update_string_column(
+                    "progress_measurement",
                     task.id,
-                    task.task_attachments.clone(),
+                    task.progress_measurement.as_str(),
                 )?;
-            }
 
-            self.update_string_column(
-                "progress_measurement",
-                task.id,
-                task.progress_measurement.as_str(),
-            )?;
+                let rationale_json = serde_json::to_string(&task.pre_defined_rationale)
+                    .map_err(|err| PolarsError::ComputeError(err.to_string().into()))?;
+                schedule.update_string_column(
+                    "pre_defined_rationale",
+                    task.id,
+                    rationale_json.as_str(),
+                )?;
 
-            let rationale_json = serde_json::to_string(&task.pre_defined_rationale)
-                .map_err(|err| PolarsError::ComputeError(err.to_string().into()))?;
-            self.update_string_column("pre_defined_rationale", task.id, rationale_json.as_str())?;
+                let allocations_json = serde_json::to_string(&task.resource_allocations)
+                    .map_err(|err| PolarsError::ComputeError(err.to_string().into()))?;
+                schedule.update_string_column(
+                    "resource_allocations",
+                    task.id,
+                    allocations_json.as_str(),
+                )?;
 
-            let allocations_json = serde_json::to_string(&task.resource_allocations)
-                .map_err(|err| PolarsError::ComputeError(err.to_string().into()))?;
-            self.update_string_column("resource_allocations", task.id, allocations_json.as_str())?;
+                let custom_fields_json = serde_json::to_string(&task.custom_fields)
+                    .map_err(|err| PolarsError::ComputeError(err.to_string().into()))?;
+                schedule.update_string_column(
+                    "custom_fields",
+                    task.id,
+                    custom_fields_json.as_str(),
+                )?;
 
-            return Ok(());
-        }
+                // Like the other list fields, an empty log leaves the history as is.
+                if !task.progress_log.is_empty() {
+                    let progress_log_json = serde_json::to_string(&task.progress_log)
+                        .map_err(|err| PolarsError::ComputeError(err.to_string().into()))?;
+                    schedule.update_string_column(
+                        "progress_log",
+                        task.id,
+                        progress_log_json.as_str(),
+                    )?;
+                }
+
+                return Ok(());
+            }
 
-        let new_row = task.to_dataframe_row()?;
-        self.df = self.df.vstack(&new_row)?;
+            schedule.append_row(task.id, &task.to_dataframe_row()?)?;
+            Ok(())
+        })
+    }
+
+    /// Overwrite every column of the stored task with `task`. Unlike
+    /// [`Schedule::upsert_task_record`], `None` fields and empty lists are
+    /// written as given rather than keeping the stored value. Bumps the
+    /// stored revision; fails if the task does not exist.
+    pub fn replace_task_record(&mut self, mut task: Task) -> Result<(), PolarsError> {
+        task_validation::validate_task(&task).map_err(Self::validation_error)?;
+        let (Some(row), Some(revision)) = (self.row_of(task.id), self.task_revision(task.id)?)
+        else {
+            return Err(TaskNotFound::task(task.id).into());
+        };
+        task.revision = revision + 1;
+        let height = self.df.height();
+        let mut df = self.df.slice(0, row);
+        df.vstack_mut(&task.to_dataframe_row()?)?;
+        df.vstack_mut(&self.df.slice(row as i64 + 1, height - row - 1))?;
+        self.touch();
+        self.df = df;
         Ok(())
     }
 
+    /// Add a finish-to-start link from `predecessor_id` to `task_id`. Both tasks
+    /// must exist and the link must not close a cycle; an existing link is
+    /// left as is. Call [`Schedule::refresh`] afterwards to recompute dates.
+    pub fn add_predecessor(
+        &mut self,
+        task_id: i32,
+        predecessor_id: i32,
+    ) -> Result<(), PolarsError> {
+        self.edit(|schedule| {
+            let task = schedule
+                .find_task(task_id)?
+                .ok_or_else(|| TaskNotFound::task(task_id))?;
+            if schedule.find_task(predecessor_id)?.is_none() {
+                return Err(TaskNotFound::predecessor(predecessor_id).into());
+            }
+            if schedule.would_create_cycle(task_id, predecessor_id)? {
+                return Err(PolarsError::ComputeError(
+                    format!(
+                        "linking {predecessor_id} -> {task_id} would create a dependency cycle"
+                    )
+                    .into(),
+                ));
+            }
+            if task.predecessors.contains(&predecessor_id) {
+                return Ok(());
+            }
+            let mut predecessors = task.predecessors;
+            predecessors.push(predecessor_id);
+            schedule.update_list_i32_column("predecessors", task_id, predecessors)?;
+            schedule.bump_task_revision(task_id)
+        })
+    }
+
+    /// Remove the link from `predecessor_id` to `task_id`. Returns whether a
+    /// link was removed.
+    pub fn remove_predecessor(
+        &mut self,
+        task_id: i32,
+        predecessor_id: i32,
+    ) -> Result<bool, PolarsError> {
+        self.edit(|schedule| {
+            let task = schedule
+                .find_task(task_id)?
+                .ok_or_else(|| TaskNotFound::task(task_id))?;
+            if !task.predecessors.contains(&predecessor_id) {
+                return Ok(false);
+            }
+            let predecessors = task
+                .predecessors
+                .into_iter()
+                .filter(|pred| *pred != predecessor_id)
+                .collect();
+            schedule.update_list_i32_column("predecessors", task_id, predecessors)?;
+            schedule.bump_task_revision(task_id)?;
+            Ok(true)
+        })
+    }
+
+    /// Count an edit made outside [`Schedule::upsert_task_record`] against
+    /// the task's revision, so a write based on the old revision is refused.
+    fn bump_task_revision(&mut self, task_id: i32) -> Result<(), PolarsError> {
+        let revision = self.task_revision(task_id)?.unwrap_or(0);
+        self.update_u64_column("revision", task_id, revision + 1)
+    }
+
+    /// Current revision of a stored task, or `None` if it does not exist.
+    pub fn task_revision(&self, task_id: i32) -> Result<Option<u64>, PolarsError> {
+        let Some(row) = self.row_of(task_id) else {
+            return Ok(None);
+        };
+        Ok(Some(
+            self.df.column("revision")?.u64()?.get(row).unwrap_or(0),
+        ))
+    }
+
+    /// Set one custom field on a task, replacing any existing value for `key`.
+    pub fn set_custom_field(
+        &mut self,
+        task_id: i32,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Result<(), PolarsError> {
+        self.edit(|schedule| {
+            let mut task = schedule
+                .find_task(task_id)?
+                .ok_or_else(|| TaskNotFound::task(task_id))?;
+            task.custom_fields.insert(key.into(), value.into());
+            task_validation::validate_task(&task).map_err(Self::validation_error)?;
+            let custom_fields_json = serde_json::to_string(&task.custom_fields)
+                .map_err(|err| PolarsError::ComputeError(err.to_string().into()))?;
+            schedule.update_string_column("custom_fields", task_id, custom_fields_json.as_str())?;
+            schedule.bump_task_revision(task_id)
+        })
+    }
+
+    /// Append a progress measurement for `task_id` taken on `date`. The log
+    /// is kept in date order; a second entry for the same date is placed after
+    /// the first and wins in [`Task::percent_complete_as_of`]. The task's
+    /// current `percent_complete` is not changed.
+    pub fn record_progress(
+        &mut self,
+        task_id: i32,
+        date: NaiveDate,
+        percent_complete: f64,
+    ) -> Result<(), PolarsError> {
+        self.edit(|schedule| {
+            let mut task = schedule
+                .find_task(task_id)?
+                .ok_or_else(|| TaskNotFound::task(task_id))?;
+            let position = task
+                .progress_log
+                .partition_point(|entry| entry.date <= date);
+            task.progress_log.insert(
+                position,
+                ProgressEntry {
+                    date,
+                    percent_complete,
+                },
+            );
+            task_validation::validate_task(&task).map_err(Self::validation_error)?;
+            let progress_log_json = serde_json::to_string(&task.progress_log)
+                .map_err(|err| PolarsError::ComputeError(err.to_string().into()))?;
+            schedule.update_string_column("progress_log", task_id, progress_log_json.as_str())?;
+            schedule.bump_task_revision(task_id)
+        })
+    }
+
     // Public setters for common columns to enable CLI editing
     #[cfg(feature = "cli_api")]
     pub fn set_baseline_start(&mut self, task_id: i32, date: NaiveDate) -> Result<(), PolarsError> {
@@ -1144,12 +2660,14 @@ impl Schedule {
 
     #[cfg(feature = "cli_api")]
     pub fn set_percent_complete(&mut self, task_id: i32, percent: f64) -> Result<(), PolarsError> {
-        let mut task = self.find_task(task_id)?.ok_or_else(|| {
-            PolarsError::ComputeError(format!("task {} not found", task_id).into())
-        })?;
-        task.percent_complete = Some(percent);
-        task_validation::validate_task(&task).map_err(Self::validation_error)?;
-        self.update_float_column("percent_complete", task_id, percent)
+        self.edit(|schedule| {
+            let mut task = schedule
+                .find_task(task_id)?
+                .ok_or_else(|| TaskNotFound::task(task_id))?;
+            task.percent_complete = Some(percent);
+            task_validation::validate_task(&task).map_err(Self::validation_error)?;
+            schedule.update_float_column("percent_complete", task_id, percent)
+        })
     }
 
     #[cfg(feature = "cli_api")]
@@ -1187,25 +2705,49 @@ impl Schedule {
         task_id: i32,
         successors: Vec<i32>,
     ) -> Result<(), PolarsError> {
-        self.update_list_i32_column("successors", task_id, successors)
+        self.edit(|schedule| schedule.update_list_i32_column("successors", task_id, successors))
     }
 
     pub fn set_calendar(&mut self, calendar: WorkCalendar) -> Result<(), PolarsError> {
-        self.calendar = calendar;
-        self.calendar_is_custom = true;
-        if self.df.height() == 0 {
-            return Ok(());
-        }
-        self.refresh().map(|_| ())
+        self.edit(|schedule| {
+            schedule.touch();
+            schedule.calendar = calendar;
+            schedule.calendar_is_custom = true;
+            if schedule.df.height() == 0 {
+                return Ok(());
+            }
+            schedule.refresh().map(|_| ())
+        })
+    }
+
+    /// Swap the calendar like [`Schedule::set_calendar`], but keep tasks
+    /// with recorded actual dates where they are. `set_calendar` recomputes
+    /// every task's dates under the new calendar; this recompute pins actual
+    /// dates as [`Schedule::set_pin_actual_dates`] does, so started and
+    /// finished tasks keep them and only the rest of the network moves. The
+    /// pin applies to this recompute only; the setting is left as it was.
+    pub fn set_calendar_preserving_manual(
+        &mut self,
+        calendar: WorkCalendar,
+    ) -> Result<(), PolarsError> {
+        self.edit(|schedule| {
+            let pinned = std::mem::replace(&mut schedule.pin_actual_dates, true);
+            let result = schedule.set_calendar(calendar);
+            schedule.pin_actual_dates = pinned;
+            result
+        })
     }
 
     pub fn reset_calendar_to_default(&mut self) -> Result<(), PolarsError> {
-        self.calendar = Self::calendar_for_metadata(&self.metadata);
-        self.calendar_is_custom = false;
-        if self.df.height() == 0 {
-            return Ok(());
-        }
-        self.refresh().map(|_| ())
+        self.edit(|schedule| {
+            schedule.touch();
+            schedule.calendar = Self::calendar_for_metadata(&schedule.metadata);
+            schedule.calendar_is_custom = false;
+            if schedule.df.height() == 0 {
+                return Ok(());
+            }
+            schedule.refresh().map(|_| ())
+        })
     }
 }
 
@@ -1240,9 +2782,16 @@ mod tests {
             "task_notes",
             "task_attachments",
             "resource_allocations",
+            "deadline",
+            "forced_early_start",
+            "priority",
+            "task_kind",
+            "custom_fields",
+            "progress_log",
+            "revision",
         ];
         for name in expected {
-            assert!(schema.contains(name.into()), "missing column {name}");
+            assert!(schema.contains(name), "missing column {name}");
         }
     }
 
@@ -1267,4 +2816,31 @@ mod tests {
         assert_eq!(name, "Task A1");
         assert_eq!(dur, 7);
     }
+
+    #[test]
+    fn backward_pass_reports_tasks_left_out_of_the_graph() {
+        let mut s = Schedule::new();
+        s.upsert_task(1, "Design", 2, None).unwrap();
+        s.upsert_task(2, "Build", 3, Some(vec![1])).unwrap();
+        s.upsert_task(3, "Disconnected", 1, None).unwrap();
+        s.forward_pass().unwrap();
+
+        // A graph missing task 3 stands in for an engine that cannot place it.
+        let mask = s.df.column("id").unwrap().i32().unwrap().not_equal(3);
+        let dag = ScheduleDag::build(&s.df.filter(&mask).unwrap()).unwrap();
+        s.backward_pass_with_dag(&dag).unwrap();
+
+        assert_eq!(s.current_summary().unwrap().unscheduled_tasks, vec![3]);
+        let task = s.find_task(3).unwrap().unwrap();
+        assert_eq!(task.late_start, task.early_start);
+        assert_eq!(task.late_finish, task.early_finish);
+        assert!(s.find_task(2).unwrap().unwrap().late_finish.is_some());
+
+        // With the full graph task 3 gets real late dates, but it is still
+        // reported because nothing links it to the network.
+        s.backward_pass().unwrap();
+        assert_eq!(s.current_summary().unwrap().unscheduled_tasks, vec![3]);
+        let task = s.find_task(3).unwrap().unwrap();
+        assert_ne!(task.late_finish, task.early_finish);
+    }
 }
@@ -1,12 +1,15 @@
+use crate::calendar::WorkCalendar;
 use crate::resource::ResourceAllocation;
 use crate::task_validation::{self, TaskValidationError};
 use chrono::{Duration, NaiveDate};
 use polars::prelude::PlSmallStr;
 use polars::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
 use std::str::FromStr;
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub enum ProgressMeasurement {
     #[serde(rename = "0_100")]
     ZeroOneHundred,
@@ -17,17 +20,12 @@ pub enum ProgressMeasurement {
     #[serde(rename = "75_25")]
     SeventyFiveTwentyFive,
     #[serde(rename = "percent_complete")]
+    #[default]
     PercentComplete,
     #[serde(rename = "pre_defined_rationale")]
     PreDefinedRationale,
 }
 
-impl Default for ProgressMeasurement {
-    fn default() -> Self {
-        Self::PercentComplete
-    }
-}
-
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RationaleTemplateEntry {
     pub name: String,
@@ -90,6 +88,12 @@ impl ProgressRationaleTemplate {
         }
     }
 
+    /// Preview the rationale items this template would produce, without
+    /// applying it to a task.
+    pub fn items(&self) -> Vec<RationaleItem> {
+        self.materialize()
+    }
+
     pub fn materialize(&self) -> Vec<RationaleItem> {
         self.entries()
             .into_iter()
@@ -122,6 +126,18 @@ impl FromStr for ProgressRationaleTemplate {
 }
 
 impl ProgressMeasurement {
+    /// Every measurement, in declaration order.
+    pub const ALL: [ProgressMeasurement; 6] = [
+        ProgressMeasurement::ZeroOneHundred,
+        ProgressMeasurement::FiftyFifty,
+        ProgressMeasurement::TwentyFiveSeventyFive,
+        ProgressMeasurement::SeventyFiveTwentyFive,
+        ProgressMeasurement::PercentComplete,
+        ProgressMeasurement::PreDefinedRationale,
+    ];
+
+    /// Wire token for the measurement. The serde renames on the enum use the
+    /// same tokens, so CSV columns and JSON agree.
     pub fn as_str(&self) -> &'static str {
         match self {
             ProgressMeasurement::ZeroOneHundred => "0_100",
@@ -133,17 +149,88 @@ impl ProgressMeasurement {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(value: &str) -> Option<Self> {
-        match value {
-            "0_100" => Some(ProgressMeasurement::ZeroOneHundred),
-            "50_50" => Some(ProgressMeasurement::FiftyFifty),
-            "25_75" => Some(ProgressMeasurement::TwentyFiveSeventyFive),
-            "75_25" => Some(ProgressMeasurement::SeventyFiveTwentyFive),
-            "percent_complete" => Some(ProgressMeasurement::PercentComplete),
-            "pre_defined_rationale" => Some(ProgressMeasurement::PreDefinedRationale),
-            _ => None,
+        Self::ALL
+            .into_iter()
+            .find(|measurement| measurement.as_str() == value)
+    }
+
+    /// The only `percent_complete` values the measurement accepts, or
+    /// `None` when any value from 0 to 1 is allowed.
+    pub fn allowed_percents(&self) -> Option<&'static [f64]> {
+        match self {
+            ProgressMeasurement::ZeroOneHundred => Some(&[0.0, 1.0]),
+            ProgressMeasurement::FiftyFifty => Some(&[0.0, 0.5, 1.0]),
+            ProgressMeasurement::TwentyFiveSeventyFive
+            | ProgressMeasurement::SeventyFiveTwentyFive => Some(&[0.0, 0.25, 0.75, 1.0]),
+            ProgressMeasurement::PercentComplete | ProgressMeasurement::PreDefinedRationale => None,
+        }
+    }
+
+    /// `percent` moved to the nearest value the measurement accepts; a tie
+    /// goes to the lower value. Unchanged for measurements without steps,
+    /// and for non-finite values, which validation still rejects.
+    pub fn snap_percent(&self, percent: f64) -> f64 {
+        let Some(allowed) = self.allowed_percents() else {
+            return percent;
+        };
+        if !percent.is_finite() {
+            return percent;
+        }
+        allowed.iter().copied().fold(allowed[0], |best, candidate| {
+            if (candidate - percent).abs() < (best - percent).abs() {
+                candidate
+            } else {
+                best
+            }
+        })
+    }
+}
+
+/// What a task represents. Level-of-effort and hammock tasks span other
+/// work rather than adding to it, so the project finish ignores them unless
+/// `Schedule::set_finish_includes_support_tasks` says otherwise. Buffers are
+/// contingency inserted by `Schedule::insert_project_buffer` and
+/// `Schedule::insert_feeding_buffers`; they push the finish like work does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskKind {
+    #[default]
+    Work,
+    LevelOfEffort,
+    Hammock,
+    Buffer,
+}
+
+impl TaskKind {
+    /// Every kind, in declaration order.
+    pub const ALL: [TaskKind; 4] = [
+        TaskKind::Work,
+        TaskKind::LevelOfEffort,
+        TaskKind::Hammock,
+        TaskKind::Buffer,
+    ];
+
+    /// Wire token for the kind, matching the serde representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskKind::Work => "work",
+            TaskKind::LevelOfEffort => "level_of_effort",
+            TaskKind::Hammock => "hammock",
+            TaskKind::Buffer => "buffer",
         }
     }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == value)
+    }
+
+    /// Level-of-effort and hammock tasks.
+    pub fn is_support(&self) -> bool {
+        matches!(self, TaskKind::LevelOfEffort | TaskKind::Hammock)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -165,6 +252,13 @@ impl RationaleItem {
     }
 }
 
+/// One point in a task's progress history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProgressEntry {
+    pub date: NaiveDate,
+    pub percent_complete: f64,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Task {
     pub id: i32,
@@ -183,6 +277,10 @@ pub struct Task {
     pub schedule_variance_days: Option<i64>,
     pub total_float: Option<i64>,
     pub is_critical: Option<bool>,
+    /// Derived from the other tasks' `predecessors`; [`Schedule::refresh`]
+    /// regenerates it and discards whatever was stored.
+    ///
+    /// [`Schedule::refresh`]: crate::schedule::Schedule::refresh
     pub successors: Vec<i32>,
     pub parent_id: Option<i32>,
     pub wbs_code: Option<String>,
@@ -192,6 +290,33 @@ pub struct Task {
     pub pre_defined_rationale: Vec<RationaleItem>,
     #[serde(default)]
     pub resource_allocations: Vec<ResourceAllocation>,
+    /// Target finish date. The early dates ignore it, but the backward pass
+    /// caps the late finish at it, so a deadline can lower the late dates
+    /// and total float and make the task critical.
+    #[serde(default)]
+    pub deadline: Option<NaiveDate>,
+    /// Earliest date the forward pass may start this task, e.g. a
+    /// contractual mobilisation date. The later of this and the
+    /// network-derived start wins; successors follow as usual.
+    #[serde(default)]
+    pub forced_early_start: Option<NaiveDate>,
+    /// Higher is more important; unset behaves as [`Task::DEFAULT_PRIORITY`].
+    #[serde(default)]
+    pub priority: Option<i32>,
+    /// Work by default; see [`TaskKind`].
+    #[serde(default)]
+    pub task_kind: TaskKind,
+    /// Organisation-specific attributes such as cost code or phase.
+    #[serde(default)]
+    pub custom_fields: HashMap<String, String>,
+    /// Recorded progress history, oldest first; see
+    /// `Schedule::record_progress`. Independent of `percent_complete`.
+    #[serde(default)]
+    pub progress_log: Vec<ProgressEntry>,
+    /// Incremented each time the stored task is updated through
+    /// `Schedule::upsert_task_record`; used for optimistic concurrency.
+    #[serde(default)]
+    pub revision: u64,
 }
 
 impl Task {
@@ -221,7 +346,50 @@ impl Task {
             progress_measurement: ProgressMeasurement::default(),
             pre_defined_rationale: Vec::new(),
             resource_allocations: Vec::new(),
+            deadline: None,
+            forced_early_start: None,
+            priority: None,
+            task_kind: TaskKind::default(),
+            custom_fields: HashMap::new(),
+            progress_log: Vec::new(),
+            revision: 0,
+        }
+    }
+
+    /// Start a [`TaskBuilder`] for a task with the given id, name and duration.
+    pub fn builder(id: i32, name: impl Into<String>, duration_days: i64) -> TaskBuilder {
+        TaskBuilder {
+            task: Task::new(id, name, duration_days),
+        }
+    }
+
+    /// Neutral priority assumed for tasks that do not set one.
+    pub const DEFAULT_PRIORITY: i32 = 500;
+
+    pub fn effective_priority(&self) -> i32 {
+        self.priority.unwrap_or(Self::DEFAULT_PRIORITY)
+    }
+
+    /// Working days implied by the baseline dates, counted the way the
+    /// forward pass sets finishes: available days after the start up to and
+    /// including the finish. `None` unless both dates are set and in order.
+    pub fn baseline_working_duration(&self, calendar: &WorkCalendar) -> Option<i64> {
+        let (start, finish) = (self.baseline_start?, self.baseline_finish?);
+        if finish < start {
+            return None;
         }
+        Some(calendar.count_available_days(start + Duration::days(1), finish))
+    }
+
+    /// Working days between the actual dates, counted like
+    /// [`Task::baseline_working_duration`]. `None` until the task has both
+    /// actual dates in order.
+    pub fn actual_working_duration(&self, calendar: &WorkCalendar) -> Option<i64> {
+        let (start, finish) = (self.actual_start?, self.actual_finish?);
+        if finish < start {
+            return None;
+        }
+        Some(calendar.count_available_days(start + Duration::days(1), finish))
     }
 
     pub fn with_rationale_template(
@@ -236,93 +404,88 @@ impl Task {
     }
 
     pub fn to_dataframe_row(&self) -> PolarsResult<DataFrame> {
-        let mut columns: Vec<Column> = Vec::with_capacity(23);
-
-        let id_data: [i32; 1] = [self.id];
-        columns.push(Series::new(PlSmallStr::from_static("id"), id_data).into_column());
-
-        let name_data: [&str; 1] = [self.name.as_str()];
-        columns.push(Series::new(PlSmallStr::from_static("name"), name_data).into_column());
-
-        let duration_data: [i64; 1] = [self.duration_days];
-        columns.push(
-            Series::new(PlSmallStr::from_static("duration_days"), duration_data).into_column(),
-        );
+        Self::tasks_to_dataframe(std::slice::from_ref(self))
+    }
 
-        columns.push(Self::series_from_i32_list("predecessors", &self.predecessors).into_column());
-        columns.push(Self::series_from_date("early_start", self.early_start)?.into_column());
-        columns.push(Self::series_from_date("early_finish", self.early_finish)?.into_column());
-        columns.push(Self::series_from_date("late_start", self.late_start)?.into_column());
-        columns.push(Self::series_from_date("late_finish", self.late_finish)?.into_column());
-        columns.push(Self::series_from_date("baseline_start", self.baseline_start)?.into_column());
-        columns
-            .push(Self::series_from_date("baseline_finish", self.baseline_finish)?.into_column());
-        columns.push(Self::series_from_date("actual_start", self.actual_start)?.into_column());
-        columns.push(Self::series_from_date("actual_finish", self.actual_finish)?.into_column());
-
-        let percent_complete: [Option<f64>; 1] = [self.percent_complete];
-        columns.push(
-            Series::new(
-                PlSmallStr::from_static("percent_complete"),
-                percent_complete,
-            )
-            .into_column(),
-        );
+    /// Build a DataFrame with one row per task, in order, constructing each
+    /// column in a single pass rather than stacking rows.
+    pub fn tasks_to_dataframe(tasks: &[Task]) -> PolarsResult<DataFrame> {
+        fn column<T, F>(tasks: &[Task], name: &'static str, value: F) -> Column
+        where
+            F: Fn(&Task) -> T,
+            Series: NamedFrom<Vec<T>, [T]>,
+        {
+            let values: Vec<T> = tasks.iter().map(value).collect();
+            Series::new(PlSmallStr::from_static(name), values).into_column()
+        }
+        fn json_column<T, F>(tasks: &[Task], name: &'static str, value: F) -> PolarsResult<Column>
+        where
+            T: Serialize + ?Sized,
+            F: Fn(&Task) -> &T,
+        {
+            let values = tasks
+                .iter()
+                .map(|task| serde_json::to_string(value(task)))
+                .collect::<Result<Vec<String>, _>>()
+                .map_err(|err| PolarsError::ComputeError(err.to_string().into()))?;
+            Ok(Series::new(PlSmallStr::from_static(name), values).into_column())
+        }
+        let date_column = |name: &'static str, value: fn(&Task) -> Option<NaiveDate>| {
+            Self::series_from_dates(name, tasks.iter().map(value)).map(Series::into_column)
+        };
 
+        let mut columns: Vec<Column> = Vec::with_capacity(31);
+        columns.push(column(tasks, "id", |t| t.id));
+        columns.push(column(tasks, "name", |t| t.name.clone()));
+        columns.push(column(tasks, "duration_days", |t| t.duration_days));
         columns.push(
-            Series::new(
-                PlSmallStr::from_static("progress_measurement"),
-                &[self.progress_measurement.as_str()],
-            )
-            .into_column(),
+            Self::series_from_i32_lists("predecessors", tasks.iter().map(|t| &t.predecessors))
+                .into_column(),
         );
-
-        let rationale_json = serde_json::to_string(&self.pre_defined_rationale)
-            .map_err(|err| PolarsError::ComputeError(err.to_string().into()))?;
+        columns.push(date_column("early_start", |t| t.early_start)?);
+        columns.push(date_column("early_finish", |t| t.early_finish)?);
+        columns.push(date_column("late_start", |t| t.late_start)?);
+        columns.push(date_column("late_finish", |t| t.late_finish)?);
+        columns.push(date_column("baseline_start", |t| t.baseline_start)?);
+        columns.push(date_column("baseline_finish", |t| t.baseline_finish)?);
+        columns.push(date_column("actual_start", |t| t.actual_start)?);
+        columns.push(date_column("actual_finish", |t| t.actual_finish)?);
+        columns.push(column(tasks, "percent_complete", |t| t.percent_complete));
+        columns.push(column(tasks, "progress_measurement", |t| {
+            t.progress_measurement.as_str()
+        }));
+        columns.push(json_column(tasks, "pre_defined_rationale", |t| {
+            &t.pre_defined_rationale
+        })?);
+        columns.push(column(tasks, "schedule_variance_days", |t| {
+            t.schedule_variance_days
+        }));
+        columns.push(column(tasks, "total_float", |t| t.total_float));
+        columns.push(column(tasks, "is_critical", |t| t.is_critical));
         columns.push(
-            Series::new(
-                PlSmallStr::from_static("pre_defined_rationale"),
-                &[rationale_json.as_str()],
-            )
-            .into_column(),
+            Self::series_from_i32_lists("successors", tasks.iter().map(|t| &t.successors))
+                .into_column(),
         );
-
-        let allocations_json = serde_json::to_string(&self.resource_allocations)
-            .map_err(|err| PolarsError::ComputeError(err.to_string().into()))?;
+        columns.push(column(tasks, "parent_id", |t| t.parent_id));
+        columns.push(column(tasks, "wbs_code", |t| t.wbs_code.clone()));
+        columns.push(column(tasks, "task_notes", |t| t.task_notes.clone()));
         columns.push(
-            Series::new(
-                PlSmallStr::from_static("resource_allocations"),
-                &[allocations_json.as_str()],
+            Self::series_from_string_lists(
+                "task_attachments",
+                tasks.iter().map(|t| &t.task_attachments),
             )
             .into_column(),
         );
-
-        let variance: [Option<i64>; 1] = [self.schedule_variance_days];
-        columns.push(
-            Series::new(PlSmallStr::from_static("schedule_variance_days"), variance).into_column(),
-        );
-
-        let total_float: [Option<i64>; 1] = [self.total_float];
-        columns
-            .push(Series::new(PlSmallStr::from_static("total_float"), total_float).into_column());
-
-        let is_critical: [Option<bool>; 1] = [self.is_critical];
-        columns
-            .push(Series::new(PlSmallStr::from_static("is_critical"), is_critical).into_column());
-
-        columns.push(Self::series_from_i32_list("successors", &self.successors).into_column());
-        let parent: [Option<i32>; 1] = [self.parent_id];
-        columns.push(Series::new(PlSmallStr::from_static("parent_id"), parent).into_column());
-
-        let wbs: [Option<&str>; 1] = [self.wbs_code.as_deref()];
-        columns.push(Series::new(PlSmallStr::from_static("wbs_code"), wbs).into_column());
-
-        let notes: [Option<&str>; 1] = [self.task_notes.as_deref()];
-        columns.push(Series::new(PlSmallStr::from_static("task_notes"), notes).into_column());
-
-        columns.push(
-            Self::series_from_string_list("task_attachments", &self.task_attachments).into_column(),
-        );
+        columns.push(json_column(tasks, "resource_allocations", |t| {
+            &t.resource_allocations
+        })?);
+        columns.push(date_column("deadline", |t| t.deadline)?);
+        columns.push(date_column("forced_early_start", |t| t.forced_early_start)?);
+        columns.push(column(tasks, "priority", |t| t.priority));
+        columns.push(column(tasks, "task_kind", |t| t.task_kind.as_str()));
+        columns.push(json_column(tasks, "custom_fields", |t| &t.custom_fields)?);
+        columns.push(json_column(tasks, "progress_log", |t| &t.progress_log)?);
+        columns.push(column(tasks, "revision", |t| t.revision));
 
         DataFrame::new(columns)
     }
@@ -408,6 +571,29 @@ impl Task {
             Vec::new()
         };
 
+        let custom_fields = match df.column("custom_fields")?.str()?.get(row_idx) {
+            Some(raw) if !raw.trim().is_empty() => {
+                serde_json::from_str::<HashMap<String, String>>(raw.trim()).map_err(|err| {
+                    PolarsError::ComputeError(format!("invalid custom_fields: {err}").into())
+                })?
+            }
+            _ => HashMap::new(),
+        };
+
+        // Older parquet files predate the progress log column.
+        let progress_log = match df
+            .column("progress_log")
+            .ok()
+            .and_then(|col| col.str().ok()?.get(row_idx))
+        {
+            Some(raw) if !raw.trim().is_empty() => {
+                serde_json::from_str::<Vec<ProgressEntry>>(raw.trim()).map_err(|err| {
+                    PolarsError::ComputeError(format!("invalid progress_log: {err}").into())
+                })?
+            }
+            _ => Vec::new(),
+        };
+
         Ok(Self {
             id,
             name,
@@ -441,9 +627,55 @@ impl Task {
             progress_measurement,
             pre_defined_rationale,
             resource_allocations,
+            deadline: Self::date_from_series(df.column("deadline")?.date()?, row_idx),
+            // Older parquet files predate the forced early start column.
+            forced_early_start: match df.column("forced_early_start") {
+                Ok(col) => Self::date_from_series(col.date()?, row_idx),
+                Err(_) => None,
+            },
+            priority: df.column("priority")?.i32()?.get(row_idx),
+            // Older parquet files predate the task kind column.
+            task_kind: df
+                .column("task_kind")
+                .ok()
+                .and_then(|col| col.str().ok()?.get(row_idx))
+                .and_then(TaskKind::from_str)
+                .unwrap_or_default(),
+            custom_fields,
+            progress_log,
+            revision: df.column("revision")?.u64()?.get(row_idx).unwrap_or(0),
         })
     }
 
+    /// Latest recorded progress on or before `date`, or `None` if nothing was
+    /// recorded by then. Entries recorded for the same date resolve to the
+    /// last one recorded.
+    pub fn percent_complete_as_of(&self, date: NaiveDate) -> Option<f64> {
+        self.progress_log
+            .iter()
+            .rev()
+            .find(|entry| entry.date <= date)
+            .map(|entry| entry.percent_complete)
+    }
+
+    /// Percent complete implied by the progress measurement. Rationale-based
+    /// tasks sum the weights of their completed items; every other
+    /// measurement returns the stored `percent_complete`.
+    pub fn computed_percent_complete(&self) -> Option<f64> {
+        match self.progress_measurement {
+            ProgressMeasurement::PreDefinedRationale if !self.pre_defined_rationale.is_empty() => {
+                Some(
+                    self.pre_defined_rationale
+                        .iter()
+                        .filter(|item| item.is_complete)
+                        .map(|item| item.weight)
+                        .sum(),
+                )
+            }
+            _ => self.percent_complete,
+        }
+    }
+
     pub fn apply_rationale_template(
         &mut self,
         template: ProgressRationaleTemplate,
@@ -455,19 +687,40 @@ impl Task {
         Ok(())
     }
 
-    fn series_from_i32_list(name: &str, values: &[i32]) -> Series {
-        let inner = Series::new(PlSmallStr::from_static(""), values.to_vec());
-        Series::new(name.into(), &[inner])
+    fn series_from_i32_lists<'a>(name: &str, rows: impl Iterator<Item = &'a Vec<i32>>) -> Series {
+        let inner: Vec<Series> = rows
+            .map(|values| Series::new(PlSmallStr::from_static(""), values.as_slice()))
+            .collect();
+        Self::list_series(name, inner, DataType::Int32)
+    }
+
+    fn series_from_string_lists<'a>(
+        name: &str,
+        rows: impl Iterator<Item = &'a Vec<String>>,
+    ) -> Series {
+        let inner: Vec<Series> = rows
+            .map(|values| {
+                let inner_values: Vec<&str> = values.iter().map(|s| s.as_str()).collect();
+                Series::new(PlSmallStr::from_static(""), inner_values)
+            })
+            .collect();
+        Self::list_series(name, inner, DataType::String)
     }
 
-    fn series_from_string_list(name: &str, values: &[String]) -> Series {
-        let inner_values: Vec<&str> = values.iter().map(|s| s.as_str()).collect();
-        let inner = Series::new(PlSmallStr::from_static(""), inner_values);
-        Series::new(name.into(), &[inner])
+    /// A list column from per-row series; an empty input still carries the
+    /// list dtype so it matches the schedule schema.
+    fn list_series(name: &str, rows: Vec<Series>, inner: DataType) -> Series {
+        if rows.is_empty() {
+            return Series::new_empty(name.into(), &DataType::List(Box::new(inner)));
+        }
+        Series::new(name.into(), rows)
     }
 
-    fn series_from_date(name: &str, date: Option<NaiveDate>) -> PolarsResult<Series> {
-        let data: [Option<i32>; 1] = [date.map(Self::date_to_i32)];
+    fn series_from_dates(
+        name: &str,
+        dates: impl Iterator<Item = Option<NaiveDate>>,
+    ) -> PolarsResult<Series> {
+        let data: Vec<Option<i32>> = dates.map(|date| date.map(Self::date_to_i32)).collect();
         Series::new(name.into(), data).cast(&DataType::Date)
     }
 
@@ -506,3 +759,124 @@ impl Task {
         epoch + Duration::days(days as i64)
     }
 }
+
+/// One line: `#id name (Nd)`, then the early dates or "unscheduled", total
+/// float when computed, and "critical" for critical tasks.
+impl fmt::Display for Task {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{} {} ({}d)", self.id, self.name, self.duration_days)?;
+        match (self.early_start, self.early_finish) {
+            (Some(start), Some(finish)) => write!(f, " {start}..{finish}")?,
+            _ => write!(f, " unscheduled")?,
+        }
+        if let Some(total_float) = self.total_float {
+            write!(f, " float={total_float}")?;
+        }
+        if self.is_critical == Some(true) {
+            write!(f, " critical")?;
+        }
+        Ok(())
+    }
+}
+
+/// Fluent construction of a fully specified [`Task`], validated once by
+/// [`TaskBuilder::build`].
+#[derive(Debug, Clone)]
+pub struct TaskBuilder {
+    task: Task,
+}
+
+impl TaskBuilder {
+    pub fn predecessors(mut self, predecessors: impl IntoIterator<Item = i32>) -> Self {
+        self.task.predecessors = predecessors.into_iter().collect();
+        self
+    }
+
+    pub fn baseline(mut self, start: NaiveDate, finish: NaiveDate) -> Self {
+        self.task.baseline_start = Some(start);
+        self.task.baseline_finish = Some(finish);
+        self
+    }
+
+    /// Record actual dates; `finish` stays `None` while work is in progress.
+    pub fn actual(mut self, start: NaiveDate, finish: Option<NaiveDate>) -> Self {
+        self.task.actual_start = Some(start);
+        self.task.actual_finish = finish;
+        self
+    }
+
+    pub fn progress(mut self, measurement: ProgressMeasurement, percent_complete: f64) -> Self {
+        self.task.progress_measurement = measurement;
+        self.task.percent_complete = Some(percent_complete);
+        self
+    }
+
+    /// Measure progress by pre-defined rationale items. Percent complete is
+    /// derived from the items, so any value set earlier is cleared.
+    pub fn rationale(mut self, items: impl IntoIterator<Item = RationaleItem>) -> Self {
+        self.task.progress_measurement = ProgressMeasurement::PreDefinedRationale;
+        self.task.pre_defined_rationale = items.into_iter().collect();
+        self.task.percent_complete = None;
+        self
+    }
+
+    pub fn rationale_template(self, template: ProgressRationaleTemplate) -> Self {
+        self.rationale(template.materialize())
+    }
+
+    pub fn resource(mut self, allocation: ResourceAllocation) -> Self {
+        self.task.resource_allocations.push(allocation);
+        self
+    }
+
+    pub fn parent(mut self, parent_id: i32) -> Self {
+        self.task.parent_id = Some(parent_id);
+        self
+    }
+
+    pub fn wbs_code(mut self, code: impl Into<String>) -> Self {
+        self.task.wbs_code = Some(code.into());
+        self
+    }
+
+    pub fn notes(mut self, notes: impl Into<String>) -> Self {
+        self.task.task_notes = Some(notes.into());
+        self
+    }
+
+    pub fn attachment(mut self, attachment: impl Into<String>) -> Self {
+        self.task.task_attachments.push(attachment.into());
+        self
+    }
+
+    pub fn deadline(mut self, deadline: NaiveDate) -> Self {
+        self.task.deadline = Some(deadline);
+        self
+    }
+
+    pub fn forced_early_start(mut self, date: NaiveDate) -> Self {
+        self.task.forced_early_start = Some(date);
+        self
+    }
+
+    pub fn task_kind(mut self, kind: TaskKind) -> Self {
+        self.task.task_kind = kind;
+        self
+    }
+
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.task.priority = Some(priority);
+        self
+    }
+
+    pub fn custom_field(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.task.custom_fields.insert(key.into(), value.into());
+        self
+    }
+
+    /// Validate the task with the same rules schedules and loaders apply.
+    pub fn build(self) -> Result<Task, TaskValidationError> {
+        task_validation::validate_task(&self.task)?;
+        Ok(self.task)
+    }
+}
@@ -1,25 +1,90 @@
+use crate::resource::ResourceAllocation;
 use crate::task::{ProgressMeasurement, Task};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 
 const EPSILON: f64 = 1e-6;
 
+/// Tolerances applied when validating tasks. The default is strict; relax it
+/// to accept data from tools that round rationale weights on export.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValidationOptions {
+    /// How far the pre-defined rationale weights of a task may sum from 1.0.
+    pub weight_tolerance: f64,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        Self {
+            weight_tolerance: EPSILON,
+        }
+    }
+}
+
+impl ValidationOptions {
+    pub fn with_weight_tolerance(mut self, tolerance: f64) -> Self {
+        self.weight_tolerance = tolerance;
+        self
+    }
+}
+
+/// What a [`TaskValidationError`] is about. The common cases carry their
+/// values so callers can branch on them; everything else is `Other`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TaskValidationKind {
+    DuplicateTaskId(i32),
+    NegativeDuration { id: i32, duration: i64 },
+    InvalidProgress { id: i32, pct: f64 },
+    RationaleWeightSum { id: i32, total: f64 },
+    Other(String),
+}
+
+impl fmt::Display for TaskValidationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaskValidationKind::DuplicateTaskId(id) => write!(f, "duplicate task id {id}"),
+            TaskValidationKind::NegativeDuration { id, duration } => {
+                write!(f, "task {id} has negative duration {duration}")
+            }
+            TaskValidationKind::InvalidProgress { id, pct } => write!(
+                f,
+                "task {id} has invalid percent_complete {pct} (must be between 0 and 1)"
+            ),
+            TaskValidationKind::RationaleWeightSum { id, total } => write!(
+                f,
+                "task {id} pre_defined_rationale weights must sum to 1.0 (got {total:.4})"
+            ),
+            TaskValidationKind::Other(message) => write!(f, "{message}"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TaskValidationError {
-    message: String,
+    kind: TaskValidationKind,
 }
 
 impl TaskValidationError {
     pub fn new(message: impl Into<String>) -> Self {
         Self {
-            message: message.into(),
+            kind: TaskValidationKind::Other(message.into()),
         }
     }
+
+    pub(crate) fn into_kind(self) -> TaskValidationKind {
+        self.kind
+    }
+}
+
+impl From<TaskValidationKind> for TaskValidationError {
+    fn from(kind: TaskValidationKind) -> Self {
+        Self { kind }
+    }
 }
 
 impl fmt::Display for TaskValidationError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.message)
+        self.kind.fmt(f)
     }
 }
 
@@ -30,38 +95,59 @@ fn approx_equal(a: f64, b: f64) -> bool {
 }
 
 pub fn validate_task(task: &Task) -> Result<(), TaskValidationError> {
+    validate_task_with(task, &ValidationOptions::default())
+}
+
+pub fn validate_task_with(
+    task: &Task,
+    options: &ValidationOptions,
+) -> Result<(), TaskValidationError> {
+    match task_errors_with(task, options).into_iter().next() {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+/// Collect every problem with a single task, in the order `validate_task` checks them.
+pub fn task_errors(task: &Task) -> Vec<TaskValidationError> {
+    task_errors_with(task, &ValidationOptions::default())
+}
+
+/// [`task_errors`] with caller-supplied tolerances.
+pub fn task_errors_with(task: &Task, options: &ValidationOptions) -> Vec<TaskValidationError> {
+    let mut errors = Vec::new();
     if task.duration_days < 0 {
-        return Err(TaskValidationError::new(format!(
-            "task {} has negative duration {}",
-            task.id, task.duration_days
-        )));
+        errors.push(
+            TaskValidationKind::NegativeDuration {
+                id: task.id,
+                duration: task.duration_days,
+            }
+            .into(),
+        );
     }
 
-    if let Some(pct) = task.percent_complete {
-        if !pct.is_finite() || pct < -EPSILON || pct > 1.0 + EPSILON {
-            return Err(TaskValidationError::new(format!(
-                "task {} has invalid percent_complete {} (must be between 0 and 1)",
-                task.id, pct
-            )));
-        }
+    if let Some(pct) = task.percent_complete
+        && (!pct.is_finite() || !(-EPSILON..=1.0 + EPSILON).contains(&pct))
+    {
+        errors.push(TaskValidationKind::InvalidProgress { id: task.id, pct }.into());
     }
 
     match task.progress_measurement {
         ProgressMeasurement::ZeroOneHundred => {
-            if let Some(pct) = task.percent_complete {
-                if !(approx_equal(pct, 0.0) || approx_equal(pct, 1.0)) {
-                    return Err(TaskValidationError::new(format!(
-                        "task {} progress_measurement=0_100 requires percent_complete of 0 or 1 (got {})",
-                        task.id, pct
-                    )));
-                }
+            if let Some(pct) = task.percent_complete
+                && !(approx_equal(pct, 0.0) || approx_equal(pct, 1.0))
+            {
+                errors.push(TaskValidationError::new(format!(
+                    "task {} progress_measurement=0_100 requires percent_complete of 0 or 1 (got {})",
+                    task.id, pct
+                )));
             }
         }
         ProgressMeasurement::FiftyFifty => {
             if let Some(pct) = task.percent_complete {
                 let allowed = [0.0, 0.5, 1.0];
                 if !allowed.iter().any(|v| approx_equal(*v, pct)) {
-                    return Err(TaskValidationError::new(format!(
+                    errors.push(TaskValidationError::new(format!(
                         "task {} progress_measurement=50_50 requires percent_complete of 0, 0.5, or 1 (got {})",
                         task.id, pct
                     )));
@@ -72,7 +158,7 @@ pub fn validate_task(task: &Task) -> Result<(), TaskValidationError> {
             if let Some(pct) = task.percent_complete {
                 let allowed = [0.0, 0.25, 0.75, 1.0];
                 if !allowed.iter().any(|v| approx_equal(*v, pct)) {
-                    return Err(TaskValidationError::new(format!(
+                    errors.push(TaskValidationError::new(format!(
                         "task {} progress_measurement={} requires percent_complete of 0, 0.25, 0.75, or 1 (got {})",
                         task.id,
                         task.progress_measurement.as_str(),
@@ -84,7 +170,7 @@ pub fn validate_task(task: &Task) -> Result<(), TaskValidationError> {
         ProgressMeasurement::PercentComplete => {}
         ProgressMeasurement::PreDefinedRationale => {
             if task.pre_defined_rationale.is_empty() {
-                return Err(TaskValidationError::new(format!(
+                errors.push(TaskValidationError::new(format!(
                     "task {} progress_measurement=pre_defined_rationale requires at least one rationale item",
                     task.id
                 )));
@@ -93,70 +179,146 @@ pub fn validate_task(task: &Task) -> Result<(), TaskValidationError> {
             let mut rationale_ids = HashSet::with_capacity(task.pre_defined_rationale.len());
             for rationale in &task.pre_defined_rationale {
                 if !rationale.weight.is_finite() {
-                    return Err(TaskValidationError::new(format!(
+                    errors.push(TaskValidationError::new(format!(
                         "task {} has non-finite rationale weight for '{}'",
                         task.id, rationale.name
                     )));
                 }
                 if rationale.weight < 0.0 {
-                    return Err(TaskValidationError::new(format!(
+                    errors.push(TaskValidationError::new(format!(
                         "task {} has negative rationale weight for '{}'",
                         task.id, rationale.name
                     )));
                 }
                 if !rationale_ids.insert(rationale.id) {
-                    return Err(TaskValidationError::new(format!(
+                    errors.push(TaskValidationError::new(format!(
                         "task {} has duplicate rationale id {}",
                         task.id, rationale.id
                     )));
                 }
                 total += rationale.weight;
             }
-            if !approx_equal(total, 1.0) {
-                return Err(TaskValidationError::new(format!(
-                    "task {} pre_defined_rationale weights must sum to 1.0 (got {:.4})",
-                    task.id, total
-                )));
+            if (total - 1.0).abs() > options.weight_tolerance {
+                errors.push(TaskValidationKind::RationaleWeightSum { id: task.id, total }.into());
             }
         }
     }
 
+    let mut first_by_resource: HashMap<&str, &ResourceAllocation> = HashMap::new();
     for (idx, allocation) in task.resource_allocations.iter().enumerate() {
         if allocation.resource_id.trim().is_empty() {
-            return Err(TaskValidationError::new(format!(
+            errors.push(TaskValidationError::new(format!(
                 "task {} resource allocation #{} requires a non-empty resource_id",
                 task.id, idx
             )));
         }
         if !allocation.allocation_units.is_finite() || allocation.allocation_units < -EPSILON {
-            return Err(TaskValidationError::new(format!(
+            errors.push(TaskValidationError::new(format!(
                 "task {} allocation for '{}' has invalid allocation_units {}",
                 task.id, allocation.resource_id, allocation.allocation_units
             )));
         }
-        if let Some(cost_rate) = allocation.cost_rate {
-            if !cost_rate.is_finite() || cost_rate < -EPSILON {
-                return Err(TaskValidationError::new(format!(
-                    "task {} allocation for '{}' has invalid cost_rate {}",
-                    task.id, allocation.resource_id, cost_rate
+        match allocation.max_units {
+            Some(max) if !max.is_finite() || max <= 0.0 => {
+                errors.push(TaskValidationError::new(format!(
+                    "task {} allocation for '{}' has invalid max_units {}",
+                    task.id, allocation.resource_id, max
+                )));
+            }
+            _ if allocation.allocation_units > allocation.unit_limit() + EPSILON => {
+                errors.push(TaskValidationError::new(format!(
+                    "task {} allocation for '{}' has allocation_units {} above the limit of {} (set max_units to allow over-allocation)",
+                    task.id,
+                    allocation.resource_id,
+                    allocation.allocation_units,
+                    allocation.unit_limit()
+                )));
+            }
+            _ => {}
+        }
+        if let Some(cost_rate) = allocation.cost_rate
+            && (!cost_rate.is_finite() || cost_rate < -EPSILON)
+        {
+            errors.push(TaskValidationError::new(format!(
+                "task {} allocation for '{}' has invalid cost_rate {}",
+                task.id, allocation.resource_id, cost_rate
+            )));
+        }
+        let mut previous_fraction: Option<f64> = None;
+        for &(fraction, units) in &allocation.profile {
+            if !fraction.is_finite()
+                || !(0.0..1.0).contains(&fraction)
+                || previous_fraction.is_some_and(|previous| fraction <= previous)
+            {
+                errors.push(TaskValidationError::new(format!(
+                    "task {} allocation for '{}' has profile fraction {} (fractions must ascend within 0..1)",
+                    task.id, allocation.resource_id, fraction
+                )));
+            }
+            if !units.is_finite() || units < -EPSILON || units > allocation.unit_limit() + EPSILON {
+                errors.push(TaskValidationError::new(format!(
+                    "task {} allocation for '{}' has profile units {} (must be between 0 and {})",
+                    task.id,
+                    allocation.resource_id,
+                    units,
+                    allocation.unit_limit()
                 )));
             }
+            previous_fraction = Some(fraction);
+        }
+        // Listing a resource twice is tolerated only when both entries agree.
+        if let Some(first) = first_by_resource.get(allocation.resource_id.as_str()) {
+            if !approx_equal(first.allocation_units, allocation.allocation_units)
+                || first.cost_rate != allocation.cost_rate
+                || first.profile != allocation.profile
+            {
+                errors.push(TaskValidationError::new(format!(
+                    "task {} lists resource '{}' more than once with conflicting allocation_units, cost_rate or profile",
+                    task.id, allocation.resource_id
+                )));
+            }
+        } else {
+            first_by_resource.insert(allocation.resource_id.as_str(), allocation);
         }
     }
 
-    Ok(())
+    for entry in &task.progress_log {
+        if !entry.percent_complete.is_finite()
+            || !(-EPSILON..=1.0 + EPSILON).contains(&entry.percent_complete)
+        {
+            errors.push(TaskValidationError::new(format!(
+                "task {} progress log entry for {} has invalid percent_complete {} (must be between 0 and 1)",
+                task.id, entry.date, entry.percent_complete
+            )));
+        }
+    }
+
+    for key in task.custom_fields.keys() {
+        if key.chars().any(char::is_control) {
+            errors.push(TaskValidationError::new(format!(
+                "task {} custom field key {:?} contains control characters",
+                task.id, key
+            )));
+        }
+    }
+
+    errors
 }
 
 pub fn validate_task_collection(tasks: &[Task]) -> Result<(), TaskValidationError> {
+    validate_task_collection_with(tasks, &ValidationOptions::default())
+}
+
+pub fn validate_task_collection_with(
+    tasks: &[Task],
+    options: &ValidationOptions,
+) -> Result<(), TaskValidationError> {
     let mut seen_ids = HashSet::with_capacity(tasks.len());
     for task in tasks {
         if !seen_ids.insert(task.id) {
-            return Err(TaskValidationError::new(format!(
-                "duplicate task id {}",
-                task.id
-            )));
+            return Err(TaskValidationKind::DuplicateTaskId(task.id).into());
         }
-        validate_task(task)?;
+        validate_task_with(task, options)?;
     }
     Ok(())
 }
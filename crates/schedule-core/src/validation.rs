@@ -0,0 +1,296 @@
+use crate::graph::schedule_dag::ScheduleDag;
+use crate::schedule::Schedule;
+use crate::task_validation;
+use petgraph::algo::{has_path_connecting, tarjan_scc};
+use polars::prelude::PolarsError;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationSeverity {
+    /// The schedule cannot be computed or persisted until this is fixed.
+    Error,
+    /// The schedule is usable but the data is likely wrong.
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    pub severity: ValidationSeverity,
+    /// Tasks involved in the problem; empty for project-level issues.
+    pub task_ids: Vec<i32>,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    fn error(task_ids: Vec<i32>, message: impl Into<String>) -> Self {
+        Self {
+            severity: ValidationSeverity::Error,
+            task_ids,
+            message: message.into(),
+        }
+    }
+
+    fn warning(task_ids: Vec<i32>, message: impl Into<String>) -> Self {
+        Self {
+            severity: ValidationSeverity::Warning,
+            task_ids,
+            message: message.into(),
+        }
+    }
+}
+
+/// A task whose baseline dates imply a different duration than it has.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineDurationMismatch {
+    pub task_id: i32,
+    pub duration_days: i64,
+    /// See [`crate::task::Task::baseline_working_duration`].
+    pub baseline_working_days: i64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// True when the report contains no errors (warnings are allowed).
+    pub fn is_valid(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == ValidationSeverity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == ValidationSeverity::Warning)
+    }
+
+    /// Issues that mention the given task.
+    pub fn for_task(&self, task_id: i32) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(move |issue| issue.task_ids.contains(&task_id))
+    }
+}
+
+impl Schedule {
+    /// Check the whole schedule and report every problem found, without
+    /// stopping at the first one. The schedule itself is not modified.
+    pub fn validate(&self) -> Result<ValidationReport, PolarsError> {
+        let mut issues = Vec::new();
+        let tasks = self.tasks()?;
+
+        let mut seen_ids: HashMap<i32, usize> = HashMap::with_capacity(tasks.len());
+        for task in &tasks {
+            *seen_ids.entry(task.id).or_default() += 1;
+        }
+        let mut duplicates: Vec<i32> = seen_ids
+            .iter()
+            .filter(|(_, count)| **count > 1)
+            .map(|(id, _)| *id)
+            .collect();
+        duplicates.sort_unstable();
+        for id in duplicates {
+            issues.push(ValidationIssue::error(
+                vec![id],
+                format!("duplicate task id {id}"),
+            ));
+        }
+
+        for task in &tasks {
+            for err in task_validation::task_errors(task) {
+                issues.push(ValidationIssue::error(vec![task.id], err.to_string()));
+            }
+
+            for pred in &task.predecessors {
+                if *pred == task.id {
+                    issues.push(ValidationIssue::error(
+                        vec![task.id],
+                        format!("task {} depends on itself", task.id),
+                    ));
+                } else if !seen_ids.contains_key(pred) {
+                    issues.push(ValidationIssue::error(
+                        vec![task.id],
+                        format!("task {} references missing predecessor {}", task.id, pred),
+                    ));
+                }
+            }
+
+            if let (Some(start), Some(finish)) = (task.actual_start, task.actual_finish)
+                && finish < start
+            {
+                issues.push(ValidationIssue::error(
+                    vec![task.id],
+                    format!(
+                        "task {} actual_finish {finish} is before actual_start {start}",
+                        task.id
+                    ),
+                ));
+            }
+            if let (Some(start), Some(finish)) = (task.baseline_start, task.baseline_finish)
+                && finish < start
+            {
+                issues.push(ValidationIssue::error(
+                    vec![task.id],
+                    format!(
+                        "task {} baseline_finish {finish} is before baseline_start {start}",
+                        task.id
+                    ),
+                ));
+            }
+            if task.actual_finish.is_some() && task.actual_start.is_none() {
+                issues.push(ValidationIssue::warning(
+                    vec![task.id],
+                    format!("task {} has an actual_finish but no actual_start", task.id),
+                ));
+            }
+            if task.actual_finish.is_some()
+                && task
+                    .percent_complete
+                    .is_none_or(|pct| pct < 1.0 - f64::EPSILON)
+            {
+                let percent = task
+                    .percent_complete
+                    .map_or_else(|| "unset".to_string(), |pct| pct.to_string());
+                issues.push(ValidationIssue::warning(
+                    vec![task.id],
+                    format!(
+                        "task {} has an actual_finish but percent_complete is {percent}, not 1",
+                        task.id
+                    ),
+                ));
+            }
+        }
+
+        let dag = ScheduleDag::build(self.dataframe())?;
+        for component in tarjan_scc(&dag.graph) {
+            if component.len() < 2 {
+                continue;
+            }
+            let mut ids: Vec<i32> = component.iter().map(|ix| dag.graph[*ix]).collect();
+            ids.sort_unstable();
+            let chain = ids
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            issues.push(ValidationIssue::error(
+                ids,
+                format!("dependency cycle between tasks {chain}"),
+            ));
+        }
+
+        let start = self.project_start_date();
+        let end = self.project_end_date();
+        if start > end {
+            issues.push(ValidationIssue::error(
+                Vec::new(),
+                format!("project start date {start} must be on or before project end date {end}"),
+            ));
+        }
+        if let Some(finish) = self.latest_early_finish()?
+            && finish > end
+        {
+            let mut late: Vec<i32> = tasks
+                .iter()
+                .filter(|task| task.early_finish.is_some_and(|ef| ef > end))
+                .map(|task| task.id)
+                .collect();
+            late.sort_unstable();
+            issues.push(ValidationIssue::error(
+                late,
+                format!("project_end_date {end} precedes schedule finish {finish}"),
+            ));
+        }
+        let mut early: Vec<i32> = tasks
+            .iter()
+            .filter(|task| task.early_start.is_some_and(|es| es < start))
+            .map(|task| task.id)
+            .collect();
+        if !early.is_empty() {
+            early.sort_unstable();
+            issues.push(ValidationIssue::warning(
+                early,
+                format!("tasks start before project start date {start}"),
+            ));
+        }
+
+        Ok(ValidationReport { issues })
+    }
+
+    /// Whether making `predecessor_id` a predecessor of `task_id` would close a
+    /// dependency cycle, i.e. the predecessor already depends on the task.
+    pub fn would_create_cycle(
+        &self,
+        task_id: i32,
+        predecessor_id: i32,
+    ) -> Result<bool, PolarsError> {
+        if task_id == predecessor_id {
+            return Ok(true);
+        }
+        let dag = ScheduleDag::build(self.dataframe())?;
+        let (Some(&task), Some(&pred)) = (
+            dag.id_to_index.get(&task_id),
+            dag.id_to_index.get(&predecessor_id),
+        ) else {
+            return Ok(false);
+        };
+        Ok(has_path_connecting(&dag.graph, task, pred, None))
+    }
+
+    /// Names shared by more than one task, with the ids using each, sorted by
+    /// name. Duplicate names are legal, so this is a separate opt-in check
+    /// rather than part of [`Schedule::validate`].
+    pub fn duplicate_names(&self) -> Result<Vec<(String, Vec<i32>)>, PolarsError> {
+        let mut by_name: BTreeMap<String, Vec<i32>> = BTreeMap::new();
+        for task in self.iter_tasks() {
+            let task = task?;
+            by_name.entry(task.name).or_default().push(task.id);
+        }
+        Ok(by_name
+            .into_iter()
+            .filter(|(_, ids)| ids.len() > 1)
+            .map(|(name, mut ids)| {
+                ids.sort_unstable();
+                (name, ids)
+            })
+            .collect())
+    }
+
+    /// Tasks whose baseline span, in working days on the schedule calendar,
+    /// differs from `duration_days` by more than `tolerance_days`, in
+    /// ascending id order. Tasks without both baseline dates are skipped.
+    /// Mismatches usually mean an import mixed up dates or durations.
+    pub fn baseline_duration_mismatches(
+        &self,
+        tolerance_days: i64,
+    ) -> Result<Vec<BaselineDurationMismatch>, PolarsError> {
+        let mut mismatches = Vec::new();
+        for task in self.iter_tasks() {
+            let task = task?;
+            if let Some(baseline_working_days) = task.baseline_working_duration(self.calendar())
+                && (baseline_working_days - task.duration_days).abs() > tolerance_days
+            {
+                mismatches.push(BaselineDurationMismatch {
+                    task_id: task.id,
+                    duration_days: task.duration_days,
+                    baseline_working_days,
+                });
+            }
+        }
+        mismatches.sort_by_key(|mismatch| mismatch.task_id);
+        Ok(mismatches)
+    }
+}
@@ -0,0 +1,355 @@
+use chrono::NaiveDate;
+use schedule_tool::{FastTrackCandidate, ResourceAllocation, Schedule, ScheduleMetadata, Task};
+
+fn d(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+fn base_schedule() -> Schedule {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 1, 31),
+        ..ScheduleMetadata::default()
+    };
+    Schedule::new_with_metadata(metadata)
+}
+
+fn allocated_task(id: i32, duration: i64, allocations: &[(&str, f64)]) -> Task {
+    let mut task = Task::new(id, format!("T{id}"), duration);
+    task.resource_allocations = allocations
+        .iter()
+        .map(|(resource, units)| {
+            // Crew sizes, so allow more than one unit per allocation.
+            let mut allocation = ResourceAllocation::new(*resource, *units);
+            allocation.max_units = Some(10.0);
+            allocation
+        })
+        .collect();
+    task
+}
+
+#[test]
+fn resource_profile_sums_overlapping_allocations() {
+    let mut schedule = base_schedule();
+    schedule
+        .upsert_task_record(allocated_task(1, 2, &[("crane", 1.0)]))
+        .unwrap();
+    schedule
+        .upsert_task_record(allocated_task(2, 6, &[("crane", 0.5), ("crew", 4.0)]))
+        .unwrap();
+    schedule.refresh().unwrap();
+
+    let first = schedule.find_task(1).unwrap().unwrap();
+    let second = schedule.find_task(2).unwrap().unwrap();
+    let profile = schedule.resource_profile("crane").unwrap();
+
+    // One entry per working day in the project span.
+    let working_days = schedule
+        .calendar()
+        .count_available_days(d(2025, 1, 6), d(2025, 1, 31));
+    assert_eq!(profile.len() as i64, working_days);
+    assert!(profile.iter().all(|(day, _)| *day >= d(2025, 1, 6)));
+
+    let peak = profile.iter().map(|(_, units)| *units).fold(0.0, f64::max);
+    assert!((peak - 1.5).abs() < 1e-9);
+    let usage_on = |day: NaiveDate| {
+        profile
+            .iter()
+            .find(|(date, _)| *date == day)
+            .map(|(_, units)| *units)
+            .unwrap()
+    };
+    assert!((usage_on(first.early_start.unwrap()) - 1.5).abs() < 1e-9);
+    assert!((usage_on(second.early_finish.unwrap()) - 0.5).abs() < 1e-9);
+    assert_eq!(usage_on(d(2025, 1, 31)), 0.0);
+
+    let profiles = schedule.resource_profiles().unwrap();
+    assert_eq!(profiles.len(), 2);
+    assert_eq!(profiles["crane"], profile);
+    assert!(
+        schedule
+            .resource_profile("welder")
+            .unwrap()
+            .iter()
+            .all(|(_, u)| *u == 0.0)
+    );
+}
+
+#[test]
+fn cost_rolls_up_units_rate_and_duration() {
+    let mut schedule = base_schedule();
+    let mut design = allocated_task(1, 5, &[("engineer", 8.0), ("intern", 4.0)]);
+    design.resource_allocations[0].cost_rate = Some(100.0);
+    schedule.upsert_task_record(design).unwrap();
+    let mut build = allocated_task(2, 3, &[("crew", 2.0)]);
+    build.predecessors = vec![1];
+    build.resource_allocations[0].cost_rate = Some(250.0);
+    schedule.upsert_task_record(build).unwrap();
+    schedule.refresh().unwrap();
+
+    // 8 units * 100 * 5 days; the intern has no rate and costs nothing.
+    assert_eq!(schedule.task_cost(1).unwrap(), Some(4000.0));
+    // 2 units * 250 * 3 days.
+    assert_eq!(schedule.task_cost(2).unwrap(), Some(1500.0));
+    assert_eq!(schedule.task_cost(99).unwrap(), None);
+    assert_eq!(schedule.total_cost().unwrap(), 5500.0);
+
+    let summary = schedule.analytics_summary().unwrap();
+    assert_eq!(summary.task_count, 2);
+    assert_eq!(summary.total_cost, 5500.0);
+}
+
+#[test]
+fn ramped_profile_time_phases_cost_and_usage() {
+    let mut schedule = base_schedule();
+    let mut flat = allocated_task(1, 10, &[("crew", 1.0)]);
+    flat.resource_allocations[0].cost_rate = Some(100.0);
+    let mut ramped = flat.clone();
+    ramped.id = 2;
+    ramped.resource_allocations[0].resource_id = "ramped crew".into();
+    // Half a unit for the first half of the task, the full unit after.
+    ramped.resource_allocations[0].profile = vec![(0.0, 0.5), (0.5, 1.0)];
+    schedule.upsert_task_record(flat).unwrap();
+    schedule.upsert_task_record(ramped).unwrap();
+    schedule.refresh().unwrap();
+
+    // 1 unit * 100 * 10 days against 5 days at 0.5 plus 5 days at 1.0.
+    assert_eq!(schedule.task_cost(1).unwrap(), Some(1000.0));
+    assert_eq!(schedule.task_cost(2).unwrap(), Some(750.0));
+
+    let usage = schedule.resource_profile("ramped crew").unwrap();
+    let on = |date| usage.iter().find(|(day, _)| *day == date).unwrap().1;
+    assert_eq!(on(d(2025, 1, 6)), 0.5);
+    assert_eq!(on(d(2025, 1, 10)), 0.5);
+    assert_eq!(on(d(2025, 1, 13)), 1.0);
+    assert_eq!(on(d(2025, 1, 17)), 1.0);
+
+    let mut descending = allocated_task(3, 4, &[("crew", 1.0)]);
+    descending.resource_allocations[0].profile = vec![(0.5, 1.0), (0.25, 0.5)];
+    assert!(schedule.upsert_task_record(descending).is_err());
+}
+
+#[test]
+fn critical_edges_and_near_critical_tasks_follow_total_float() {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 1, 14),
+        ..ScheduleMetadata::default()
+    };
+    let mut schedule = Schedule::new_with_metadata(metadata);
+    schedule.upsert_task(1, "Design", 2, None).unwrap();
+    schedule.upsert_task(2, "Build", 3, Some(vec![1])).unwrap();
+    schedule.upsert_task(3, "Order", 1, None).unwrap();
+    schedule.refresh().unwrap();
+
+    let float_of = |id: i32| schedule.find_task(id).unwrap().unwrap().total_float;
+    assert_eq!(float_of(1), Some(0));
+    assert_eq!(float_of(2), Some(0));
+    let order_float = float_of(3).unwrap();
+    assert!(order_float > 0);
+
+    assert_eq!(schedule.critical_path_edges().unwrap(), vec![(1, 2)]);
+    assert_eq!(schedule.near_critical_tasks(order_float).unwrap(), vec![3]);
+    assert!(
+        schedule
+            .near_critical_tasks(order_float - 1)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn free_float_measures_slack_before_the_earliest_successor() {
+    let mut schedule = base_schedule();
+    schedule
+        .upsert_task_record(Task::new(1, "Short", 2))
+        .unwrap();
+    schedule
+        .upsert_task_record(Task::new(2, "Long", 5))
+        .unwrap();
+    let mut merge = Task::new(3, "Merge", 2);
+    merge.predecessors = vec![1, 2];
+    schedule.upsert_task_record(merge).unwrap();
+    schedule.refresh().unwrap();
+
+    // Task 1 finishes on the 7th but task 3 cannot start before the 13th.
+    assert_eq!(schedule.free_float(1).unwrap(), Some(3));
+    assert_eq!(schedule.free_float(2).unwrap(), Some(0));
+    let merge = schedule.find_task(3).unwrap().unwrap();
+    assert_eq!(schedule.free_float(3).unwrap(), merge.total_float);
+    assert_eq!(schedule.free_float(99).unwrap(), None);
+}
+
+#[test]
+fn link_lag_reports_gap_forced_by_another_predecessor() {
+    let mut schedule = base_schedule();
+    schedule
+        .upsert_task_record(Task::new(1, "Design", 2))
+        .unwrap();
+    let mut build = Task::new(2, "Build", 2);
+    build.predecessors = vec![1];
+    schedule.upsert_task_record(build).unwrap();
+    schedule
+        .upsert_task_record(Task::new(3, "Procure", 6))
+        .unwrap();
+    let mut install = Task::new(4, "Install", 1);
+    install.predecessors = vec![2, 3];
+    schedule.upsert_task_record(install).unwrap();
+    schedule.refresh().unwrap();
+
+    // Build finishes on the 13th; Procure holds Install until the 15th.
+    assert_eq!(schedule.link_lag(1, 2).unwrap(), Some(0));
+    assert_eq!(schedule.link_lag(2, 4).unwrap(), Some(1));
+    assert_eq!(schedule.link_lag(3, 4).unwrap(), Some(0));
+    assert_eq!(schedule.link_lag(1, 4).unwrap(), None);
+    assert_eq!(schedule.link_lag(99, 4).unwrap(), None);
+}
+
+#[test]
+fn fast_track_candidates_flag_critical_finish_to_start_links() {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 1, 14),
+        ..ScheduleMetadata::default()
+    };
+    let mut schedule = Schedule::new_with_metadata(metadata);
+    schedule
+        .upsert_task_record(Task::new(1, "Design", 3))
+        .unwrap();
+    let mut build = Task::new(2, "Build", 2);
+    build.predecessors = vec![1];
+    schedule.upsert_task_record(build).unwrap();
+    schedule.refresh().unwrap();
+
+    let candidates = schedule.fast_track_candidates().unwrap();
+
+    assert_eq!(
+        candidates,
+        vec![FastTrackCandidate {
+            predecessor_id: 1,
+            successor_id: 2,
+            total_float: 0,
+            potential_days_saved: 3,
+        }]
+    );
+}
+
+#[test]
+fn s_curve_tracks_planned_and_earned_completion() {
+    let mut schedule = base_schedule();
+    let mut design = Task::new(1, "Design", 4);
+    design.baseline_start = Some(d(2025, 1, 6));
+    design.baseline_finish = Some(d(2025, 1, 10));
+    design.actual_start = Some(d(2025, 1, 6));
+    design.percent_complete = Some(0.5);
+    let mut build = Task::new(2, "Build", 4);
+    build.predecessors = vec![1];
+    build.baseline_start = Some(d(2025, 1, 10));
+    build.baseline_finish = Some(d(2025, 1, 16));
+    schedule.upsert_task_record(design).unwrap();
+    schedule.upsert_task_record(build).unwrap();
+    schedule.refresh().unwrap();
+
+    let curve = schedule.s_curve(d(2025, 1, 8)).unwrap();
+    let at = |date| {
+        curve
+            .points
+            .iter()
+            .find(|point| point.date == date)
+            .unwrap()
+    };
+
+    assert_eq!(curve.points.first().unwrap().date, d(2025, 1, 6));
+    // Design is two of its four baseline days in; Build has not started.
+    assert_eq!(at(d(2025, 1, 8)).planned_percent, Some(0.25));
+    // Design is done and Build two of four days in.
+    assert_eq!(at(d(2025, 1, 14)).planned_percent, Some(0.75));
+    assert_eq!(at(d(2025, 1, 16)).planned_percent, Some(1.0));
+    // Half of Design earned by the data date, nothing reported after it.
+    assert_eq!(at(d(2025, 1, 8)).actual_percent, Some(0.25));
+    assert_eq!(at(d(2025, 1, 9)).actual_percent, None);
+}
+
+#[test]
+fn refresh_reports_weighted_percent_and_forecast_finish() {
+    let mut schedule = base_schedule();
+    let mut done = Task::new(1, "Design", 2);
+    done.percent_complete = Some(1.0);
+    let mut half = Task::new(2, "Build", 6);
+    half.predecessors = vec![1];
+    half.percent_complete = Some(0.5);
+    schedule.upsert_task_record(done).unwrap();
+    schedule.upsert_task_record(half).unwrap();
+    schedule.set_status_date(Some(d(2025, 1, 13)));
+
+    let summary = schedule.refresh().unwrap();
+
+    // (2 * 1.0 + 6 * 0.5) / 8
+    assert_eq!(summary.project_percent_complete, Some(0.625));
+    // Three remaining days of Build from Monday the 13th.
+    assert_eq!(summary.forecast_finish, Some(d(2025, 1, 16)));
+    assert!(summary.to_cli_summary().contains("complete=62.5%"));
+}
+
+#[test]
+fn project_percent_complete_ignores_summary_tasks_and_counts_missing_as_zero() {
+    let mut schedule = base_schedule();
+    let mut parent = Task::new(1, "Phase", 10);
+    parent.percent_complete = Some(1.0);
+    let mut child = Task::new(2, "Work", 4);
+    child.parent_id = Some(1);
+    schedule.upsert_task_record(parent).unwrap();
+    schedule.upsert_task_record(child).unwrap();
+
+    assert_eq!(schedule.project_percent_complete().unwrap(), Some(0.0));
+}
+
+#[test]
+fn analytics_frames_normalize_links_and_allocations() {
+    let mut schedule = base_schedule();
+    schedule
+        .upsert_task_record(allocated_task(1, 2, &[("crew", 2.0), ("crane", 1.0)]))
+        .unwrap();
+    schedule.upsert_task(2, "Frame", 3, Some(vec![1])).unwrap();
+    schedule
+        .upsert_task(3, "Roof", 2, Some(vec![1, 2]))
+        .unwrap();
+    schedule.refresh().unwrap();
+
+    let frames = schedule.analytics_frames().unwrap();
+    let link_count: usize = schedule
+        .tasks()
+        .unwrap()
+        .iter()
+        .map(|task| task.predecessors.len())
+        .sum();
+    assert_eq!(frames.dependencies.height(), link_count);
+    assert_eq!(frames.dependencies.height(), 3);
+    let successors = frames
+        .dependencies
+        .column("successor_id")
+        .unwrap()
+        .i32()
+        .unwrap();
+    assert_eq!(
+        successors.into_no_null_iter().collect::<Vec<_>>(),
+        vec![2, 3, 3]
+    );
+
+    assert_eq!(frames.resource_allocations.height(), 2);
+    let resources = frames
+        .resource_allocations
+        .column("resource_id")
+        .unwrap()
+        .str()
+        .unwrap();
+    assert_eq!(
+        resources.into_no_null_iter().collect::<Vec<_>>(),
+        vec!["crew", "crane"]
+    );
+
+    assert_eq!(frames.tasks.height(), 3);
+    assert!(frames.tasks.column("predecessors").is_err());
+    assert!(frames.tasks.column("resource_allocations").is_err());
+    assert!(frames.tasks.column("early_finish").is_ok());
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "http_api")]
+
+use schedule_tool::persistence::PersistenceResult;
+use schedule_tool::{AsyncScheduleStore, BlockingStore, Schedule, ScheduleStore, Task};
+use std::sync::Mutex;
+
+/// Keeps the last saved schedule in memory.
+#[derive(Default)]
+struct MemoryStore {
+    saved: Mutex<Option<Schedule>>,
+}
+
+impl ScheduleStore for MemoryStore {
+    fn save_schedule(&self, schedule: &Schedule) -> PersistenceResult<()> {
+        *self.saved.lock().unwrap() = Some(schedule.fork());
+        Ok(())
+    }
+
+    fn load_schedule(&self) -> PersistenceResult<Option<Schedule>> {
+        Ok(self.saved.lock().unwrap().as_ref().map(Schedule::fork))
+    }
+}
+
+/// The same store written directly against the async trait.
+#[derive(Default)]
+struct AsyncMemoryStore {
+    saved: Mutex<Option<Schedule>>,
+}
+
+impl AsyncScheduleStore for AsyncMemoryStore {
+    async fn save_schedule(&self, schedule: &Schedule) -> PersistenceResult<()> {
+        *self.saved.lock().unwrap() = Some(schedule.fork());
+        Ok(())
+    }
+
+    async fn load_schedule(&self) -> PersistenceResult<Option<Schedule>> {
+        Ok(self.saved.lock().unwrap().as_ref().map(Schedule::fork))
+    }
+}
+
+fn sample_schedule() -> Schedule {
+    let mut schedule = Schedule::new();
+    schedule
+        .upsert_task_record(Task::new(1, "Design", 3))
+        .unwrap();
+    let mut build = Task::new(2, "Build", 5);
+    build.predecessors = vec![1];
+    schedule.upsert_task_record(build).unwrap();
+    schedule.refresh().unwrap();
+    schedule
+}
+
+async fn round_trip(store: &impl AsyncScheduleStore) {
+    assert!(store.load_schedule().await.unwrap().is_none());
+    let schedule = sample_schedule();
+    store.save_schedule(&schedule).await.unwrap();
+    let loaded = store.load_schedule().await.unwrap().unwrap();
+    assert!(loaded.semantically_equals(&schedule));
+}
+
+#[tokio::test]
+async fn async_store_round_trips_a_schedule() {
+    round_trip(&AsyncMemoryStore::default()).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn blocking_store_runs_a_sync_store_off_the_runtime() {
+    let store = BlockingStore::new(MemoryStore::default());
+    round_trip(&store).await;
+    assert!(store.inner().saved.lock().unwrap().is_some());
+}
@@ -1,5 +1,6 @@
 use chrono::NaiveDate;
-use schedule_tool::{Schedule, ScheduleMetadata};
+use schedule_tool::calculations::{self, CriticalPathOptions};
+use schedule_tool::{Schedule, ScheduleMetadata, SchedulingMode, Task};
 
 fn d(y: i32, m: u32, d: u32) -> NaiveDate {
     NaiveDate::from_ymd_opt(y, m, d).unwrap()
@@ -9,9 +10,11 @@ fn d(y: i32, m: u32, d: u32) -> NaiveDate {
 fn backward_pass_sets_late_dates_and_floats() {
     let mut s = Schedule::new();
     // Set project start and end to match forward finish
-    let mut md = ScheduleMetadata::default();
-    md.project_start_date = d(2025, 1, 6);
-    md.project_end_date = d(2025, 1, 17);
+    let md = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 1, 17),
+        ..ScheduleMetadata::default()
+    };
     s.set_metadata(md).unwrap();
 
     // Graph: 1 -> {2,3} -> 4 with durations 2,3,1,2
@@ -65,8 +68,119 @@ fn backward_pass_sets_late_dates_and_floats() {
         Some((td(d(2025, 1, 9)), td(d(2025, 1, 14)), 0, true))
     );
     // T3 has slack
-    assert_eq!(m.get(&3).map(|v| (v.2, v.3, v.4, v.5)).unwrap().3, false);
+    assert!(!m.get(&3).map(|v| (v.2, v.3, v.4, v.5)).unwrap().3);
     assert!(m.get(&3).unwrap().4 > 0);
     // T1 is critical
     assert_eq!(m.get(&1).unwrap().4, 0);
 }
+
+#[test]
+fn free_critical_path_matches_refresh_summary() {
+    let mut s = Schedule::new();
+    s.set_project_dates(d(2025, 1, 6), d(2025, 2, 6)).unwrap();
+    // Same diamond as above, plus a floating side branch and a tail.
+    s.upsert_task(1, "T1", 2, None).unwrap();
+    s.upsert_task(2, "T2", 3, Some(vec![1])).unwrap();
+    s.upsert_task(3, "T3", 1, Some(vec![1])).unwrap();
+    s.upsert_task(4, "T4", 2, Some(vec![2, 3])).unwrap();
+    s.upsert_task(5, "Side", 1, None).unwrap();
+    s.upsert_task(6, "Tail", 12, Some(vec![4])).unwrap();
+
+    let before = s.dataframe().clone();
+    let free = calculations::critical_path(
+        &before,
+        s.calendar(),
+        s.metadata().project_start_date,
+        s.metadata().project_end_date,
+    )
+    .unwrap();
+    // Read-only: the table is left exactly as it was.
+    assert!(s.dataframe().equals_missing(&before));
+
+    let summary = s.refresh().unwrap();
+    assert_eq!(free, summary.critical_path);
+    assert_eq!(free, vec![1, 2, 4, 6]);
+}
+
+#[test]
+fn free_critical_path_takes_the_schedule_options() {
+    let mut s = Schedule::new();
+    s.set_project_dates(d(2025, 1, 6), d(2025, 1, 21)).unwrap();
+    s.upsert_task(1, "Build", 4, None).unwrap();
+    let mut order = Task::new(2, "Order", 2);
+    order.actual_start = Some(d(2025, 1, 6));
+    order.percent_complete = Some(0.5);
+    s.upsert_task_record(order).unwrap();
+    s.upsert_task(3, "Install", 1, Some(vec![1, 2])).unwrap();
+    // The rest of Order is counted from the data date, which pushes Install
+    // out to the project end.
+    s.set_status_date(Some(d(2025, 1, 15)));
+    s.set_pin_actual_dates(true);
+    let summary = s.refresh().unwrap();
+    assert_eq!(summary.critical_path, vec![3]);
+
+    let free = calculations::critical_path_with_options(
+        s.dataframe(),
+        s.calendar(),
+        s.metadata().project_start_date,
+        s.metadata().project_end_date,
+        s.critical_path_options(),
+    )
+    .unwrap();
+    assert_eq!(free, summary.critical_path);
+    assert_eq!(
+        s.critical_path_options(),
+        CriticalPathOptions::default()
+            .with_data_date(Some(d(2025, 1, 15)))
+            .with_pinned_actuals(true)
+    );
+
+    // Without the data date the whole network floats.
+    let plain = calculations::critical_path(
+        s.dataframe(),
+        s.calendar(),
+        s.metadata().project_start_date,
+        s.metadata().project_end_date,
+    )
+    .unwrap();
+    assert!(plain.is_empty());
+}
+
+#[test]
+fn alap_mode_moves_floating_tasks_to_late_dates() {
+    let md = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 1, 17),
+        ..ScheduleMetadata::default()
+    };
+    let build = |mode| {
+        let mut s = Schedule::new_with_metadata(md.clone());
+        s.set_scheduling_mode(mode);
+        // 1 -> {2,3} -> 4; T3 is one day against T2's three.
+        s.upsert_task(1, "T1", 2, None).unwrap();
+        s.upsert_task(2, "T2", 3, Some(vec![1])).unwrap();
+        s.upsert_task(3, "T3", 1, Some(vec![1])).unwrap();
+        s.upsert_task(4, "T4", 2, Some(vec![2, 3])).unwrap();
+        let summary = s.refresh().unwrap();
+        (s, summary)
+    };
+    let (asap, asap_summary) = build(SchedulingMode::Asap);
+    let (alap, alap_summary) = build(SchedulingMode::Alap);
+    assert_eq!(alap.scheduling_mode(), SchedulingMode::Alap);
+
+    let asap_t3 = asap.find_task(3).unwrap().unwrap();
+    let alap_t3 = alap.find_task(3).unwrap().unwrap();
+    assert!(asap_t3.total_float.unwrap() > 0);
+    assert_eq!(alap_t3.early_start, asap_t3.late_start);
+    assert_eq!(alap_t3.early_finish, asap_t3.late_finish);
+    assert!(alap_t3.early_start > asap_t3.early_start);
+    assert_eq!(alap_t3.total_float, asap_t3.total_float);
+
+    for id in [1, 2, 4] {
+        let asap_task = asap.find_task(id).unwrap().unwrap();
+        let alap_task = alap.find_task(id).unwrap().unwrap();
+        assert_eq!(alap_task.early_start, asap_task.early_start);
+        assert_eq!(alap_task.early_finish, asap_task.early_finish);
+    }
+    assert_eq!(alap_summary.critical_path, asap_summary.critical_path);
+}
@@ -1,5 +1,7 @@
 use chrono::{Datelike, NaiveDate, Weekday};
-use schedule_tool::calendar::WorkCalendar;
+use schedule_tool::calendar::{
+    CalendarError, CalendarPreset, RecurringHoliday, WorkCalendar, WorkCalendarConfig,
+};
 
 #[test]
 fn default_calendar_weekends_unavailable() {
@@ -61,7 +63,8 @@ fn set_working_days_includes_saturday() {
         Weekday::Thu,
         Weekday::Fri,
         Weekday::Sat,
-    ]);
+    ])
+    .unwrap();
     let sat = NaiveDate::from_ymd_opt(2025, 1, 4).unwrap();
     assert!(cal.is_available(sat));
 }
@@ -119,3 +122,421 @@ fn custom_calendar_builds_non_standard_week_and_round_trips() {
     let recreated = WorkCalendar::from_config(&config);
     assert_eq!(recreated.to_config(), config);
 }
+
+#[test]
+fn working_days_in_month_excludes_weekends_and_holidays() {
+    let cal = WorkCalendar::default();
+    // January 2025 has 23 weekdays; New Year's Day and MLK Day are holidays.
+    assert_eq!(cal.working_days_in_month(2025, 1), Some(21));
+    // February 2025 has 20 weekdays and Presidents' Day.
+    assert_eq!(cal.working_days_in_month(2025, 2), Some(19));
+    assert_eq!(cal.working_days_in_month(2025, 13), None);
+
+    let first = NaiveDate::from_ymd_opt(2025, 12, 1).unwrap();
+    let last = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+    assert_eq!(
+        cal.working_days_in_month(2025, 12),
+        Some(cal.count_available_days(first, last))
+    );
+}
+
+#[test]
+fn working_days_in_year_matches_day_by_day_count() {
+    let cal = WorkCalendar::default();
+    // 261 weekdays in 2025, all ten federal holidays fall on weekdays.
+    assert_eq!(cal.working_days_in_year(2025), Some(251));
+
+    let six_day = WorkCalendar::custom(
+        [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+        ],
+        [NaiveDate::from_ymd_opt(2026, 3, 7).unwrap()],
+    );
+    let first = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+    let last = NaiveDate::from_ymd_opt(2026, 12, 31).unwrap();
+    assert_eq!(
+        six_day.working_days_in_year(2026),
+        Some(six_day.count_available_days(first, last))
+    );
+}
+
+#[test]
+fn nth_working_day_from_matches_find_next_available() {
+    let cal = WorkCalendar::with_year_range(2025, 2027);
+    let start = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+    for n in 0..=600 {
+        let fast = cal.nth_working_day_from(start, n);
+        assert_eq!(fast, cal.find_next_available(start, n), "n = {n}");
+        if n > 0 {
+            assert_eq!(
+                cal.count_available_days(start.succ_opt().unwrap(), fast),
+                n,
+                "n = {n}"
+            );
+        }
+    }
+    // Starting on a holiday weekend still lands on the same date.
+    let thanksgiving = NaiveDate::from_ymd_opt(2025, 11, 27).unwrap();
+    assert_eq!(
+        cal.nth_working_day_from(thanksgiving, 25),
+        cal.find_next_available(thanksgiving, 25)
+    );
+}
+
+#[test]
+fn removing_a_holiday_restores_availability() {
+    let mut cal = WorkCalendar::default();
+    let company_day = NaiveDate::from_ymd_opt(2025, 3, 14).unwrap(); // Friday
+    cal.add_holiday(company_day);
+    assert!(!cal.is_available(company_day));
+
+    assert!(cal.remove_holiday(company_day));
+    assert!(cal.is_available(company_day));
+    assert!(!cal.remove_holiday(company_day));
+
+    // Removing a weekend date that was never a holiday leaves it unavailable.
+    let saturday = NaiveDate::from_ymd_opt(2025, 3, 15).unwrap();
+    assert!(!cal.remove_holiday(saturday));
+    assert!(!cal.is_available(saturday));
+}
+
+#[test]
+fn holidays_enumerates_and_clear_holidays_empties_the_set() {
+    let mut cal = WorkCalendar::default();
+    let new_year = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+    let mut holidays: Vec<NaiveDate> = cal.holidays().copied().collect();
+    holidays.sort();
+    assert_eq!(holidays.len(), 10);
+    assert_eq!(holidays.first(), Some(&new_year));
+    assert_eq!(holidays, cal.to_config().holidays());
+
+    cal.clear_holidays();
+    assert_eq!(cal.holidays().count(), 0);
+    assert!(cal.is_available(new_year));
+}
+
+#[test]
+fn fixed_recurring_rule_survives_config_round_trip() {
+    let christmas_eve = RecurringHoliday::Fixed { month: 12, day: 24 };
+    let config = WorkCalendarConfig::new(
+        [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ],
+        [NaiveDate::from_ymd_opt(2025, 7, 4).unwrap()],
+    )
+    .with_recurring_rules([christmas_eve]);
+
+    let json = serde_json::to_string(&config).unwrap();
+    let restored: WorkCalendarConfig = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, config);
+
+    let calendar = WorkCalendar::from_config(&restored);
+    assert_eq!(calendar.to_config(), config);
+    // The rule applies in every year, not just the years of listed holidays:
+    // 2025-12-24 and 2031-12-24 are both Wednesdays.
+    assert!(!calendar.is_available(NaiveDate::from_ymd_opt(2025, 12, 24).unwrap()));
+    assert!(!calendar.is_available(NaiveDate::from_ymd_opt(2031, 12, 24).unwrap()));
+    assert!(calendar.is_available(NaiveDate::from_ymd_opt(2031, 12, 23).unwrap()));
+}
+
+#[test]
+fn config_without_rules_keeps_legacy_json_shape() {
+    let config = WorkCalendarConfig::new([Weekday::Mon], []);
+    let json = serde_json::to_value(&config).unwrap();
+    assert!(json.get("recurring_rules").is_none());
+    let legacy = serde_json::json!({ "working_days": ["Mon"], "holidays": [] });
+    let parsed: WorkCalendarConfig = serde_json::from_value(legacy).unwrap();
+    assert_eq!(parsed, config);
+}
+
+#[test]
+fn nth_weekday_rule_counts_consistently() {
+    let mut calendar = WorkCalendar::custom(
+        [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ],
+        [],
+    );
+    let black_friday_eve = RecurringHoliday::NthWeekday {
+        month: 11,
+        weekday: Weekday::Thu,
+        n: 4,
+    };
+    calendar.add_recurring_rule(black_friday_eve);
+    calendar.add_recurring_rule(black_friday_eve);
+    assert_eq!(calendar.recurring_rules(), [black_friday_eve]);
+
+    assert_eq!(
+        black_friday_eve.date_in(2027),
+        NaiveDate::from_ymd_opt(2027, 11, 25)
+    );
+    assert_eq!(
+        RecurringHoliday::NthWeekday {
+            month: 2,
+            weekday: Weekday::Mon,
+            n: 5
+        }
+        .date_in(2027),
+        None
+    );
+
+    let first = NaiveDate::from_ymd_opt(2027, 11, 1).unwrap();
+    let last = NaiveDate::from_ymd_opt(2027, 11, 30).unwrap();
+    assert_eq!(
+        calendar.working_days_in_month(2027, 11),
+        Some(calendar.count_available_days(first, last))
+    );
+    assert_eq!(calendar.working_days_in_month(2027, 11), Some(21));
+    let start = NaiveDate::from_ymd_opt(2027, 1, 1).unwrap();
+    assert_eq!(
+        calendar.nth_working_day_from(start, 300),
+        calendar.find_next_available(start, 300)
+    );
+}
+
+#[test]
+fn set_working_days_rejects_empty_and_ignores_duplicates() {
+    let mut cal = WorkCalendar::default();
+    assert_eq!(
+        cal.set_working_days(vec![]),
+        Err(CalendarError::NoWorkingDays)
+    );
+    // The rejected call leaves the weekday pattern alone.
+    let mon = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+    assert!(cal.is_available(mon));
+
+    cal.set_working_days(vec![Weekday::Tue, Weekday::Tue])
+        .unwrap();
+    assert!(!cal.is_available(mon));
+    assert_eq!(cal.to_config().working_days(), &[Weekday::Tue]);
+}
+
+#[test]
+fn try_from_config_rejects_deserialized_config_without_working_days() {
+    let config: WorkCalendarConfig =
+        serde_json::from_str(r#"{"working_days":[],"holidays":[]}"#).unwrap();
+    assert_eq!(
+        WorkCalendar::try_from_config(&config),
+        Err(CalendarError::NoWorkingDays)
+    );
+
+    let mut schedule = schedule_tool::Schedule::new();
+    assert!(schedule.set_calendar_from_config(&config).is_err());
+}
+
+#[test]
+fn availability_search_gives_up_instead_of_hanging() {
+    let all_days =
+        r#"{"holidays":[],"non_working_days":["Mon","Tue","Wed","Thu","Fri","Sat","Sun"]}"#;
+    let cal: WorkCalendar = serde_json::from_str(all_days).unwrap();
+    let from = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+
+    let expected = Err(CalendarError::NoAvailableDay { from });
+    assert_eq!(cal.try_next_available(from), expected);
+    assert_eq!(cal.try_find_next_available(from, 3), expected);
+    assert_eq!(cal.try_prev_available(from), expected);
+    assert_eq!(cal.try_find_prev_available(from, 0), Ok(from));
+}
+
+fn week_of_march_3() -> Vec<NaiveDate> {
+    (3..=9)
+        .map(|day| NaiveDate::from_ymd_opt(2025, 3, day).unwrap())
+        .collect()
+}
+
+fn weekday_calendars() -> (WorkCalendar, WorkCalendar) {
+    let wed = NaiveDate::from_ymd_opt(2025, 3, 5).unwrap();
+    let thu = NaiveDate::from_ymd_opt(2025, 3, 6).unwrap();
+    let shared = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+    let mon_fri = WorkCalendar::custom(
+        [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ],
+        [wed, shared],
+    );
+    let tue_sat = WorkCalendar::custom(
+        [
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+        ],
+        [thu, shared],
+    );
+    (mon_fri, tue_sat)
+}
+
+#[test]
+fn intersect_keeps_days_both_calendars_work() {
+    let (mon_fri, tue_sat) = weekday_calendars();
+    let both = mon_fri.intersect(&tue_sat);
+
+    let available: Vec<Weekday> = week_of_march_3()
+        .into_iter()
+        .filter(|date| both.is_available(*date))
+        .map(|date| date.weekday())
+        .collect();
+    // Tue–Fri, minus Wednesday (holiday in the first) and Thursday (second).
+    assert_eq!(available, vec![Weekday::Tue, Weekday::Fri]);
+    assert_eq!(
+        both.to_config().working_days(),
+        &[Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri]
+    );
+}
+
+#[test]
+fn union_keeps_days_either_calendar_works() {
+    let (mon_fri, tue_sat) = weekday_calendars();
+    let either = mon_fri.union(&tue_sat);
+
+    // Each one-sided holiday is covered by the other calendar.
+    let available: Vec<Weekday> = week_of_march_3()
+        .into_iter()
+        .filter(|date| either.is_available(*date))
+        .map(|date| date.weekday())
+        .collect();
+    assert_eq!(
+        available,
+        vec![
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+        ]
+    );
+    let shared = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+    assert!(!either.is_available(shared));
+    assert_eq!(either.holidays().collect::<Vec<_>>(), vec![&shared]);
+}
+
+#[test]
+fn intersect_and_union_combine_recurring_rules() {
+    let christmas_eve = RecurringHoliday::Fixed { month: 12, day: 24 };
+    let boxing_day = RecurringHoliday::Fixed { month: 12, day: 26 };
+    let mut first = WorkCalendar::custom([Weekday::Mon, Weekday::Tue, Weekday::Wed], []);
+    first.add_recurring_rule(christmas_eve);
+    first.add_recurring_rule(boxing_day);
+    let mut second = WorkCalendar::custom([Weekday::Mon, Weekday::Tue, Weekday::Wed], []);
+    second.add_recurring_rule(christmas_eve);
+
+    assert_eq!(
+        first.intersect(&second).recurring_rules(),
+        &[christmas_eve, boxing_day]
+    );
+    assert_eq!(first.union(&second).recurring_rules(), &[christmas_eve]);
+}
+
+#[cfg(feature = "ics")]
+#[test]
+fn add_holidays_from_ics_reads_event_start_dates() {
+    let ics = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART;VALUE=DATE:20250303\r\n\
+SUMMARY:Company\r\n  offsite\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART;TZID=America/New_York:20250305T090000\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+    let mut cal = WorkCalendar::custom(
+        [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ],
+        [],
+    );
+
+    assert_eq!(cal.add_holidays_from_ics(ics.as_bytes()), Ok(2));
+    assert!(!cal.is_available(NaiveDate::from_ymd_opt(2025, 3, 3).unwrap()));
+    assert!(!cal.is_available(NaiveDate::from_ymd_opt(2025, 3, 5).unwrap()));
+    assert!(cal.is_available(NaiveDate::from_ymd_opt(2025, 3, 4).unwrap()));
+    // Already known dates are not counted again.
+    assert_eq!(cal.add_holidays_from_ics(ics.as_bytes()), Ok(0));
+
+    let broken = "BEGIN:VEVENT\nDTSTART:2025-03-07\nEND:VEVENT\n";
+    assert_eq!(
+        cal.add_holidays_from_ics(broken.as_bytes()),
+        Err(CalendarError::InvalidIcs {
+            line: 2,
+            message: "unreadable DTSTART '2025-03-07'".to_string(),
+        })
+    );
+}
+
+#[test]
+fn six_day_preset_works_saturday_but_not_sunday() {
+    let cal = WorkCalendar::preset(CalendarPreset::SixDay, 2025, 2025);
+    // 2025-01-04 is a Saturday, 2025-01-05 is a Sunday
+    assert!(cal.is_available(NaiveDate::from_ymd_opt(2025, 1, 4).unwrap()));
+    assert!(!cal.is_available(NaiveDate::from_ymd_opt(2025, 1, 5).unwrap()));
+    assert!(!cal.is_available(NaiveDate::from_ymd_opt(2025, 12, 25).unwrap()));
+    assert_eq!(cal.preset_origin(), Some(CalendarPreset::SixDay));
+
+    let seven = WorkCalendar::preset(CalendarPreset::SevenDay, 2025, 2025);
+    assert!(seven.is_available(NaiveDate::from_ymd_opt(2025, 12, 25).unwrap()));
+}
+
+#[test]
+fn preset_origin_survives_customization_and_config_round_trip() {
+    let mut cal = WorkCalendar::preset(CalendarPreset::FourDay, 2025, 2025);
+    let extra = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+    cal.add_holiday(extra);
+
+    let json = serde_json::to_string(&cal.to_config()).unwrap();
+    assert!(json.contains("\"preset\":\"four_day\""));
+    let config: WorkCalendarConfig = serde_json::from_str(&json).unwrap();
+    let restored = WorkCalendar::from_config(&config);
+
+    assert_eq!(restored.preset_origin(), Some(CalendarPreset::FourDay));
+    assert!(!restored.is_available(extra));
+    assert!(!restored.is_available(NaiveDate::from_ymd_opt(2025, 3, 7).unwrap()));
+    assert_eq!(WorkCalendar::default().to_config().preset_origin(), None);
+}
+
+#[test]
+fn named_holiday_label_survives_config_round_trip() {
+    let shutdown = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+    let mut cal = WorkCalendar::default();
+    assert!(cal.is_available(shutdown));
+    cal.add_named_holiday(shutdown, "Company Shutdown");
+    assert!(!cal.is_available(shutdown));
+    assert_eq!(cal.holiday_label(shutdown), Some("Company Shutdown"));
+    // Plain holidays have no label.
+    let july_4 = NaiveDate::from_ymd_opt(2025, 7, 4).unwrap();
+    assert_eq!(cal.holiday_label(july_4), None);
+
+    let json = serde_json::to_string(&cal.to_config()).unwrap();
+    let restored: WorkCalendarConfig = serde_json::from_str(&json).unwrap();
+    let restored = WorkCalendar::from_config(&restored);
+    assert_eq!(restored.holiday_label(shutdown), Some("Company Shutdown"));
+    assert!(!restored.is_available(shutdown));
+    assert_eq!(restored, cal);
+
+    let mut cleared = restored.clone();
+    assert!(cleared.remove_holiday(shutdown));
+    assert_eq!(cleared.holiday_label(shutdown), None);
+}
@@ -10,6 +10,12 @@ fn run_cli(script: &str) -> assert_cmd::assert::Assert {
     cmd.write_stdin(script.to_string()).assert()
 }
 
+#[allow(deprecated)]
+fn run_cli_with_args(args: &[&str], script: &str) -> assert_cmd::assert::Assert {
+    let mut cmd = Command::cargo_bin("cli").expect("cli binary");
+    cmd.args(args).write_stdin(script.to_string()).assert()
+}
+
 #[test]
 fn cli_reports_metadata_validation_errors() {
     run_cli("meta dates 2025-01-10 2025-01-05\nquit\n")
@@ -64,3 +70,211 @@ fn cli_applies_rationale_template() {
         ))
         .stdout(str_contains("pre_defined_rationale"));
 }
+
+#[test]
+fn cli_reports_critical_path_and_float() {
+    run_cli(
+        "meta dates 2025-01-06 2025-01-17\nadd 1 A 5\nadd 2 B 3 1\nadd 3 C 2 1\ncompute\ncritical\nfloat 3\nquit\n",
+    )
+    .success()
+    .stdout(str_contains("Critical path: 1 -> 2"))
+    .stdout(str_contains("Task 3 (C)"))
+    .stdout(str_contains("total_float: 1"))
+    .stdout(str_contains("free_float:  1"))
+    .stdout(str_contains("late_finish: 2025-01-17"));
+}
+
+#[test]
+fn cli_float_command_validates_arguments() {
+    run_cli("float\nfloat abc\nfloat 42\nquit\n")
+        .success()
+        .stdout(str_contains("Usage: float <id>"))
+        .stdout(str_contains("Invalid id"))
+        .stdout(str_contains("Task 42 not found."));
+}
+
+#[test]
+fn cli_rejects_unknown_persistence_format() {
+    run_cli("save xml out.xml\nload mspdi in.xml\nquit\n")
+        .success()
+        .stdout(str_contains("Usage: save <json|csv|parquet|mspdi> <path>"))
+        .stdout(str_contains("mspdi is export-only"));
+}
+
+#[test]
+fn cli_save_mspdi_exports_xml() {
+    let tmp = NamedTempFile::new().expect("create temp file");
+    let path = tmp.path().to_string_lossy().replace('\\', "\\\\");
+    run_cli(&format!("add 1 TaskXml 2\nsave mspdi {}\nquit\n", path))
+        .success()
+        .stdout(str_contains("Schedule exported to"));
+    let xml = std::fs::read_to_string(tmp.path()).expect("read export");
+    assert!(xml.contains("<Name>TaskXml</Name>"));
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn cli_parquet_token_selects_parquet_backend() {
+    let tmp = NamedTempFile::new().expect("create temp file");
+    let path = tmp.path().to_string_lossy().replace('\\', "\\\\");
+    let script = format!(
+        "add 1 TaskParquet 4\nsave parquet {}\nadd 2 Temp 1\nload parquet {}\nquit\n",
+        path, path
+    );
+    let assert = run_cli(&script).success();
+    let output = String::from_utf8_lossy(&assert.get_output().stdout);
+    let after_reload = output
+        .split("Schedule loaded from")
+        .nth(1)
+        .expect("parquet load should succeed");
+    assert!(after_reload.contains("TaskParquet"));
+    assert!(!after_reload.contains("Temp"));
+    // The written file must be Parquet, not one of the other formats.
+    let bytes = std::fs::read(tmp.path()).expect("read parquet file");
+    assert_eq!(&bytes[..4], b"PAR1");
+}
+
+#[test]
+fn cli_resource_assign_stores_allocation() {
+    let tmp = NamedTempFile::new().expect("create temp file");
+    let path = tmp.path().to_string_lossy().replace('\\', "\\\\");
+    let script = format!(
+        "add 1 TaskA 3\nresource assign 1 crane 2 150 2\nresource show 1\nsave json {}\nquit\n",
+        path
+    );
+    run_cli(&script)
+        .success()
+        .stdout(str_contains("Assigned resource 'crane' to task 1."))
+        .stdout(str_contains("crane  units=2  cost_rate=150"));
+
+    let loaded = schedule_tool::load_schedule_from_json(tmp.path()).expect("load saved schedule");
+    let task = loaded.find_task(1).unwrap().expect("task 1");
+    assert_eq!(task.resource_allocations.len(), 1);
+    assert_eq!(task.resource_allocations[0].resource_id, "crane");
+    assert_eq!(task.resource_allocations[0].allocation_units, 2.0);
+    assert_eq!(task.resource_allocations[0].cost_rate, Some(150.0));
+    assert_eq!(task.resource_allocations[0].max_units, Some(2.0));
+}
+
+#[test]
+fn cli_resource_commands_report_invalid_input() {
+    run_cli(
+        "add 1 TaskA 3\nresource assign 1 crane -4\nresource assign 1 crane x\nresource unassign 1 crane\nresource show 1\nquit\n",
+    )
+    .success()
+    .stdout(str_contains("Error assigning resource:"))
+    .stdout(str_contains("invalid allocation_units -4"))
+    .stdout(str_contains("Invalid units"))
+    .stdout(str_contains("Task 1 has no resource 'crane'."))
+    .stdout(str_contains("Task 1 has no resources assigned."));
+}
+
+#[test]
+fn cli_opens_workspace_given_on_startup() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let path = dir.path().join("session.json");
+    let path_s = path.to_string_lossy().to_string();
+
+    run_cli(&format!(
+        "add 1 Persisted 4\nworkspace save {}\nquit\n",
+        path_s
+    ))
+    .success()
+    .stdout(str_contains("Workspace saved to"));
+
+    run_cli_with_args(&[&path_s], "workspace\nworkspace save\nquit\n")
+        .success()
+        .stdout(str_contains("Workspace opened from"))
+        .stdout(str_contains("Persisted"))
+        .stdout(str_contains(format!("Workspace: {}", path_s)))
+        .stdout(str_contains(format!("Workspace saved to {}.", path_s)));
+}
+
+#[test]
+fn cli_startup_with_missing_workspace_starts_empty() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let path = dir.path().join("new.json");
+    let path_s = path.to_string_lossy().to_string();
+
+    run_cli_with_args(&[&path_s], "add 1 Fresh 2\nworkspace save\nquit\n")
+        .success()
+        .stdout(str_contains("does not exist yet"));
+    assert!(
+        path.exists(),
+        "workspace save should create the startup path"
+    );
+}
+
+#[test]
+fn cli_rejects_extra_startup_arguments() {
+    run_cli_with_args(&["a.json", "b.json"], "quit\n")
+        .failure()
+        .stderr(str_contains("Usage: cli [--json] [workspace.json]"));
+}
+
+#[test]
+fn cli_json_mode_show_parses_into_tasks() {
+    let assert =
+        run_cli_with_args(&["--json"], "add 1 Alpha 3\nadd 2 Beta 2 1\nshow\nquit\n").success();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).to_string();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines.len(),
+        1,
+        "stdout should hold only the JSON document:\n{stdout}"
+    );
+    let tasks: Vec<schedule_tool::Task> = serde_json::from_str(lines[0]).expect("valid tasks json");
+    let names: Vec<&str> = tasks.iter().map(|task| task.name.as_str()).collect();
+    assert_eq!(names, ["Alpha", "Beta"]);
+    assert_eq!(tasks[1].predecessors, vec![1]);
+}
+
+#[test]
+fn cli_show_accepts_sort_order() {
+    let assert = run_cli_with_args(
+        &["--json"],
+        "add 2 Beta 2\nadd 1 Alpha 3 2\nshow id\nshow topo\nshow sideways\nquit\n",
+    )
+    .success();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).to_string();
+    let orders: Vec<Vec<i32>> = stdout
+        .lines()
+        .map(|line| {
+            let tasks: Vec<schedule_tool::Task> = serde_json::from_str(line).unwrap();
+            tasks.iter().map(|task| task.id).collect()
+        })
+        .collect();
+    assert_eq!(orders, vec![vec![1, 2], vec![2, 1]]);
+    assert.stderr(str_contains("Unknown order 'sideways'"));
+}
+
+#[test]
+fn cli_calendar_preset_applies_named_calendar() {
+    run_cli("calendar preset six_day\ncalendar preset lunar\nquit\n")
+        .success()
+        .stdout(str_contains("Calendar set to preset six_day."))
+        .stdout(str_contains("Preset             : six_day"))
+        .stdout(str_contains("Sat"))
+        .stdout(str_contains(
+            "Usage: calendar preset <five_day_us|six_day|four_day|seven_day>",
+        ));
+}
+
+#[test]
+fn cli_format_command_switches_compute_and_meta_to_json() {
+    let assert = run_cli("add 1 Alpha 3\nformat json\ncompute\nmeta show\nquit\n").success();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).to_string();
+    // The text-mode prompt before `format json` shares a line with the first document.
+    let json_lines: Vec<&str> = stdout
+        .lines()
+        .map(|line| line.trim_start_matches("> "))
+        .filter(|line| line.starts_with('{'))
+        .collect();
+    assert_eq!(json_lines.len(), 2, "unexpected stdout:\n{stdout}");
+    let summary: schedule_tool::RefreshSummary =
+        serde_json::from_str(json_lines[0]).expect("refresh summary json");
+    assert_eq!(summary.task_count, 1);
+    let metadata: schedule_tool::ScheduleMetadata =
+        serde_json::from_str(json_lines[1]).expect("metadata json");
+    assert_eq!(metadata.project_name, "New Project");
+}
@@ -0,0 +1,119 @@
+use chrono::NaiveDate;
+use schedule_tool::calculations::cpm::{self, CpmCalendar, CpmError, CpmTask};
+use schedule_tool::{Schedule, ScheduleMetadata};
+
+fn d(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+/// Plain day numbers where every day is a working day.
+struct EveryDay;
+
+impl CpmCalendar for EveryDay {
+    type Date = i64;
+    type Error = ();
+
+    fn is_available(&self, _date: i64) -> bool {
+        true
+    }
+
+    fn next_available(&self, from: i64) -> Result<i64, ()> {
+        Ok(from + 1)
+    }
+
+    fn prev_available(&self, from: i64) -> Result<i64, ()> {
+        Ok(from - 1)
+    }
+
+    fn float_between(&self, early: i64, late: i64) -> i64 {
+        late - early
+    }
+}
+
+fn diamond<D>() -> Vec<CpmTask<D>> {
+    // 1 -> {2,3} -> 4 with durations 2,3,1,2
+    vec![
+        CpmTask::new(1, 2, vec![]),
+        CpmTask::new(2, 3, vec![1]),
+        CpmTask::new(3, 1, vec![1]),
+        CpmTask::new(4, 2, vec![2, 3]),
+    ]
+}
+
+#[test]
+fn cpm_matches_schedule_passes_on_diamond() {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 1, 17),
+        ..ScheduleMetadata::default()
+    };
+    let mut schedule = Schedule::new_with_metadata(metadata);
+    schedule.upsert_task(1, "T1", 2, None).unwrap();
+    schedule.upsert_task(2, "T2", 3, Some(vec![1])).unwrap();
+    schedule.upsert_task(3, "T3", 1, Some(vec![1])).unwrap();
+    schedule.upsert_task(4, "T4", 2, Some(vec![2, 3])).unwrap();
+    schedule.refresh().unwrap();
+
+    let result = cpm::compute(
+        &diamond(),
+        schedule.calendar(),
+        d(2025, 1, 6),
+        d(2025, 1, 17),
+    )
+    .unwrap();
+
+    assert_eq!(result.tasks.len(), 4);
+    for computed in &result.tasks {
+        let task = schedule.find_task(computed.id).unwrap().unwrap();
+        assert_eq!(task.early_start, Some(computed.early_start));
+        assert_eq!(task.early_finish, Some(computed.early_finish));
+        assert_eq!(task.late_start, Some(computed.late_start));
+        assert_eq!(task.late_finish, Some(computed.late_finish));
+        assert_eq!(task.total_float, Some(computed.total_float));
+        assert_eq!(task.is_critical, Some(computed.is_critical));
+    }
+    let t2 = &result.tasks[1];
+    assert_eq!(
+        (t2.late_start, t2.late_finish),
+        (d(2025, 1, 9), d(2025, 1, 14))
+    );
+}
+
+#[test]
+fn cpm_runs_on_plain_day_numbers() {
+    let result = cpm::compute(&diamond(), &EveryDay, 0, 9).unwrap();
+
+    let dates: Vec<(i32, i64, i64, i64, i64, i64)> = result
+        .tasks
+        .iter()
+        .map(|t| {
+            (
+                t.id,
+                t.early_start,
+                t.early_finish,
+                t.late_start,
+                t.late_finish,
+                t.total_float,
+            )
+        })
+        .collect();
+    assert_eq!(
+        dates,
+        vec![
+            (1, 0, 2, 0, 2, 0),
+            (2, 3, 6, 3, 6, 0),
+            (3, 3, 4, 5, 6, 2),
+            (4, 7, 9, 7, 9, 0),
+        ]
+    );
+    assert!(!result.tasks[2].is_critical);
+}
+
+#[test]
+fn cpm_reports_cycles() {
+    let tasks = vec![CpmTask::new(1, 1, vec![2]), CpmTask::new(2, 1, vec![1])];
+
+    let err = cpm::compute(&tasks, &EveryDay, 0, 9).unwrap_err();
+
+    assert_eq!(err, CpmError::Cycle);
+}
@@ -9,8 +9,10 @@ fn d(y: i32, m: u32, d: u32) -> NaiveDate {
 fn forward_pass_computes_early_dates_across_dag() {
     let mut s = Schedule::new();
     // Set project start to Monday 2025-01-06
-    let mut md = schedule_tool::ScheduleMetadata::default();
-    md.project_start_date = d(2025, 1, 6);
+    let md = schedule_tool::ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        ..schedule_tool::ScheduleMetadata::default()
+    };
     s.set_metadata(md).unwrap();
 
     // Graph:
@@ -63,3 +65,134 @@ fn forward_pass_computes_early_dates_across_dag() {
         Some((to_days(d(2025, 1, 15)), to_days(d(2025, 1, 17))))
     );
 }
+
+#[test]
+fn forced_early_start_delays_task_and_its_successors() {
+    let md = schedule_tool::ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        ..schedule_tool::ScheduleMetadata::default()
+    };
+    let mut s = Schedule::new_with_metadata(md);
+    s.upsert_task(1, "Design", 2, None).unwrap();
+    // Saturday 2025-01-11 rolls to Monday the 13th, later than the
+    // network-derived start of the 9th.
+    let mobilise = schedule_tool::Task::builder(2, "Mobilise", 2)
+        .predecessors(vec![1])
+        .forced_early_start(d(2025, 1, 11))
+        .build()
+        .unwrap();
+    s.upsert_task_record(mobilise).unwrap();
+    s.upsert_task(3, "Build", 1, Some(vec![2])).unwrap();
+    // A forced start before the network start changes nothing.
+    let early = schedule_tool::Task::builder(4, "Survey", 1)
+        .predecessors(vec![1])
+        .forced_early_start(d(2025, 1, 6))
+        .build()
+        .unwrap();
+    s.upsert_task_record(early).unwrap();
+
+    s.forward_pass().unwrap();
+
+    let task = |id| s.find_task(id).unwrap().unwrap();
+    assert_eq!(task(2).early_start, Some(d(2025, 1, 13)));
+    assert_eq!(task(2).early_finish, Some(d(2025, 1, 15)));
+    assert_eq!(task(3).early_start, Some(d(2025, 1, 16)));
+    assert_eq!(task(4).early_start, Some(d(2025, 1, 9)));
+}
+
+#[test]
+fn refresh_fails_instead_of_hanging_on_calendar_without_available_days() {
+    let mut s = Schedule::new();
+    s.upsert_task(1, "T1", 2, None).unwrap();
+    s.upsert_task(2, "T2", 1, Some(vec![1])).unwrap();
+    // Only reachable by deserializing a calendar directly; configs reject it.
+    let no_days: schedule_tool::WorkCalendar = serde_json::from_str(
+        r#"{"holidays":[],"non_working_days":["Mon","Tue","Wed","Thu","Fri","Sat","Sun"]}"#,
+    )
+    .unwrap();
+
+    let err = s.set_calendar(no_days).unwrap_err();
+    assert!(
+        matches!(&err, polars::prelude::PolarsError::ComputeError(msg) if msg.contains("no available day")),
+        "unexpected error: {err}"
+    );
+    assert!(s.refresh().is_err());
+}
+
+#[test]
+fn engine_covers_joins_and_branches_without_fallback() {
+    let mut s = Schedule::new();
+    // 1 -> {2, 3}; 3 -> {4, 5}; {2, 4, 5} -> 6; 7 stands alone.
+    s.upsert_task(1, "Start", 2, None).unwrap();
+    s.upsert_task(2, "Branch A", 3, Some(vec![1])).unwrap();
+    s.upsert_task(3, "Branch B", 1, Some(vec![1])).unwrap();
+    s.upsert_task(4, "B1", 2, Some(vec![3])).unwrap();
+    s.upsert_task(5, "B2", 4, Some(vec![3])).unwrap();
+    s.upsert_task(6, "Join", 1, Some(vec![2, 4, 5])).unwrap();
+    s.upsert_task(7, "Solo", 2, None).unwrap();
+
+    let summary = s.refresh().unwrap();
+
+    assert_eq!(summary.forward_pass_fallback_count, 0);
+    assert_eq!(s.current_summary().unwrap().forward_pass_fallback_count, 0);
+}
+
+#[test]
+fn in_progress_task_finishes_its_remaining_work_from_actual_start() {
+    let md = schedule_tool::ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        ..schedule_tool::ScheduleMetadata::default()
+    };
+    let mut s = Schedule::new_with_metadata(md);
+    s.upsert_task(1, "Design", 2, None).unwrap();
+    // Started on Tuesday the 7th, before Design finished, and half done.
+    let build = schedule_tool::Task::builder(2, "Build", 10)
+        .predecessors(vec![1])
+        .actual(d(2025, 1, 7), None)
+        .progress(schedule_tool::ProgressMeasurement::PercentComplete, 0.5)
+        .build()
+        .unwrap();
+    s.upsert_task_record(build).unwrap();
+    s.upsert_task(3, "Test", 1, Some(vec![2])).unwrap();
+    s.refresh().unwrap();
+
+    // Five remaining working days from the 7th, not ten from the 9th.
+    let build = s.find_task(2).unwrap().unwrap();
+    assert_eq!(build.early_start, Some(d(2025, 1, 7)));
+    assert_eq!(build.early_finish, Some(d(2025, 1, 14)));
+    let test = s.find_task(3).unwrap().unwrap();
+    assert_eq!(test.early_start, Some(d(2025, 1, 15)));
+
+    // With a data date the remaining work cannot start before it.
+    s.set_status_date(Some(d(2025, 1, 13)));
+    s.refresh().unwrap();
+    let build = s.find_task(2).unwrap().unwrap();
+    assert_eq!(build.early_start, Some(d(2025, 1, 7)));
+    // Five working days from the 13th, skipping MLK Day on the 20th.
+    assert_eq!(build.early_finish, Some(d(2025, 1, 21)));
+}
+
+#[test]
+fn derived_rationale_percent_drives_the_forecast_in_the_same_refresh() {
+    let md = schedule_tool::ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        ..schedule_tool::ScheduleMetadata::default()
+    };
+    let mut s = Schedule::new_with_metadata(md);
+    let mut fit_out = schedule_tool::Task::new(1, "Fit-out", 10);
+    fit_out.actual_start = Some(d(2025, 1, 7));
+    fit_out.progress_measurement = schedule_tool::ProgressMeasurement::PreDefinedRationale;
+    fit_out.pre_defined_rationale = vec![
+        schedule_tool::RationaleItem::new(1, "Framing", 0.5, true),
+        schedule_tool::RationaleItem::new(2, "Finishes", 0.5, false),
+    ];
+    s.upsert_task_record(fit_out).unwrap();
+    s.set_derive_rationale_percent(true);
+    s.refresh().unwrap();
+
+    // Half done per the rationale: five remaining days from the 7th.
+    let fit_out = s.find_task(1).unwrap().unwrap();
+    assert_eq!(fit_out.percent_complete, Some(0.5));
+    assert_eq!(fit_out.early_start, Some(d(2025, 1, 7)));
+    assert_eq!(fit_out.early_finish, Some(d(2025, 1, 14)));
+}
@@ -1,5 +1,7 @@
 use polars::prelude::*;
+use schedule_tool::graph::dependency_tree::{Branch, ExecutionNode, JoinPoint};
 use schedule_tool::graph::schedule_dag::ScheduleDag;
+use schedule_tool::{Schedule, SortOrder};
 
 fn df_with_preds(ids: &[i32], preds: &[Vec<i32>]) -> DataFrame {
     let id_series = Series::new("id".into(), ids.to_vec());
@@ -20,10 +22,191 @@ fn df_with_preds(ids: &[i32], preds: &[Vec<i32>]) -> DataFrame {
 #[test]
 fn schedule_dag_builds_edges_from_predecessors() {
     // 1 -> {2, 3}
-    let df = df_with_preds(&[1, 2, 3], &vec![vec![], vec![1], vec![1]]);
+    let df = df_with_preds(&[1, 2, 3], &[vec![], vec![1], vec![1]]);
     let dag = ScheduleDag::build(&df).unwrap();
 
     // Expect 3 nodes and 2 edges
     assert_eq!(dag.graph.node_count(), 3);
     assert_eq!(dag.graph.edge_count(), 2);
 }
+
+fn schedule_with_preds(links: &[(i32, Vec<i32>)]) -> Schedule {
+    let mut schedule = Schedule::new();
+    for (id, preds) in links {
+        let mut task = schedule_tool::Task::new(*id, format!("T{id}"), 1);
+        task.predecessors = preds.clone();
+        schedule.upsert_task_record(task).unwrap();
+    }
+    schedule
+}
+
+#[test]
+fn dependency_tree_groups_diamond_middle_tasks_as_parallel_branches() {
+    // 1 -> {2, 3} -> 4
+    let schedule = schedule_with_preds(&[(1, vec![]), (2, vec![1]), (3, vec![1]), (4, vec![2, 3])]);
+    let tree = schedule.dependency_tree().unwrap();
+
+    assert_eq!(
+        tree.nodes,
+        vec![
+            ExecutionNode::Task { task_id: 1 },
+            ExecutionNode::ParallelBranches {
+                branches: vec![Branch { tasks: vec![2] }, Branch { tasks: vec![3] }],
+            },
+            ExecutionNode::Task { task_id: 4 },
+        ]
+    );
+    assert_eq!(
+        tree.join_points,
+        vec![JoinPoint {
+            task_id: 4,
+            predecessors: vec![2, 3],
+        }]
+    );
+    assert_eq!(tree.execution_order(), vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn dependency_tree_keeps_one_to_one_chains_in_a_single_branch() {
+    // 1 -> 2 -> 3 -> 5 and 1 -> 4 -> 5
+    let schedule = schedule_with_preds(&[
+        (1, vec![]),
+        (2, vec![1]),
+        (3, vec![2]),
+        (4, vec![1]),
+        (5, vec![3, 4]),
+    ]);
+    let tree = schedule.dependency_tree().unwrap();
+
+    assert_eq!(
+        tree.nodes[1],
+        ExecutionNode::ParallelBranches {
+            branches: vec![Branch { tasks: vec![2, 3] }, Branch { tasks: vec![4] }],
+        }
+    );
+}
+
+#[test]
+fn concurrent_tasks_excludes_ancestors_and_descendants() {
+    // 1 -> {2, 3} -> 4, with 5 unlinked
+    let schedule = schedule_with_preds(&[
+        (1, vec![]),
+        (2, vec![1]),
+        (3, vec![1]),
+        (4, vec![2, 3]),
+        (5, vec![]),
+    ]);
+
+    assert_eq!(schedule.concurrent_tasks(2).unwrap(), vec![3, 5]);
+    assert_eq!(schedule.concurrent_tasks(1).unwrap(), vec![5]);
+    assert!(schedule.concurrent_tasks(99).is_err());
+}
+
+#[test]
+fn graph_queries_walk_direct_and_transitive_links() {
+    // 1 -> {2, 3} -> 4
+    let schedule = schedule_with_preds(&[(1, vec![]), (2, vec![1]), (3, vec![1]), (4, vec![2, 3])]);
+
+    assert_eq!(schedule.all_downstream(1).unwrap(), vec![2, 3, 4]);
+    assert_eq!(schedule.all_upstream(4).unwrap(), vec![1, 2, 3]);
+    assert_eq!(schedule.successors_of(1).unwrap(), vec![2, 3]);
+    assert_eq!(schedule.predecessors_of(4).unwrap(), vec![2, 3]);
+    assert!(schedule.all_downstream(4).unwrap().is_empty());
+    assert!(schedule.predecessors_of(99).is_err());
+}
+
+#[test]
+fn sorted_tasks_topological_places_predecessors_first() {
+    // Inserted out of order: 5 -> 3 -> {1, 4}, 2 -> 4
+    let schedule = schedule_with_preds(&[
+        (4, vec![3, 2]),
+        (1, vec![3]),
+        (3, vec![5]),
+        (2, vec![]),
+        (5, vec![]),
+    ]);
+    let sorted = schedule
+        .sorted_tasks(SortOrder::TopologicalDependency)
+        .unwrap();
+    let position = |id: i32| sorted.iter().position(|task| task.id == id).unwrap();
+
+    assert_eq!(sorted.len(), 5);
+    for task in &sorted {
+        for pred in &task.predecessors {
+            assert!(
+                position(*pred) < position(task.id),
+                "{pred} after {}",
+                task.id
+            );
+        }
+    }
+    // Presentation only: the stored order is unchanged.
+    let stored: Vec<i32> = schedule.tasks().unwrap().iter().map(|t| t.id).collect();
+    assert_eq!(stored, vec![4, 1, 3, 2, 5]);
+}
+
+#[test]
+fn sorted_tasks_orders_wbs_segments_numerically() {
+    let mut schedule = Schedule::new();
+    for (id, wbs) in [
+        (1, Some("1.10")),
+        (2, None),
+        (3, Some("1.2")),
+        (4, Some("1")),
+    ] {
+        let mut task = schedule_tool::Task::new(id, format!("T{id}"), 1);
+        task.wbs_code = wbs.map(str::to_string);
+        schedule.upsert_task_record(task).unwrap();
+    }
+
+    let ids = |order| -> Vec<i32> {
+        schedule
+            .sorted_tasks(order)
+            .unwrap()
+            .iter()
+            .map(|task| task.id)
+            .collect()
+    };
+    assert_eq!(ids(SortOrder::Wbs), vec![4, 3, 1, 2]);
+    assert_eq!(ids(SortOrder::Id), vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn longest_path_matches_critical_path_until_a_constraint_is_added() {
+    let d = |day| chrono::NaiveDate::from_ymd_opt(2025, 1, day).unwrap();
+    let sample = |project_end| {
+        let mut schedule = Schedule::new();
+        schedule
+            .set_metadata(schedule_tool::ScheduleMetadata {
+                project_start_date: d(6),
+                project_end_date: project_end,
+                ..schedule_tool::ScheduleMetadata::default()
+            })
+            .unwrap();
+        // 1(2d) -> {2(3d), 3(1d)} -> 4(2d)
+        schedule.upsert_task(1, "T1", 2, None).unwrap();
+        schedule.upsert_task(2, "T2", 3, Some(vec![1])).unwrap();
+        schedule.upsert_task(3, "T3", 1, Some(vec![1])).unwrap();
+        schedule.upsert_task(4, "T4", 2, Some(vec![2, 3])).unwrap();
+        schedule
+    };
+
+    let mut schedule = sample(d(17));
+    schedule.refresh().unwrap();
+    assert_eq!(schedule.longest_path().unwrap(), vec![1, 2, 4]);
+    assert_eq!(schedule.critical_path().unwrap(), vec![1, 2, 4]);
+
+    // Holding T3 back makes it drive T4, but the durations are unchanged.
+    let mut constrained = sample(d(21));
+    let mut t3 = constrained.find_task(3).unwrap().unwrap();
+    t3.forced_early_start = Some(d(14));
+    constrained.upsert_task_record(t3).unwrap();
+    constrained.refresh().unwrap();
+    let critical = constrained.critical_path().unwrap();
+    assert!(
+        critical.contains(&3),
+        "unexpected critical path {critical:?}"
+    );
+    assert_eq!(constrained.longest_path().unwrap(), vec![1, 2, 4]);
+    assert_ne!(constrained.longest_path().unwrap(), critical);
+}
@@ -4,7 +4,7 @@ use axum::{
     body::{self, Body},
     http::{Request, StatusCode},
 };
-use schedule_tool::{ProgressMeasurement, Schedule, Task, http_api};
+use schedule_tool::{ProgressMeasurement, ResourceAllocation, Schedule, Task, http_api};
 use serde_json::json;
 use tower::util::ServiceExt;
 
@@ -131,6 +131,62 @@ async fn apply_rationale_template_via_http_api() {
     assert_eq!(updated.pre_defined_rationale.len(), 2);
 }
 
+#[tokio::test]
+async fn update_task_accepts_custom_fields() {
+    let app = new_router();
+    let mut task = Task::new(1, "HTTP Demo", 5);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/tasks")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_vec(&task).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    task.custom_fields.insert("phase".into(), "Build".into());
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri("/tasks/1")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_vec(&task).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let updated: Task = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(updated.custom_fields["phase"], "Build");
+
+    task.revision = updated.revision;
+    task.custom_fields.insert("bad\u{7}key".into(), "x".into());
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri("/tasks/1")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_vec(&task).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
 #[tokio::test]
 async fn invalid_progress_payload_returns_bad_request() {
     let app = new_router();
@@ -163,3 +219,737 @@ async fn invalid_progress_payload_returns_bad_request() {
             .contains("progress_measurement=0_100")
     );
 }
+
+#[tokio::test]
+async fn analytics_endpoint_reports_read_only_summary() {
+    let mut schedule = Schedule::new();
+    let mut survey = Task::new(1, "Survey", 3);
+    let mut crane = ResourceAllocation::new("crane", 1.0);
+    crane.cost_rate = Some(100.0);
+    survey.resource_allocations = vec![crane.clone()];
+    schedule.upsert_task_record(survey).unwrap();
+    let mut lift = Task::new(2, "Lift", 2);
+    lift.resource_allocations = vec![crane];
+    schedule.upsert_task_record(lift).unwrap();
+    let mut close = Task::new(3, "Close", 1);
+    close.predecessors = vec![1, 2];
+    schedule.upsert_task_record(close).unwrap();
+    schedule.refresh().unwrap();
+    let expected_edges = schedule.critical_path_edges().unwrap();
+    let before = schedule.tasks().unwrap();
+
+    let state = http_api::AppState::new(schedule);
+    let app = http_api::router(state);
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/analytics")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let payload: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+    assert_eq!(payload["task_count"], 3);
+    assert_eq!(payload["total_cost"], 500.0);
+    assert_eq!(
+        payload["critical_path_edges"],
+        serde_json::to_value(&expected_edges).unwrap()
+    );
+    assert!(payload["near_critical_task_ids"].is_array());
+    let overallocations = payload["overallocations"].as_array().unwrap();
+    assert!(!overallocations.is_empty());
+    assert_eq!(overallocations[0]["resource_id"], "crane");
+    assert_eq!(overallocations[0]["task_ids"], json!([1, 2]));
+
+    // Polling analytics must not change the schedule.
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/tasks")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let bytes = body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let after: Vec<Task> = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(after, before);
+}
+
+async fn put_task(
+    app: &axum::Router,
+    task: &Task,
+    if_match: Option<&str>,
+) -> axum::response::Response {
+    let mut request = Request::builder()
+        .method("PUT")
+        .uri(format!("/tasks/{}", task.id))
+        .header("content-type", "application/json");
+    if let Some(revision) = if_match {
+        request = request.header("if-match", revision);
+    }
+    app.clone()
+        .oneshot(
+            request
+                .body(Body::from(serde_json::to_vec(task).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn update_task_requires_current_revision() {
+    let app = new_router();
+    let task = Task::new(1, "Shared", 5);
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/tasks")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_vec(&task).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let created: Task = serde_json::from_slice(&bytes).unwrap();
+
+    // Two clients start from the same revision.
+    let mut first = created.clone();
+    first.name = "First edit".into();
+    let mut second = created.clone();
+    second.name = "Second edit".into();
+
+    let response = put_task(&app, &first, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let updated: Task = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(updated.name, "First edit");
+    assert_eq!(updated.revision, created.revision + 1);
+
+    let response = put_task(&app, &second, None).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    // If-Match takes precedence over the payload revision.
+    let stale = format!("\"{}\"", created.revision);
+    let current = format!("\"{}\"", updated.revision);
+    let response = put_task(&app, &second, Some(&stale)).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let response = put_task(&app, &second, Some(&current)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let updated: Task = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(updated.name, "Second edit");
+    assert_eq!(updated.revision, created.revision + 2);
+}
+
+#[tokio::test]
+async fn list_tasks_supports_limit_and_offset() {
+    let mut schedule = Schedule::new();
+    for id in 1..=5 {
+        schedule
+            .upsert_task(id, &format!("Task {id}"), 1, None)
+            .unwrap();
+    }
+    let app = http_api::router(http_api::AppState::new(schedule));
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/tasks?limit=2&offset=2")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-total-count"], "5");
+    let bytes = body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let page: Vec<Task> = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(page.iter().map(|t| t.id).collect::<Vec<_>>(), vec![3, 4]);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/tasks")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.headers()["x-total-count"], "5");
+    let bytes = body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let all: Vec<Task> = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(all.len(), 5);
+}
+
+#[tokio::test]
+async fn serve_with_shutdown_stops_and_keeps_shared_schedule() {
+    use std::io::{Read, Write};
+    use std::sync::Arc;
+
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let schedule = Arc::new(parking_lot::RwLock::new(Schedule::new()));
+    let (stop_tx, stop_rx) = tokio::sync::oneshot::channel::<()>();
+    let server = tokio::spawn(http_api::serve_with_shutdown(
+        addr,
+        schedule.clone(),
+        async move {
+            let _ = stop_rx.await;
+        },
+    ));
+
+    let response = tokio::task::spawn_blocking(move || {
+        let mut stream = loop {
+            match std::net::TcpStream::connect(addr) {
+                Ok(stream) => break stream,
+                Err(_) => std::thread::sleep(std::time::Duration::from_millis(10)),
+            }
+        };
+        let body = serde_json::to_string(&Task::new(7, "Posted", 2)).unwrap();
+        write!(
+            stream,
+            "POST /tasks HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    })
+    .await
+    .unwrap();
+    assert!(response.starts_with("HTTP/1.1 201"), "{response}");
+
+    stop_tx.send(()).unwrap();
+    server.await.unwrap().unwrap();
+    assert!(schedule.read().find_task(7).unwrap().is_some());
+}
+
+fn linked_router() -> axum::Router {
+    let mut schedule = Schedule::new();
+    schedule.upsert_task(1, "Design", 3, None).unwrap();
+    schedule.upsert_task(2, "Build", 4, Some(vec![1])).unwrap();
+    schedule.upsert_task(3, "Test", 2, None).unwrap();
+    schedule.refresh().unwrap();
+    http_api::router(http_api::AppState::new(schedule))
+}
+
+async fn send_json(
+    app: &axum::Router,
+    method: &str,
+    uri: &str,
+    payload: Option<serde_json::Value>,
+) -> (StatusCode, serde_json::Value) {
+    let mut request = Request::builder().method(method).uri(uri);
+    let body = match payload {
+        Some(payload) => {
+            request = request.header("content-type", "application/json");
+            Body::from(serde_json::to_vec(&payload).unwrap())
+        }
+        None => Body::empty(),
+    };
+    let response = app
+        .clone()
+        .oneshot(request.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let value = serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null);
+    (status, value)
+}
+
+#[tokio::test]
+async fn predecessor_links_can_be_added_and_removed() {
+    let app = linked_router();
+
+    let (status, preds) = send_json(&app, "GET", "/tasks/2/predecessors", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(preds, json!([1]));
+
+    let (status, task) = send_json(
+        &app,
+        "POST",
+        "/tasks/3/predecessors",
+        Some(json!({ "predecessor_id": 2, "kind": "FS" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let task: Task = serde_json::from_value(task).unwrap();
+    assert_eq!(task.predecessors, vec![2]);
+    let (_, build) = send_json(&app, "GET", "/tasks/2", None).await;
+    let build: Task = serde_json::from_value(build).unwrap();
+    assert!(task.early_start > build.early_finish);
+
+    let (status, task) = send_json(&app, "DELETE", "/tasks/3/predecessors/2", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let task: Task = serde_json::from_value(task).unwrap();
+    assert!(task.predecessors.is_empty());
+
+    let (status, _) = send_json(&app, "DELETE", "/tasks/3/predecessors/2", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send_json(
+        &app,
+        "POST",
+        "/tasks/3/predecessors",
+        Some(json!({ "predecessor_id": 99 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn missing_tasks_are_not_found_on_every_route() {
+    let app = linked_router();
+
+    for (method, uri, payload) in [
+        (
+            "POST",
+            "/tasks/99/predecessors",
+            Some(json!({ "predecessor_id": 1 })),
+        ),
+        ("DELETE", "/tasks/99/predecessors/1", None),
+        (
+            "POST",
+            "/tasks/99/rationale_template",
+            Some(json!({ "template": "fifty_fifty" })),
+        ),
+    ] {
+        let (status, body) = send_json(&app, method, uri, payload).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{method} {uri}");
+        assert_eq!(body["error"], "not_found");
+        assert_eq!(body["message"], "task 99 not found");
+    }
+
+    let (_, body) = send_json(
+        &app,
+        "POST",
+        "/tasks/3/predecessors",
+        Some(json!({ "predecessor_id": 99 })),
+    )
+    .await;
+    assert_eq!(body["message"], "predecessor 99 not found");
+}
+
+#[tokio::test]
+async fn stale_put_cannot_undo_a_predecessor_edit() {
+    let app = linked_router();
+    let (_, task) = send_json(&app, "GET", "/tasks/3", None).await;
+    let stale: Task = serde_json::from_value(task).unwrap();
+
+    let (status, task) = send_json(
+        &app,
+        "POST",
+        "/tasks/3/predecessors",
+        Some(json!({ "predecessor_id": 2 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let linked: Task = serde_json::from_value(task).unwrap();
+    assert_eq!(linked.revision, stale.revision + 1);
+
+    // A client still holding the old revision would drop the new link.
+    let response = put_task(&app, &stale, None).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let (_, preds) = send_json(&app, "GET", "/tasks/3/predecessors", None).await;
+    assert_eq!(preds, json!([2]));
+
+    let (status, task) = send_json(&app, "DELETE", "/tasks/3/predecessors/2", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let unlinked: Task = serde_json::from_value(task).unwrap();
+    assert_eq!(unlinked.revision, linked.revision + 1);
+}
+
+#[tokio::test]
+async fn stale_put_cannot_undo_recorded_progress() {
+    let mut schedule = Schedule::new();
+    schedule.upsert_task(1, "Design", 3, None).unwrap();
+    let shared = std::sync::Arc::new(parking_lot::RwLock::new(schedule));
+    let app = http_api::router(http_api::AppState::with_shared(shared.clone()));
+    let (_, task) = send_json(&app, "GET", "/tasks/1", None).await;
+    let stale: Task = serde_json::from_value(task).unwrap();
+
+    let date = chrono::NaiveDate::from_ymd_opt(2025, 1, 8).unwrap();
+    shared.write().record_progress(1, date, 0.4).unwrap();
+
+    // A client still holding the old revision would drop the new entry.
+    let response = put_task(&app, &stale, None).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let (_, task) = send_json(&app, "GET", "/tasks/1", None).await;
+    let current: Task = serde_json::from_value(task).unwrap();
+    assert_eq!(current.revision, stale.revision + 1);
+    assert_eq!(current.progress_log.len(), 1);
+}
+
+#[tokio::test]
+async fn predecessor_link_that_closes_a_cycle_is_rejected() {
+    let app = linked_router();
+
+    let (status, body) = send_json(
+        &app,
+        "POST",
+        "/tasks/1/predecessors",
+        Some(json!({ "predecessor_id": 2 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["error"], "conflict");
+    assert_eq!(
+        body["message"],
+        "linking 2 -> 1 would create a dependency cycle"
+    );
+
+    // A task cannot precede itself either.
+    let (status, _) = send_json(
+        &app,
+        "POST",
+        "/tasks/2/predecessors",
+        Some(json!({ "predecessor_id": 2 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (_, preds) = send_json(&app, "GET", "/tasks/1/predecessors", None).await;
+    assert_eq!(preds, json!([]));
+}
+
+#[tokio::test]
+async fn summary_endpoint_reports_last_refresh() {
+    let mut schedule = Schedule::new();
+    schedule.upsert_task(1, "Survey", 3, None).unwrap();
+    schedule.upsert_task(2, "Lift", 2, Some(vec![1])).unwrap();
+    let refreshed = schedule.refresh().unwrap();
+
+    let app = http_api::router(http_api::AppState::new(schedule));
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/summary")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let payload: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+    assert_eq!(payload["task_count"], 2);
+    assert_eq!(payload["critical_path"], json!(refreshed.critical_path));
+    assert_eq!(payload["latest_finish"], json!(refreshed.latest_finish));
+}
+
+#[tokio::test]
+async fn compute_endpoint_returns_critical_path_without_storing() {
+    let app = new_router();
+    let mut build = Task::new(2, "Build", 4);
+    build.predecessors = vec![1];
+    let mut test = Task::new(4, "Test", 2);
+    test.predecessors = vec![2, 3];
+    let tasks = vec![
+        Task::new(1, "Design", 3),
+        build,
+        Task::new(3, "Docs", 1),
+        test,
+    ];
+    // End the project on the chain's finish so the 1 -> 2 -> 4 chain has no float.
+    let metadata = schedule_tool::ScheduleMetadata {
+        project_end_date: chrono::NaiveDate::from_ymd_opt(2025, 1, 16).unwrap(),
+        ..Default::default()
+    };
+
+    let (status, body) = send_json(
+        &app,
+        "POST",
+        "/compute",
+        Some(json!({ "metadata": metadata, "tasks": tasks })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["summary"]["task_count"], 4);
+    assert_eq!(body["summary"]["critical_path"], json!([1, 2, 4]));
+    let computed: Vec<Task> = serde_json::from_value(body["tasks"].clone()).unwrap();
+    let docs = computed.iter().find(|task| task.id == 3).unwrap();
+    assert_eq!(docs.is_critical, Some(false));
+
+    let (_, held) = send_json(&app, "GET", "/tasks", None).await;
+    assert_eq!(held, json!([]));
+
+    let mut invalid = Task::new(5, "Broken", 2);
+    invalid.predecessors = vec![5];
+    let (status, body) = send_json(
+        &app,
+        "POST",
+        "/compute",
+        Some(json!({ "metadata": metadata, "tasks": [invalid] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_request");
+}
+
+async fn patch_task(app: &axum::Router, task_id: i32, patch: serde_json::Value) -> Task {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("PATCH")
+                .uri(format!("/tasks/{task_id}"))
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_vec(&patch).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(
+        status,
+        StatusCode::OK,
+        "{}",
+        String::from_utf8_lossy(&bytes)
+    );
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn patch_task_applies_only_present_fields() {
+    let app = new_router();
+    let mut task = Task::new(1, "Pour slab", 5);
+    task.task_notes = Some("Check rebar first".into());
+    task.wbs_code = Some("1.2".into());
+    task.priority = Some(3);
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/tasks")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_vec(&task).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let created: Task = serde_json::from_slice(&bytes).unwrap();
+
+    let patched = patch_task(&app, 1, json!({ "percent_complete": 0.4 })).await;
+    assert_eq!(patched.percent_complete, Some(0.4));
+    assert_eq!(patched.name, created.name);
+    assert_eq!(patched.duration_days, created.duration_days);
+    assert_eq!(patched.task_notes, created.task_notes);
+    assert_eq!(patched.wbs_code, created.wbs_code);
+    assert_eq!(patched.priority, created.priority);
+    assert_eq!(patched.early_start, created.early_start);
+    assert_eq!(patched.revision, created.revision + 1);
+
+    // An explicit null clears an optional field.
+    let cleared = patch_task(&app, 1, json!({ "task_notes": null })).await;
+    assert_eq!(cleared.task_notes, None);
+    assert_eq!(cleared.percent_complete, Some(0.4));
+    assert_eq!(cleared.wbs_code, created.wbs_code);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("PATCH")
+                .uri("/tasks/1")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::to_vec(&json!({ "name": "Stale", "revision": created.revision }))
+                        .unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+}
+
+async fn get_ready(app: &axum::Router) -> (StatusCode, serde_json::Value) {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/ready")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn ready_reports_task_count_when_schedule_refreshes() {
+    let mut schedule = Schedule::new();
+    schedule.upsert_task(1, "Design", 3, None).unwrap();
+    schedule.upsert_task(2, "Build", 5, Some(vec![1])).unwrap();
+    schedule.refresh().unwrap();
+    let app = http_api::router(http_api::AppState::new(schedule));
+
+    let (status, body) = get_ready(&app).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ready");
+    assert_eq!(body["task_count"], 2);
+    assert!(body["latest_finish"].is_string());
+}
+
+#[tokio::test]
+async fn ready_fails_once_horizon_no_longer_fits() {
+    let app = new_router();
+
+    // The task is stored even though the refresh it triggers fails: a year
+    // of working days cannot fit the default 2025 horizon.
+    let task = Task::new(1, "Long haul", 400);
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/tasks")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_vec(&task).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let (status, body) = get_ready(&app).await;
+
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["status"], "unavailable");
+    assert!(
+        body["error"]
+            .as_str()
+            .unwrap()
+            .contains("precedes schedule finish")
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn snapshot_export_does_not_block_writers() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let metadata = schedule_tool::ScheduleMetadata {
+        project_name: "edit 0".into(),
+        project_start_date: chrono::NaiveDate::from_ymd_opt(2025, 1, 6).unwrap(),
+        project_end_date: chrono::NaiveDate::from_ymd_opt(2030, 12, 31).unwrap(),
+        ..schedule_tool::ScheduleMetadata::default()
+    };
+    let tasks: Vec<Task> = (1..=20_000)
+        .map(|id| {
+            let mut task = Task::new(id, format!("Task {id}"), i64::from(id % 5 + 1));
+            if id > 100 {
+                task.predecessors = vec![id - 100];
+            }
+            task
+        })
+        .collect();
+    let calendar = Schedule::new_with_metadata(metadata.clone())
+        .calendar()
+        .clone();
+    let schedule = Schedule::from_tasks(metadata, calendar, tasks).unwrap();
+    let shared = Arc::new(parking_lot::RwLock::new(schedule));
+    let app = http_api::router(http_api::AppState::with_shared(shared.clone()));
+
+    // Keep renaming the project until the export has been sent back.
+    let done = Arc::new(AtomicBool::new(false));
+    let writer = {
+        let shared = shared.clone();
+        let done = done.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut edits = 0u32;
+            while !done.load(Ordering::Acquire) {
+                edits += 1;
+                shared.write().set_project_name(format!("edit {edits}"));
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            }
+            edits
+        })
+    };
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/snapshot")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    done.store(true, Ordering::Release);
+    let edits = writer.await.unwrap();
+
+    let snapshot: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(snapshot["tasks"].as_array().unwrap().len(), 20_000);
+    let forked_at: u32 = snapshot["metadata"]["project_name"]
+        .as_str()
+        .unwrap()
+        .trim_start_matches("edit ")
+        .parse()
+        .unwrap();
+    // Writes kept landing after the fork while the body was being built.
+    assert!(
+        edits > forked_at + 1,
+        "only {edits} edits, snapshot forked at edit {forked_at}"
+    );
+}
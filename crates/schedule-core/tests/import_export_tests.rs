@@ -1,8 +1,15 @@
 use chrono::{NaiveDate, Weekday};
+use schedule_tool::persistence::{format_links, parse_links};
 use schedule_tool::{
-    PersistenceError, Schedule, ScheduleMetadata, Task, WorkCalendar, load_schedule_from_csv,
-    load_schedule_from_json, save_schedule_to_csv, save_schedule_to_json,
+    ColumnMapping, CsvOptions, JsonOptions, LinkKind, LinkSpec, PersistenceError,
+    ResourceAllocation, SCHEDULE_SNAPSHOT_VERSION, Schedule, ScheduleMetadata, Task,
+    ValidationOptions, WorkCalendar, load_resource_allocations_from_csv, load_schedule_from_csv,
+    load_schedule_from_csv_mapped, load_schedule_from_csv_mapped_with, load_schedule_from_csv_with,
+    load_schedule_from_json, load_schedule_from_json_with, save_resource_allocations_to_csv,
+    save_schedule_to_csv, save_schedule_to_csv_with, save_schedule_to_json,
+    save_schedule_to_json_with, save_schedule_to_mspdi,
     task::{ProgressMeasurement, RationaleItem},
+    validate_tasks, validate_tasks_with,
 };
 use tempfile::NamedTempFile;
 
@@ -11,11 +18,14 @@ fn d(y: i32, m: u32, d: u32) -> NaiveDate {
 }
 
 fn build_sample_schedule() -> Schedule {
-    let mut metadata = ScheduleMetadata::default();
-    metadata.project_name = "Export Project".into();
-    metadata.project_description = "Testing persistence helpers".into();
-    metadata.project_start_date = d(2025, 1, 6);
-    metadata.project_end_date = d(2025, 2, 28);
+    let metadata = ScheduleMetadata {
+        project_name: "Export Project".into(),
+        project_description: "Testing persistence helpers".into(),
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 2, 28),
+        timezone: Some("America/New_York".into()),
+        ..ScheduleMetadata::default()
+    };
 
     let mut schedule = Schedule::new_with_metadata(metadata);
 
@@ -37,6 +47,11 @@ fn build_sample_schedule() -> Schedule {
     task2.schedule_variance_days = Some(1);
     task2.is_critical = Some(true);
     task2.parent_id = Some(10);
+    task2.deadline = Some(d(2025, 1, 24));
+    task2.priority = Some(700);
+    task2
+        .custom_fields
+        .insert("cost_code".into(), "CC-100".into());
     task2.progress_measurement = ProgressMeasurement::PreDefinedRationale;
     task2.pre_defined_rationale = vec![
         RationaleItem::new(1, "Assembly", 0.3, false),
@@ -73,6 +88,10 @@ fn json_round_trip_preserves_schedule() {
         loaded.metadata().project_description,
         schedule.metadata().project_description
     );
+    assert_eq!(
+        loaded.metadata().timezone.as_deref(),
+        Some("America/New_York")
+    );
 
     let mut original_tasks = collect_tasks(&schedule);
     original_tasks.sort_by_key(|t| t.id);
@@ -85,6 +104,9 @@ fn json_round_trip_preserves_schedule() {
         schedule.calendar().to_config()
     );
     assert!(!loaded.calendar_is_custom());
+    assert!(loaded.semantically_equals(&schedule));
+    assert_eq!(loaded.revision(), schedule.revision());
+    assert_eq!(loaded.modified_at(), schedule.modified_at());
 }
 
 #[test]
@@ -113,6 +135,7 @@ fn csv_round_trip_preserves_schedule_and_calendar() {
         loaded.metadata().project_end_date,
         schedule.metadata().project_end_date
     );
+    assert_eq!(loaded.metadata().timezone, schedule.metadata().timezone);
     assert_eq!(
         loaded.calendar().to_config(),
         schedule.calendar().to_config()
@@ -120,6 +143,161 @@ fn csv_round_trip_preserves_schedule_and_calendar() {
     assert!(!loaded.calendar_is_custom());
 }
 
+#[cfg(feature = "parquet")]
+#[test]
+fn parquet_round_trip_preserves_tasks_and_keeps_base_metadata() {
+    use schedule_tool::{load_schedule_from_parquet, save_schedule_to_parquet};
+
+    let schedule = build_sample_schedule();
+    let file = NamedTempFile::new().unwrap();
+
+    save_schedule_to_parquet(&schedule, file.path()).unwrap();
+    let base = Schedule::new_with_metadata(ScheduleMetadata {
+        project_name: "Receiving Project".into(),
+        ..schedule.metadata().clone()
+    });
+    let loaded = load_schedule_from_parquet(file.path(), &base).unwrap();
+
+    let mut original_tasks = collect_tasks(&schedule);
+    original_tasks.sort_by_key(|t| t.id);
+    let mut loaded_tasks = collect_tasks(&loaded);
+    loaded_tasks.sort_by_key(|t| t.id);
+    assert_eq!(original_tasks, loaded_tasks);
+    assert_eq!(loaded.metadata().project_name, "Receiving Project");
+}
+
+#[test]
+fn mspdi_export_writes_tasks_and_links() {
+    let mut schedule = build_sample_schedule();
+    schedule.set_project_name("R&D <Pilot>".to_string());
+    let file = NamedTempFile::new().unwrap();
+
+    save_schedule_to_mspdi(&schedule, file.path()).unwrap();
+    let xml = std::fs::read_to_string(file.path()).unwrap();
+
+    assert!(xml.starts_with("<?xml"));
+    assert!(xml.contains("<Name>R&amp;D &lt;Pilot&gt;</Name>"));
+    assert!(xml.contains("<UID>2</UID>"));
+    assert!(xml.contains("<Duration>PT64H0M0S</Duration>"));
+    assert!(xml.contains("<Start>2025-01-13T08:00:00</Start>"));
+    assert!(xml.contains("<PercentComplete>50</PercentComplete>"));
+    assert!(xml.contains("<PredecessorUID>1</PredecessorUID>"));
+    assert_eq!(xml.matches("<Task>").count(), 2);
+}
+
+#[test]
+fn json_save_writes_snapshot_version() {
+    let schedule = build_sample_schedule();
+    let file = NamedTempFile::new().unwrap();
+    save_schedule_to_json(&schedule, file.path()).unwrap();
+
+    let raw: serde_json::Value =
+        serde_json::from_reader(std::fs::File::open(file.path()).unwrap()).unwrap();
+    assert_eq!(raw["version"], SCHEDULE_SNAPSHOT_VERSION);
+}
+
+fn unrefreshed_schedule() -> Schedule {
+    let mut schedule = Schedule::new();
+    schedule.upsert_task(1, "Survey", 3, None).unwrap();
+    schedule.upsert_task(2, "Lift", 2, Some(vec![1])).unwrap();
+    schedule
+}
+
+#[test]
+fn json_save_can_refresh_before_writing() {
+    let schedule = unrefreshed_schedule();
+    let late_finishes = |options: &JsonOptions| {
+        let file = NamedTempFile::new().unwrap();
+        save_schedule_to_json_with(&schedule, file.path(), options).unwrap();
+        let raw: serde_json::Value =
+            serde_json::from_reader(std::fs::File::open(file.path()).unwrap()).unwrap();
+        raw["tasks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|task| task["late_finish"].clone())
+            .collect::<Vec<_>>()
+    };
+
+    assert!(
+        late_finishes(&JsonOptions::default())
+            .iter()
+            .all(|date| date.is_null())
+    );
+    let refreshed = late_finishes(&JsonOptions { refresh: true });
+    assert!(refreshed.iter().all(|date| date.is_string()));
+    // The caller's schedule is left as it was.
+    assert!(
+        schedule
+            .find_task(1)
+            .unwrap()
+            .unwrap()
+            .late_finish
+            .is_none()
+    );
+}
+
+#[test]
+fn csv_save_can_refresh_before_writing() {
+    let schedule = unrefreshed_schedule();
+    let file = NamedTempFile::new().unwrap();
+    save_schedule_to_csv_with(
+        &schedule,
+        file.path(),
+        &CsvOptions::default().with_refresh(true),
+    )
+    .unwrap();
+
+    let mut reader = csv::Reader::from_path(file.path()).unwrap();
+    let headers = reader.headers().unwrap().clone();
+    let column = |name: &str| headers.iter().position(|header| header == name).unwrap();
+    let (late_finish, total_float) = (column("late_finish"), column("total_float"));
+    // The first record carries schedule metadata; tasks follow.
+    let tasks: Vec<csv::StringRecord> = reader.records().skip(1).map(Result::unwrap).collect();
+    assert_eq!(tasks.len(), 2);
+    for task in &tasks {
+        assert!(!task[late_finish].is_empty());
+        assert!(!task[total_float].is_empty());
+    }
+}
+
+#[test]
+fn json_load_accepts_versionless_legacy_snapshot() {
+    let snapshot = serde_json::json!({
+        "metadata": ScheduleMetadata::default(),
+        "tasks": [Task::new(1, "A", 2), Task::new(2, "B", 3)]
+    });
+
+    let file = NamedTempFile::new().unwrap();
+    serde_json::to_writer_pretty(file.as_file(), &snapshot).unwrap();
+
+    let loaded = load_schedule_from_json(file.path()).unwrap();
+    assert_eq!(loaded.tasks().unwrap().len(), 2);
+}
+
+#[test]
+fn json_load_rejects_future_snapshot_version() {
+    let snapshot = serde_json::json!({
+        "version": SCHEDULE_SNAPSHOT_VERSION + 1,
+        "metadata": ScheduleMetadata::default(),
+        "tasks": [Task::new(1, "A", 2)]
+    });
+
+    let file = NamedTempFile::new().unwrap();
+    serde_json::to_writer_pretty(file.as_file(), &snapshot).unwrap();
+
+    match load_schedule_from_json(file.path()) {
+        Ok(_) => panic!("expected future snapshot version to be rejected"),
+        Err(PersistenceError::InvalidData(msg)) => {
+            let found = format!("version {}", SCHEDULE_SNAPSHOT_VERSION + 1);
+            let expected = format!("expected {SCHEDULE_SNAPSHOT_VERSION}");
+            assert!(msg.contains(&found), "unexpected message: {msg}");
+            assert!(msg.contains(&expected), "unexpected message: {msg}");
+        }
+        Err(other) => panic!("expected InvalidData error, got {other:?}"),
+    }
+}
+
 #[test]
 fn json_load_rejects_duplicate_ids() {
     let snapshot = serde_json::json!({
@@ -136,11 +314,10 @@ fn json_load_rejects_duplicate_ids() {
     let result = load_schedule_from_json(file.path());
     match result {
         Ok(_) => panic!("expected duplicate ids to be rejected"),
-        Err(PersistenceError::InvalidData(msg)) => assert!(
-            msg.contains("duplicate task id"),
-            "unexpected message: {msg}"
-        ),
-        Err(other) => panic!("expected InvalidData error, got {other:?}"),
+        Err(err @ PersistenceError::DuplicateTaskId(1)) => {
+            assert_eq!(err.to_string(), "invalid data: duplicate task id 1")
+        }
+        Err(other) => panic!("expected DuplicateTaskId error, got {other:?}"),
     }
 }
 
@@ -158,11 +335,10 @@ fn json_load_rejects_negative_duration() {
     let result = load_schedule_from_json(file.path());
     match result {
         Ok(_) => panic!("expected negative duration to be rejected"),
-        Err(PersistenceError::InvalidData(msg)) => assert!(
-            msg.contains("negative duration"),
-            "unexpected message: {msg}"
-        ),
-        Err(other) => panic!("expected InvalidData error, got {other:?}"),
+        Err(PersistenceError::NegativeDuration { id, duration }) => {
+            assert_eq!((id, duration), (1, -5))
+        }
+        Err(other) => panic!("expected NegativeDuration error, got {other:?}"),
     }
 }
 
@@ -228,6 +404,53 @@ fn json_save_rejects_predefined_rationale_weight_sum() {
     );
 }
 
+#[test]
+fn custom_fields_survive_json_and_csv_round_trips() {
+    let mut schedule = build_sample_schedule();
+    let revision = schedule.task_revision(1).unwrap().unwrap();
+    schedule.set_custom_field(1, "phase", "Concept").unwrap();
+    schedule
+        .set_custom_field(1, "responsible_party", "Design Team")
+        .unwrap();
+    assert_eq!(schedule.task_revision(1).unwrap(), Some(revision + 2));
+    let mut task = schedule.find_task(1).unwrap().unwrap();
+    task.forced_early_start = Some(d(2025, 1, 7));
+    schedule.upsert_task_record(task).unwrap();
+
+    let json_file = NamedTempFile::new().unwrap();
+    save_schedule_to_json(&schedule, json_file.path()).unwrap();
+    let from_json = load_schedule_from_json(json_file.path()).unwrap();
+
+    let csv_file = NamedTempFile::new().unwrap();
+    save_schedule_to_csv(&schedule, csv_file.path()).unwrap();
+    let from_csv = load_schedule_from_csv(csv_file.path()).unwrap();
+
+    for loaded in [from_json, from_csv] {
+        let task = loaded.find_task(1).unwrap().unwrap();
+        assert_eq!(task.custom_fields.len(), 2);
+        assert_eq!(task.custom_fields["phase"], "Concept");
+        assert_eq!(task.custom_fields["responsible_party"], "Design Team");
+        assert_eq!(task.forced_early_start, Some(d(2025, 1, 7)));
+    }
+}
+
+#[test]
+fn set_custom_field_rejects_control_characters_in_key() {
+    let mut schedule = build_sample_schedule();
+    let err = schedule
+        .set_custom_field(1, "cost\ncode", "CC-1")
+        .unwrap_err();
+    assert!(err.to_string().contains("control characters"), "{err}");
+    assert!(
+        schedule
+            .find_task(1)
+            .unwrap()
+            .unwrap()
+            .custom_fields
+            .is_empty()
+    );
+}
+
 #[test]
 fn json_round_trip_preserves_custom_calendar() {
     let mut schedule = build_sample_schedule();
@@ -273,3 +496,450 @@ fn csv_round_trip_preserves_custom_calendar() {
     assert_eq!(loaded.calendar().to_config(), custom_calendar.to_config());
     assert!(loaded.calendar_is_custom());
 }
+
+#[test]
+fn csv_round_trip_with_custom_date_format_and_delimiter() {
+    let schedule = build_sample_schedule();
+    let file = NamedTempFile::new().unwrap();
+    let options = CsvOptions::new("%d/%m/%Y", b';');
+
+    save_schedule_to_csv_with(&schedule, file.path(), &options).unwrap();
+    let raw = std::fs::read_to_string(file.path()).unwrap();
+    assert!(
+        raw.contains("13/01/2025"),
+        "dates should use %d/%m/%Y:\n{raw}"
+    );
+    assert!(raw.lines().next().unwrap().starts_with("id;name;"));
+
+    let loaded = load_schedule_from_csv_with(file.path(), &options).unwrap();
+    let mut original_tasks = collect_tasks(&schedule);
+    original_tasks.sort_by_key(|t| t.id);
+    let mut loaded_tasks = collect_tasks(&loaded);
+    loaded_tasks.sort_by_key(|t| t.id);
+    assert_eq!(original_tasks, loaded_tasks);
+
+    // The ISO loader cannot read the localized file.
+    assert!(load_schedule_from_csv(file.path()).is_err());
+}
+
+#[test]
+fn csv_options_reject_invalid_date_format() {
+    let schedule = build_sample_schedule();
+    let file = NamedTempFile::new().unwrap();
+    let options = CsvOptions::new("%Q", b',');
+
+    match save_schedule_to_csv_with(&schedule, file.path(), &options) {
+        Err(PersistenceError::InvalidData(msg)) => assert!(msg.contains("invalid date format")),
+        other => panic!("expected InvalidData error, got {other:?}"),
+    }
+}
+
+#[test]
+fn csv_import_snaps_progress_to_measurement_when_asked() {
+    let mut schedule = Schedule::new();
+    let mut task = Task::new(1, "Pour", 4);
+    task.progress_measurement = ProgressMeasurement::TwentyFiveSeventyFive;
+    task.percent_complete = Some(0.25);
+    schedule.upsert_task_record(task).unwrap();
+    let file = NamedTempFile::new().unwrap();
+    save_schedule_to_csv(&schedule, file.path()).unwrap();
+
+    // A spreadsheet edit left a value 25/75 cannot represent.
+    let raw = std::fs::read_to_string(file.path()).unwrap();
+    assert!(raw.contains(",0.25,25_75,"), "{raw}");
+    std::fs::write(file.path(), raw.replace(",0.25,25_75,", ",0.6,25_75,")).unwrap();
+
+    match load_schedule_from_csv(file.path()) {
+        Err(PersistenceError::InvalidData(msg)) => assert!(msg.contains("25_75"), "{msg}"),
+        Ok(_) => panic!("expected InvalidData error, got Ok"),
+        Err(other) => panic!("expected InvalidData error, got {other:?}"),
+    }
+
+    let options = CsvOptions::default().with_snap_progress_to_measurement(true);
+    let loaded = load_schedule_from_csv_with(file.path(), &options).unwrap();
+    let task = loaded.find_task(1).unwrap().unwrap();
+    assert_eq!(task.percent_complete, Some(0.75));
+}
+
+#[test]
+fn link_encoding_round_trips_kind_and_lag() {
+    let links = vec![
+        LinkSpec::new(3, LinkKind::FinishToStart, 2),
+        LinkSpec::new(5, LinkKind::StartToStart, 0),
+        LinkSpec::new(7, LinkKind::FinishToFinish, -1),
+        LinkSpec::finish_to_start(9),
+    ];
+    let encoded = format_links(&links);
+    assert_eq!(encoded, "3FS+2;5SS;7FF-1;9");
+    assert_eq!(parse_links(&encoded).unwrap(), links);
+
+    // Bare id lists from older files read as plain finish-to-start links.
+    assert_eq!(
+        parse_links("3, 5").unwrap(),
+        vec![LinkSpec::finish_to_start(3), LinkSpec::finish_to_start(5)]
+    );
+    assert_eq!(
+        parse_links("4ss+1").unwrap()[0].kind,
+        LinkKind::StartToStart
+    );
+
+    for bad in ["3XX", "3FS+", "FS", "3;;5"] {
+        match parse_links(bad) {
+            Err(PersistenceError::InvalidData(msg)) => {
+                assert!(
+                    msg.starts_with("invalid link '"),
+                    "unexpected message: {msg}"
+                )
+            }
+            other => panic!("expected {bad:?} to be rejected, got {other:?}"),
+        }
+    }
+    match parse_links("3;5QQ+1") {
+        Err(err) => assert_eq!(err.to_string(), "invalid data: invalid link '5QQ+1'"),
+        Ok(links) => panic!("expected malformed token to be rejected, got {links:?}"),
+    }
+}
+
+#[test]
+fn csv_import_rejects_links_the_schedule_cannot_hold() {
+    let file = NamedTempFile::new().unwrap();
+    std::fs::write(
+        file.path(),
+        "Task ID,Task Name,Duration (d),Predecessors,Planned Start\n\
+         1,Design,5,,2025-01-06\n\
+         2,Build,8,1FS,2025-01-13\n\
+         3,Test,3,1;2FS+0,2025-01-23\n",
+    )
+    .unwrap();
+    let loaded = load_schedule_from_csv_mapped(file.path(), &upstream_mapping()).unwrap();
+    let mut tasks = collect_tasks(&loaded);
+    tasks.sort_by_key(|t| t.id);
+    assert_eq!(tasks[1].predecessors, vec![1]);
+    assert_eq!(tasks[2].predecessors, vec![1, 2]);
+
+    std::fs::write(
+        file.path(),
+        "Task ID,Task Name,Duration (d),Predecessors,Planned Start\n\
+         1,Design,5,,2025-01-06\n\
+         2,Build,8,1SS+2,2025-01-13\n",
+    )
+    .unwrap();
+    match load_schedule_from_csv_mapped(file.path(), &upstream_mapping()) {
+        Err(PersistenceError::InvalidData(msg)) => assert!(
+            msg.contains("task 2 predecessors link '1SS+2' is not supported"),
+            "unexpected message: {msg}"
+        ),
+        other => panic!(
+            "expected unsupported link to be rejected, got {:?}",
+            other.err()
+        ),
+    }
+}
+
+#[test]
+fn resource_allocations_round_trip_through_their_own_csv() {
+    let mut schedule = Schedule::new();
+    let mut design = Task::new(1, "Design", 3);
+    let mut architect = ResourceAllocation::new("architect", 0.5);
+    architect.cost_rate = Some(120.0);
+    architect.role = Some("lead".into());
+    design.resource_allocations = vec![architect];
+    schedule.upsert_task_record(design).unwrap();
+    let mut build = Task::new(2, "Build", 5);
+    build.predecessors = vec![1];
+    build.resource_allocations = vec![ResourceAllocation::new("crew", 1.0)];
+    schedule.upsert_task_record(build).unwrap();
+
+    let file = NamedTempFile::new().unwrap();
+    save_resource_allocations_to_csv(&schedule, file.path()).unwrap();
+    let contents = std::fs::read_to_string(file.path()).unwrap();
+    assert_eq!(contents.lines().count(), 3);
+    assert!(contents.starts_with("task_id,resource_id,allocation_units,cost_rate,"));
+
+    let mut bare = Schedule::new();
+    bare.upsert_task_record(Task::new(1, "Design", 3)).unwrap();
+    bare.upsert_task_record(Task::new(2, "Build", 5)).unwrap();
+    assert_eq!(
+        load_resource_allocations_from_csv(&mut bare, file.path()).unwrap(),
+        2
+    );
+    for id in [1, 2] {
+        assert_eq!(
+            bare.find_task(id).unwrap().unwrap().resource_allocations,
+            schedule
+                .find_task(id)
+                .unwrap()
+                .unwrap()
+                .resource_allocations
+        );
+    }
+
+    let mut unrelated = Schedule::new();
+    unrelated
+        .upsert_task_record(Task::new(1, "Design", 3))
+        .unwrap();
+    match load_resource_allocations_from_csv(&mut unrelated, file.path()) {
+        Err(PersistenceError::InvalidData(msg)) => {
+            assert_eq!(msg, "allocation for unknown task 2")
+        }
+        other => panic!("expected unknown task to be rejected, got {other:?}"),
+    }
+    assert!(
+        unrelated
+            .find_task(1)
+            .unwrap()
+            .unwrap()
+            .resource_allocations
+            .is_empty()
+    );
+}
+
+fn upstream_mapping() -> ColumnMapping {
+    ColumnMapping::new()
+        .map("id", "Task ID")
+        .map("name", "Task Name")
+        .map("duration_days", "Duration (d)")
+        .map("predecessors", "Predecessors")
+        .map("baseline_start", "Planned Start")
+}
+
+#[test]
+fn csv_mapped_import_reads_renamed_headers() {
+    let file = NamedTempFile::new().unwrap();
+    std::fs::write(
+        file.path(),
+        "Task ID,Task Name,Duration (d),Predecessors,Planned Start,Owner\n\
+         1,Design,5,,2025-01-06,alice\n\
+         2,Build,8,1,2025-01-13,bob\n",
+    )
+    .unwrap();
+
+    let loaded = load_schedule_from_csv_mapped(file.path(), &upstream_mapping()).unwrap();
+    let mut tasks = collect_tasks(&loaded);
+    tasks.sort_by_key(|t| t.id);
+
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(tasks[0].name, "Design");
+    assert_eq!(tasks[0].duration_days, 5);
+    assert!(tasks[0].predecessors.is_empty());
+    assert_eq!(tasks[1].predecessors, vec![1]);
+    assert_eq!(tasks[1].baseline_start, Some(d(2025, 1, 13)));
+    assert_eq!(tasks[1].actual_start, None);
+    assert_eq!(
+        tasks[1].progress_measurement,
+        ProgressMeasurement::PercentComplete
+    );
+}
+
+#[test]
+fn csv_mapped_import_requires_core_fields() {
+    let file = NamedTempFile::new().unwrap();
+    std::fs::write(file.path(), "Task ID,Task Name\n1,Design\n").unwrap();
+
+    let mapping = ColumnMapping::new()
+        .map("id", "Task ID")
+        .map("name", "Task Name");
+    match load_schedule_from_csv_mapped(file.path(), &mapping) {
+        Err(PersistenceError::InvalidData(msg)) => assert!(
+            msg.contains("missing required field 'duration_days'"),
+            "unexpected message: {msg}"
+        ),
+        other => panic!("expected InvalidData error, got {:?}", other.err()),
+    }
+
+    match load_schedule_from_csv_mapped(file.path(), &upstream_mapping()) {
+        Err(PersistenceError::InvalidData(msg)) => {
+            assert!(msg.contains("'Duration (d)'"), "unexpected message: {msg}")
+        }
+        other => panic!("expected InvalidData error, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn validate_tasks_reports_typed_progress_errors() {
+    let mut task = Task::new(1, "Overdone", 2);
+    task.percent_complete = Some(1.5);
+    match validate_tasks(&[task]) {
+        Err(PersistenceError::InvalidProgress { id: 1, pct }) => assert_eq!(pct, 1.5),
+        other => panic!("expected InvalidProgress error, got {other:?}"),
+    }
+
+    let mut task = Task::new(2, "Composite", 3);
+    task.progress_measurement = ProgressMeasurement::PreDefinedRationale;
+    task.pre_defined_rationale = vec![
+        RationaleItem::new(1, "Phase A", 0.4, false),
+        RationaleItem::new(2, "Phase B", 0.4, false),
+    ];
+    let err = validate_tasks(&[task]).unwrap_err();
+    match &err {
+        PersistenceError::RationaleWeightSum { id: 2, total } => {
+            assert!((total - 0.8).abs() < 1e-9)
+        }
+        other => panic!("expected RationaleWeightSum error, got {other:?}"),
+    }
+    assert_eq!(
+        err.to_string(),
+        "invalid data: task 2 pre_defined_rationale weights must sum to 1.0 (got 0.8000)"
+    );
+}
+
+#[test]
+fn relaxed_weight_tolerance_accepts_rounded_rationale_weights() {
+    let mut task = Task::new(1, "Rounded", 3);
+    task.progress_measurement = ProgressMeasurement::PreDefinedRationale;
+    task.pre_defined_rationale = vec![
+        RationaleItem::new(1, "Phase A", 0.3333, false),
+        RationaleItem::new(2, "Phase B", 0.3333, false),
+        RationaleItem::new(3, "Phase C", 0.3332, false),
+    ];
+    let relaxed = ValidationOptions::default().with_weight_tolerance(1e-3);
+
+    assert!(matches!(
+        validate_tasks(std::slice::from_ref(&task)),
+        Err(PersistenceError::RationaleWeightSum { id: 1, .. })
+    ));
+    validate_tasks_with(std::slice::from_ref(&task), &relaxed).unwrap();
+
+    let snapshot = serde_json::json!({
+        "metadata": ScheduleMetadata::default(),
+        "tasks": [task]
+    });
+    let file = NamedTempFile::new().unwrap();
+    serde_json::to_writer_pretty(file.as_file(), &snapshot).unwrap();
+
+    assert!(matches!(
+        load_schedule_from_json(file.path()),
+        Err(PersistenceError::RationaleWeightSum { id: 1, .. })
+    ));
+    let loaded = load_schedule_from_json_with(file.path(), &relaxed).unwrap();
+    assert_eq!(loaded.dataframe().height(), 1);
+}
+
+#[test]
+fn mapped_csv_import_honours_csv_options() {
+    let rationale = vec![
+        RationaleItem::new(1, "Phase A", 0.3333, false),
+        RationaleItem::new(2, "Phase B", 0.3333, false),
+        RationaleItem::new(3, "Phase C", 0.3332, false),
+    ];
+    let file = NamedTempFile::new().unwrap();
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(b';')
+        .from_path(file.path())
+        .unwrap();
+    writer
+        .write_record(["Task ID", "Task Name", "Duration (d)", "Method", "Steps"])
+        .unwrap();
+    writer
+        .write_record([
+            "1",
+            "Rounded",
+            "3",
+            "pre_defined_rationale",
+            &serde_json::to_string(&rationale).unwrap(),
+        ])
+        .unwrap();
+    writer.flush().unwrap();
+    let mapping = ColumnMapping::new()
+        .map("id", "Task ID")
+        .map("name", "Task Name")
+        .map("duration_days", "Duration (d)")
+        .map("progress_measurement", "Method")
+        .map("pre_defined_rationale", "Steps");
+
+    let options = CsvOptions {
+        delimiter: b';',
+        ..CsvOptions::default()
+    };
+    assert!(matches!(
+        load_schedule_from_csv_mapped_with(file.path(), &mapping, &options),
+        Err(PersistenceError::RationaleWeightSum { id: 1, .. })
+    ));
+    let relaxed = options.with_validation(ValidationOptions::default().with_weight_tolerance(1e-3));
+    let loaded = load_schedule_from_csv_mapped_with(file.path(), &mapping, &relaxed).unwrap();
+    let task = loaded.find_task(1).unwrap().unwrap();
+    assert_eq!(task.pre_defined_rationale, rationale);
+}
+
+#[test]
+fn json_snapshot_round_trips_progress_log() {
+    let mut schedule = Schedule::new();
+    schedule.upsert_task(1, "Frame", 10, None).unwrap();
+    schedule
+        .record_progress(1, NaiveDate::from_ymd_opt(2025, 2, 7).unwrap(), 0.3)
+        .unwrap();
+    schedule
+        .record_progress(1, NaiveDate::from_ymd_opt(2025, 2, 14).unwrap(), 0.55)
+        .unwrap();
+
+    let file = NamedTempFile::new().unwrap();
+    save_schedule_to_json(&schedule, file.path()).unwrap();
+    let loaded = load_schedule_from_json(file.path()).unwrap();
+
+    let original = schedule.find_task(1).unwrap().unwrap();
+    let restored = loaded.find_task(1).unwrap().unwrap();
+    assert_eq!(restored.progress_log, original.progress_log);
+    assert_eq!(
+        restored.percent_complete_as_of(NaiveDate::from_ymd_opt(2025, 2, 10).unwrap()),
+        Some(0.3)
+    );
+}
+
+#[test]
+fn json_load_builds_like_from_tasks_without_refreshing() {
+    let metadata = build_sample_schedule().metadata().clone();
+    let mut design = Task::new(1, "Design", 2);
+    design.early_start = Some(d(2025, 1, 6));
+    let mut build = Task::new(2, "Build", 3);
+    build.predecessors = vec![1];
+    let mut ship = Task::new(3, "Ship", 1);
+    ship.predecessors = vec![2];
+    let tasks = vec![ship, build, design];
+    let snapshot = serde_json::json!({
+        "metadata": ScheduleMetadata {
+            revision: 7,
+            ..metadata.clone()
+        },
+        "tasks": tasks,
+    });
+    let file = NamedTempFile::new().unwrap();
+    serde_json::to_writer_pretty(file.as_file(), &snapshot).unwrap();
+
+    let mut loaded = load_schedule_from_json(file.path()).unwrap();
+    let ids: Vec<i32> = collect_tasks(&loaded).iter().map(|task| task.id).collect();
+    assert_eq!(ids, vec![1, 2, 3]);
+    assert_eq!(loaded.find_task(3).unwrap().unwrap().early_start, None);
+    assert_eq!(loaded.revision(), 7);
+
+    loaded.refresh().unwrap();
+    let calendar = loaded.calendar().clone();
+    let built = Schedule::from_tasks(metadata, calendar, tasks).unwrap();
+    assert!(loaded.dataframe().equals_missing(built.dataframe()));
+}
+
+#[test]
+fn refresh_regenerates_imported_successors_from_predecessors() {
+    let mut schedule = Schedule::new_with_metadata(build_sample_schedule().metadata().clone());
+    let mut design = Task::new(1, "Design", 2);
+    design.successors = vec![3];
+    let mut build = Task::new(2, "Build", 3);
+    build.predecessors = vec![1];
+    build.successors = vec![1];
+    let mut ship = Task::new(3, "Ship", 1);
+    ship.successors = vec![2];
+    for task in [design, build, ship] {
+        schedule.upsert_task_record(task).unwrap();
+    }
+
+    let file = NamedTempFile::new().unwrap();
+    save_schedule_to_json(&schedule, file.path()).unwrap();
+    let mut loaded = load_schedule_from_json(file.path()).unwrap();
+    assert_eq!(loaded.find_task(3).unwrap().unwrap().successors, vec![2]);
+
+    loaded.refresh().unwrap();
+    let successors: Vec<Vec<i32>> = collect_tasks(&loaded)
+        .into_iter()
+        .map(|task| task.successors)
+        .collect();
+    assert_eq!(successors, vec![vec![2], vec![], vec![]]);
+}
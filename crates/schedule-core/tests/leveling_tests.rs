@@ -0,0 +1,87 @@
+use chrono::NaiveDate;
+use schedule_tool::{ResourceAllocation, Schedule, ScheduleMetadata, Task};
+
+fn d(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+fn crane_task(id: i32, name: &str, priority: Option<i32>) -> Task {
+    let mut task = Task::new(id, name, 3);
+    task.resource_allocations = vec![ResourceAllocation::new("crane", 1.0)];
+    task.priority = priority;
+    task
+}
+
+fn build_schedule(tasks: Vec<Task>) -> Schedule {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 2, 28),
+        ..ScheduleMetadata::default()
+    };
+    let mut schedule = Schedule::new_with_metadata(metadata);
+    for task in tasks {
+        schedule.upsert_task_record(task).unwrap();
+    }
+    schedule.refresh().unwrap();
+    schedule
+}
+
+#[test]
+fn leveling_delays_lower_priority_task_on_shared_resource() {
+    let schedule = build_schedule(vec![
+        crane_task(1, "Lift steel", Some(200)),
+        crane_task(2, "Lift roof", Some(800)),
+    ]);
+    let first = schedule.find_task(1).unwrap().unwrap();
+    let second = schedule.find_task(2).unwrap().unwrap();
+    assert_eq!(first.total_float, second.total_float);
+
+    let leveled = schedule.level_resources().unwrap();
+    let high = leveled.get(2).unwrap();
+    let low = leveled.get(1).unwrap();
+    assert_eq!(high.delay_days, 0);
+    assert_eq!(high.start, d(2025, 1, 6));
+    assert!(low.delay_days > 0);
+    assert!(low.start > high.finish);
+    assert_eq!(
+        leveled
+            .delayed()
+            .map(|task| task.task_id)
+            .collect::<Vec<_>>(),
+        vec![1]
+    );
+
+    // Leveling is a read-only analysis.
+    assert_eq!(
+        schedule.find_task(1).unwrap().unwrap().early_start,
+        Some(d(2025, 1, 6))
+    );
+}
+
+#[test]
+fn leveling_without_priorities_matches_default_ordering() {
+    let schedule = build_schedule(vec![crane_task(1, "A", None), crane_task(2, "B", None)]);
+    let leveled = schedule.level_resources().unwrap();
+    assert_eq!(leveled.get(1).unwrap().delay_days, 0);
+    assert!(leveled.get(2).unwrap().delay_days > 0);
+}
+
+#[test]
+fn leveling_pushes_successors_of_delayed_tasks() {
+    let mut inspect_steel = Task::new(3, "Inspect steel", 1);
+    inspect_steel.predecessors = vec![1];
+    let mut inspect_roof = Task::new(4, "Inspect roof", 1);
+    inspect_roof.predecessors = vec![2];
+    let schedule = build_schedule(vec![
+        crane_task(1, "Lift steel", Some(200)),
+        crane_task(2, "Lift roof", Some(800)),
+        inspect_steel,
+        inspect_roof,
+    ]);
+
+    let leveled = schedule.level_resources().unwrap();
+    let delayed = leveled.get(1).unwrap();
+    let successor = leveled.get(3).unwrap();
+    assert!(successor.start > delayed.finish);
+    assert!(successor.delay_days > 0);
+}
@@ -0,0 +1,171 @@
+use chrono::NaiveDate;
+use schedule_tool::{QualityCheckKind, QualityThresholds, Schedule, ScheduleMetadata, Task};
+
+fn d(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+fn linked_schedule() -> Schedule {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 2, 28),
+        ..ScheduleMetadata::default()
+    };
+    let mut schedule = Schedule::new_with_metadata(metadata);
+    schedule.upsert_task(1, "Start", 0, None).unwrap();
+    schedule.upsert_task(2, "Design", 5, Some(vec![1])).unwrap();
+    schedule.upsert_task(3, "Build", 10, Some(vec![2])).unwrap();
+    schedule.upsert_task(4, "Finish", 0, Some(vec![3])).unwrap();
+    schedule.refresh().unwrap();
+    schedule
+}
+
+#[test]
+fn quality_checks_flag_logic_gaps() {
+    let mut schedule = linked_schedule();
+    schedule.upsert_task(5, "Orphan", 3, None).unwrap();
+    schedule.refresh().unwrap();
+
+    let report = schedule.quality_checks().unwrap();
+    let logic = report.check(QualityCheckKind::Logic).unwrap();
+
+    // The open start and finish plus the orphan all lack a link.
+    assert_eq!(logic.task_ids, vec![1, 4, 5]);
+    assert!(!logic.passed);
+    assert!(!report.passed());
+}
+
+#[test]
+fn quality_checks_flag_high_and_negative_float() {
+    let mut schedule = linked_schedule();
+    let mut loose = Task::new(6, "Loose", 1);
+    loose.predecessors = vec![1];
+    loose.total_float = Some(60);
+    schedule.upsert_task_record(loose).unwrap();
+    let mut late = Task::new(7, "Late", 1);
+    late.predecessors = vec![1];
+    late.total_float = Some(-2);
+    schedule.upsert_task_record(late).unwrap();
+
+    let report = schedule.quality_checks().unwrap();
+
+    let high_float = report.check(QualityCheckKind::HighFloat).unwrap();
+    assert_eq!(high_float.task_ids, vec![6]);
+    assert!(!high_float.passed);
+
+    let negative_float = report.check(QualityCheckKind::NegativeFloat).unwrap();
+    assert_eq!(negative_float.task_ids, vec![7]);
+    assert!(!negative_float.passed);
+}
+
+#[test]
+fn quality_checks_flag_deadlines_as_hard_constraints() {
+    let mut schedule = linked_schedule();
+    let mut build = schedule.find_task(3).unwrap().unwrap();
+    build.deadline = Some(d(2025, 2, 14));
+    schedule.upsert_task_record(build).unwrap();
+    // A forced early start is a soft constraint and is not flagged.
+    let mut design = schedule.find_task(2).unwrap().unwrap();
+    design.forced_early_start = Some(d(2025, 1, 7));
+    schedule.upsert_task_record(design).unwrap();
+    schedule.refresh().unwrap();
+
+    let report = schedule.quality_checks().unwrap();
+    let hard = report.check(QualityCheckKind::HardConstraints).unwrap();
+    assert_eq!(hard.task_ids, vec![3]);
+    assert!((hard.ratio - 0.25).abs() < 1e-9);
+    assert!(!hard.passed);
+    assert_eq!(
+        QualityCheckKind::HardConstraints.as_str(),
+        "hard_constraints"
+    );
+}
+
+#[test]
+fn quality_checks_respect_configurable_duration_limit() {
+    let schedule = linked_schedule();
+
+    let report = schedule.quality_checks().unwrap();
+    assert!(report.check(QualityCheckKind::HighDuration).unwrap().passed);
+
+    let thresholds = QualityThresholds {
+        high_duration_days: 8,
+        ..QualityThresholds::default()
+    };
+    let report = schedule.quality_checks_with(&thresholds).unwrap();
+    let high_duration = report.check(QualityCheckKind::HighDuration).unwrap();
+    assert_eq!(high_duration.task_ids, vec![3]);
+    assert!(!high_duration.passed);
+    assert!((high_duration.ratio - 0.25).abs() < 1e-9);
+}
+
+#[test]
+fn quality_checks_skip_completed_tasks() {
+    let mut schedule = linked_schedule();
+    let mut done = Task::new(5, "Done", 60);
+    done.actual_start = Some(d(2025, 1, 6));
+    done.actual_finish = Some(d(2025, 3, 3));
+    schedule.upsert_task_record(done).unwrap();
+
+    let report = schedule.quality_checks().unwrap();
+    assert_eq!(report.evaluated_task_count, 4);
+    assert!(
+        report
+            .checks
+            .iter()
+            .all(|check| !check.task_ids.contains(&5))
+    );
+}
+
+#[test]
+fn dangling_tasks_are_isolated_from_the_network() {
+    let mut schedule = linked_schedule();
+    schedule.upsert_task(5, "Orphan", 3, None).unwrap();
+    let summary = schedule.refresh().unwrap();
+
+    // The open start and finish are linked on one side; only the orphan is
+    // cut off entirely.
+    assert_eq!(schedule.dangling_tasks().unwrap(), vec![5]);
+    assert_eq!(summary.open_ends, 1);
+    assert!(summary.to_cli_summary().contains("open_ends=1"));
+
+    let mut single = Schedule::new_with_metadata(schedule.metadata().clone());
+    single.upsert_task(1, "Only", 2, None).unwrap();
+    assert_eq!(single.refresh().unwrap().open_ends, 0);
+    assert!(single.dangling_tasks().unwrap().is_empty());
+}
+
+#[test]
+fn enforce_bookends_links_open_finishes_to_the_finish_milestone() {
+    let mut schedule = linked_schedule();
+    // Two tasks that start from the start milestone but feed nothing.
+    schedule
+        .upsert_task(5, "Permits", 3, Some(vec![1]))
+        .unwrap();
+    schedule
+        .upsert_task(6, "Procure", 4, Some(vec![1]))
+        .unwrap();
+    schedule.refresh().unwrap();
+
+    let report = schedule.enforce_bookends(1, 4, false).unwrap();
+    assert!(report.open_starts.is_empty());
+    assert_eq!(report.open_finishes, vec![5, 6]);
+    assert!(!report.linked);
+    assert_eq!(
+        schedule.find_task(4).unwrap().unwrap().predecessors,
+        vec![3]
+    );
+
+    let report = schedule.enforce_bookends(1, 4, true).unwrap();
+    assert_eq!(report.open_finishes, vec![5, 6]);
+    assert!(report.linked);
+    schedule.refresh().unwrap();
+    assert_eq!(
+        schedule.find_task(4).unwrap().unwrap().predecessors,
+        vec![3, 5, 6]
+    );
+    assert!(schedule.enforce_bookends(1, 4, false).unwrap().is_clean());
+
+    let err = schedule.enforce_bookends(2, 4, false).unwrap_err();
+    assert!(err.to_string().contains("start milestone 2 has duration 5"));
+}
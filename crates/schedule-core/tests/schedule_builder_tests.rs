@@ -1,7 +1,8 @@
 use chrono::{Datelike, Duration, NaiveDate, Weekday};
 use schedule_tool::{
-    ProgressMeasurement, ProgressRationaleTemplate, Schedule, ScheduleMetadata,
-    ScheduleMetadataError, Task, WorkCalendar, WorkCalendarConfig,
+    ProgressMeasurement, ProgressRationaleTemplate, ResourceAllocation, Schedule, ScheduleMetadata,
+    ScheduleMetadataError, Task, TaskKind, TaskNotFound, VarianceBasis, WorkCalendar,
+    WorkCalendarConfig,
 };
 
 fn d(y: i32, m: u32, d: u32) -> NaiveDate {
@@ -10,9 +11,11 @@ fn d(y: i32, m: u32, d: u32) -> NaiveDate {
 
 #[test]
 fn new_with_metadata_uses_calendar_year_range() {
-    let mut metadata = ScheduleMetadata::default();
-    metadata.project_start_date = d(2024, 12, 1);
-    metadata.project_end_date = d(2026, 1, 31);
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2024, 12, 1),
+        project_end_date: d(2026, 1, 31),
+        ..ScheduleMetadata::default()
+    };
 
     let schedule = Schedule::new_with_metadata(metadata);
 
@@ -31,9 +34,11 @@ fn new_with_year_range_sets_metadata_bounds() {
 #[test]
 fn set_metadata_refreshes_calendar() {
     let mut schedule = Schedule::new();
-    let mut metadata = ScheduleMetadata::default();
-    metadata.project_start_date = d(2027, 1, 1);
-    metadata.project_end_date = d(2027, 12, 31);
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2027, 1, 1),
+        project_end_date: d(2027, 12, 31),
+        ..ScheduleMetadata::default()
+    };
     schedule.set_metadata(metadata).unwrap();
 
     // New Year's Day 2027 should be unavailable in the refreshed calendar.
@@ -43,9 +48,11 @@ fn set_metadata_refreshes_calendar() {
 #[test]
 fn updating_duration_recomputes_downstream_dates() {
     let mut schedule = Schedule::new();
-    let mut metadata = ScheduleMetadata::default();
-    metadata.project_start_date = d(2025, 1, 6);
-    metadata.project_end_date = d(2025, 2, 28);
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 2, 28),
+        ..ScheduleMetadata::default()
+    };
     schedule.set_metadata(metadata).unwrap();
 
     schedule.upsert_task(1, "T1", 2, None).unwrap();
@@ -69,9 +76,11 @@ fn updating_duration_recomputes_downstream_dates() {
 #[test]
 fn refresh_runs_full_pipeline() {
     let mut schedule = Schedule::new();
-    let mut metadata = ScheduleMetadata::default();
-    metadata.project_start_date = d(2025, 1, 6);
-    metadata.project_end_date = d(2025, 1, 17);
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 1, 17),
+        ..ScheduleMetadata::default()
+    };
     schedule.set_metadata(metadata).unwrap();
 
     schedule.upsert_task(1, "T1", 2, None).unwrap();
@@ -113,9 +122,11 @@ fn refresh_runs_full_pipeline() {
 #[test]
 fn refresh_computes_schedule_variance_from_baseline_and_actual() {
     let mut schedule = Schedule::new();
-    let mut metadata = ScheduleMetadata::default();
-    metadata.project_start_date = d(2025, 1, 6);
-    metadata.project_end_date = d(2025, 2, 28);
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 2, 28),
+        ..ScheduleMetadata::default()
+    };
     schedule.set_metadata(metadata).unwrap();
 
     schedule.upsert_task(1, "T1", 2, None).unwrap();
@@ -135,12 +146,180 @@ fn refresh_computes_schedule_variance_from_baseline_and_actual() {
     assert_eq!(refreshed.schedule_variance_days, Some(2));
 }
 
+#[test]
+fn forecast_variance_compares_baseline_with_early_dates_before_actuals() {
+    let mut schedule = Schedule::new_with_metadata(ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 2, 28),
+        ..ScheduleMetadata::default()
+    });
+    schedule.upsert_task(1, "Design", 2, None).unwrap();
+    schedule.upsert_task(2, "Build", 3, Some(vec![1])).unwrap();
+    schedule.upsert_task(3, "Procure", 2, None).unwrap();
+    schedule.refresh().unwrap();
+
+    // Baseline the current plan; task 3 has also started late.
+    for id in [1, 2, 3] {
+        let mut task = schedule.find_task(id).unwrap().unwrap();
+        task.baseline_start = task.early_start;
+        task.baseline_finish = task.early_finish;
+        if id == 3 {
+            task.actual_start = Some(d(2025, 1, 7));
+        }
+        schedule.upsert_task_record(task).unwrap();
+    }
+
+    let summary = schedule.refresh().unwrap();
+    assert_eq!(
+        schedule
+            .find_task(2)
+            .unwrap()
+            .unwrap()
+            .schedule_variance_days,
+        None
+    );
+    assert_eq!(summary.variance_basis.get(&3), Some(&VarianceBasis::Actual));
+    assert_eq!(summary.variance_basis.len(), 1);
+
+    schedule.set_forecast_variance(true);
+    let mut design = schedule.find_task(1).unwrap().unwrap();
+    design.duration_days = 4;
+    schedule.upsert_task_record(design).unwrap();
+    let summary = schedule.refresh().unwrap();
+
+    let design = schedule.find_task(1).unwrap().unwrap();
+    let build = schedule.find_task(2).unwrap().unwrap();
+    let procure = schedule.find_task(3).unwrap().unwrap();
+    assert_eq!(design.schedule_variance_days, Some(2));
+    assert_eq!(build.schedule_variance_days, Some(2));
+    assert_eq!(procure.schedule_variance_days, Some(1));
+    assert_eq!(summary.positive_variance_count, 3);
+    assert_eq!(
+        summary.variance_basis.get(&1),
+        Some(&VarianceBasis::Forecast)
+    );
+    assert_eq!(
+        summary.variance_basis.get(&2),
+        Some(&VarianceBasis::Forecast)
+    );
+    assert_eq!(summary.variance_basis.get(&3), Some(&VarianceBasis::Actual));
+}
+
+#[test]
+fn refresh_measures_in_progress_variance_from_the_forecast_finish() {
+    let mut schedule = Schedule::new_with_metadata(ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        ..ScheduleMetadata::default()
+    });
+    let build = Task::builder(1, "Build", 10)
+        .baseline(d(2025, 1, 6), d(2025, 1, 20))
+        .build()
+        .unwrap();
+    schedule.upsert_task_record(build).unwrap();
+    schedule.refresh().unwrap();
+
+    // 30% done by Wednesday the 15th: seven days remain, finishing on the
+    // 27th (MLK Day on the 20th is a holiday) instead of the 20th.
+    let mut build = schedule.find_task(1).unwrap().unwrap();
+    build.actual_start = Some(d(2025, 1, 6));
+    build.percent_complete = Some(0.3);
+    schedule.upsert_task_record(build).unwrap();
+    schedule.set_status_date(Some(d(2025, 1, 15)));
+    let summary = schedule.refresh().unwrap();
+
+    let build = schedule.find_task(1).unwrap().unwrap();
+    assert_eq!(build.actual_finish, None);
+    assert_eq!(build.early_finish, Some(d(2025, 1, 27)));
+    assert_eq!(build.schedule_variance_days, Some(4));
+    assert_eq!(
+        summary.variance_basis.get(&1),
+        Some(&VarianceBasis::InProgress)
+    );
+}
+
+#[test]
+fn refresh_reports_missed_deadlines_without_moving_dates() {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 2, 28),
+        ..ScheduleMetadata::default()
+    };
+    let mut schedule = Schedule::new_with_metadata(metadata);
+
+    let mut design = Task::new(1, "Design", 5);
+    design.deadline = Some(d(2025, 1, 17));
+    schedule.upsert_task_record(design).unwrap();
+    let mut build = Task::new(2, "Build", 3);
+    build.predecessors = vec![1];
+    build.deadline = Some(d(2025, 1, 15));
+    schedule.upsert_task_record(build).unwrap();
+
+    let summary = schedule.refresh().unwrap();
+    let build = schedule.find_task(2).unwrap().unwrap();
+    assert_eq!(build.early_finish, Some(d(2025, 1, 17)));
+    assert_eq!(build.deadline, Some(d(2025, 1, 15)));
+
+    assert_eq!(summary.missed_deadlines.len(), 1);
+    let missed = &summary.missed_deadlines[0];
+    assert_eq!(missed.task_id, 2);
+    assert_eq!(missed.deadline, d(2025, 1, 15));
+    assert_eq!(missed.early_finish, d(2025, 1, 17));
+    assert_eq!(missed.days_late, 2);
+}
+
+#[test]
+fn deadline_before_earliest_finish_produces_negative_float() {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 2, 28),
+        ..ScheduleMetadata::default()
+    };
+    let mut schedule = Schedule::new_with_metadata(metadata);
+
+    schedule
+        .upsert_task_record(Task::new(1, "Design", 5))
+        .unwrap();
+    let mut build = Task::new(2, "Build", 3);
+    build.predecessors = vec![1];
+    build.deadline = Some(d(2025, 1, 15));
+    schedule.upsert_task_record(build).unwrap();
+    schedule
+        .upsert_task_record(Task::new(3, "Docs", 2))
+        .unwrap();
+
+    let summary = schedule.refresh().unwrap();
+    let design = schedule.find_task(1).unwrap().unwrap();
+    let build = schedule.find_task(2).unwrap().unwrap();
+    let docs = schedule.find_task(3).unwrap().unwrap();
+
+    // Build cannot finish before the 17th, so its late finish (the deadline)
+    // precedes its early finish and the whole chain goes negative.
+    assert_eq!(build.late_finish, Some(d(2025, 1, 15)));
+    assert_eq!(build.total_float, Some(-4));
+    assert_eq!(design.total_float, Some(-4));
+    assert_eq!(design.is_critical, Some(true));
+    assert_eq!(build.is_critical, Some(true));
+    assert!(docs.total_float.unwrap() > 0);
+
+    assert_eq!(summary.negative_float_count, 2);
+    let worst: Vec<(i32, i64)> = summary
+        .worst_negative_float
+        .iter()
+        .map(|entry| (entry.task_id, entry.total_float))
+        .collect();
+    assert_eq!(worst, [(1, -4), (2, -4)]);
+    assert_eq!(summary.critical_path, [1, 2]);
+    assert!(summary.to_cli_summary().contains("negative_float=2"));
+}
+
 #[test]
 fn refresh_errors_when_project_end_before_finish() {
     let mut schedule = Schedule::new();
-    let mut metadata = ScheduleMetadata::default();
-    metadata.project_start_date = d(2025, 1, 6);
-    metadata.project_end_date = d(2025, 1, 10);
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 1, 10),
+        ..ScheduleMetadata::default()
+    };
     schedule.set_metadata(metadata).unwrap();
 
     schedule.upsert_task(1, "T1", 2, None).unwrap();
@@ -169,13 +348,35 @@ fn set_metadata_preserves_custom_calendar_flag() {
     assert!(schedule.calendar_is_custom());
 
     let mut metadata = schedule.metadata().clone();
-    metadata.project_end_date = metadata.project_end_date + Duration::days(7);
+    metadata.project_end_date += Duration::days(7);
     schedule.set_metadata(metadata).unwrap();
 
     assert!(schedule.calendar_is_custom());
     assert_eq!(schedule.calendar().to_config(), custom_calendar.to_config());
 }
 
+#[test]
+fn new_with_metadata_and_configures_a_custom_calendar() {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 3, 3),
+        project_end_date: d(2025, 3, 31),
+        ..ScheduleMetadata::default()
+    };
+    let mut schedule = Schedule::new_with_metadata_and(metadata, |calendar| {
+        calendar.add_holiday(d(2025, 3, 5));
+    });
+    assert!(schedule.calendar_is_custom());
+    assert!(!schedule.calendar().is_available(d(2025, 3, 5)));
+    // The default holidays for the year are still there.
+    assert!(!schedule.calendar().is_available(d(2025, 7, 4)));
+
+    schedule.upsert_task(1, "Survey", 3, None).unwrap();
+    schedule.refresh().unwrap();
+    // Monday plus three working days, skipping Wednesday the 5th.
+    let survey = schedule.find_task(1).unwrap().unwrap();
+    assert_eq!(survey.early_finish, Some(d(2025, 3, 7)));
+}
+
 #[test]
 fn set_metadata_regenerates_default_calendar_when_not_custom() {
     let mut schedule = Schedule::new();
@@ -241,7 +442,7 @@ fn upsert_task_record_rejects_invalid_progress_combination() {
 fn apply_rationale_template_updates_task() {
     let mut schedule = Schedule::new();
     schedule.upsert_task(1, "Task", 5, None).unwrap();
-    schedule
+    let returned = schedule
         .apply_rationale_template(1, ProgressRationaleTemplate::FiftyFifty)
         .unwrap();
     let task = schedule
@@ -253,6 +454,7 @@ fn apply_rationale_template_updates_task() {
         ProgressMeasurement::PreDefinedRationale
     );
     assert_eq!(task.pre_defined_rationale.len(), 2);
+    assert_eq!(returned, task);
 }
 
 #[test]
@@ -309,3 +511,1023 @@ fn set_project_dates_updates_calendar_when_not_custom() {
     assert_eq!(schedule.project_end_date(), d(2026, 12, 31));
     assert!(!schedule.calendar().is_available(d(2026, 12, 25)));
 }
+
+#[test]
+fn delete_tasks_removes_several_and_cleans_references() {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 2, 28),
+        ..ScheduleMetadata::default()
+    };
+    let mut schedule = Schedule::new_with_metadata(metadata);
+    schedule.upsert_task(1, "Survey", 2, None).unwrap();
+    schedule.upsert_task(2, "Design", 5, Some(vec![1])).unwrap();
+    schedule.upsert_task(3, "Permit", 4, Some(vec![1])).unwrap();
+    schedule
+        .upsert_task(4, "Build", 3, Some(vec![2, 3]))
+        .unwrap();
+    schedule.upsert_task(5, "Procure", 2, None).unwrap();
+    schedule.refresh().unwrap();
+    let build_before = schedule.find_task(4).unwrap().unwrap();
+    let revisions_before: Vec<u64> = schedule
+        .tasks()
+        .unwrap()
+        .iter()
+        .map(|t| t.revision)
+        .collect();
+
+    let removed = schedule.delete_tasks(&[1, 2, 99]).unwrap();
+    assert_eq!(removed, 2);
+
+    let remaining: Vec<i32> = schedule.tasks().unwrap().iter().map(|t| t.id).collect();
+    assert_eq!(remaining, vec![3, 4, 5]);
+    // Only tasks that lost a link count as edited.
+    let revisions: Vec<u64> = schedule
+        .tasks()
+        .unwrap()
+        .iter()
+        .map(|t| t.revision)
+        .collect();
+    assert_eq!(
+        revisions,
+        vec![
+            revisions_before[2] + 1,
+            revisions_before[3] + 1,
+            revisions_before[4]
+        ]
+    );
+    let permit = schedule.find_task(3).unwrap().unwrap();
+    let build = schedule.find_task(4).unwrap().unwrap();
+    assert!(permit.predecessors.is_empty());
+    assert_eq!(build.predecessors, vec![3]);
+    assert_eq!(permit.successors, vec![4]);
+
+    // Permit now starts the project and Build follows it directly.
+    assert_eq!(permit.early_start, Some(d(2025, 1, 6)));
+    assert!(build.early_start < build_before.early_start);
+    assert!(build.early_start > permit.early_finish);
+
+    assert_eq!(schedule.delete_tasks(&[1, 2]).unwrap(), 0);
+}
+
+#[test]
+fn rename_task_id_rewrites_links_and_parents() {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 2, 28),
+        ..ScheduleMetadata::default()
+    };
+    let mut schedule = Schedule::new_with_metadata(metadata);
+    schedule.upsert_task(1, "Phase 1", 2, None).unwrap();
+    let mut child = Task::new(2, "Survey", 2);
+    child.parent_id = Some(1);
+    schedule.upsert_task_record(child).unwrap();
+    schedule.upsert_task(3, "Build", 3, Some(vec![1])).unwrap();
+    schedule.refresh().unwrap();
+    let build_before = schedule.find_task(3).unwrap().unwrap();
+
+    schedule.rename_task_id(1, 10).unwrap();
+
+    assert!(schedule.find_task(1).unwrap().is_none());
+    let phase = schedule.find_task(10).unwrap().unwrap();
+    assert_eq!(phase.name, "Phase 1");
+    assert_eq!(phase.successors, vec![3]);
+    let survey = schedule.find_task(2).unwrap().unwrap();
+    assert_eq!(survey.parent_id, Some(10));
+    let build = schedule.find_task(3).unwrap().unwrap();
+    assert_eq!(build.predecessors, vec![10]);
+    assert_eq!(build.early_start, build_before.early_start);
+
+    let err = schedule.rename_task_id(2, 3).unwrap_err();
+    assert!(err.to_string().contains("task 3 already exists"));
+    let err = schedule.rename_task_id(1, 4).unwrap_err();
+    assert!(err.to_string().contains("task 1 not found"));
+}
+
+#[test]
+fn missing_tasks_are_reported_as_task_not_found() {
+    let mut schedule = Schedule::new();
+    schedule.upsert_task(1, "Survey", 2, None).unwrap();
+
+    let err = schedule.record_progress(7, d(2025, 1, 6), 0.5).unwrap_err();
+    assert_eq!(TaskNotFound::find(&err), Some(TaskNotFound::task(7)));
+    assert_eq!(err.to_string(), "task 7 not found");
+
+    let err = schedule.add_predecessor(1, 9).unwrap_err();
+    assert_eq!(TaskNotFound::find(&err), Some(TaskNotFound::predecessor(9)));
+    assert_eq!(err.to_string(), "predecessor 9 not found");
+
+    // Other failures are not mistaken for a missing task.
+    let err = schedule.add_predecessor(1, 1).unwrap_err();
+    assert_eq!(TaskNotFound::find(&err), None);
+}
+
+#[test]
+fn refresh_summary_display_lists_critical_chain_and_variance() {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 3, 3),
+        project_end_date: d(2025, 3, 14),
+        ..ScheduleMetadata::default()
+    };
+    let mut schedule = Schedule::new_with_metadata(metadata);
+    schedule.upsert_task(1, "Survey", 2, None).unwrap();
+    schedule.upsert_task(2, "Design", 4, Some(vec![1])).unwrap();
+    schedule.upsert_task(3, "Build", 1, Some(vec![2])).unwrap();
+    let summary = schedule.refresh().unwrap();
+
+    let text = summary.to_string();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[0], "Tasks: 3 (3 critical)");
+    assert!(lines.contains(&"Critical path: 1 -> 2 -> 3"));
+    assert!(lines.contains(&"Variance: 0 positive, 0 negative, 0 on track"));
+}
+
+#[test]
+fn shift_project_moves_every_early_date() {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 3, 3),
+        project_end_date: d(2025, 3, 31),
+        ..ScheduleMetadata::default()
+    };
+    let mut schedule = Schedule::new_with_metadata(metadata);
+    schedule.upsert_task(1, "Survey", 2, None).unwrap();
+    schedule.upsert_task(2, "Design", 4, Some(vec![1])).unwrap();
+    schedule.upsert_task(3, "Build", 3, Some(vec![2])).unwrap();
+    schedule.refresh().unwrap();
+    let before = schedule.tasks().unwrap();
+
+    schedule.shift_project(d(2025, 3, 10)).unwrap();
+
+    assert_eq!(schedule.project_start_date(), d(2025, 3, 10));
+    for (old, new) in before.iter().zip(schedule.tasks().unwrap()) {
+        assert_eq!(
+            new.early_start,
+            old.early_start.map(|date| date + Duration::days(7))
+        );
+        assert_eq!(
+            new.early_finish,
+            old.early_finish.map(|date| date + Duration::days(7))
+        );
+    }
+
+    // Three more weeks push Build past the unchanged end date.
+    let shifted = schedule.tasks().unwrap();
+    assert!(schedule.shift_project(d(2025, 3, 31)).is_err());
+    assert_eq!(schedule.project_start_date(), d(2025, 3, 10));
+    assert_eq!(schedule.tasks().unwrap(), shifted);
+}
+
+#[test]
+fn shift_all_working_days_moves_baseline_and_actual_dates() {
+    let mut schedule = Schedule::new();
+    let mut task = Task::new(1, "Survey", 2);
+    task.baseline_start = Some(d(2025, 3, 6));
+    task.baseline_finish = Some(d(2025, 3, 7));
+    task.actual_start = Some(d(2025, 3, 7));
+    schedule.upsert_task_record(task).unwrap();
+
+    schedule.shift_all_working_days(2).unwrap();
+    let task = schedule.find_task(1).unwrap().unwrap();
+    // Thursday and Friday move past the weekend.
+    assert_eq!(task.baseline_start, Some(d(2025, 3, 10)));
+    assert_eq!(task.baseline_finish, Some(d(2025, 3, 11)));
+    assert_eq!(task.actual_start, Some(d(2025, 3, 11)));
+    assert_eq!(task.actual_finish, None);
+
+    schedule.shift_all_working_days(-2).unwrap();
+    let task = schedule.find_task(1).unwrap().unwrap();
+    assert_eq!(task.baseline_start, Some(d(2025, 3, 6)));
+    assert_eq!(task.actual_start, Some(d(2025, 3, 7)));
+}
+
+#[test]
+fn semantically_equals_ignores_insert_order_and_computed_fields() {
+    let mut forward = Schedule::new();
+    forward.upsert_task(1, "Design", 3, None).unwrap();
+    forward.upsert_task(2, "Permit", 2, None).unwrap();
+    forward
+        .upsert_task(3, "Build", 4, Some(vec![1, 2]))
+        .unwrap();
+    forward.refresh().unwrap();
+
+    let mut reversed = Schedule::new();
+    reversed
+        .upsert_task(3, "Build", 4, Some(vec![2, 1]))
+        .unwrap();
+    reversed.upsert_task(2, "Permit", 2, None).unwrap();
+    reversed.upsert_task(1, "Design", 3, None).unwrap();
+
+    assert!(forward.semantically_equals(&reversed));
+    assert!(reversed.semantically_equals(&forward));
+
+    reversed.upsert_task(2, "Permit", 5, None).unwrap();
+    assert!(!forward.semantically_equals(&reversed));
+
+    let mut renamed = forward.fork();
+    renamed.set_project_name("Other");
+    assert!(!forward.semantically_equals(&renamed));
+}
+
+#[test]
+fn compact_ids_renumbers_densely_and_keeps_links() {
+    let mut schedule = Schedule::new();
+    schedule.upsert_task(9, "Test", 2, Some(vec![5])).unwrap();
+    schedule.upsert_task(2, "Phase", 1, None).unwrap();
+    let mut build = Task::new(5, "Build", 3);
+    build.predecessors = vec![2];
+    build.parent_id = Some(2);
+    schedule.upsert_task_record(build).unwrap();
+    schedule.refresh().unwrap();
+
+    let mapping = schedule.compact_ids().unwrap();
+
+    assert_eq!(
+        mapping,
+        std::collections::HashMap::from([(2, 1), (5, 2), (9, 3)])
+    );
+    let tasks = schedule.tasks().unwrap();
+    let ids: Vec<i32> = tasks.iter().map(|task| task.id).collect();
+    assert_eq!(ids, vec![3, 1, 2]);
+    let build = schedule.find_task(2).unwrap().unwrap();
+    assert_eq!(build.name, "Build");
+    assert_eq!(build.predecessors, vec![1]);
+    assert_eq!(build.successors, vec![3]);
+    assert_eq!(build.parent_id, Some(1));
+    assert_eq!(
+        schedule.find_task(3).unwrap().unwrap().predecessors,
+        vec![2]
+    );
+    assert!(schedule.find_task(9).unwrap().is_none());
+}
+
+#[test]
+fn generate_wbs_codes_follows_parent_hierarchy() {
+    let mut schedule = Schedule::new();
+    let tasks = [
+        (1, "Phase 1", None),
+        (2, "Phase 2", None),
+        (3, "Survey", Some(1)),
+        (4, "Excavate", Some(2)),
+        (5, "Design", Some(1)),
+        (6, "Pour", Some(2)),
+    ];
+    for (id, name, parent) in tasks {
+        let mut task = Task::new(id, name, 1);
+        task.parent_id = parent;
+        task.wbs_code = Some("manual".to_string());
+        schedule.upsert_task_record(task).unwrap();
+    }
+
+    schedule.generate_wbs_codes().unwrap();
+
+    let codes: Vec<(i32, Option<String>)> = schedule
+        .tasks()
+        .unwrap()
+        .into_iter()
+        .map(|task| (task.id, task.wbs_code))
+        .collect();
+    let expected = [
+        (1, "1"),
+        (2, "2"),
+        (3, "1.1"),
+        (4, "2.1"),
+        (5, "1.2"),
+        (6, "2.2"),
+    ];
+    assert_eq!(
+        codes,
+        expected
+            .iter()
+            .map(|(id, code)| (*id, Some(code.to_string())))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn generate_wbs_codes_rejects_parent_cycles() {
+    let mut schedule = Schedule::new();
+    schedule.upsert_task(1, "Top", 1, None).unwrap();
+    for (id, parent) in [(2, 3), (3, 2)] {
+        let mut task = Task::new(id, "Loop", 1);
+        task.parent_id = Some(parent);
+        schedule.upsert_task_record(task).unwrap();
+    }
+
+    let err = schedule.generate_wbs_codes().unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("parent_id cycle detected at task 2")
+    );
+    assert_eq!(schedule.find_task(1).unwrap().unwrap().wbs_code, None);
+}
+
+#[test]
+fn fork_edits_leave_original_untouched() {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 2, 28),
+        ..ScheduleMetadata::default()
+    };
+    let mut original = Schedule::new_with_metadata(metadata);
+    original.upsert_task(1, "Design", 5, None).unwrap();
+    original.upsert_task(2, "Build", 3, Some(vec![1])).unwrap();
+    original.refresh().unwrap();
+    let before = original.tasks().unwrap();
+
+    let mut fork = original.fork();
+    fork.update_task_duration(1, 10).unwrap();
+    fork.upsert_task(3, "Test", 2, Some(vec![2])).unwrap();
+    fork.set_project_name("What-if");
+    fork.refresh().unwrap();
+
+    assert_eq!(fork.dataframe().height(), 3);
+    assert_ne!(
+        fork.find_task(2).unwrap().unwrap().early_start,
+        before[1].early_start
+    );
+    assert_eq!(original.dataframe().height(), 2);
+    assert_eq!(original.tasks().unwrap(), before);
+    assert_eq!(original.project_name(), "New Project");
+}
+
+#[test]
+fn iter_tasks_matches_collected_tasks() {
+    let mut schedule = Schedule::new();
+    schedule.upsert_task(3, "T3", 1, None).unwrap();
+    schedule.upsert_task(1, "T1", 2, None).unwrap();
+    schedule.upsert_task(2, "T2", 4, Some(vec![1, 3])).unwrap();
+    schedule.refresh().unwrap();
+
+    let collected = schedule.tasks().unwrap();
+    let streamed: Vec<Task> = schedule.iter_tasks().collect::<Result<_, _>>().unwrap();
+
+    assert_eq!(streamed, collected);
+    assert_eq!(
+        streamed.iter().map(|task| task.id).collect::<Vec<_>>(),
+        vec![3, 1, 2]
+    );
+}
+
+#[test]
+fn refresh_matches_individual_passes_on_large_network() {
+    let build = || {
+        let mut schedule = Schedule::new();
+        schedule
+            .set_project_dates(d(2025, 1, 6), d(2030, 12, 31))
+            .unwrap();
+        for id in 1..=300 {
+            let preds = match id {
+                1 => None,
+                _ if id % 7 == 0 => Some(vec![id - 1, id / 2]),
+                _ => Some(vec![id - 1]),
+            };
+            schedule
+                .upsert_task(id, &format!("T{id}"), (id % 4) as i64, preds)
+                .unwrap();
+        }
+        schedule
+    };
+
+    let mut refreshed = build();
+    refreshed.refresh().unwrap();
+
+    let mut stepped = build();
+    stepped.forward_pass().unwrap();
+    stepped.backward_pass().unwrap();
+
+    let refreshed_tasks = refreshed.tasks().unwrap();
+    let stepped_tasks = stepped.tasks().unwrap();
+    for (a, b) in refreshed_tasks.iter().zip(stepped_tasks.iter()) {
+        assert_eq!(a.early_start, b.early_start, "task {}", a.id);
+        assert_eq!(a.early_finish, b.early_finish, "task {}", a.id);
+        assert_eq!(a.late_start, b.late_start, "task {}", a.id);
+        assert_eq!(a.late_finish, b.late_finish, "task {}", a.id);
+        assert_eq!(a.total_float, b.total_float, "task {}", a.id);
+    }
+}
+
+fn bulk_tasks(count: i32) -> Vec<Task> {
+    (1..=count)
+        .map(|id| {
+            let mut task = Task::new(id, format!("T{id}"), (id % 5) as i64);
+            task.predecessors = match id {
+                1 => Vec::new(),
+                _ if id % 7 == 0 => vec![id - 1, id / 2],
+                _ => vec![id - 1],
+            };
+            if id % 3 == 0 {
+                task.baseline_start = Some(d(2025, 1, 6));
+                task.baseline_finish = Some(d(2025, 2, 3));
+                task.deadline = Some(d(2027, 6, 30));
+            }
+            if id % 4 == 0 {
+                task.task_notes = Some(format!("note {id}"));
+                task.task_attachments = vec![format!("spec-{id}.pdf")];
+                task.custom_fields.insert("area".into(), "north".into());
+                task.resource_allocations = vec![ResourceAllocation::new("crew", 1.0)];
+            }
+            if id > 1 && id % 10 == 1 {
+                task.parent_id = Some(id - 1);
+            }
+            task.priority = Some(id % 3);
+            task.percent_complete = Some(f64::from(id % 2) * 0.5);
+            task
+        })
+        .collect()
+}
+
+#[test]
+fn from_tasks_matches_upsert_loop() {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2030, 12, 31),
+        ..ScheduleMetadata::default()
+    };
+    let tasks = bulk_tasks(400);
+
+    let mut looped = Schedule::new_with_metadata(metadata.clone());
+    for task in tasks.clone() {
+        looped.upsert_task_record(task).unwrap();
+    }
+    looped.refresh().unwrap();
+
+    let calendar = looped.calendar().clone();
+    let bulk = Schedule::from_tasks(metadata, calendar, tasks).unwrap();
+
+    assert!(!bulk.calendar_is_custom());
+    assert!(bulk.dataframe().equals_missing(looped.dataframe()));
+    assert_eq!(bulk.tasks().unwrap(), looped.tasks().unwrap());
+}
+
+#[test]
+fn from_tasks_rejects_invalid_collection() {
+    let tasks = vec![Task::new(1, "A", 1), Task::new(1, "B", 2)];
+    let metadata = ScheduleMetadata::default();
+    let calendar = Schedule::new().calendar().clone();
+    assert!(Schedule::from_tasks(metadata, calendar, tasks).is_err());
+}
+
+#[test]
+fn from_tasks_accepts_empty_collection() {
+    let metadata = ScheduleMetadata::default();
+    let calendar = WorkCalendar::from_config(&WorkCalendarConfig::new(
+        [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu],
+        Vec::new(),
+    ));
+    let schedule = Schedule::from_tasks(metadata, calendar, Vec::new()).unwrap();
+    assert_eq!(schedule.dataframe().height(), 0);
+    assert_eq!(
+        schedule.dataframe().schema(),
+        Schedule::new().dataframe().schema()
+    );
+    assert!(schedule.calendar_is_custom());
+}
+
+#[test]
+fn single_task_edits_match_rebuilt_table() {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2030, 12, 31),
+        ..ScheduleMetadata::default()
+    };
+    let mut schedule = Schedule::new_with_metadata(metadata);
+    for task in bulk_tasks(120) {
+        schedule.upsert_task_record(task).unwrap();
+    }
+    schedule.refresh().unwrap();
+    let mut expected = schedule.tasks().unwrap();
+
+    // Enough edits to push the touched columns past the rechunk threshold.
+    for round in 0..150 {
+        let position = (round * 37) % expected.len();
+        let task = &mut expected[position];
+        task.name = format!("edited {round}");
+        task.task_notes = Some(format!("round {round}"));
+        task.task_attachments = vec![format!("r{round}.pdf")];
+        task.custom_fields.insert("round".into(), round.to_string());
+        task.priority = Some(round as i32);
+        task.percent_complete = Some(0.25);
+        task.baseline_finish = Some(d(2025, 3, 3));
+        task.wbs_code = Some(format!("1.{round}"));
+        schedule.upsert_task_record(task.clone()).unwrap();
+        task.revision += 1;
+    }
+
+    assert_eq!(schedule.tasks().unwrap(), expected);
+    let rebuilt = Task::tasks_to_dataframe(&expected).unwrap();
+    assert!(schedule.dataframe().equals_missing(&rebuilt));
+    for task in &expected {
+        assert_eq!(schedule.find_task(task.id).unwrap().as_ref(), Some(task));
+    }
+}
+
+#[test]
+fn row_lookup_follows_deletes_and_inserts() {
+    let mut schedule = Schedule::new();
+    for id in 1..=5 {
+        schedule
+            .upsert_task(id, &format!("T{id}"), 1, None)
+            .unwrap();
+    }
+    assert!(schedule.delete_task(2).unwrap());
+    schedule.upsert_task(6, "T6", 1, None).unwrap();
+    schedule.set_custom_field(5, "zone", "east").unwrap();
+
+    assert!(schedule.find_task(2).unwrap().is_none());
+    assert_eq!(schedule.task_revision(2).unwrap(), None);
+    for id in [1, 3, 4, 5, 6] {
+        assert_eq!(schedule.find_task(id).unwrap().unwrap().id, id);
+    }
+    let task5 = schedule.find_task(5).unwrap().unwrap();
+    assert_eq!(
+        task5.custom_fields.get("zone").map(String::as_str),
+        Some("east")
+    );
+    assert!(
+        schedule
+            .find_task(4)
+            .unwrap()
+            .unwrap()
+            .custom_fields
+            .is_empty()
+    );
+}
+
+#[test]
+fn forward_pass_extends_default_calendar_into_uncovered_years() {
+    let mut schedule = Schedule::new_with_year_range(2025, 2025);
+    schedule.upsert_task(1, "Long haul", 300, None).unwrap();
+    assert!(schedule.calendar().is_available(d(2026, 1, 1)));
+
+    schedule.forward_pass().unwrap();
+
+    assert!(!schedule.calendar().is_available(d(2026, 1, 1)));
+    assert_eq!(schedule.calendar().holiday_years(), Some((2025, 2026)));
+    let task = schedule.find_task(1).unwrap().unwrap();
+    let covering = WorkCalendar::with_year_range(2025, 2026);
+    assert_eq!(
+        task.early_finish,
+        Some(covering.find_next_available(task.early_start.unwrap(), 300))
+    );
+    assert!(schedule.calendar_coverage_gap().unwrap().is_none());
+}
+
+#[test]
+fn refresh_warns_when_custom_calendar_misses_years() {
+    let mut schedule = Schedule::new();
+    schedule
+        .set_project_dates(d(2025, 1, 6), d(2027, 12, 31))
+        .unwrap();
+    schedule
+        .set_calendar(WorkCalendar::with_year_range(2025, 2025))
+        .unwrap();
+    schedule.upsert_task(1, "Long haul", 400, None).unwrap();
+
+    let summary = schedule.refresh().unwrap();
+
+    let gap = summary
+        .calendar_coverage_gap
+        .clone()
+        .expect("coverage gap reported");
+    assert_eq!(
+        (gap.first_holiday_year, gap.last_holiday_year),
+        (2025, 2025)
+    );
+    assert_eq!(gap.schedule_start, d(2025, 1, 6));
+    assert_eq!(gap.schedule_finish, d(2027, 12, 31));
+    assert!(
+        summary
+            .to_cli_summary()
+            .contains("calendar_holidays=2025..2025")
+    );
+    // Custom calendars are reported, never changed.
+    assert!(schedule.calendar().is_available(d(2026, 1, 1)));
+    assert_eq!(
+        summary.project_working_days,
+        schedule
+            .calendar()
+            .count_available_days(d(2025, 1, 6), d(2027, 12, 31))
+    );
+}
+
+#[test]
+fn record_progress_answers_as_of_queries() {
+    let mut schedule = Schedule::new();
+    schedule.upsert_task(1, "Pour", 5, None).unwrap();
+    schedule.record_progress(1, d(2025, 3, 14), 0.6).unwrap();
+    schedule.record_progress(1, d(2025, 3, 7), 0.25).unwrap();
+
+    let task = schedule.find_task(1).unwrap().unwrap();
+    assert_eq!(task.percent_complete_as_of(d(2025, 3, 6)), None);
+    assert_eq!(task.percent_complete_as_of(d(2025, 3, 7)), Some(0.25));
+    assert_eq!(task.percent_complete_as_of(d(2025, 3, 12)), Some(0.25));
+    assert_eq!(task.percent_complete_as_of(d(2025, 3, 20)), Some(0.6));
+    assert_eq!(task.percent_complete, None);
+
+    assert!(schedule.record_progress(1, d(2025, 3, 21), 1.5).is_err());
+    assert!(schedule.record_progress(99, d(2025, 3, 21), 0.5).is_err());
+
+    // Updating the task without a log keeps the recorded history.
+    let mut renamed = task.clone();
+    renamed.name = "Pour slab".into();
+    renamed.progress_log.clear();
+    schedule.upsert_task_record(renamed).unwrap();
+    let task = schedule.find_task(1).unwrap().unwrap();
+    assert_eq!(task.progress_log.len(), 2);
+}
+
+#[test]
+fn preview_duration_change_reports_downstream_shift_without_editing() {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 2, 28),
+        ..ScheduleMetadata::default()
+    };
+    let mut schedule = Schedule::new_with_metadata(metadata);
+    schedule.upsert_task(1, "T1", 2, None).unwrap();
+    schedule.upsert_task(2, "T2", 2, Some(vec![1])).unwrap();
+    schedule.upsert_task(3, "T3", 1, None).unwrap();
+    schedule.refresh().unwrap();
+    let before = schedule.tasks().unwrap();
+
+    let diff = schedule.preview_duration_change(1, 5).unwrap();
+
+    let t2 = diff.changed.iter().find(|c| c.task_id == 2).unwrap();
+    assert_eq!(t2.early_finish_before, Some(d(2025, 1, 13)));
+    assert_eq!(t2.early_finish_after, Some(d(2025, 1, 16)));
+    assert_eq!(t2.finish_shift_days, Some(3));
+    assert_eq!(diff.project_finish_after, Some(d(2025, 1, 16)));
+    assert!(diff.added_task_ids.is_empty() && diff.removed_task_ids.is_empty());
+    assert_eq!(schedule.tasks().unwrap(), before);
+    assert!(schedule.diff(&schedule.fork()).unwrap().is_empty());
+    assert!(schedule.preview_duration_change(99, 1).is_err());
+}
+
+#[test]
+fn current_summary_matches_last_refresh_without_recomputing() {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 2, 28),
+        ..ScheduleMetadata::default()
+    };
+    let mut schedule = Schedule::new_with_metadata(metadata);
+    schedule.set_status_date(Some(d(2025, 1, 6)));
+    schedule.upsert_task(1, "T1", 2, None).unwrap();
+    schedule.upsert_task(2, "T2", 3, Some(vec![1])).unwrap();
+    let mut late = Task::new(3, "T3", 1);
+    late.baseline_finish = Some(d(2025, 1, 6));
+    late.actual_finish = Some(d(2025, 1, 8));
+    late.deadline = Some(d(2025, 1, 6));
+    schedule.upsert_task_record(late).unwrap();
+
+    let refreshed = schedule.refresh().unwrap();
+    let before = schedule.tasks().unwrap();
+
+    assert_eq!(schedule.current_summary().unwrap(), refreshed);
+    assert_eq!(schedule.tasks().unwrap(), before);
+}
+
+#[test]
+fn project_finish_ignores_hammock_tasks_by_default() {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 1, 17),
+        ..ScheduleMetadata::default()
+    };
+    let mut schedule = Schedule::new_with_metadata(metadata);
+    schedule.upsert_task(1, "T1", 2, None).unwrap();
+    schedule.upsert_task(2, "T2", 3, Some(vec![1])).unwrap();
+    schedule.upsert_task(3, "T3", 1, Some(vec![1])).unwrap();
+    schedule.upsert_task(4, "T4", 2, Some(vec![2, 3])).unwrap();
+    let oversight = Task::builder(5, "Site supervision", 20)
+        .task_kind(TaskKind::Hammock)
+        .build()
+        .unwrap();
+    schedule.upsert_task_record(oversight).unwrap();
+
+    let summary = schedule.refresh().unwrap();
+
+    let hammock = schedule.find_task(5).unwrap().unwrap();
+    assert!(hammock.early_finish.unwrap() > d(2025, 1, 17));
+    assert_eq!(hammock.task_kind, TaskKind::Hammock);
+    assert_eq!(summary.latest_finish, Some(d(2025, 1, 17)));
+
+    schedule.set_finish_includes_support_tasks(true);
+    let err = schedule.refresh().unwrap_err();
+    assert!(err.to_string().contains("precedes schedule finish"));
+}
+
+#[test]
+fn project_buffer_extends_finish_by_its_length() {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 1, 17),
+        ..ScheduleMetadata::default()
+    };
+    let mut schedule = Schedule::new_with_metadata(metadata.clone());
+    schedule.upsert_task(1, "T1", 2, None).unwrap();
+    schedule.upsert_task(2, "T2", 3, Some(vec![1])).unwrap();
+    schedule.upsert_task(3, "T3", 1, Some(vec![1])).unwrap();
+    schedule.upsert_task(4, "T4", 2, Some(vec![2, 3])).unwrap();
+    let before = schedule.refresh().unwrap();
+    assert_eq!(before.latest_finish, Some(d(2025, 1, 17)));
+
+    // Half of the 7-day critical path 1 -> 2 -> 4, rounded up.
+    let buffer_id = schedule.insert_project_buffer(0.5).unwrap();
+    assert_eq!(buffer_id, 5);
+    let buffer = schedule.find_task(buffer_id).unwrap().unwrap();
+    assert_eq!(buffer.task_kind, TaskKind::Buffer);
+    assert_eq!(buffer.duration_days, 4);
+    assert_eq!(buffer.predecessors, vec![4]);
+
+    // T3 feeds the critical T4; a buffer now sits between them.
+    assert_eq!(schedule.insert_feeding_buffers(0.5).unwrap(), vec![6]);
+    assert_eq!(
+        schedule.find_task(6).unwrap().unwrap().predecessors,
+        vec![3]
+    );
+    assert_eq!(
+        schedule.find_task(4).unwrap().unwrap().predecessors,
+        vec![2, 6]
+    );
+
+    schedule
+        .set_metadata(ScheduleMetadata {
+            project_end_date: d(2025, 1, 31),
+            ..metadata
+        })
+        .unwrap();
+    let after = schedule.refresh().unwrap();
+    // Four working days after Friday the 17th, skipping MLK Day (the 20th).
+    assert_eq!(after.latest_finish, Some(d(2025, 1, 27)));
+    let buffer = schedule.find_task(buffer_id).unwrap().unwrap();
+    assert_eq!(buffer.early_start, Some(d(2025, 1, 21)));
+}
+
+#[test]
+fn revision_bumps_once_per_change() {
+    let mut schedule = Schedule::new();
+    assert_eq!(schedule.revision(), 0);
+    assert_eq!(schedule.modified_at(), None);
+
+    // One call is one change, however many columns and passes it runs.
+    schedule.upsert_task(1, "Design", 2, None).unwrap();
+    assert_eq!(schedule.revision(), 1);
+    assert!(schedule.modified_at().is_some());
+    let mut build = Task::new(2, "Build", 3);
+    build.predecessors = vec![1];
+    schedule.upsert_task_record(build.clone()).unwrap();
+    assert_eq!(schedule.revision(), 2);
+    build.duration_days = 4;
+    schedule.upsert_task_record(build).unwrap();
+    assert_eq!(schedule.revision(), 3);
+    schedule.refresh().unwrap();
+    assert_eq!(schedule.revision(), 4);
+    schedule.record_progress(1, d(2025, 1, 8), 0.5).unwrap();
+    assert_eq!(schedule.revision(), 5);
+    schedule
+        .set_calendar(WorkCalendar::with_year_range(2024, 2026))
+        .unwrap();
+    assert_eq!(schedule.revision(), 6);
+    assert!(schedule.delete_task(2).unwrap());
+    assert_eq!(schedule.revision(), 7);
+
+    // Reads and no-op edits leave it alone.
+    schedule.tasks().unwrap();
+    schedule.current_summary().unwrap();
+    assert!(!schedule.delete_task(2).unwrap());
+    assert_eq!(schedule.revision(), 7);
+    assert_eq!(schedule.fork().revision(), 7);
+
+    schedule.set_project_name("Renamed");
+    assert_eq!(schedule.revision(), 8);
+
+    // Settings count as changes, and metadata passed in cannot rewind it.
+    schedule.set_status_date(Some(d(2025, 1, 15)));
+    schedule.set_forecast_variance(true);
+    schedule.set_scheduling_mode(schedule_tool::SchedulingMode::Alap);
+    assert_eq!(schedule.revision(), 11);
+    let stale = ScheduleMetadata {
+        revision: 0,
+        ..schedule.metadata().clone()
+    };
+    schedule.set_metadata(stale).unwrap();
+    assert_eq!(schedule.revision(), 12);
+    assert_eq!(schedule.metadata().revision, schedule.revision());
+}
+
+#[test]
+fn reorder_rows_follows_the_given_id_order() {
+    let mut schedule = Schedule::new();
+    schedule.upsert_task(1, "Design", 2, None).unwrap();
+    schedule.upsert_task(2, "Build", 3, Some(vec![1])).unwrap();
+    schedule.upsert_task(3, "Test", 1, Some(vec![2])).unwrap();
+    schedule.refresh().unwrap();
+    let before = schedule.find_task(2).unwrap().unwrap();
+
+    schedule.reorder_rows(&[3, 1, 2]).unwrap();
+    let ids: Vec<i32> = schedule.tasks().unwrap().iter().map(|t| t.id).collect();
+    assert_eq!(ids, vec![3, 1, 2]);
+    assert_eq!(schedule.find_task(2).unwrap().unwrap(), before);
+
+    for bad in [&[3, 1][..], &[3, 1, 2, 4], &[3, 1, 1]] {
+        assert!(schedule.reorder_rows(bad).is_err(), "accepted {bad:?}");
+    }
+    let ids: Vec<i32> = schedule.tasks().unwrap().iter().map(|t| t.id).collect();
+    assert_eq!(ids, vec![3, 1, 2]);
+}
+
+#[cfg(feature = "timezone")]
+#[test]
+fn set_metadata_validates_timezone_names() {
+    let mut schedule = Schedule::new();
+    let metadata = ScheduleMetadata {
+        timezone: Some("Not/AZone".into()),
+        ..ScheduleMetadata::default()
+    };
+
+    let err = schedule.set_metadata(metadata).unwrap_err();
+    assert!(matches!(
+        err,
+        ScheduleMetadataError::UnknownTimezone(ref zone) if zone == "Not/AZone"
+    ));
+    assert_eq!(schedule.metadata().timezone, None);
+
+    let metadata = ScheduleMetadata {
+        timezone: Some("America/New_York".into()),
+        ..ScheduleMetadata::default()
+    };
+    schedule.set_metadata(metadata).unwrap();
+    assert_eq!(
+        schedule.metadata().timezone.as_deref(),
+        Some("America/New_York")
+    );
+}
+
+#[test]
+fn tasks_active_on_uses_early_dates_or_actuals() {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 3, 3),
+        project_end_date: d(2025, 3, 31),
+        ..ScheduleMetadata::default()
+    };
+    let mut schedule = Schedule::new_with_metadata(metadata);
+    schedule.upsert_task(1, "Survey", 3, None).unwrap();
+    schedule.upsert_task(2, "Design", 4, Some(vec![1])).unwrap();
+    schedule.upsert_task(3, "Permit", 2, None).unwrap();
+    schedule.refresh().unwrap();
+
+    let ids = |tasks: Vec<Task>| tasks.iter().map(|task| task.id).collect::<Vec<_>>();
+    // Survey runs 3rd-6th, Permit 3rd-5th and Design 7th-13th.
+    assert_eq!(
+        ids(schedule.tasks_active_on(d(2025, 3, 5)).unwrap()),
+        vec![1, 3]
+    );
+    assert_eq!(
+        ids(schedule.tasks_active_on(d(2025, 3, 6)).unwrap()),
+        vec![1]
+    );
+    assert_eq!(
+        ids(schedule.tasks_active_on(d(2025, 3, 10)).unwrap()),
+        vec![2]
+    );
+    assert!(schedule.tasks_active_on(d(2025, 3, 20)).unwrap().is_empty());
+
+    // Design actually started a day early.
+    let mut design = schedule.find_task(2).unwrap().unwrap();
+    design.actual_start = Some(d(2025, 3, 6));
+    schedule.upsert_task_record(design).unwrap();
+    assert_eq!(
+        ids(schedule
+            .tasks_active_on_with_actuals(d(2025, 3, 6))
+            .unwrap()),
+        vec![1, 2]
+    );
+    assert_eq!(
+        ids(schedule.tasks_active_on(d(2025, 3, 6)).unwrap()),
+        vec![1]
+    );
+}
+
+#[test]
+fn set_calendar_preserving_manual_keeps_pinned_tasks_in_place() {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 3, 3),
+        project_end_date: d(2025, 3, 31),
+        ..ScheduleMetadata::default()
+    };
+    let mut schedule = Schedule::new_with_metadata(metadata);
+    let survey = Task::builder(1, "Survey", 3)
+        .actual(d(2025, 3, 3), Some(d(2025, 3, 6)))
+        .build()
+        .unwrap();
+    schedule.upsert_task_record(survey).unwrap();
+    schedule.upsert_task(2, "Design", 4, Some(vec![1])).unwrap();
+    schedule.upsert_task(3, "Review", 1, Some(vec![2])).unwrap();
+    schedule.refresh().unwrap();
+    assert_eq!(
+        schedule.find_task(3).unwrap().unwrap().early_start,
+        Some(d(2025, 3, 14))
+    );
+
+    let mut calendar = WorkCalendar::with_year_range(2025, 2025);
+    calendar.add_holidays(&[d(2025, 3, 5), d(2025, 3, 11)]);
+    let mut recomputed = schedule.fork();
+    recomputed.set_calendar(calendar.clone()).unwrap();
+    schedule.set_calendar_preserving_manual(calendar).unwrap();
+
+    let early = |schedule: &Schedule, id| {
+        let task = schedule.find_task(id).unwrap().unwrap();
+        (task.early_start.unwrap(), task.early_finish.unwrap())
+    };
+    // Survey keeps its recorded dates despite the holiday on the 5th, and
+    // Design follows them; only the holiday on the 11th moves Review.
+    let survey = schedule.find_task(1).unwrap().unwrap();
+    assert_eq!(survey.actual_start, Some(d(2025, 3, 3)));
+    assert_eq!(early(&schedule, 1), (d(2025, 3, 3), d(2025, 3, 6)));
+    assert_eq!(early(&schedule, 2), (d(2025, 3, 7), d(2025, 3, 14)));
+    assert_eq!(early(&schedule, 3).0, d(2025, 3, 17));
+
+    // The pin was for that recompute only: a plain refresh does not pin.
+    assert!(!schedule.pin_actual_dates());
+    schedule.refresh().unwrap();
+    assert_eq!(early(&schedule, 1), early(&recomputed, 1));
+    assert_eq!(early(&schedule, 2), early(&recomputed, 2));
+
+    // A recompute that fails leaves the setting as it was, too.
+    let mondays = WorkCalendar::from_config(&WorkCalendarConfig::new([Weekday::Mon], Vec::new()));
+    assert!(schedule.set_calendar_preserving_manual(mondays).is_err());
+    assert!(!schedule.pin_actual_dates());
+
+    // A plain calendar swap recomputes Survey as well.
+    assert_eq!(early(&recomputed, 1), (d(2025, 3, 3), d(2025, 3, 7)));
+    assert_eq!(early(&recomputed, 2).0, d(2025, 3, 10));
+}
+
+#[test]
+fn import_tasks_orders_by_dependency_and_matches_ordered_import() {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 3, 3),
+        project_end_date: d(2025, 4, 30),
+        ..ScheduleMetadata::default()
+    };
+    let chain = |id: i32, days: i64, preds: Vec<i32>| {
+        let mut task = Task::new(id, format!("T{id}"), days);
+        task.predecessors = preds;
+        task
+    };
+    let ordered = vec![
+        chain(1, 3, Vec::new()),
+        chain(2, 2, vec![1]),
+        chain(3, 4, vec![1]),
+        chain(4, 1, vec![2, 3]),
+    ];
+    let reversed: Vec<Task> = ordered.iter().rev().cloned().collect();
+
+    let mut expected = Schedule::new_with_metadata(metadata.clone());
+    assert_eq!(expected.import_tasks(ordered).unwrap(), vec![1, 2, 3, 4]);
+    let mut imported = Schedule::new_with_metadata(metadata);
+    assert_eq!(imported.import_tasks(reversed).unwrap(), vec![1, 2, 3, 4]);
+
+    assert!(imported.dataframe().equals_missing(expected.dataframe()));
+    let finish = imported.find_task(4).unwrap().unwrap();
+    assert_eq!(finish.early_start, Some(d(2025, 3, 14)));
+
+    // Re-importing a stored task replaces it in place and bumps its revision.
+    let applied = imported.import_tasks(vec![chain(3, 1, vec![1])]).unwrap();
+    assert_eq!(applied, vec![3]);
+    let shortened = imported.find_task(3).unwrap().unwrap();
+    assert_eq!(shortened.revision, 1);
+    assert_eq!(
+        imported.find_task(4).unwrap().unwrap().early_start,
+        Some(d(2025, 3, 12))
+    );
+}
+
+#[test]
+fn refresh_reports_disconnected_tasks_as_unscheduled() {
+    let mut schedule = Schedule::new_with_metadata(ScheduleMetadata {
+        project_start_date: d(2025, 3, 3),
+        project_end_date: d(2025, 3, 31),
+        ..ScheduleMetadata::default()
+    });
+    schedule.upsert_task(1, "Design", 3, None).unwrap();
+    schedule.upsert_task(2, "Build", 4, Some(vec![1])).unwrap();
+    schedule.upsert_task(3, "Island", 2, None).unwrap();
+    schedule.upsert_task(4, "Kickoff", 0, None).unwrap();
+    let mut oversight = Task::new(5, "Oversight", 10);
+    oversight.task_kind = TaskKind::LevelOfEffort;
+    schedule.upsert_task_record(oversight).unwrap();
+    let summary = schedule.refresh().unwrap();
+
+    // Only the island is reported: milestones and support tasks are not
+    // expected to carry logic.
+    assert_eq!(summary.unscheduled_tasks, vec![3]);
+    assert!(summary.to_cli_summary().contains("unscheduled=1"));
+    // Its dates are still filled in.
+    let island = schedule.find_task(3).unwrap().unwrap();
+    assert_eq!(island.late_finish, Some(d(2025, 3, 31)));
+
+    schedule.add_predecessor(3, 2).unwrap();
+    assert!(schedule.refresh().unwrap().unscheduled_tasks.is_empty());
+}
@@ -2,7 +2,8 @@
 
 use chrono::NaiveDate;
 use schedule_tool::{
-    Schedule, ScheduleMetadata, ScheduleStore, SqliteScheduleStore, Task,
+    DEFAULT_SCHEDULE_NAME, PersistenceError, Schedule, ScheduleMetadata, ScheduleStore,
+    SqliteScheduleStore, Task,
     task::{ProgressMeasurement, RationaleItem},
 };
 use tempfile::NamedTempFile;
@@ -16,10 +17,12 @@ fn sqlite_store_round_trip_schedule() {
     let file = NamedTempFile::new().unwrap();
     let store = SqliteScheduleStore::new(file.path()).unwrap();
 
-    let mut metadata = ScheduleMetadata::default();
-    metadata.project_name = "SQLite Project".into();
-    metadata.project_start_date = d(2025, 1, 6);
-    metadata.project_end_date = d(2025, 2, 28);
+    let metadata = ScheduleMetadata {
+        project_name: "SQLite Project".into(),
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 2, 28),
+        ..ScheduleMetadata::default()
+    };
 
     let mut schedule = Schedule::new_with_metadata(metadata.clone());
     schedule
@@ -49,6 +52,8 @@ fn sqlite_store_round_trip_schedule() {
 
     assert_eq!(loaded.metadata().project_name, "SQLite Project");
     assert_eq!(loaded.metadata().project_start_date, d(2025, 1, 6));
+    assert_eq!(loaded.revision(), schedule.revision());
+    assert_eq!(loaded.modified_at(), schedule.modified_at());
     assert_eq!(loaded.dataframe().height(), 2);
 
     let task = Task::from_dataframe_row(loaded.dataframe(), 1).unwrap();
@@ -67,3 +72,158 @@ fn sqlite_store_round_trip_schedule() {
     );
     assert_eq!(task.percent_complete, Some(0.6));
 }
+
+fn named_schedule(project_name: &str, task_names: &[&str]) -> Schedule {
+    let mut schedule = Schedule::new_with_metadata(ScheduleMetadata {
+        project_name: project_name.into(),
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 3, 31),
+        ..ScheduleMetadata::default()
+    });
+    for (idx, name) in task_names.iter().enumerate() {
+        schedule
+            .upsert_task(idx as i32 + 1, name, 2, None)
+            .expect("insert task");
+    }
+    schedule
+}
+
+fn task_names(schedule: &Schedule) -> Vec<String> {
+    schedule
+        .tasks()
+        .unwrap()
+        .into_iter()
+        .map(|task| task.name)
+        .collect()
+}
+
+#[test]
+fn sqlite_store_keeps_named_schedules_separate() {
+    let file = NamedTempFile::new().unwrap();
+    let store = SqliteScheduleStore::new(file.path()).unwrap();
+
+    store
+        .save_named("bridge", &named_schedule("Bridge", &["Piers", "Deck"]))
+        .unwrap();
+    store
+        .save_named("tunnel", &named_schedule("Tunnel", &["Bore"]))
+        .unwrap();
+    // Re-saving one schedule must not touch the other.
+    store
+        .save_named("bridge", &named_schedule("Bridge v2", &["Survey"]))
+        .unwrap();
+
+    assert_eq!(store.list_schedules().unwrap(), ["bridge", "tunnel"]);
+
+    let bridge = store.load_named("bridge").unwrap().expect("bridge stored");
+    assert_eq!(bridge.metadata().project_name, "Bridge v2");
+    assert_eq!(task_names(&bridge), ["Survey"]);
+
+    let tunnel = store.load_named("tunnel").unwrap().expect("tunnel stored");
+    assert_eq!(tunnel.metadata().project_name, "Tunnel");
+    assert_eq!(task_names(&tunnel), ["Bore"]);
+
+    assert!(store.load_named("canal").unwrap().is_none());
+    assert!(store.load_schedule().unwrap().is_none());
+}
+
+#[test]
+fn sqlite_store_default_schedule_is_named_default() {
+    let file = NamedTempFile::new().unwrap();
+    let store = SqliteScheduleStore::new(file.path()).unwrap();
+
+    store
+        .save_schedule(&named_schedule("Portfolio", &["Kickoff"]))
+        .unwrap();
+
+    assert_eq!(store.list_schedules().unwrap(), [DEFAULT_SCHEDULE_NAME]);
+    let loaded = store.load_named(DEFAULT_SCHEDULE_NAME).unwrap().unwrap();
+    assert_eq!(task_names(&loaded), ["Kickoff"]);
+}
+
+#[test]
+fn sqlite_store_migrates_single_schedule_database() {
+    let file = NamedTempFile::new().unwrap();
+    let task = Task::new(7, "Legacy", 3);
+    {
+        let conn = rusqlite::Connection::open(file.path()).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE schedule_metadata (id INTEGER PRIMARY KEY CHECK (id = 1), metadata_json TEXT NOT NULL);
+             CREATE TABLE tasks (id INTEGER PRIMARY KEY, task_json TEXT NOT NULL);",
+        )
+        .unwrap();
+        let metadata = ScheduleMetadata {
+            project_name: "Old Store".into(),
+            ..ScheduleMetadata::default()
+        };
+        conn.execute(
+            "INSERT INTO schedule_metadata (id, metadata_json) VALUES (1, ?1)",
+            [serde_json::to_string(&metadata).unwrap()],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO tasks (id, task_json) VALUES (7, ?1)",
+            [serde_json::to_string(&task).unwrap()],
+        )
+        .unwrap();
+    }
+
+    let store = SqliteScheduleStore::new(file.path()).unwrap();
+    let loaded = store.load_schedule().unwrap().expect("migrated schedule");
+    assert_eq!(loaded.metadata().project_name, "Old Store");
+    assert_eq!(task_names(&loaded), ["Legacy"]);
+}
+
+#[test]
+fn sqlite_save_tasks_upserts_into_stored_schedule() {
+    let file = NamedTempFile::new().unwrap();
+    let store = SqliteScheduleStore::new(file.path()).unwrap();
+    store
+        .save_schedule(&named_schedule("Batch", &["Original"]))
+        .unwrap();
+
+    let mut renamed = Task::new(1, "Renamed", 2);
+    renamed.percent_complete = Some(0.5);
+    let mut added = Task::new(2, "Added", 4);
+    added.predecessors = vec![1];
+    store.save_tasks(&[renamed, added]).unwrap();
+
+    let loaded = store.load_schedule().unwrap().unwrap();
+    assert_eq!(loaded.metadata().project_name, "Batch");
+    assert_eq!(task_names(&loaded), ["Renamed", "Added"]);
+}
+
+#[test]
+fn sqlite_save_tasks_rolls_back_when_a_later_task_fails() {
+    let file = NamedTempFile::new().unwrap();
+    let store = SqliteScheduleStore::new(file.path()).unwrap();
+
+    // Nothing stored yet: the failed batch must not leave a schedule behind.
+    let batch = [
+        Task::new(1, "First", 1),
+        Task::new(2, "Second", 1),
+        Task::new(3, "Broken", -1),
+    ];
+    let err = store.save_tasks(&batch).unwrap_err();
+    assert!(err.to_string().contains("negative duration"), "{err}");
+    assert!(store.load_schedule().unwrap().is_none());
+    assert!(store.list_schedules().unwrap().is_empty());
+
+    // With a stored schedule, earlier writes in the failed batch are undone.
+    store
+        .save_schedule(&named_schedule("Stored", &["Original"]))
+        .unwrap();
+    let batch = [
+        Task::new(1, "Overwritten", 1),
+        Task::new(2, "Second", 1),
+        Task::new(2, "Duplicate", 1),
+    ];
+    let err = store.save_tasks(&batch).unwrap_err();
+    assert!(
+        matches!(err, PersistenceError::DuplicateTaskId(2)),
+        "{err:?}"
+    );
+    assert_eq!(err.to_string(), "invalid data: duplicate task id 2");
+    let loaded = store.load_schedule().unwrap().unwrap();
+    assert_eq!(task_names(&loaded), ["Original"]);
+}
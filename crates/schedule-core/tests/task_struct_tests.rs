@@ -1,6 +1,6 @@
 use chrono::NaiveDate;
 use schedule_tool::{
-    ProgressRationaleTemplate, ResourceAllocation, Schedule, Task,
+    ProgressRationaleTemplate, ResourceAllocation, Schedule, Task, TaskValidationError,
     task::{ProgressMeasurement, RationaleItem},
 };
 
@@ -27,6 +27,7 @@ fn task_roundtrips_through_schedule_dataframe() {
         RationaleItem::new(2, "Review", 0.5, true),
     ];
     let mut allocation = ResourceAllocation::new("ENG-1", 40.0);
+    allocation.max_units = Some(40.0);
     allocation.role = Some("Designer".into());
     allocation.cost_rate = Some(120.0);
     allocation.notes = Some("Week 1".into());
@@ -75,3 +76,185 @@ fn task_with_rationale_template_builds_predefined_items() {
             .all(|item| !item.is_complete)
     );
 }
+
+#[test]
+fn computed_percent_complete_sums_completed_rationale_weights() {
+    let mut task = Task::new(1, "Weighted", 5);
+    task.progress_measurement = ProgressMeasurement::PreDefinedRationale;
+    task.pre_defined_rationale = vec![
+        RationaleItem::new(1, "Drawings", 0.2, true),
+        RationaleItem::new(2, "Fabrication", 0.5, true),
+        RationaleItem::new(3, "Install", 0.3, false),
+    ];
+    let computed = task.computed_percent_complete().unwrap();
+    assert!((computed - 0.7).abs() < 1e-9);
+
+    let mut plain = Task::new(2, "Plain", 5);
+    plain.percent_complete = Some(0.4);
+    assert_eq!(plain.computed_percent_complete(), Some(0.4));
+
+    let mut schedule = Schedule::new();
+    schedule.upsert_task_record(task).unwrap();
+    schedule.upsert_task_record(plain).unwrap();
+    schedule.refresh().unwrap();
+    assert_eq!(
+        schedule.find_task(1).unwrap().unwrap().percent_complete,
+        None
+    );
+
+    schedule.set_derive_rationale_percent(true);
+    schedule.refresh().unwrap();
+    let derived = schedule
+        .find_task(1)
+        .unwrap()
+        .unwrap()
+        .percent_complete
+        .unwrap();
+    assert!((derived - 0.7).abs() < 1e-9);
+    assert_eq!(
+        schedule.find_task(2).unwrap().unwrap().percent_complete,
+        Some(0.4)
+    );
+}
+
+#[test]
+fn rationale_template_items_preview_without_applying() {
+    let items = ProgressRationaleTemplate::FiftyFifty.items();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0], RationaleItem::new(1, "Phase A", 0.5, false));
+    assert_eq!(items[1], RationaleItem::new(2, "Phase B", 0.5, false));
+}
+
+#[test]
+fn builder_matches_field_by_field_construction() {
+    let built = Task::builder(3, "Install", 4)
+        .predecessors([1, 2])
+        .baseline(d(2025, 2, 3), d(2025, 2, 6))
+        .actual(d(2025, 2, 4), None)
+        .progress(ProgressMeasurement::FiftyFifty, 0.5)
+        .resource(ResourceAllocation::new("CREW-A", 0.5))
+        .parent(1)
+        .wbs_code("1.3")
+        .notes("Weekend access only")
+        .attachment("permit.pdf")
+        .deadline(d(2025, 2, 28))
+        .priority(700)
+        .custom_field("zone", "east")
+        .build()
+        .unwrap();
+
+    let mut expected = Task::new(3, "Install", 4);
+    expected.predecessors = vec![1, 2];
+    expected.baseline_start = Some(d(2025, 2, 3));
+    expected.baseline_finish = Some(d(2025, 2, 6));
+    expected.actual_start = Some(d(2025, 2, 4));
+    expected.progress_measurement = ProgressMeasurement::FiftyFifty;
+    expected.percent_complete = Some(0.5);
+    expected.resource_allocations = vec![ResourceAllocation::new("CREW-A", 0.5)];
+    expected.parent_id = Some(1);
+    expected.wbs_code = Some("1.3".into());
+    expected.task_notes = Some("Weekend access only".into());
+    expected.task_attachments = vec!["permit.pdf".into()];
+    expected.deadline = Some(d(2025, 2, 28));
+    expected.priority = Some(700);
+    expected.custom_fields.insert("zone".into(), "east".into());
+    assert_eq!(built, expected);
+}
+
+#[test]
+fn builder_validates_rationale_weights() {
+    let task = Task::builder(1, "Composite", 3)
+        .rationale([
+            RationaleItem::new(1, "Fabricate", 0.6, true),
+            RationaleItem::new(2, "Install", 0.4, false),
+        ])
+        .build()
+        .unwrap();
+    assert_eq!(
+        task.progress_measurement,
+        ProgressMeasurement::PreDefinedRationale
+    );
+    assert_eq!(task.computed_percent_complete(), Some(0.6));
+
+    let err: TaskValidationError = Task::builder(2, "Composite", 3)
+        .rationale([
+            RationaleItem::new(1, "Fabricate", 0.6, true),
+            RationaleItem::new(2, "Install", 0.6, false),
+        ])
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "task 2 pre_defined_rationale weights must sum to 1.0 (got 1.2000)"
+    );
+}
+
+#[test]
+fn builder_rejects_allocation_units_above_limit_unless_raised() {
+    let err: TaskValidationError = Task::builder(1, "Lift", 2)
+        .resource(ResourceAllocation::new("crane", 1.5))
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "task 1 allocation for 'crane' has allocation_units 1.5 above the limit of 1 (set max_units to allow over-allocation)"
+    );
+
+    let mut allocation = ResourceAllocation::new("crane", 1.5);
+    allocation.max_units = Some(2.0);
+    let task = Task::builder(1, "Lift", 2)
+        .resource(allocation)
+        .build()
+        .unwrap();
+    assert_eq!(task.resource_allocations[0].unit_limit(), 2.0);
+}
+
+#[test]
+fn builder_rejects_duplicate_resource_with_conflicting_units() {
+    let err: TaskValidationError = Task::builder(1, "Pour", 2)
+        .resource(ResourceAllocation::new("crew", 0.5))
+        .resource(ResourceAllocation::new("crew", 1.0))
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "task 1 lists resource 'crew' more than once with conflicting allocation_units, cost_rate or profile"
+    );
+
+    let task = Task::builder(1, "Pour", 2)
+        .resource(ResourceAllocation::new("crew", 0.5))
+        .resource(ResourceAllocation::new("crew", 0.5))
+        .build()
+        .unwrap();
+    assert_eq!(task.resource_allocations.len(), 2);
+}
+
+#[test]
+fn progress_measurement_serde_uses_as_str_tokens() {
+    for measurement in ProgressMeasurement::ALL {
+        let token = measurement.as_str();
+        let json = serde_json::to_string(&measurement).unwrap();
+        assert_eq!(json, format!("\"{token}\""));
+        let parsed: ProgressMeasurement = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, measurement);
+        assert_eq!(ProgressMeasurement::from_str(token), Some(measurement));
+    }
+    assert_eq!(ProgressMeasurement::from_str("100_0"), None);
+}
+
+#[test]
+fn task_display_shows_name_dates_and_criticality() {
+    let mut schedule = Schedule::new();
+    schedule.upsert_task(1, "Design", 3, None).unwrap();
+    assert_eq!(
+        schedule.find_task(1).unwrap().unwrap().to_string(),
+        "#1 Design (3d) unscheduled"
+    );
+
+    schedule.refresh().unwrap();
+    let task = schedule.find_task(1).unwrap().unwrap();
+    let line = task.to_string();
+    assert!(line.contains("Design"));
+    assert!(line.contains(&task.early_start.unwrap().to_string()));
+    assert_eq!(line.ends_with(" critical"), task.is_critical == Some(true));
+}
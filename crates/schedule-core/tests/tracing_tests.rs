@@ -0,0 +1,56 @@
+#![cfg(feature = "tracing")]
+
+use std::sync::{Arc, Mutex};
+
+use chrono::NaiveDate;
+use schedule_tool::{Schedule, ScheduleMetadata};
+use tracing::span::{Attributes, Id};
+use tracing::{Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Records the name and level of every span opened while installed.
+#[derive(Clone, Default)]
+struct SpanCapture(Arc<Mutex<Vec<(&'static str, Level)>>>);
+
+impl<S> Layer<S> for SpanCapture
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+        let meta = attrs.metadata();
+        self.0.lock().unwrap().push((meta.name(), *meta.level()));
+    }
+}
+
+#[test]
+fn refresh_emits_debug_spans_for_each_pass() {
+    let mut s = Schedule::new();
+    s.set_metadata(ScheduleMetadata {
+        project_start_date: NaiveDate::from_ymd_opt(2025, 1, 6).unwrap(),
+        ..ScheduleMetadata::default()
+    })
+    .unwrap();
+    s.upsert_task(1, "T1", 2, None).unwrap();
+    s.upsert_task(2, "T2", 3, Some(vec![1])).unwrap();
+
+    let capture = SpanCapture::default();
+    let subscriber = tracing_subscriber::registry().with(capture.clone());
+    tracing::subscriber::with_default(subscriber, || {
+        s.refresh().unwrap();
+    });
+
+    let spans = capture.0.lock().unwrap().clone();
+    for name in [
+        "refresh",
+        "forward_pass",
+        "backward_pass",
+        "set_schedule_variance",
+        "set_successors_column",
+    ] {
+        assert!(
+            spans.contains(&(name, Level::DEBUG)),
+            "missing debug span {name}, saw {spans:?}"
+        );
+    }
+}
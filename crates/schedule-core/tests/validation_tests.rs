@@ -0,0 +1,149 @@
+use chrono::NaiveDate;
+use schedule_tool::{
+    BaselineDurationMismatch, Schedule, ScheduleMetadata, Task, ValidationSeverity,
+};
+
+fn d(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+fn base_schedule() -> Schedule {
+    let metadata = ScheduleMetadata {
+        project_start_date: d(2025, 1, 6),
+        project_end_date: d(2025, 2, 28),
+        ..ScheduleMetadata::default()
+    };
+    Schedule::new_with_metadata(metadata)
+}
+
+#[test]
+fn validate_reports_clean_schedule_as_empty() {
+    let mut schedule = base_schedule();
+    schedule.upsert_task(1, "T1", 2, None).unwrap();
+    schedule.upsert_task(2, "T2", 3, Some(vec![1])).unwrap();
+    schedule.refresh().unwrap();
+
+    let report = schedule.validate().unwrap();
+    assert!(report.is_empty(), "unexpected issues: {:?}", report.issues);
+    assert!(report.is_valid());
+}
+
+#[test]
+fn validate_reports_independent_problems_together() {
+    let mut schedule = base_schedule();
+    schedule.upsert_task(1, "T1", 2, Some(vec![2])).unwrap();
+    schedule.upsert_task(2, "T2", 3, Some(vec![1])).unwrap();
+    schedule.upsert_task(3, "T3", 1, Some(vec![99])).unwrap();
+
+    let report = schedule.validate().unwrap();
+
+    assert!(!report.is_valid());
+    let cycle = report
+        .errors()
+        .find(|issue| issue.message.contains("dependency cycle"))
+        .expect("cycle should be reported");
+    assert_eq!(cycle.task_ids, vec![1, 2]);
+    let missing = report
+        .errors()
+        .find(|issue| issue.message.contains("missing predecessor 99"))
+        .expect("missing predecessor should be reported");
+    assert_eq!(missing.task_ids, vec![3]);
+}
+
+#[test]
+fn validate_flags_horizon_and_date_order_issues() {
+    let mut schedule = base_schedule();
+    schedule.upsert_task(1, "Long", 60, None).unwrap();
+    let mut task = Task::new(2, "Backwards", 1);
+    task.actual_start = Some(d(2025, 1, 10));
+    task.actual_finish = Some(d(2025, 1, 8));
+    schedule.upsert_task_record(task).unwrap();
+    schedule.forward_pass().unwrap();
+
+    let report = schedule.validate().unwrap();
+
+    assert!(
+        report
+            .for_task(1)
+            .any(|issue| issue.message.contains("precedes schedule finish"))
+    );
+    assert!(report.for_task(2).any(|issue| {
+        issue.severity == ValidationSeverity::Error && issue.message.contains("before actual_start")
+    }));
+}
+
+#[test]
+fn duplicate_names_groups_shared_names() {
+    let mut schedule = base_schedule();
+    schedule.upsert_task(1, "Review", 1, None).unwrap();
+    schedule.upsert_task(2, "Build", 3, Some(vec![1])).unwrap();
+    schedule.upsert_task(3, "Review", 1, Some(vec![2])).unwrap();
+    schedule.refresh().unwrap();
+
+    let duplicates = schedule.duplicate_names().unwrap();
+    assert_eq!(duplicates, vec![("Review".to_string(), vec![1, 3])]);
+
+    // Shared names are not a validation error.
+    assert!(schedule.validate().unwrap().is_valid());
+}
+
+#[test]
+fn baseline_duration_mismatches_counts_working_days_across_weekend() {
+    let mut schedule = base_schedule();
+    // Wednesday to the following Wednesday: Thu, Fri, Mon, Tue, Wed.
+    let matching = Task::builder(1, "Matches", 5)
+        .baseline(d(2025, 1, 8), d(2025, 1, 15))
+        .build()
+        .unwrap();
+    let short = Task::builder(2, "Too short", 3)
+        .baseline(d(2025, 1, 8), d(2025, 1, 15))
+        .build()
+        .unwrap();
+    schedule.upsert_task_record(matching.clone()).unwrap();
+    schedule.upsert_task_record(short).unwrap();
+    schedule.upsert_task(3, "No baseline", 4, None).unwrap();
+
+    assert_eq!(
+        matching.baseline_working_duration(schedule.calendar()),
+        Some(5)
+    );
+    assert_eq!(
+        schedule.baseline_duration_mismatches(1).unwrap(),
+        vec![BaselineDurationMismatch {
+            task_id: 2,
+            duration_days: 3,
+            baseline_working_days: 5,
+        }]
+    );
+    assert!(schedule.baseline_duration_mismatches(2).unwrap().is_empty());
+}
+
+#[test]
+fn refresh_marks_fully_actualized_tasks_complete() {
+    let mut schedule = base_schedule();
+    let mut done = Task::new(1, "Done", 3);
+    done.actual_start = Some(d(2025, 1, 6));
+    done.actual_finish = Some(d(2025, 1, 8));
+    done.percent_complete = Some(0.5);
+    schedule.upsert_task_record(done).unwrap();
+    let mut started = Task::new(2, "Started", 3);
+    started.actual_start = Some(d(2025, 1, 9));
+    started.percent_complete = Some(0.4);
+    schedule.upsert_task_record(started).unwrap();
+
+    let report = schedule.validate().unwrap();
+    assert!(report.for_task(1).any(|issue| {
+        issue.severity == ValidationSeverity::Warning
+            && issue.message.contains("percent_complete is 0.5, not 1")
+    }));
+
+    schedule.refresh().unwrap();
+
+    let done = schedule.find_task(1).unwrap().unwrap();
+    assert_eq!(done.percent_complete, Some(1.0));
+    // Monday to Wednesday: two working days after the start.
+    assert_eq!(done.actual_working_duration(schedule.calendar()), Some(2));
+    let started = schedule.find_task(2).unwrap().unwrap();
+    assert_eq!(started.percent_complete, Some(0.4));
+    assert_eq!(schedule.validate().unwrap().for_task(1).count(), 0);
+}